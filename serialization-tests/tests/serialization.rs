@@ -564,3 +564,84 @@ fn json_graphmap_struct() {
         (1, 2.)
     );
 }
+
+#[test]
+fn json_acyclic_graph() {
+    use petgraph::acyclic::Acyclic;
+    use petgraph::data::Build;
+
+    let mut dag = Acyclic::<DiGraph<u32, ()>>::new();
+    let nodes: Vec<_> = (0..4).map(|i| dag.add_node(i)).collect();
+    // Add the edges against the initial order, so that it is not the identity.
+    dag.try_add_edge(nodes[3], nodes[1], ()).unwrap();
+    dag.try_add_edge(nodes[1], nodes[0], ()).unwrap();
+    dag.try_add_edge(nodes[2], nodes[1], ()).unwrap();
+
+    let dag_deser: Acyclic<DiGraph<u32, ()>> = rejson!(&dag);
+    assert_graph_eq(dag.inner(), dag_deser.inner());
+    assert_equal(dag.nodes_iter(), dag_deser.nodes_iter());
+}
+
+#[test]
+fn json_acyclic_stable_graph() {
+    use petgraph::acyclic::Acyclic;
+
+    // Keep the edges going from a higher to a lower index, with node holes.
+    let mut gr = make_stable_graph::<Directed, u32>();
+    gr.retain_edges(|g, e| {
+        let (a, b) = g.edge_endpoints(e).unwrap();
+        a > b
+    });
+    let dag = Acyclic::try_from_graph(gr).unwrap();
+
+    let dag_deser: Acyclic<StableDiGraph<String, i32>> = rejson!(&dag);
+    assert_stable_graph_eq(dag.inner(), dag_deser.inner());
+    assert_equal(dag.nodes_iter(), dag_deser.nodes_iter());
+}
+
+const ACYCLIC_NILS: &str = r#"{
+    "graph": {
+        "nodes":[null,null,null],
+        "edge_property": "directed",
+        "edges":[[0,1,null],[2,1,null]]
+    },
+    "order": [2,0,1]
+    }"#;
+
+const ACYCLIC_NILS_WRONG_ORDER: &str = r#"{
+    "graph": {
+        "nodes":[null,null,null],
+        "edge_property": "directed",
+        "edges":[[0,1,null],[2,1,null]]
+    },
+    "order": [0,1,2]
+    }"#;
+
+const ACYCLIC_NILS_MISSING_NODE: &str = r#"{
+    "graph": {
+        "nodes":[null,null,null],
+        "edge_property": "directed",
+        "edges":[[0,1,null],[2,1,null]]
+    },
+    "order": [2,0,0]
+    }"#;
+
+type AcyclicNils = petgraph::acyclic::Acyclic<DiGraphNils>;
+
+#[test]
+fn from_json_acyclic_nils() {
+    let dag: AcyclicNils = fromjson!(&ACYCLIC_NILS);
+    assert_equal(dag.nodes_iter(), [2, 0, 1].iter().map(|&i| node_index(i)));
+}
+
+#[test]
+#[should_panic(expected = "not consistent with the topological order")]
+fn from_json_acyclic_wrong_order() {
+    let _: AcyclicNils = fromjson!(&ACYCLIC_NILS_WRONG_ORDER);
+}
+
+#[test]
+#[should_panic(expected = "listed twice")]
+fn from_json_acyclic_missing_node() {
+    let _: AcyclicNils = fromjson!(&ACYCLIC_NILS_MISSING_NODE);
+}
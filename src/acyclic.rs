@@ -3,30 +3,40 @@
 use std::{
     cell::RefCell,
     cmp::Ordering,
-    collections::{BTreeMap, BTreeSet},
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
     convert::TryFrom,
     ops::{Deref, RangeBounds},
 };
 
 use crate::{
     adj::IndexType,
-    algo::Cycle,
+    algo::{
+        dag_paths::dag_paths_in_order, longest_path::longest_path_in_order,
+        tred::partition_redundant_edges, Cycle, DagPaths, Measure,
+    },
     data::{Build, Create, DataMap, DataMapMut},
     graph::NodeIndex,
     prelude::DiGraph,
     visit::{
-        dfs_visitor, Control, Data, DfsEvent, EdgeCount, EdgeIndexable, GetAdjacencyMatrix,
-        GraphBase, GraphProp, IntoEdgeReferences, IntoEdges, IntoEdgesDirected, IntoNeighbors,
-        IntoNeighborsDirected, IntoNodeIdentifiers, IntoNodeReferences, NodeCompactIndexable,
-        NodeCount, NodeIndexable, Reversed, Time, Visitable,
+        dfs_visitor, Control, Data, DfsEvent, EdgeCount, EdgeIndexable, EdgeRef,
+        GetAdjacencyMatrix, GraphBase, GraphProp, IntoEdgeReferences, IntoEdges, IntoEdgesDirected,
+        IntoNeighbors, IntoNeighborsDirected, IntoNodeIdentifiers, IntoNodeReferences,
+        NodeCompactIndexable, NodeCount, NodeIndexable, Reversed, Time, Visitable,
     },
     Direction,
 };
 
 #[cfg(feature = "stable_graph")]
 use crate::stable_graph::StableDiGraph;
+#[cfg(feature = "matrix_graph")]
+use crate::{
+    matrix_graph::{MatrixGraph, Nullable},
+    Directed,
+};
 
 mod order_map;
+#[cfg(feature = "serde-1")]
+mod serialization;
 use fixedbitset::FixedBitSet;
 use order_map::OrderMap;
 pub use order_map::TopologicalPosition;
@@ -51,11 +61,39 @@ pub use order_map::TopologicalPosition;
 /// balance between simplicity and performance in practice, see the paper for
 /// discussions of the running time.
 ///
+/// ## Topological order
+/// The maintained order is part of the public API: [`Acyclic::get_position`]
+/// and [`Acyclic::at_position`] map between nodes and their
+/// [`TopologicalPosition`], while [`Acyclic::nodes_iter`] and
+/// [`Acyclic::range`] iterate over the nodes in order. For every edge `a -> b`
+/// of the graph, the position of `a` is strictly smaller than that of `b`.
+/// Positions are not necessarily contiguous.
+///
+/// Positions are only invalidated by the following operations:
+/// - adding a node assigns it a position after all existing nodes, and does
+///   not move any other node;
+/// - adding an edge `a -> b` (or a batch of edges) that is consistent with the
+///   current order does not move any node. Otherwise, only the nodes between
+///   the positions of `b` and `a` may move, and they are reassigned the
+///   positions they collectively held before;
+/// - removing a node frees its position, and does not move any other node
+///   (with [`DiGraph`], the last node takes the index of the removed node and
+///   keeps its position);
+/// - removing an edge does not move any node.
+///
+/// Failed edge additions leave all positions unchanged.
+///
 /// ## Graph traits
 /// All graph traits are delegated to the inner graph, with the exception of
 /// the graph construction trait [`Build`]. The wrapped graph can thus only
 /// be modified through the wrapped API that ensures no cycles are created.
 ///
+/// ## Serialization
+/// With the `serde-1` crate feature, `Acyclic` serializes the underlying graph
+/// together with its topological order. On deserialization, the order is
+/// checked against the edges of the graph in linear time instead of being
+/// recomputed.
+///
 /// ## Behaviour on cycles
 /// By design, edge additions to this datatype may fail. It is recommended to
 /// prefer the dedicated [`Acyclic::try_add_edge`] and
@@ -77,6 +115,9 @@ pub struct Acyclic<G: Visitable> {
     discovered: RefCell<FixedBitSet>,
     /// Helper map for DFS tracking finished nodes.
     finished: RefCell<FixedBitSet>,
+    /// Optional transitive closure of the graph: for each node index, the
+    /// indices of the nodes reachable from it through at least one edge.
+    reachability_index: Option<Vec<FixedBitSet>>,
 }
 
 /// An error that can occur during edge addition for acyclic graphs.
@@ -90,6 +131,18 @@ pub enum AcyclicEdgeError<N> {
     InvalidEdge,
 }
 
+/// An error returned when a caller-provided order is not a topological order
+/// of the graph.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AcyclicOrderError<N> {
+    /// The node is not in the graph, or is listed twice in the order.
+    InvalidNode(N),
+    /// The node of the graph is missing from the order.
+    MissingNode(N),
+    /// The edge from the first to the second node goes backwards in the order.
+    InconsistentEdge(N, N),
+}
+
 impl<N> From<Cycle<N>> for AcyclicEdgeError<N> {
     fn from(cycle: Cycle<N>) -> Self {
         AcyclicEdgeError::Cycle(cycle)
@@ -105,6 +158,19 @@ impl<G: Visitable> Acyclic<G> {
         Default::default()
     }
 
+    /// Whether the reachability index is enabled.
+    pub fn has_reachability_index(&self) -> bool {
+        self.reachability_index.is_some()
+    }
+
+    /// Build the reachability index from scratch, enabling it.
+    pub(crate) fn build_reachability_index<'a>(&'a mut self)
+    where
+        &'a G: NodeIndexable + IntoNeighborsDirected + GraphBase<NodeId = G::NodeId>,
+    {
+        self.reachability_index = Some(reachability_index(&self.graph, &self.order_map));
+    }
+
     /// Get an iterator over the nodes, ordered by their position.
     pub fn nodes_iter(&self) -> impl Iterator<Item = G::NodeId> + '_ {
         self.order_map.nodes_iter()
@@ -146,7 +212,8 @@ where
     ///
     /// The graph types [`DiGraph`] and [`StableDiGraph`] also implement
     /// [`TryFrom`], which can be used instead of this method and have looser
-    /// type bounds.
+    /// type bounds. If a topological order of the graph is already known, use
+    /// [`Self::try_from_parts`] instead.
     pub fn try_from_graph(graph: G) -> Result<Self, Cycle<G::NodeId>> {
         let order_map = OrderMap::try_from_graph(&graph)?;
         let discovered = RefCell::new(FixedBitSet::with_capacity(graph.node_bound()));
@@ -156,9 +223,64 @@ where
             order_map,
             discovered,
             finished,
+            reachability_index: None,
         })
     }
 
+    /// Recompute a fresh topological order from scratch.
+    ///
+    /// The new positions are contiguous, and the memory used by the order
+    /// map is shrunk to the current node bound. The maintained order is always
+    /// a valid topological sort, but after many incremental updates of a
+    /// long-lived graph, its positions may be sparse and spread out, e.g.
+    /// after many node removals. All positions may change.
+    ///
+    /// Computes in **O(|V| + |E|)** time.
+    pub fn rebuild_order(&mut self) {
+        self.order_map = match OrderMap::try_from_graph(&self.graph) {
+            Ok(order_map) => order_map,
+            Err(_) => unreachable!("graph is acyclic"),
+        };
+        self.shrink_scratch_space();
+    }
+
+    /// Recompute a fresh topological order from scratch, breaking ties with
+    /// `key`.
+    ///
+    /// Among the nodes whose predecessors have all been ordered, the node with
+    /// the smallest key comes first. As for [`Self::rebuild_order`], the new
+    /// positions are contiguous and the order map is shrunk. All positions may
+    /// change.
+    ///
+    /// Computes in **O(|E| + |V| log |V|)** time.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::acyclic::Acyclic;
+    /// use petgraph::data::Build;
+    /// use petgraph::prelude::*;
+    ///
+    /// use std::cmp::Reverse;
+    ///
+    /// let mut dag = Acyclic::<DiGraph<(), ()>>::new();
+    /// let (a, b, c) = (dag.add_node(()), dag.add_node(()), dag.add_node(()));
+    /// dag.try_add_edge(b, a, ()).unwrap();
+    ///
+    /// // Order the nodes by decreasing index, unless an edge forbids it.
+    /// dag.rebuild_order_by_key(|n| Reverse(n.index()));
+    /// assert_eq!(dag.nodes_iter().collect::<Vec<_>>(), vec![c, b, a]);
+    /// ```
+    pub fn rebuild_order_by_key<K: Ord>(&mut self, key: impl FnMut(G::NodeId) -> K) {
+        self.order_map = OrderMap::from_graph_by_key(&self.graph, key);
+        self.shrink_scratch_space();
+    }
+
+    /// Reallocate the DFS scratch space to the current node bound.
+    fn shrink_scratch_space(&mut self) {
+        self.discovered = RefCell::new(FixedBitSet::with_capacity(self.graph.node_bound()));
+        self.finished = RefCell::new(FixedBitSet::with_capacity(self.graph.node_bound()));
+    }
+
     /// Add an edge to the graph using [`Build::add_edge`].
     ///
     /// Returns the id of the added edge, or an [`AcyclicEdgeError`] if the edge
@@ -168,7 +290,8 @@ where
     /// In cases where edge addition cannot fail in the underlying graph (e.g.
     /// when multi-edges are allowed, as in [`DiGraph`] and [`StableDiGraph`]),
     /// this will return an error if and only if [`Self::is_valid_edge`]
-    /// returns `false`.
+    /// returns `false`. Use [`Self::cycle_path`] to find the existing path
+    /// that a rejected edge would close into a cycle.
     pub fn try_add_edge(
         &mut self,
         a: G::NodeId,
@@ -184,9 +307,12 @@ where
             return Err(AcyclicEdgeError::SelfLoop);
         }
         self.update_ordering(a, b)?;
-        self.graph
+        let edge = self
+            .graph
             .add_edge(a, b, weight)
-            .ok_or(AcyclicEdgeError::InvalidEdge)
+            .ok_or(AcyclicEdgeError::InvalidEdge)?;
+        self.update_reachability_index(a, b);
+        Ok(edge)
     }
 
     /// Update an edge in a graph using [`Build::update_edge`].
@@ -212,7 +338,73 @@ where
             return Err(AcyclicEdgeError::SelfLoop);
         }
         self.update_ordering(a, b)?;
-        Ok(self.graph.update_edge(a, b, weight))
+        let edge = self.graph.update_edge(a, b, weight);
+        self.update_reachability_index(a, b);
+        Ok(edge)
+    }
+
+    /// Add a batch of edges to the graph using [`Build::add_edge`].
+    ///
+    /// The whole batch is checked for cycles and self-loops before any edge
+    /// is added: if the edges would create a cycle or a self-loop, an
+    /// [`AcyclicEdgeError`] is returned and `self` remains unchanged.
+    /// Otherwise the topological order is updated once for the whole batch,
+    /// which is faster than repeated calls to [`Self::try_add_edge`] when
+    /// adding many edges at once.
+    ///
+    /// Returns the ids of the added edges, in iteration order. The edges are
+    /// then added one at a time, and edge addition in the underlying graph is
+    /// not checked beforehand: if it fails, an
+    /// [`AcyclicEdgeError::InvalidEdge`] is returned, and the batch is only
+    /// partially applied. The edges that precede the failing edge remain in
+    /// the graph, and the topological order stays valid.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::acyclic::{Acyclic, AcyclicEdgeError};
+    /// use petgraph::data::Build;
+    /// use petgraph::prelude::*;
+    ///
+    /// let mut dag = Acyclic::<DiGraph<(), ()>>::new();
+    /// let (a, b, c) = (dag.add_node(()), dag.add_node(()), dag.add_node(()));
+    ///
+    /// let edges = dag.try_add_edges([(c, b, ()), (b, a, ())]).unwrap();
+    /// assert_eq!(edges.len(), 2);
+    /// assert!(dag.get_position(c) < dag.get_position(b));
+    /// assert!(dag.get_position(b) < dag.get_position(a));
+    ///
+    /// // Nothing is added if the batch would create a cycle.
+    /// let res = dag.try_add_edges([(a, c, ()), (c, a, ())]);
+    /// assert!(matches!(res, Err(AcyclicEdgeError::Cycle(_))));
+    /// assert_eq!(dag.edge_count(), 2);
+    /// ```
+    pub fn try_add_edges<I>(
+        &mut self,
+        edges: I,
+    ) -> Result<Vec<G::EdgeId>, AcyclicEdgeError<G::NodeId>>
+    where
+        G: Build,
+        G::NodeId: IndexType,
+        I: IntoIterator<Item = (G::NodeId, G::NodeId, G::EdgeWeight)>,
+    {
+        let edges: Vec<_> = edges.into_iter().collect();
+        if edges.iter().any(|(a, b, _)| a == b) {
+            // No self-loops allowed
+            return Err(AcyclicEdgeError::SelfLoop);
+        }
+        let endpoints: Vec<_> = edges.iter().map(|&(a, b, _)| (a, b)).collect();
+        update_ordering_batch(&self.graph, &mut self.order_map, &endpoints)?;
+        edges
+            .into_iter()
+            .map(|(a, b, weight)| {
+                let edge = self
+                    .graph
+                    .add_edge(a, b, weight)
+                    .ok_or(AcyclicEdgeError::InvalidEdge)?;
+                self.update_reachability_index(a, b);
+                Ok(edge)
+            })
+            .collect()
     }
 
     /// Check if an edge would be valid, i.e. adding it would not create a cycle.
@@ -231,6 +423,304 @@ where
         }
     }
 
+    /// Find the path that an edge a -> b would close into a cycle.
+    ///
+    /// If adding the edge would create a cycle, return a shortest path
+    /// `[b, ..., a]` from `b` to `a` in the graph, i.e. the chain of existing
+    /// edges that conflicts with the new edge. For a self-loop, this is
+    /// `[a]`. Return `None` if the edge is valid.
+    ///
+    /// The search is restricted to the nodes between the positions of `b`
+    /// and `a` in the topological order.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::acyclic::Acyclic;
+    /// use petgraph::data::Build;
+    /// use petgraph::prelude::*;
+    ///
+    /// let mut dag = Acyclic::<DiGraph<(), ()>>::new();
+    /// let (a, b, c) = (dag.add_node(()), dag.add_node(()), dag.add_node(()));
+    /// dag.try_add_edge(a, b, ()).unwrap();
+    /// dag.try_add_edge(b, c, ()).unwrap();
+    ///
+    /// assert!(dag.try_add_edge(c, a, ()).is_err());
+    /// assert_eq!(dag.cycle_path(c, a), Some(vec![a, b, c]));
+    /// assert_eq!(dag.cycle_path(a, c), None);
+    /// ```
+    pub fn cycle_path(&self, a: G::NodeId, b: G::NodeId) -> Option<Vec<G::NodeId>>
+    where
+        G::NodeId: IndexType,
+    {
+        if a == b {
+            return Some(vec![a]);
+        }
+        let max_order = self.get_position(a);
+        if max_order < self.get_position(b) {
+            return None;
+        }
+
+        // Breadth-first search from `b`, pruning the nodes ordered after `a`
+        let mut predecessor = HashMap::new();
+        let mut queue = VecDeque::from(vec![b]);
+        while let Some(u) = queue.pop_front() {
+            for v in self.graph.neighbors_directed(u, Direction::Outgoing) {
+                if v == b || predecessor.contains_key(&v) || self.get_position(v) > max_order {
+                    continue;
+                }
+                predecessor.insert(v, u);
+                if v == a {
+                    let mut path = vec![a];
+                    let mut node = a;
+                    while node != b {
+                        node = predecessor[&node];
+                        path.push(node);
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                queue.push_back(v);
+            }
+        }
+        None
+    }
+
+    /// Get the nodes reachable from `u` whose position lies strictly between
+    /// the positions of `u` and `v`.
+    ///
+    /// These are the nodes that must be reordered if an edge `v -> u` is
+    /// added. The traversal never leaves the range of positions, so its cost
+    /// depends on the size of the range rather than on the size of the graph.
+    /// The nodes are returned in topological order, and the result is empty
+    /// if `u` is not before `v` in the order.
+    ///
+    /// See [`Self::nodes_between`] for all nodes within the range.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::acyclic::Acyclic;
+    /// use petgraph::data::Build;
+    /// use petgraph::prelude::*;
+    ///
+    /// let mut dag = Acyclic::<DiGraph<(), ()>>::new();
+    /// let (a, b, c, d) = (dag.add_node(()), dag.add_node(()), dag.add_node(()), dag.add_node(()));
+    /// dag.try_add_edge(a, c, ()).unwrap();
+    /// dag.try_add_edge(c, d, ()).unwrap();
+    ///
+    /// assert_eq!(dag.nodes_between(a, d).collect::<Vec<_>>(), vec![b, c]);
+    /// assert_eq!(dag.reachable_between(a, d), vec![c]);
+    /// assert_eq!(dag.reachable_between(d, a), vec![]);
+    /// ```
+    pub fn reachable_between(&self, u: G::NodeId, v: G::NodeId) -> Vec<G::NodeId>
+    where
+        G::NodeId: IndexType,
+    {
+        let max_position = self.get_position(v);
+        if self.get_position(u) >= max_position {
+            return Vec::new();
+        }
+        let cone = self.pruned_cone(u, Direction::Outgoing, |pos| pos < max_position);
+        cone.values().copied().filter(|&n| n != u).collect()
+    }
+
+    /// Get the nodes reachable from `n` through at least one edge, ordered by
+    /// their position in the topological order.
+    ///
+    /// The traversal only follows outgoing edges, and thus only visits nodes
+    /// ordered after `n`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::acyclic::Acyclic;
+    /// use petgraph::data::Build;
+    /// use petgraph::prelude::*;
+    ///
+    /// let mut dag = Acyclic::<DiGraph<(), ()>>::new();
+    /// let (a, b, c, d) = (dag.add_node(()), dag.add_node(()), dag.add_node(()), dag.add_node(()));
+    /// dag.try_add_edge(a, b, ()).unwrap();
+    /// dag.try_add_edge(b, c, ()).unwrap();
+    /// dag.try_add_edge(d, c, ()).unwrap();
+    ///
+    /// assert_eq!(dag.descendants(a).collect::<Vec<_>>(), vec![b, c]);
+    /// assert_eq!(dag.ancestors(c).count(), 3);
+    /// assert_eq!(dag.ancestors(d).count(), 0);
+    /// ```
+    pub fn descendants(&self, n: G::NodeId) -> impl Iterator<Item = G::NodeId>
+    where
+        G::NodeId: IndexType,
+    {
+        let cone = self.pruned_cone(n, Direction::Outgoing, |_| true);
+        cone.into_iter().map(|(_, v)| v).filter(move |&v| v != n)
+    }
+
+    /// Get the nodes from which `n` is reachable through at least one edge,
+    /// ordered by their position in the topological order.
+    ///
+    /// The traversal only follows incoming edges, and thus only visits nodes
+    /// ordered before `n`. See [`Self::descendants`] for an example.
+    pub fn ancestors(&self, n: G::NodeId) -> impl Iterator<Item = G::NodeId>
+    where
+        G::NodeId: IndexType,
+    {
+        let cone = self.pruned_cone(n, Direction::Incoming, |_| true);
+        cone.into_iter().map(|(_, v)| v).filter(move |&v| v != n)
+    }
+
+    /// Check whether there is a path from `u` to `v` in the graph.
+    ///
+    /// Every node is reachable from itself. If `v` is ordered before `u`, this
+    /// returns `false` immediately. Otherwise, the reachability index is used
+    /// if it is enabled (see [`Self::enable_reachability_index`]), answering
+    /// in **O(1)** time. Without the index, a depth-first search from `u` is
+    /// run through the nodes ordered before `v`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::acyclic::Acyclic;
+    /// use petgraph::data::Build;
+    /// use petgraph::prelude::*;
+    ///
+    /// let mut dag = Acyclic::<DiGraph<(), ()>>::new();
+    /// let (a, b, c) = (dag.add_node(()), dag.add_node(()), dag.add_node(()));
+    /// dag.enable_reachability_index();
+    /// dag.try_add_edge(a, b, ()).unwrap();
+    /// dag.try_add_edge(b, c, ()).unwrap();
+    ///
+    /// assert!(dag.is_reachable(a, c));
+    /// assert!(!dag.is_reachable(c, a));
+    /// ```
+    pub fn is_reachable(&self, u: G::NodeId, v: G::NodeId) -> bool
+    where
+        G::NodeId: IndexType,
+    {
+        if u == v {
+            return true;
+        }
+        let max_position = self.get_position(v);
+        if self.get_position(u) >= max_position {
+            return false;
+        }
+        if let Some(index) = &self.reachability_index {
+            return index
+                .get(u.index())
+                .map_or(false, |reach| reach.contains(v.index()));
+        }
+        let cone = self.pruned_cone(u, Direction::Outgoing, |pos| pos <= max_position);
+        cone.contains_key(&max_position)
+    }
+
+    /// Build a reachability index and maintain it on subsequent edge
+    /// insertions, so that [`Self::is_reachable`] runs in **O(1)** time.
+    ///
+    /// The index is a transitive closure of the graph, stored as one bit set
+    /// per node, and takes **O(|V|²)** bits of memory. Building it takes
+    /// **O(|V| · |E| / 64)** time. Adding an edge `a -> b` updates it in
+    /// **O(|V|² / 64)** time in the worst case, and is free if `b` was
+    /// already reachable from `a`. Removing a node or an edge rebuilds it
+    /// from scratch.
+    ///
+    /// Does nothing if the index is already enabled.
+    pub fn enable_reachability_index(&mut self)
+    where
+        G::NodeId: IndexType,
+    {
+        if self.reachability_index.is_none() {
+            self.reachability_index = Some(reachability_index(&self.graph, &self.order_map));
+        }
+    }
+
+    /// Drop the reachability index and release its memory.
+    pub fn disable_reachability_index(&mut self) {
+        self.reachability_index = None;
+    }
+
+    /// Update the reachability index, if it is enabled, after adding an edge
+    /// a -> b.
+    ///
+    /// The nodes from which `a` is reachable are all ordered before `a`, so
+    /// only those need to be considered.
+    fn update_reachability_index(&mut self, a: G::NodeId, b: G::NodeId)
+    where
+        G::NodeId: IndexType,
+    {
+        let index = match &mut self.reachability_index {
+            Some(index) => index,
+            None => return,
+        };
+        let bound = self.graph.node_bound();
+        if index.len() < bound {
+            index.resize(bound, FixedBitSet::with_capacity(bound));
+        }
+        if index[a.index()].contains(b.index()) {
+            return;
+        }
+        let mut reach = index[b.index()].clone();
+        reach.grow(bound);
+        reach.insert(b.index());
+        let max_position = self.order_map.get_position(a, &self.graph);
+        for n in self.order_map.range(..=max_position) {
+            if n == a || index[n.index()].contains(a.index()) {
+                index[n.index()].union_with(&reach);
+            }
+        }
+    }
+
+    /// Collect the nodes reachable from `start` in direction `dir`, through
+    /// nodes whose position satisfies `within`.
+    ///
+    /// The result includes `start` and is ordered by position.
+    fn pruned_cone(
+        &self,
+        start: G::NodeId,
+        dir: Direction,
+        mut within: impl FnMut(TopologicalPosition) -> bool,
+    ) -> BTreeMap<TopologicalPosition, G::NodeId>
+    where
+        G::NodeId: IndexType,
+    {
+        debug_assert!(self.discovered.borrow().is_clear());
+        debug_assert!(self.finished.borrow().is_clear());
+
+        // Prepare DFS scratch space: make sure the maps have enough capacity
+        if self.discovered.borrow().len() < self.graph.node_bound() {
+            self.discovered.borrow_mut().grow(self.graph.node_bound());
+            self.finished.borrow_mut().grow(self.graph.node_bound());
+        }
+
+        let mut cone = BTreeMap::new();
+        let valid_order = |pos| Ok(within(pos));
+        let discovered = &mut self.discovered.borrow_mut();
+        let finished = &mut self.finished.borrow_mut();
+        let res = match dir {
+            Direction::Outgoing => dfs(
+                &self.graph,
+                start,
+                &self.order_map,
+                valid_order,
+                &mut cone,
+                discovered,
+                finished,
+            ),
+            Direction::Incoming => dfs(
+                Reversed(&self.graph),
+                start,
+                &self.order_map,
+                valid_order,
+                &mut cone,
+                discovered,
+                finished,
+            ),
+        };
+        debug_assert!(res.is_ok());
+
+        // Cleanup: reset the maps, as in `causal_cones`.
+        for &v in cone.values() {
+            discovered.set(v.index(), false);
+            finished.set(v.index(), false);
+        }
+        cone
+    }
+
     /// Update the ordering of the nodes in the order map resulting from adding an
     /// edge a -> b.
     ///
@@ -393,6 +883,221 @@ where
     }
 }
 
+impl<G: Visitable> Acyclic<G> {
+    /// Find a longest (critical) path of the graph.
+    ///
+    /// The cost of a path is the sum of the costs of its nodes, given by
+    /// `node_cost`, and of its edges, given by `edge_cost`. Returns the cost
+    /// of a path of maximal cost and its nodes, in order. Among paths of the
+    /// same cost, the longer ones are preferred. Returns the default cost and
+    /// an empty path if the graph is empty.
+    ///
+    /// Costs may be negative: the path may start and end at any node.
+    ///
+    /// Computes in **O(|V| + |E|)** time, with a single pass over the nodes in
+    /// reverse topological order.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::acyclic::Acyclic;
+    /// use petgraph::data::Build;
+    /// use petgraph::prelude::*;
+    ///
+    /// // Build tasks with their durations, and their dependencies.
+    /// let mut dag = Acyclic::<DiGraph<u32, ()>>::new();
+    /// let fetch = dag.add_node(2);
+    /// let compile = dag.add_node(10);
+    /// let docs = dag.add_node(4);
+    /// let link = dag.add_node(3);
+    /// dag.try_add_edge(fetch, compile, ()).unwrap();
+    /// dag.try_add_edge(fetch, docs, ()).unwrap();
+    /// dag.try_add_edge(compile, link, ()).unwrap();
+    ///
+    /// let (duration, path) = dag.longest_path(|n| dag[n], |_| 0);
+    /// assert_eq!(duration, 15);
+    /// assert_eq!(path, vec![fetch, compile, link]);
+    /// ```
+    pub fn longest_path<'a, K, F, H>(&'a self, node_cost: F, edge_cost: H) -> (K, Vec<G::NodeId>)
+    where
+        &'a G: IntoEdges + NodeIndexable + GraphBase<NodeId = G::NodeId>,
+        K: Measure,
+        F: FnMut(G::NodeId) -> K,
+        H: FnMut(<&'a G as IntoEdgeReferences>::EdgeRef) -> K,
+    {
+        let order: Vec<_> = self.nodes_iter().collect();
+        longest_path_in_order(&self.graph, &order, node_cost, edge_cost)
+    }
+
+    /// Compute the shortest paths from node `source`, as
+    /// [`dag_shortest_paths`](crate::algo::dag_shortest_paths) but without
+    /// sorting the graph: only the nodes after `source` in the maintained
+    /// order are visited.
+    ///
+    /// Computes in **O(|V| + |E|)** time.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::acyclic::Acyclic;
+    /// use petgraph::data::Build;
+    /// use petgraph::prelude::*;
+    ///
+    /// let mut dag = Acyclic::<DiGraph<(), i32>>::new();
+    /// let a = dag.add_node(());
+    /// let b = dag.add_node(());
+    /// let c = dag.add_node(());
+    /// dag.try_add_edge(a, b, 2).unwrap();
+    /// dag.try_add_edge(b, c, -1).unwrap();
+    /// dag.try_add_edge(a, c, 3).unwrap();
+    ///
+    /// let paths = dag.shortest_paths(a, |e| *e.weight());
+    /// assert_eq!(paths.distances[c.index()], Some(1));
+    /// assert_eq!(dag.shortest_paths(b, |e| *e.weight()).distances[a.index()], None);
+    /// ```
+    pub fn shortest_paths<'a, K, F>(
+        &'a self,
+        source: G::NodeId,
+        edge_cost: F,
+    ) -> DagPaths<G::NodeId, K>
+    where
+        &'a G: IntoEdges + NodeIndexable + GraphBase<NodeId = G::NodeId>,
+        K: Measure,
+        F: FnMut(<&'a G as IntoEdgeReferences>::EdgeRef) -> K,
+    {
+        let order = self.range(self.get_position(source)..);
+        dag_paths_in_order(&self.graph, order, source, edge_cost, false)
+    }
+
+    /// Compute the longest paths from node `source`, as
+    /// [`dag_longest_paths`](crate::algo::dag_longest_paths) but without
+    /// sorting the graph: only the nodes after `source` in the maintained
+    /// order are visited.
+    ///
+    /// Computes in **O(|V| + |E|)** time.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::acyclic::Acyclic;
+    /// use petgraph::data::Build;
+    /// use petgraph::prelude::*;
+    ///
+    /// let mut dag = Acyclic::<DiGraph<(), i32>>::new();
+    /// let a = dag.add_node(());
+    /// let b = dag.add_node(());
+    /// let c = dag.add_node(());
+    /// dag.try_add_edge(a, b, 2).unwrap();
+    /// dag.try_add_edge(b, c, -1).unwrap();
+    /// dag.try_add_edge(a, c, 3).unwrap();
+    ///
+    /// let paths = dag.longest_paths(a, |e| *e.weight());
+    /// assert_eq!(paths.distances[c.index()], Some(3));
+    /// assert_eq!(paths.path_to(dag.inner(), c), Some(vec![a, c]));
+    /// ```
+    pub fn longest_paths<'a, K, F>(
+        &'a self,
+        source: G::NodeId,
+        edge_cost: F,
+    ) -> DagPaths<G::NodeId, K>
+    where
+        &'a G: IntoEdges + NodeIndexable + GraphBase<NodeId = G::NodeId>,
+        K: Measure,
+        F: FnMut(<&'a G as IntoEdgeReferences>::EdgeRef) -> K,
+    {
+        let order = self.range(self.get_position(source)..);
+        dag_paths_in_order(&self.graph, order, source, edge_cost, true)
+    }
+}
+
+impl<G: Visitable + NodeIndexable> Acyclic<G>
+where
+    for<'a> &'a G: IntoEdgeReferences + IntoNodeIdentifiers + GraphBase<NodeId = G::NodeId>,
+{
+    /// Wrap a graph into an acyclic graph, using `order` as its topological
+    /// order.
+    ///
+    /// The order must list every node of the graph exactly once, and every
+    /// edge must go from a node to a node listed after it. Otherwise, an
+    /// [`AcyclicOrderError`] is returned.
+    ///
+    /// Checking the order takes **O(|V| + |E|)** time, and is cheaper than
+    /// [`Self::try_from_graph`] which computes a fresh topological sort.
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::acyclic::{Acyclic, AcyclicOrderError};
+    /// use petgraph::prelude::*;
+    ///
+    /// let graph = DiGraph::<(), ()>::from_edges([(1, 0), (2, 1)]);
+    /// let (a, b, c) = (NodeIndex::new(0), NodeIndex::new(1), NodeIndex::new(2));
+    ///
+    /// let dag = Acyclic::try_from_parts(graph.clone(), vec![c, b, a]).unwrap();
+    /// assert_eq!(dag.nodes_iter().collect::<Vec<_>>(), vec![c, b, a]);
+    ///
+    /// let err = Acyclic::try_from_parts(graph, vec![c, a, b]).unwrap_err();
+    /// assert_eq!(err, AcyclicOrderError::InconsistentEdge(b, a));
+    /// ```
+    pub fn try_from_parts(
+        graph: G,
+        order: impl IntoIterator<Item = G::NodeId>,
+    ) -> Result<Self, AcyclicOrderError<G::NodeId>> {
+        let order: Vec<_> = order.into_iter().collect();
+        check_permutation(&graph, order.iter().copied())?;
+        let acyclic = Self::from_parts_unchecked(graph, order);
+        acyclic.check_edges()?;
+        Ok(acyclic)
+    }
+
+    /// Check that the maintained order is a topological order of the graph.
+    ///
+    /// This always holds for graphs built through the `Acyclic` API, but may
+    /// not for graphs built with [`Self::from_parts_unchecked`].
+    ///
+    /// Computes in **O(|V| + |E|)** time.
+    pub fn validate(&self) -> Result<(), AcyclicOrderError<G::NodeId>> {
+        check_permutation(&self.graph, self.nodes_iter())?;
+        self.check_edges()
+    }
+
+    /// Check that every edge goes forward in the order.
+    fn check_edges(&self) -> Result<(), AcyclicOrderError<G::NodeId>> {
+        match self
+            .graph
+            .edge_references()
+            .find(|edge| self.get_position(edge.source()) >= self.get_position(edge.target()))
+        {
+            Some(edge) => Err(AcyclicOrderError::InconsistentEdge(
+                edge.source(),
+                edge.target(),
+            )),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<G: Visitable + NodeIndexable> Acyclic<G> {
+    /// Wrap a graph into an acyclic graph, using `order` as its topological
+    /// order without checking it.
+    ///
+    /// This is the fastest way to wrap a graph whose topological order is
+    /// already known. The order must list every node of the graph exactly
+    /// once, and every edge must go from a node to a node listed after it.
+    /// If it does not, the acyclicity invariant is broken and other methods
+    /// may panic or return incorrect results. Use [`Self::validate`] to check
+    /// the order, or [`Self::try_from_parts`] for a checked constructor.
+    pub fn from_parts_unchecked(graph: G, order: impl IntoIterator<Item = G::NodeId>) -> Self {
+        let mut order_map = OrderMap::with_capacity(graph.node_bound());
+        for id in order {
+            order_map.add_node(id, &graph);
+        }
+        Self {
+            discovered: RefCell::new(FixedBitSet::with_capacity(graph.node_bound())),
+            finished: RefCell::new(FixedBitSet::with_capacity(graph.node_bound())),
+            graph,
+            order_map,
+            reachability_index: None,
+        }
+    }
+}
+
 impl<G: Visitable> GraphBase for Acyclic<G> {
     type NodeId = G::NodeId;
     type EdgeId = G::EdgeId;
@@ -409,6 +1114,7 @@ impl<G: Default + Visitable> Default for Acyclic<G> {
             order_map,
             discovered,
             finished,
+            reachability_index: None,
         }
     }
 }
@@ -464,6 +1170,7 @@ where
             order_map,
             discovered: RefCell::new(discovered),
             finished: RefCell::new(finished),
+            reachability_index: None,
         }
     }
 }
@@ -476,6 +1183,159 @@ impl<G: Visitable> Deref for Acyclic<G> {
     }
 }
 
+/// Update the ordering of the nodes in the order map resulting from adding
+/// all `edges` at once.
+///
+/// If a cycle is detected, an error is returned and `order_map` remains
+/// unchanged.
+///
+/// Only the nodes between the lowest and the highest position spanned by
+/// edges that violate the current order need to move: any cycle, and any
+/// path between two of these nodes, lies within that range. These nodes
+/// are sorted topologically (Kahn's algorithm) and reassigned the same set
+/// of positions.
+fn update_ordering_batch<G>(
+    graph: G,
+    order_map: &mut OrderMap<G::NodeId>,
+    edges: &[(G::NodeId, G::NodeId)],
+) -> Result<(), Cycle<G::NodeId>>
+where
+    G: NodeIndexable + IntoNeighborsDirected,
+    G::NodeId: IndexType,
+{
+    // The range of positions spanned by the edges that violate the order
+    let mut region: Option<(TopologicalPosition, TopologicalPosition)> = None;
+    for &(a, b) in edges {
+        let (pos_a, pos_b) = (
+            order_map.get_position(a, graph),
+            order_map.get_position(b, graph),
+        );
+        if pos_b < pos_a {
+            region = Some(match region {
+                None => (pos_b, pos_a),
+                Some((min, max)) => (min.min(pos_b), max.max(pos_a)),
+            });
+        }
+    }
+    let (min_order, max_order) = match region {
+        None => return Ok(()), // Order is already correct
+        Some(region) => region,
+    };
+    let in_region = |pos| min_order <= pos && pos <= max_order;
+
+    // The new edges within the region, indexed by direction
+    let mut added = [HashMap::<_, Vec<_>>::new(), HashMap::<_, Vec<_>>::new()];
+    for &(a, b) in edges {
+        if in_region(order_map.get_position(a, graph))
+            && in_region(order_map.get_position(b, graph))
+        {
+            added[Direction::Outgoing.index()]
+                .entry(a)
+                .or_default()
+                .push(b);
+            added[Direction::Incoming.index()]
+                .entry(b)
+                .or_default()
+                .push(a);
+        }
+    }
+    let neighbors = |n, dir: Direction| {
+        graph
+            .neighbors_directed(n, dir)
+            .filter(|&m| in_region(order_map.get_position(m, graph)))
+            .chain(added[dir.index()].get(&n).into_iter().flatten().copied())
+    };
+
+    // Kahn's algorithm over the region, starting from the current order
+    let nodes: Vec<_> = order_map.range(min_order..=max_order).collect();
+    let mut in_degree: HashMap<_, usize> = nodes
+        .iter()
+        .map(|&n| (n, neighbors(n, Direction::Incoming).count()))
+        .collect();
+    let mut queue: VecDeque<_> = nodes
+        .iter()
+        .filter(|n| in_degree[n] == 0)
+        .copied()
+        .collect();
+    let mut sorted = Vec::with_capacity(nodes.len());
+    while let Some(n) = queue.pop_front() {
+        sorted.push(n);
+        for m in neighbors(n, Direction::Outgoing) {
+            let degree = in_degree.get_mut(&m).unwrap();
+            *degree -= 1;
+            if *degree == 0 {
+                queue.push_back(m);
+            }
+        }
+    }
+
+    if sorted.len() < nodes.len() {
+        // Every unsorted node has an unsorted predecessor: walk backwards
+        // until a node repeats, it then lies on a cycle.
+        let mut visited = HashSet::new();
+        let mut n = *nodes.iter().find(|n| in_degree[n] > 0).unwrap();
+        while visited.insert(n) {
+            n = neighbors(n, Direction::Incoming)
+                .find(|m| in_degree[m] > 0)
+                .unwrap();
+        }
+        return Err(Cycle(n));
+    }
+
+    let positions: Vec<_> = nodes
+        .iter()
+        .map(|&n| order_map.get_position(n, graph))
+        .collect();
+    for (pos, node) in positions.into_iter().zip(sorted) {
+        order_map.set_position(node, pos, graph);
+    }
+    Ok(())
+}
+
+/// Check that `order` lists every node of `graph` exactly once.
+fn check_permutation<G>(
+    graph: G,
+    order: impl Iterator<Item = G::NodeId>,
+) -> Result<(), AcyclicOrderError<G::NodeId>>
+where
+    G: NodeIndexable + IntoNodeIdentifiers,
+{
+    let mut missing = FixedBitSet::with_capacity(graph.node_bound());
+    missing.extend(graph.node_identifiers().map(|n| graph.to_index(n)));
+    for id in order {
+        let idx = graph.to_index(id);
+        if idx >= missing.len() || !missing[idx] {
+            return Err(AcyclicOrderError::InvalidNode(id));
+        }
+        missing.set(idx, false);
+    }
+    match missing.ones().next() {
+        Some(idx) => Err(AcyclicOrderError::MissingNode(graph.from_index(idx))),
+        None => Ok(()),
+    }
+}
+
+/// Compute the transitive closure of an acyclic graph, in reverse topological
+/// order: for each node index, the indices of the nodes reachable from it
+/// through at least one edge.
+fn reachability_index<G>(graph: G, order_map: &OrderMap<G::NodeId>) -> Vec<FixedBitSet>
+where
+    G: NodeIndexable + IntoNeighborsDirected,
+{
+    let bound = graph.node_bound();
+    let mut index = vec![FixedBitSet::with_capacity(bound); bound];
+    let order: Vec<_> = order_map.nodes_iter().collect();
+    for &n in order.iter().rev() {
+        let mut reach = FixedBitSet::with_capacity(bound);
+        for succ in graph.neighbors_directed(n, Direction::Outgoing) {
+            reach.insert(graph.to_index(succ));
+            reach.union_with(&index[graph.to_index(succ)]);
+        }
+        index[graph.to_index(n)] = reach;
+    }
+    index
+}
+
 /// Traverse nodes in `graph` in DFS order, starting from `start`, for as long
 /// as the predicate `valid_order` returns `true` on the current node's order.
 fn dfs<G: NodeIndexable + IntoNeighborsDirected + IntoNodeIdentifiers + Visitable>(
@@ -548,6 +1408,8 @@ where
 // - IntoNeighborsDirected
 // - IntoNodeIdentifiers
 // - IntoNodeReferences
+//
+// The `Into*` traits above are also implemented for a directed `MatrixGraph`.
 
 impl<G: Visitable + Data> Data for Acyclic<G> {
     type NodeWeight = G::NodeWeight;
@@ -660,20 +1522,199 @@ macro_rules! impl_graph_traits {
                 &mut self,
                 e: <$graph_type<N, E, Ix> as GraphBase>::EdgeId,
             ) -> Option<E> {
-                self.graph.remove_edge(e)
+                let weight = self.graph.remove_edge(e)?;
+                if self.reachability_index.is_some() {
+                    let index = reachability_index(&self.graph, &self.order_map);
+                    self.reachability_index = Some(index);
+                }
+                Some(weight)
             }
 
             /// Remove a node from the graph if it exists, and return its
             /// weight. If it doesn't exist in the graph, return None.
             ///
-            /// This updates the order in O(v) runtime and removes the node in
-            /// the underlying graph.
+            /// The position of the node in the topological order is freed
+            /// without moving any other node, in O(log v) runtime. If the
+            /// underlying graph moves another node into the index of the
+            /// removed node (as [`DiGraph`] does), that node keeps its
+            /// position. Use [`Acyclic::normalize_order`] to compact the
+            /// positions after many removals.
             pub fn remove_node(
                 &mut self,
                 n: <$graph_type<N, E, Ix> as GraphBase>::NodeId,
             ) -> Option<N> {
+                self.graph.node_weight(n)?;
+                let last = NodeIndex::new(self.graph.node_bound() - 1);
                 self.order_map.remove_node(n, &self.graph);
-                self.graph.remove_node(n)
+                let weight = self.graph.remove_node(n);
+                if n != last && self.graph.node_weight(n).is_some() {
+                    // The last node was moved into the index of `n`
+                    self.order_map.rename_node(last, n, &self.graph);
+                }
+                if self.reachability_index.is_some() {
+                    let index = reachability_index(&self.graph, &self.order_map);
+                    self.reachability_index = Some(index);
+                }
+                weight
+            }
+
+            /// Remove the redundant edges of the graph, leaving its transitive
+            /// reduction in place.
+            ///
+            /// An edge `a -> b` is redundant if `b` can be reached from `a`
+            /// through a longer path, or if it is parallel to another edge
+            /// from `a` to `b`. Returns the removed edges as
+            /// `(source, target, weight)` triples. Reachability, and hence the
+            /// topological order, is unchanged.
+            ///
+            /// See [`crate::algo::tred::transitive_reduction`] for the
+            /// complexity.
+            pub fn transitive_reduction(&mut self) -> Vec<(NodeIndex<Ix>, NodeIndex<Ix>, E)> {
+                let order: Vec<_> = self.nodes_iter().collect();
+                let (_, mut redundant) = partition_redundant_edges(&self.graph, &order);
+                // Remove the edges with the highest indices first, as removing an
+                // edge may move the last edge into the removed index.
+                redundant.sort_unstable_by(|a, b| b.cmp(a));
+                let mut removed: Vec<_> = redundant
+                    .into_iter()
+                    .filter_map(|e| {
+                        let (a, b) = self.graph.edge_endpoints(e)?;
+                        Some((a, b, self.graph.remove_edge(e)?))
+                    })
+                    .collect();
+                removed.reverse();
+                removed
+            }
+
+            /// Merge the nodes and edges of `other` into `self`, together with
+            /// new edges between the two graphs.
+            ///
+            /// Each cross edge `(a, b, dir, weight)` connects the node `a` of
+            /// `self` to the node `b` of `other`: it is the edge `a -> b` if
+            /// `dir` is [`Direction::Outgoing`], and `b -> a` otherwise.
+            ///
+            /// On success, return the map from the node indices of `other` to
+            /// their new indices in `self`, indexed by the old node index.
+            /// Vacant indices of `other` map to [`NodeIndex::end`].
+            ///
+            /// The topological order of `self` is extended with that of
+            /// `other` and updated once for all cross edges. If a cross edge
+            /// refers to a node that does not exist or if the cross edges would
+            /// create a cycle, an [`AcyclicEdgeError`] is returned, and the
+            /// nodes, edges and topological order of `self` are unchanged.
+            /// With a [`StableDiGraph`], the nodes of `other` are added then
+            /// removed when a cycle is found: their indices are left vacant,
+            /// so that the node bound and the indices of the nodes added later
+            /// differ.
+            ///
+            /// # Example
+            /// ```rust
+            /// use petgraph::acyclic::Acyclic;
+            /// use petgraph::data::Build;
+            /// use petgraph::prelude::*;
+            ///
+            /// let mut fetch = Acyclic::<DiGraph<&str, ()>>::new();
+            /// let download = fetch.add_node("download");
+            /// let unpack = fetch.add_node("unpack");
+            /// fetch.try_add_edge(download, unpack, ()).unwrap();
+            ///
+            /// let mut build = Acyclic::<DiGraph<&str, ()>>::new();
+            /// let compile = build.add_node("compile");
+            /// let test = build.add_node("test");
+            /// build.try_add_edge(compile, test, ()).unwrap();
+            ///
+            /// let cross_edges = [
+            ///     (unpack, compile, Direction::Outgoing, ()),
+            ///     (download, test, Direction::Incoming, ()),
+            /// ];
+            /// // `test -> download` would close the cycle `download -> ... -> test`
+            /// assert!(fetch.try_union(build.clone(), cross_edges).is_err());
+            /// assert_eq!(fetch.node_count(), 2);
+            ///
+            /// let node_map = fetch
+            ///     .try_union(build, [(unpack, compile, Direction::Outgoing, ())])
+            ///     .unwrap();
+            /// assert_eq!(fetch[node_map[test.index()]], "test");
+            /// assert!(fetch.is_reachable(download, node_map[test.index()]));
+            /// ```
+            pub fn try_union<I>(
+                &mut self,
+                other: Self,
+                cross_edges: I,
+            ) -> Result<Vec<NodeIndex<Ix>>, AcyclicEdgeError<NodeIndex<Ix>>>
+            where
+                I: IntoIterator<Item = (NodeIndex<Ix>, NodeIndex<Ix>, Direction, E)>,
+            {
+                let other_order: Vec<_> = other.nodes_iter().collect();
+                let mut other = other.graph;
+                let mut node_map = vec![NodeIndex::end(); other.node_bound()];
+
+                // Check the endpoints of the cross edges before modifying `self`
+                let cross_edges: Vec<_> = cross_edges.into_iter().collect();
+                for &(a, b, _, _) in &cross_edges {
+                    if self.graph.node_weight(a).is_none() || other.node_weight(b).is_none() {
+                        return Err(AcyclicEdgeError::InvalidEdge);
+                    }
+                }
+
+                // Move out the weights of `other`, removing the elements with
+                // the highest indices first so that no index is moved.
+                let mut edges = Vec::with_capacity(other.edge_count());
+                let edge_indices: Vec<_> = other.edge_indices().collect();
+                for &e in edge_indices.iter().rev() {
+                    let (a, b) = other.edge_endpoints(e).unwrap();
+                    edges.push((a, b, other.remove_edge(e).unwrap()));
+                }
+                let mut node_weights: Vec<_> = (0..other.node_bound()).map(|_| None).collect();
+                let node_indices: Vec<_> = other.node_indices().collect();
+                for &n in node_indices.iter().rev() {
+                    node_weights[n.index()] = other.remove_node(n);
+                }
+
+                // Append the nodes in the order of `other`: the edges of `other`
+                // are consistent with the order.
+                let index = self.reachability_index.take();
+                let mut added = Vec::with_capacity(other_order.len());
+                for n in other_order {
+                    let weight = node_weights[n.index()].take().unwrap();
+                    let new_n = self.graph.add_node(weight);
+                    self.order_map.add_node(new_n, &self.graph);
+                    node_map[n.index()] = new_n;
+                    added.push(new_n);
+                }
+                for (a, b, weight) in edges.into_iter().rev() {
+                    self.graph
+                        .add_edge(node_map[a.index()], node_map[b.index()], weight);
+                }
+
+                let cross_edges: Vec<_> = cross_edges
+                    .into_iter()
+                    .map(|(a, b, dir, weight)| {
+                        let b = node_map[b.index()];
+                        match dir {
+                            Direction::Outgoing => (a, b, weight),
+                            Direction::Incoming => (b, a, weight),
+                        }
+                    })
+                    .collect();
+                let endpoints: Vec<_> = cross_edges.iter().map(|&(a, b, _)| (a, b)).collect();
+                if let Err(cycle) =
+                    update_ordering_batch(&self.graph, &mut self.order_map, &endpoints)
+                {
+                    for &n in added.iter().rev() {
+                        self.remove_node(n);
+                    }
+                    self.reachability_index = index;
+                    return Err(cycle.into());
+                }
+                for (a, b, weight) in cross_edges {
+                    self.graph.add_edge(a, b, weight);
+                }
+
+                if index.is_some() {
+                    self.reachability_index = Some(reachability_index(&self.graph, &self.order_map));
+                }
+                Ok(node_map)
             }
         }
 
@@ -691,64 +1732,72 @@ macro_rules! impl_graph_traits {
                     order_map,
                     discovered,
                     finished,
+                    reachability_index: None,
                 })
             }
         }
 
-        impl<'a, N, E, Ix: IndexType> IntoEdgeReferences for &'a Acyclic<$graph_type<N, E, Ix>> {
-            type EdgeRef = <&'a $graph_type<N, E, Ix> as IntoEdgeReferences>::EdgeRef;
-            type EdgeReferences = <&'a $graph_type<N, E, Ix> as IntoEdgeReferences>::EdgeReferences;
+        impl_visit_traits!([N, E, Ix: IndexType] $graph_type<N, E, Ix>);
+    };
+}
+
+// Pass-through implementations of the visit traits on references
+macro_rules! impl_visit_traits {
+    ([$($gen:tt)*] $graph_type:ty) => {
+        impl<'a, $($gen)*> IntoEdgeReferences for &'a Acyclic<$graph_type> {
+            type EdgeRef = <&'a $graph_type as IntoEdgeReferences>::EdgeRef;
+            type EdgeReferences = <&'a $graph_type as IntoEdgeReferences>::EdgeReferences;
 
             fn edge_references(self) -> Self::EdgeReferences {
                 self.inner().edge_references()
             }
         }
 
-        impl<'a, N, E, Ix: IndexType> IntoEdges for &'a Acyclic<$graph_type<N, E, Ix>> {
-            type Edges = <&'a $graph_type<N, E, Ix> as IntoEdges>::Edges;
+        impl<'a, $($gen)*> IntoEdges for &'a Acyclic<$graph_type> {
+            type Edges = <&'a $graph_type as IntoEdges>::Edges;
 
             fn edges(self, a: Self::NodeId) -> Self::Edges {
                 self.inner().edges(a)
             }
         }
 
-        impl<'a, N, E, Ix: IndexType> IntoEdgesDirected for &'a Acyclic<$graph_type<N, E, Ix>> {
-            type EdgesDirected = <&'a $graph_type<N, E, Ix> as IntoEdgesDirected>::EdgesDirected;
+        impl<'a, $($gen)*> IntoEdgesDirected for &'a Acyclic<$graph_type> {
+            type EdgesDirected = <&'a $graph_type as IntoEdgesDirected>::EdgesDirected;
 
             fn edges_directed(self, a: Self::NodeId, dir: Direction) -> Self::EdgesDirected {
                 self.inner().edges_directed(a, dir)
             }
         }
 
-        impl<'a, N, E, Ix: IndexType> IntoNeighbors for &'a Acyclic<$graph_type<N, E, Ix>> {
-            type Neighbors = <&'a $graph_type<N, E, Ix> as IntoNeighbors>::Neighbors;
+        impl<'a, $($gen)*> IntoNeighbors for &'a Acyclic<$graph_type> {
+            type Neighbors = <&'a $graph_type as IntoNeighbors>::Neighbors;
 
             fn neighbors(self, a: Self::NodeId) -> Self::Neighbors {
                 self.inner().neighbors(a)
             }
         }
 
-        impl<'a, N, E, Ix: IndexType> IntoNeighborsDirected for &'a Acyclic<$graph_type<N, E, Ix>> {
+        impl<'a, $($gen)*> IntoNeighborsDirected for &'a Acyclic<$graph_type> {
             type NeighborsDirected =
-                <&'a $graph_type<N, E, Ix> as IntoNeighborsDirected>::NeighborsDirected;
+                <&'a $graph_type as IntoNeighborsDirected>::NeighborsDirected;
 
             fn neighbors_directed(self, n: Self::NodeId, d: Direction) -> Self::NeighborsDirected {
                 self.inner().neighbors_directed(n, d)
             }
         }
 
-        impl<'a, N, E, Ix: IndexType> IntoNodeIdentifiers for &'a Acyclic<$graph_type<N, E, Ix>> {
+        impl<'a, $($gen)*> IntoNodeIdentifiers for &'a Acyclic<$graph_type> {
             type NodeIdentifiers =
-                <&'a $graph_type<N, E, Ix> as IntoNodeIdentifiers>::NodeIdentifiers;
+                <&'a $graph_type as IntoNodeIdentifiers>::NodeIdentifiers;
 
             fn node_identifiers(self) -> Self::NodeIdentifiers {
                 self.inner().node_identifiers()
             }
         }
 
-        impl<'a, N, E, Ix: IndexType> IntoNodeReferences for &'a Acyclic<$graph_type<N, E, Ix>> {
-            type NodeRef = <&'a $graph_type<N, E, Ix> as IntoNodeReferences>::NodeRef;
-            type NodeReferences = <&'a $graph_type<N, E, Ix> as IntoNodeReferences>::NodeReferences;
+        impl<'a, $($gen)*> IntoNodeReferences for &'a Acyclic<$graph_type> {
+            type NodeRef = <&'a $graph_type as IntoNodeReferences>::NodeRef;
+            type NodeReferences = <&'a $graph_type as IntoNodeReferences>::NodeReferences;
 
             fn node_references(self) -> Self::NodeReferences {
                 self.inner().node_references()
@@ -760,6 +1809,11 @@ macro_rules! impl_graph_traits {
 impl_graph_traits!(DiGraph);
 #[cfg(feature = "stable_graph")]
 impl_graph_traits!(StableDiGraph);
+#[cfg(feature = "matrix_graph")]
+impl_visit_traits!(
+    [N, E, Null: Nullable<Wrapped = E>, Ix: IndexType]
+    MatrixGraph<N, E, Directed, Null, Ix>
+);
 
 #[cfg(test)]
 mod tests {
@@ -801,6 +1855,167 @@ mod tests {
         assert!(acyclic.add_edge(d, a, ()).is_none());
     }
 
+    #[test]
+    fn test_topological_positions() {
+        let mut acyclic = Acyclic::<DiGraph<(), ()>>::new();
+        let nodes: Vec<_> = (0..4).map(|_| acyclic.add_node(())).collect();
+        let positions = |acyclic: &Acyclic<DiGraph<(), ()>>| -> Vec<_> {
+            nodes.iter().map(|&n| acyclic.get_position(n)).collect()
+        };
+        let initial = positions(&acyclic);
+        for (&n, &pos) in nodes.iter().zip(&initial) {
+            assert_eq!(acyclic.at_position(pos), Some(n));
+        }
+        assert_eq!(acyclic.nodes_iter().collect::<Vec<_>>(), nodes);
+
+        // Edges consistent with the order do not move any node.
+        acyclic.try_add_edge(nodes[0], nodes[2], ()).unwrap();
+        assert_eq!(positions(&acyclic), initial);
+
+        // Only the nodes between the endpoints move, within their positions.
+        acyclic.try_add_edge(nodes[3], nodes[1], ()).unwrap();
+        let current = positions(&acyclic);
+        assert_eq!(current[0], initial[0]);
+        assert!(current[3] < current[1]);
+        let mut moved = current[1..].to_vec();
+        moved.sort();
+        assert_eq!(moved, initial[1..]);
+
+        // Failed additions do not move any node.
+        acyclic.try_add_edge(nodes[1], nodes[3], ()).unwrap_err();
+        assert_eq!(positions(&acyclic), current);
+
+        // New nodes are appended to the order.
+        let last = acyclic.add_node(());
+        assert!(current.iter().all(|&pos| pos < acyclic.get_position(last)));
+        assert_eq!(positions(&acyclic), current);
+    }
+
+    #[test]
+    fn test_cycle_path() {
+        let mut acyclic = Acyclic::<DiGraph<(), ()>>::new();
+        let nodes: Vec<_> = (0..5).map(|_| acyclic.add_node(())).collect();
+        // A long and a short path from 4 to 0, added against the order.
+        for &(a, b) in &[(4, 3), (3, 2), (2, 1), (1, 0), (4, 1)] {
+            acyclic.try_add_edge(nodes[a], nodes[b], ()).unwrap();
+        }
+
+        assert_eq!(
+            acyclic.cycle_path(nodes[0], nodes[4]),
+            Some(vec![nodes[4], nodes[1], nodes[0]])
+        );
+        assert_eq!(
+            acyclic.cycle_path(nodes[2], nodes[3]),
+            Some(vec![nodes[3], nodes[2]])
+        );
+        assert_eq!(acyclic.cycle_path(nodes[2], nodes[2]), Some(vec![nodes[2]]));
+        for (a, b) in [(4, 0), (3, 1), (2, 0)] {
+            assert_eq!(acyclic.cycle_path(nodes[a], nodes[b]), None);
+        }
+        acyclic.try_add_edge(nodes[4], nodes[0], ()).unwrap();
+        assert_eq!(
+            acyclic.cycle_path(nodes[0], nodes[4]),
+            Some(vec![nodes[4], nodes[0]])
+        );
+    }
+
+    #[test]
+    fn test_nodes_between() {
+        let mut acyclic = Acyclic::<DiGraph<(), ()>>::new();
+        let nodes: Vec<_> = (0..6).map(|_| acyclic.add_node(())).collect();
+        for &(a, b) in &[(0, 2), (2, 5), (5, 3), (1, 4)] {
+            acyclic.try_add_edge(nodes[a], nodes[b], ()).unwrap();
+        }
+        let order: Vec<_> = acyclic.nodes_iter().collect();
+        let (first, last) = (order[0], order[5]);
+
+        assert_eq!(
+            acyclic.nodes_between(first, last).collect::<Vec<_>>(),
+            order[1..5]
+        );
+        assert_eq!(acyclic.nodes_between(last, first).count(), 0);
+        assert_eq!(acyclic.nodes_between(first, first).count(), 0);
+
+        // Node 3 is reachable from node 0, but not before node 5.
+        assert_eq!(
+            acyclic.reachable_between(nodes[0], nodes[5]),
+            vec![nodes[2]]
+        );
+        assert_eq!(
+            acyclic.reachable_between(nodes[0], nodes[3]),
+            vec![nodes[2], nodes[5]]
+        );
+        assert_eq!(acyclic.reachable_between(nodes[3], nodes[0]), vec![]);
+        assert_eq!(
+            acyclic.reachable_between(nodes[1], nodes[3]),
+            vec![nodes[4]]
+        );
+        assert_eq!(acyclic.reachable_between(nodes[1], nodes[4]), vec![]);
+    }
+
+    #[test]
+    fn test_rebuild_order() {
+        let mut acyclic = Acyclic::<DiGraph<(), ()>>::new();
+        let nodes: Vec<_> = (0..6).map(|_| acyclic.add_node(())).collect();
+        for &(a, b) in &[(5, 4), (4, 3), (3, 0), (1, 0)] {
+            acyclic.try_add_edge(nodes[a], nodes[b], ()).unwrap();
+        }
+        acyclic.remove_node(nodes[5]);
+        acyclic.add_node(());
+
+        acyclic.rebuild_order();
+        assert_valid_topological_order(&acyclic);
+        for (i, n) in acyclic.nodes_iter().enumerate() {
+            assert_eq!(acyclic.get_position(n), TopologicalPosition(i));
+        }
+
+        acyclic.rebuild_order_by_key(|n| n.index());
+        assert_valid_topological_order(&acyclic);
+        let order: Vec<_> = acyclic.nodes_iter().map(|n| n.index()).collect();
+        assert_eq!(order, vec![1, 2, 4, 3, 0, 5]);
+        for (i, n) in acyclic.nodes_iter().enumerate() {
+            assert_eq!(acyclic.get_position(n), TopologicalPosition(i));
+        }
+    }
+
+    #[test]
+    fn test_try_add_edges() {
+        let mut acyclic = Acyclic::<DiGraph<(), ()>>::new();
+        let nodes: Vec<_> = (0..6).map(|_| acyclic.add_node(())).collect();
+        let edge = |i: usize, j: usize| (nodes[i], nodes[j], ());
+
+        // Add the path 5 -> 4 -> ... -> 0 in a single batch, against the
+        // initial order, plus an edge that is already correctly ordered.
+        let edges = acyclic
+            .try_add_edges(vec![edge(1, 0), edge(3, 2), edge(0, 5), edge(2, 1)])
+            .unwrap();
+        assert_eq!(edges.len(), 4);
+        assert_valid_topological_order(&acyclic);
+
+        // Each edge is valid on its own, but together they close a cycle.
+        assert!(acyclic.is_valid_edge(nodes[4], nodes[3]));
+        assert!(acyclic.is_valid_edge(nodes[5], nodes[4]));
+        let err = acyclic
+            .try_add_edges(vec![edge(4, 3), edge(5, 4)])
+            .unwrap_err();
+        assert!(matches!(err, AcyclicEdgeError::Cycle(_)));
+        assert_eq!(acyclic.edge_count(), 4);
+        assert_valid_topological_order(&acyclic);
+
+        // Self-loops are rejected.
+        assert_eq!(
+            acyclic.try_add_edges(vec![edge(4, 3), edge(4, 4)]),
+            Err(AcyclicEdgeError::SelfLoop)
+        );
+
+        // Valid batches within the reordered region are accepted.
+        acyclic
+            .try_add_edges(vec![edge(4, 3), edge(3, 1), edge(4, 0)])
+            .unwrap();
+        assert_eq!(acyclic.edge_count(), 7);
+        assert_valid_topological_order(&acyclic);
+    }
+
     #[cfg(feature = "stable_graph")]
     #[test]
     fn test_acyclic_graph_add_remove() {
@@ -838,6 +2053,301 @@ mod tests {
         assert!(!acyclic.inner().contains_edge(a, c));
     }
 
+    #[test]
+    fn test_remove_node_and_normalize_order() {
+        // With DiGraph, removing a node moves the last node into its index.
+        let mut acyclic = Acyclic::<DiGraph<u32, ()>>::new();
+        let nodes: Vec<_> = (0..5).map(|i| acyclic.add_node(i)).collect();
+        for &(a, b) in &[(4, 3), (3, 2), (2, 0), (1, 0)] {
+            acyclic.try_add_edge(nodes[a], nodes[b], ()).unwrap();
+        }
+        let pos_4 = acyclic.get_position(nodes[4]);
+
+        assert_eq!(acyclic.remove_node(nodes[1]), Some(1));
+        assert_eq!(acyclic.inner()[nodes[1]], 4);
+        assert_eq!(acyclic.get_position(nodes[1]), pos_4);
+        assert_eq!(acyclic.at_position(pos_4), Some(nodes[1]));
+        assert_valid_topological_order(&acyclic);
+
+        // Removing a node that does not exist is a no-op.
+        assert_eq!(acyclic.remove_node(nodes[4]), None);
+        assert_valid_topological_order(&acyclic);
+
+        assert_eq!(acyclic.remove_node(nodes[3]), Some(3));
+        let order: Vec<_> = acyclic.nodes_iter().collect();
+        acyclic.normalize_order();
+        assert_eq!(acyclic.nodes_iter().collect::<Vec<_>>(), order);
+        for (i, &n) in order.iter().enumerate() {
+            assert_eq!(acyclic.at_position(TopologicalPosition(i)), Some(n));
+        }
+        assert_valid_topological_order(&acyclic);
+
+        // The order is still maintained after normalization.
+        let n = acyclic.add_node(5);
+        assert_eq!(
+            acyclic.get_position(n),
+            TopologicalPosition(acyclic.node_count() - 1)
+        );
+        acyclic.try_add_edge(n, nodes[1], ()).unwrap();
+        assert_valid_topological_order(&acyclic);
+    }
+
+    #[test]
+    fn test_reachability_index() {
+        use crate::algo::has_path_connecting;
+
+        fn assert_reachability(acyclic: &Acyclic<DiGraph<(), ()>>) {
+            for u in acyclic.node_indices() {
+                for v in acyclic.node_indices() {
+                    assert_eq!(
+                        acyclic.is_reachable(u, v),
+                        has_path_connecting(acyclic.inner(), u, v, None),
+                        "{:?} -> {:?}",
+                        u,
+                        v
+                    );
+                }
+            }
+        }
+
+        let mut acyclic = Acyclic::<DiGraph<(), ()>>::new();
+        let nodes: Vec<_> = (0..6).map(|_| acyclic.add_node(())).collect();
+        acyclic.try_add_edge(nodes[4], nodes[5], ()).unwrap();
+        acyclic.enable_reachability_index();
+        assert!(acyclic.has_reachability_index());
+        assert_reachability(&acyclic);
+
+        // Edges are added after nodes that are not yet in the index.
+        let n = acyclic.add_node(());
+        for &(a, b) in &[(5, 3), (0, 1), (3, 0), (1, 2)] {
+            acyclic.try_add_edge(nodes[a], nodes[b], ()).unwrap();
+            assert_reachability(&acyclic);
+        }
+        acyclic
+            .try_add_edges(vec![(n, nodes[4], ()), (nodes[2], n, ())])
+            .unwrap_err();
+        acyclic.try_add_edges(vec![(n, nodes[4], ())]).unwrap();
+        assert!(acyclic.is_reachable(n, nodes[2]));
+        assert_reachability(&acyclic);
+
+        let e = acyclic.find_edge(nodes[3], nodes[0]).unwrap();
+        acyclic.remove_edge(e);
+        assert!(!acyclic.is_reachable(n, nodes[2]));
+        assert_reachability(&acyclic);
+
+        acyclic.try_add_edge(nodes[5], nodes[0], ()).unwrap();
+        acyclic.remove_node(nodes[4]);
+        assert_reachability(&acyclic);
+
+        // Without the index, reachability is computed on demand.
+        acyclic.disable_reachability_index();
+        assert!(!acyclic.has_reachability_index());
+        assert_reachability(&acyclic);
+    }
+
+    #[test]
+    fn test_ancestors_descendants() {
+        let mut acyclic = Acyclic::<DiGraph<(), ()>>::new();
+        let nodes: Vec<_> = (0..6).map(|_| acyclic.add_node(())).collect();
+        for &(a, b) in &[(5, 3), (3, 1), (4, 1), (1, 0), (4, 2)] {
+            acyclic.try_add_edge(nodes[a], nodes[b], ()).unwrap();
+        }
+
+        let descendants: Vec<_> = acyclic.descendants(nodes[4]).collect();
+        assert_eq!(descendants.len(), 3);
+        assert!(descendants
+            .windows(2)
+            .all(|w| acyclic.get_position(w[0]) < acyclic.get_position(w[1])));
+        let mut sorted = descendants.clone();
+        sorted.sort();
+        assert_eq!(sorted, vec![nodes[0], nodes[1], nodes[2]]);
+
+        let ancestors: Vec<_> = acyclic.ancestors(nodes[0]).collect();
+        assert_eq!(ancestors.len(), 4);
+        assert!(ancestors.iter().all(|&n| acyclic.is_reachable(n, nodes[0])));
+        assert_eq!(acyclic.ancestors(nodes[5]).count(), 0);
+        assert_eq!(acyclic.descendants(nodes[0]).count(), 0);
+    }
+
+    #[test]
+    fn test_longest_path() {
+        let empty = Acyclic::<DiGraph<(), ()>>::new();
+        assert_eq!(empty.longest_path(|_| 1, |_| 1), (0, vec![]));
+
+        let mut acyclic = Acyclic::<DiGraph<i32, i32>>::new();
+        let nodes: Vec<_> = (0..5).map(|i| acyclic.add_node(i)).collect();
+        for &(a, b, w) in &[(0, 1, 1), (1, 2, 1), (0, 3, 5), (3, 2, -1), (2, 4, -10)] {
+            acyclic.try_add_edge(nodes[a], nodes[b], w).unwrap();
+        }
+
+        // Edge costs only
+        let (cost, path) = acyclic.longest_path(|_| 0, |e| *e.weight());
+        assert_eq!(cost, 5);
+        assert_eq!(path, vec![nodes[0], nodes[3]]);
+
+        // Node costs only: zero-cost edges extend the path
+        let (cost, path) = acyclic.longest_path(|n| acyclic[n], |_| 0);
+        assert_eq!(cost, 9);
+        assert_eq!(path, vec![nodes[0], nodes[3], nodes[2], nodes[4]]);
+
+        // A single node with the highest cost
+        let (cost, path) = acyclic.longest_path(|n| if n == nodes[1] { 3 } else { -1 }, |_| -1);
+        assert_eq!((cost, path), (3, vec![nodes[1]]));
+    }
+
+    #[cfg(feature = "stable_graph")]
+    #[test]
+    fn test_try_union() {
+        let mut left = Acyclic::<StableDiGraph<u32, ()>>::new();
+        let l: Vec<_> = (0..3).map(|i| left.add_node(i)).collect();
+        left.try_add_edge(l[0], l[1], ()).unwrap();
+        left.remove_node(l[2]);
+
+        let mut right = Acyclic::<StableDiGraph<u32, ()>>::new();
+        let r: Vec<_> = (10..14).map(|i| right.add_node(i)).collect();
+        right.try_add_edge(r[3], r[2], ()).unwrap();
+        right.try_add_edge(r[2], r[0], ()).unwrap();
+        right.remove_node(r[1]);
+        left.enable_reachability_index();
+
+        // Rejected merges leave `left` unchanged.
+        let cycle = [
+            (l[1], r[3], Direction::Outgoing, ()),
+            (l[0], r[0], Direction::Incoming, ()),
+        ];
+        assert!(matches!(
+            left.try_union(right.clone(), cycle),
+            Err(AcyclicEdgeError::Cycle(_))
+        ));
+        let missing = [(l[2], r[0], Direction::Outgoing, ())];
+        assert_eq!(
+            left.try_union(right.clone(), missing),
+            Err(AcyclicEdgeError::InvalidEdge)
+        );
+        assert_eq!(left.node_count(), 2);
+        assert_eq!(left.edge_count(), 1);
+        assert_valid_topological_order(&left);
+
+        let cross = [
+            (l[1], r[3], Direction::Incoming, ()),
+            (l[0], r[0], Direction::Incoming, ()),
+        ];
+        let node_map = left.try_union(right, cross).unwrap();
+        assert_eq!(node_map[r[1].index()], NodeIndex::end());
+        assert_eq!(left.node_count(), 5);
+        assert_eq!(left.edge_count(), 5);
+        for &n in &[r[0], r[2], r[3]] {
+            assert_eq!(left[node_map[n.index()]], n.index() as u32 + 10);
+        }
+        assert!(left.is_reachable(node_map[r[3].index()], l[1]));
+        assert!(!left.is_reachable(l[0], node_map[r[0].index()]));
+        assert_valid_topological_order(&left);
+    }
+
+    #[test]
+    fn test_from_parts() {
+        let graph = DiGraph::<(), ()>::from_edges([(0, 1), (2, 1), (1, 3)]);
+        let n = NodeIndex::new;
+
+        let acyclic = Acyclic::try_from_parts(graph.clone(), vec![n(2), n(0), n(1), n(3)]).unwrap();
+        assert_eq!(acyclic.validate(), Ok(()));
+        assert_valid_topological_order(&acyclic);
+
+        let err = |order: Vec<usize>| {
+            Acyclic::try_from_parts(graph.clone(), order.into_iter().map(n)).unwrap_err()
+        };
+        assert_eq!(err(vec![0, 2, 1, 1]), AcyclicOrderError::InvalidNode(n(1)));
+        assert_eq!(
+            err(vec![0, 2, 1, 3, 4]),
+            AcyclicOrderError::InvalidNode(n(4))
+        );
+        assert_eq!(err(vec![0, 1, 3]), AcyclicOrderError::MissingNode(n(2)));
+        assert_eq!(
+            err(vec![0, 1, 2, 3]),
+            AcyclicOrderError::InconsistentEdge(n(2), n(1))
+        );
+
+        let unchecked = Acyclic::from_parts_unchecked(graph.clone(), vec![n(3), n(0), n(2), n(1)]);
+        assert_eq!(
+            unchecked.validate(),
+            Err(AcyclicOrderError::InconsistentEdge(n(1), n(3)))
+        );
+        let unchecked = Acyclic::from_parts_unchecked(graph, vec![n(0), n(2), n(1)]);
+        assert_eq!(
+            unchecked.validate(),
+            Err(AcyclicOrderError::MissingNode(n(3)))
+        );
+    }
+
+    #[test]
+    fn test_transitive_reduction() {
+        let mut acyclic = Acyclic::<DiGraph<(), u32>>::new();
+        let nodes: Vec<_> = (0..4).map(|_| acyclic.add_node(())).collect();
+        let edges = [
+            (0, 1, 0),
+            (1, 2, 1),
+            (0, 2, 2),
+            (2, 3, 3),
+            (0, 3, 4),
+            (1, 2, 1),
+        ];
+        for &(a, b, w) in &edges {
+            acyclic.try_add_edge(nodes[a], nodes[b], w).unwrap();
+        }
+        acyclic.enable_reachability_index();
+        let order: Vec<_> = acyclic.nodes_iter().collect();
+
+        let removed = acyclic.transitive_reduction();
+        let mut removed_weights: Vec<_> = removed.iter().map(|&(_, _, w)| w).collect();
+        removed_weights.sort_unstable();
+        assert_eq!(removed_weights, vec![1, 2, 4]);
+        assert!(removed.contains(&(nodes[0], nodes[3], 4)));
+
+        let mut kept: Vec<_> = acyclic.edge_weights().copied().collect();
+        kept.sort_unstable();
+        assert_eq!(kept, vec![0, 1, 3]);
+        assert!(acyclic.is_reachable(nodes[0], nodes[3]));
+        assert_eq!(acyclic.nodes_iter().collect::<Vec<_>>(), order);
+        assert!(acyclic.transitive_reduction().is_empty());
+    }
+
+    #[test]
+    fn test_algorithms_on_acyclic() {
+        use crate::algo::{dijkstra, has_path_connecting, toposort};
+        use crate::visit::Topo;
+
+        let mut acyclic = Acyclic::<DiGraph<(), u32>>::new();
+        let nodes: Vec<_> = (0..4).map(|_| acyclic.add_node(())).collect();
+        for &(a, b, w) in &[(0, 1, 1), (1, 3, 1), (0, 2, 3), (2, 3, 1)] {
+            acyclic.try_add_edge(nodes[a], nodes[b], w).unwrap();
+        }
+        assert_eq!(toposort(&acyclic, None).unwrap().len(), 4);
+        assert!(has_path_connecting(&acyclic, nodes[0], nodes[3], None));
+        let dist = dijkstra(&acyclic, nodes[0], None, |e| *e.weight());
+        assert_eq!(dist[&nodes[3]], 2);
+        let mut topo = Topo::new(&acyclic);
+        assert_eq!(topo.next(&acyclic), Some(nodes[0]));
+    }
+
+    #[cfg(feature = "matrix_graph")]
+    #[test]
+    fn test_acyclic_matrix_graph() {
+        use crate::algo::{dijkstra, toposort};
+        use crate::matrix_graph::DiMatrix;
+
+        let mut acyclic = Acyclic::<DiMatrix<(), u32>>::new();
+        let nodes: Vec<_> = (0..4).map(|_| acyclic.add_node(())).collect();
+        for &(a, b, w) in &[(3, 1, 1), (1, 0, 1), (3, 2, 3), (2, 0, 1)] {
+            acyclic.try_add_edge(nodes[a], nodes[b], w).unwrap();
+        }
+        assert!(acyclic.try_add_edge(nodes[0], nodes[3], 1).is_err());
+        assert_valid_topological_order(&acyclic);
+        let sorted = toposort(&acyclic, None).unwrap();
+        assert_eq!((sorted[0], sorted[3]), (nodes[3], nodes[0]));
+        let dist = dijkstra(&acyclic, nodes[3], None, |e| *e.weight());
+        assert_eq!(dist[&nodes[0]], 2);
+    }
+
     fn assert_valid_topological_order<'a, G>(acyclic: &'a Acyclic<G>)
     where
         G: Visitable + NodeCount + NodeIndexable,
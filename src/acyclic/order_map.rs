@@ -3,11 +3,17 @@
 //!
 //! This data structure is an implementation detail and is not exposed in the
 //! public API.
-use std::{collections::BTreeMap, fmt, ops::RangeBounds};
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, BinaryHeap},
+    fmt,
+    ops::{Bound, RangeBounds},
+};
 
 use crate::{
     algo::{toposort, Cycle},
     visit::{GraphBase, IntoNeighborsDirected, IntoNodeIdentifiers, NodeIndexable, Visitable},
+    Direction,
 };
 
 /// A position in the topological order of the graph.
@@ -78,6 +84,50 @@ impl<N: Copy> OrderMap<N> {
         })
     }
 
+    /// Compute a topological order of an acyclic graph with Kahn's
+    /// algorithm, choosing the available node with the smallest key first.
+    ///
+    /// Panics if the graph has a cycle.
+    pub(super) fn from_graph_by_key<G, K: Ord>(graph: G, mut key: impl FnMut(N) -> K) -> Self
+    where
+        G: NodeIndexable<NodeId = N> + IntoNeighborsDirected + IntoNodeIdentifiers,
+    {
+        let mut in_degree = vec![0; graph.node_bound()];
+        let mut available = BinaryHeap::new();
+        for id in graph.node_identifiers() {
+            let degree = graph.neighbors_directed(id, Direction::Incoming).count();
+            in_degree[graph.to_index(id)] = degree;
+            if degree == 0 {
+                available.push(Reverse((key(id), graph.to_index(id))));
+            }
+        }
+
+        let mut pos_to_node = BTreeMap::new();
+        let mut node_to_pos = vec![TopologicalPosition::default(); graph.node_bound()];
+        while let Some(Reverse((_, idx))) = available.pop() {
+            let id = graph.from_index(idx);
+            let pos = TopologicalPosition(pos_to_node.len());
+            pos_to_node.insert(pos, id);
+            node_to_pos[idx] = pos;
+            for succ in graph.neighbors_directed(id, Direction::Outgoing) {
+                let succ_idx = graph.to_index(succ);
+                in_degree[succ_idx] -= 1;
+                if in_degree[succ_idx] == 0 {
+                    available.push(Reverse((key(succ), succ_idx)));
+                }
+            }
+        }
+        assert!(
+            in_degree.iter().all(|&degree| degree == 0),
+            "graph is acyclic"
+        );
+
+        Self {
+            pos_to_node,
+            node_to_pos,
+        }
+    }
+
     pub(super) fn with_capacity(nodes: usize) -> Self {
         Self {
             pos_to_node: BTreeMap::new(),
@@ -158,6 +208,37 @@ impl<N: Copy> OrderMap<N> {
         self.pos_to_node.remove(&pos);
     }
 
+    /// Give the position of node `old_id` to node `new_id`, after the
+    /// underlying graph has changed the index of a node from `old_id` to
+    /// `new_id`.
+    ///
+    /// Panics if either node index is out of bounds.
+    pub(super) fn rename_node(
+        &mut self,
+        old_id: N,
+        new_id: N,
+        graph: impl NodeIndexable<NodeId = N>,
+    ) {
+        let old_idx = graph.to_index(old_id);
+        assert!(old_idx < self.node_to_pos.len());
+
+        let pos = self.node_to_pos[old_idx];
+        self.node_to_pos[old_idx] = TopologicalPosition::default();
+        self.set_position(new_id, pos, graph);
+    }
+
+    /// Reassign contiguous positions `0..n` to the nodes, preserving their
+    /// order, and shrink the map to the node bound of `graph`.
+    pub(super) fn normalize(&mut self, graph: impl NodeIndexable<NodeId = N>) {
+        let nodes: Vec<_> = self.nodes_iter().collect();
+        self.pos_to_node.clear();
+        self.node_to_pos.truncate(graph.node_bound());
+        self.node_to_pos.shrink_to_fit();
+        for (i, id) in nodes.into_iter().enumerate() {
+            self.set_position(id, TopologicalPosition(i), &graph);
+        }
+    }
+
     /// Set the position of a node.
     ///
     /// Panics if the node index is out of bounds.
@@ -178,6 +259,9 @@ impl<N: Copy> OrderMap<N> {
 impl<G: Visitable> super::Acyclic<G> {
     /// Get the position of a node in the topological sort.
     ///
+    /// See [the type documentation](super::Acyclic#topological-order) for
+    /// when positions may change.
+    ///
     /// Panics if the node index is out of bounds.
     pub fn get_position<'a>(&'a self, id: G::NodeId) -> TopologicalPosition
     where
@@ -186,7 +270,47 @@ impl<G: Visitable> super::Acyclic<G> {
         self.order_map.get_position(id, &self.graph)
     }
 
+    /// Get an iterator over the nodes whose position lies strictly between
+    /// the positions of `u` and `v`.
+    ///
+    /// The nodes are ordered by their position in the topological sort. The
+    /// iterator is empty if `u` is not before `v` in the order.
+    ///
+    /// Panics if either node index is out of bounds.
+    pub fn nodes_between<'a>(
+        &'a self,
+        u: G::NodeId,
+        v: G::NodeId,
+    ) -> impl Iterator<Item = G::NodeId> + 'a
+    where
+        &'a G: NodeIndexable + GraphBase<NodeId = G::NodeId>,
+    {
+        let (min, max) = (self.get_position(u), self.get_position(v));
+        let range = if min < max {
+            Some(self.range((Bound::Excluded(min), Bound::Excluded(max))))
+        } else {
+            None
+        };
+        range.into_iter().flatten()
+    }
+
+    /// Reassign contiguous positions to the nodes, without changing their
+    /// order, and release the memory used by freed positions.
+    ///
+    /// Node removals free positions without moving other nodes, so that a
+    /// long sequence of removals and additions leaves gaps in the positions.
+    /// This compacts them in O(v log v) runtime. All positions may change.
+    pub fn normalize_order<'a>(&'a mut self)
+    where
+        &'a G: NodeIndexable + GraphBase<NodeId = G::NodeId>,
+    {
+        self.order_map.normalize(&self.graph);
+    }
+
     /// Get the node at a given position in the topological sort, if it exists.
+    ///
+    /// See [the type documentation](super::Acyclic#topological-order) for
+    /// when positions may change.
     pub fn at_position(&self, pos: TopologicalPosition) -> Option<G::NodeId> {
         self.order_map.at_position(pos)
     }
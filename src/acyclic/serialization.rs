@@ -0,0 +1,118 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::serde_utils::{FromDeserialized, IntoSerializable};
+use crate::visit::{GraphBase, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable, Visitable};
+
+use super::{check_permutation, Acyclic, AcyclicOrderError};
+
+/// Serialization representation for Acyclic
+/// Keep in sync with deserialization
+///
+/// The serialization format is as follows, in Pseudorust:
+///
+/// Acyclic {
+///     graph: G,
+///     order: [G::NodeId],
+/// }
+///
+/// where `order` lists all nodes of the graph in topological order.
+#[derive(Serialize)]
+#[serde(rename = "Acyclic")]
+#[serde(bound(serialize = "G: Serialize, N: Serialize"))]
+pub struct SerAcyclic<'a, G: 'a, N> {
+    graph: &'a G,
+    order: Vec<N>,
+}
+
+// Deserialization representation for Acyclic
+// Keep in sync with serialization
+#[derive(Deserialize)]
+#[serde(rename = "Acyclic")]
+#[serde(bound(deserialize = "G: Deserialize<'de>, N: Deserialize<'de>"))]
+pub struct DeserAcyclic<G, N> {
+    graph: G,
+    order: Vec<N>,
+}
+
+impl<'a, G: Visitable> IntoSerializable for &'a Acyclic<G> {
+    type Output = SerAcyclic<'a, G, G::NodeId>;
+    fn into_serializable(self) -> Self::Output {
+        SerAcyclic {
+            graph: &self.graph,
+            order: self.nodes_iter().collect(),
+        }
+    }
+}
+
+/// Requires crate feature `"serde-1"`
+impl<G> Serialize for Acyclic<G>
+where
+    G: Visitable + Serialize,
+    G::NodeId: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.into_serializable().serialize(serializer)
+    }
+}
+
+impl<G> FromDeserialized for Acyclic<G>
+where
+    G: Visitable + NodeIndexable,
+    for<'a> &'a G: IntoEdgeReferences + IntoNodeIdentifiers + GraphBase<NodeId = G::NodeId>,
+{
+    type Input = DeserAcyclic<G, G::NodeId>;
+    fn from_deserialized<E2>(input: Self::Input) -> Result<Self, E2>
+    where
+        E2: Error,
+    {
+        let graph = input.graph;
+
+        // Check that the order is a permutation of the nodes.
+        match check_permutation(&graph, input.order.iter().copied()) {
+            Err(AcyclicOrderError::InvalidNode(id)) => Err(E2::custom(format_args!(
+                "invalid value: node `{}` in order does not exist in graph \
+                 or is listed twice",
+                graph.to_index(id)
+            )))?,
+            Err(AcyclicOrderError::MissingNode(id)) => Err(E2::custom(format_args!(
+                "invalid value: node `{}` is missing from order",
+                graph.to_index(id)
+            )))?,
+            _ => {}
+        }
+        let acyclic = Acyclic::from_parts_unchecked(graph, input.order);
+
+        // Check that the edges are consistent with the order.
+        if let Err(AcyclicOrderError::InconsistentEdge(a, b)) = acyclic.check_edges() {
+            Err(E2::custom(format_args!(
+                "invalid value: edge from node `{}` to node `{}` is not consistent \
+                 with the topological order",
+                acyclic.graph.to_index(a),
+                acyclic.graph.to_index(b)
+            )))?
+        }
+        Ok(acyclic)
+    }
+}
+
+/// Requires crate feature `"serde-1"`
+///
+/// The deserialized order is checked to be a topological order of the graph,
+/// in O(|V| + |E|) time.
+impl<'de, G> Deserialize<'de> for Acyclic<G>
+where
+    G: Visitable + NodeIndexable + Deserialize<'de>,
+    G::NodeId: Deserialize<'de>,
+    for<'a> &'a G: IntoEdgeReferences + IntoNodeIdentifiers + GraphBase<NodeId = G::NodeId>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Self::from_deserialized(DeserAcyclic::deserialize(deserializer)?)
+    }
+}
@@ -1,11 +1,12 @@
+use std::cell::{Cell, RefCell};
 use std::collections::hash_map::Entry::{Occupied, Vacant};
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::HashMap;
 
 use std::hash::Hash;
 
-use crate::scored::MinScored;
 use crate::visit::{EdgeRef, GraphBase, IntoEdges, Visitable};
 
+use crate::algo::priority_queue::{BinaryQueue, PriorityQueue};
 use crate::algo::Measure;
 
 /// \[Generic\] A* shortest path algorithm.
@@ -64,11 +65,61 @@ use crate::algo::Measure;
 /// Returns the total cost + the path of subsequent `NodeId` from start to finish, if one was
 /// found.
 pub fn astar<G, F, H, K, IsGoal>(
+    graph: G,
+    start: G::NodeId,
+    is_goal: IsGoal,
+    edge_cost: F,
+    estimate_cost: H,
+) -> Option<(K, Vec<G::NodeId>)>
+where
+    G: IntoEdges + Visitable,
+    IsGoal: FnMut(G::NodeId) -> bool,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    H: FnMut(G::NodeId) -> K,
+    K: Measure + Copy,
+{
+    astar_with_queue(
+        graph,
+        start,
+        is_goal,
+        edge_cost,
+        estimate_cost,
+        BinaryQueue::new(),
+    )
+}
+
+/// \[Generic\] A* shortest path algorithm, with a custom priority queue.
+///
+/// Same as [`astar`], with the nodes to visit stored in `queue`, which should
+/// be empty.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::astar::astar_with_queue;
+/// use petgraph::algo::priority_queue::DaryQueue;
+/// use petgraph::prelude::*;
+///
+/// let graph = DiGraph::<(), u32>::from_edges(&[(0, 1, 2), (1, 2, 2), (0, 2, 5)]);
+/// let (start, goal) = (NodeIndex::new(0), NodeIndex::new(2));
+///
+/// let path = astar_with_queue(
+///     &graph,
+///     start,
+///     |n| n == goal,
+///     |e| *e.weight(),
+///     |_| 0,
+///     DaryQueue::default(),
+/// );
+/// assert_eq!(path, Some((4, vec![start, NodeIndex::new(1), goal])));
+/// ```
+pub fn astar_with_queue<G, F, H, K, IsGoal, Q>(
     graph: G,
     start: G::NodeId,
     mut is_goal: IsGoal,
     mut edge_cost: F,
     mut estimate_cost: H,
+    mut queue: Q,
 ) -> Option<(K, Vec<G::NodeId>)>
 where
     G: IntoEdges + Visitable,
@@ -77,17 +128,17 @@ where
     F: FnMut(G::EdgeRef) -> K,
     H: FnMut(G::NodeId) -> K,
     K: Measure + Copy,
+    Q: PriorityQueue<K, G::NodeId>,
 {
-    let mut visit_next = BinaryHeap::new();
     let mut scores = HashMap::new(); // g-values, cost to reach the node
     let mut estimate_scores = HashMap::new(); // f-values, cost to reach + estimate cost to goal
     let mut path_tracker = PathTracker::<G>::new();
 
     let zero_score = K::default();
     scores.insert(start, zero_score);
-    visit_next.push(MinScored(estimate_cost(start), start));
+    queue.push(estimate_cost(start), start);
 
-    while let Some(MinScored(estimate_score, node)) = visit_next.pop() {
+    while let Some((estimate_score, node)) = queue.pop() {
         if is_goal(node) {
             let path = path_tracker.reconstruct_path_to(node);
             let cost = scores[&node];
@@ -95,7 +146,7 @@ where
         }
 
         // This lookup can be unwrapped without fear of panic since the node was necessarily scored
-        // before adding it to `visit_next`.
+        // before adding it to `queue`.
         let node_score = scores[&node];
 
         match estimate_scores.entry(node) {
@@ -132,13 +183,106 @@ where
 
             path_tracker.set_predecessor(next, node);
             let next_estimate_score = next_score + estimate_cost(next);
-            visit_next.push(MinScored(next_estimate_score, next));
+            queue.push(next_estimate_score, next);
         }
     }
 
     None
 }
 
+/// An A* heuristic that is not consistent, as found by [`astar_checked`]:
+/// along the edge from `source` to `target`, the estimate at `source` exceeds
+/// the cost of the edge plus the estimate at `target`, or the estimate at the
+/// goal `source == target` is not zero.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InconsistentHeuristic<N> {
+    pub source: N,
+    pub target: N,
+}
+
+/// \[Generic\] A* shortest path algorithm, checking that the heuristic is
+/// consistent.
+///
+/// Same as [`astar`], but the estimate of each node is checked along every
+/// edge the search relaxes: `estimate_cost(u) <= edge_cost(u -> v) +
+/// estimate_cost(v)`, and the estimate of the goal reached must be zero. A
+/// consistent heuristic is admissible, so that the path found is a shortest
+/// path. The search stops at the first violation, which is returned as an
+/// error.
+///
+/// Only the edges explored by the search are checked: this is a debugging
+/// aid for heuristics, e.g. to run in tests, rather than a proof.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::astar::{astar_checked, InconsistentHeuristic};
+/// use petgraph::prelude::*;
+///
+/// let graph = DiGraph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 1), (0, 2, 5)]);
+/// let (start, goal) = (NodeIndex::new(0), NodeIndex::new(2));
+///
+/// // The number of edges left to the goal is consistent
+/// let hops = |n: NodeIndex| 2 - n.index() as u32;
+/// let path = astar_checked(&graph, start, |n| n == goal, |e| *e.weight(), hops);
+/// assert_eq!(path, Ok(Some((2, vec![start, NodeIndex::new(1), goal]))));
+///
+/// // Overestimating at node 1 is not
+/// let wrong = |n: NodeIndex| if n.index() == 1 { 3 } else { 0 };
+/// let path = astar_checked(&graph, start, |n| n == goal, |e| *e.weight(), wrong);
+/// assert_eq!(
+///     path,
+///     Err(InconsistentHeuristic { source: NodeIndex::new(1), target: goal })
+/// );
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn astar_checked<G, F, H, K, IsGoal>(
+    graph: G,
+    start: G::NodeId,
+    mut is_goal: IsGoal,
+    mut edge_cost: F,
+    estimate_cost: H,
+) -> Result<Option<(K, Vec<G::NodeId>)>, InconsistentHeuristic<G::NodeId>>
+where
+    G: IntoEdges + Visitable,
+    IsGoal: FnMut(G::NodeId) -> bool,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    H: FnMut(G::NodeId) -> K,
+    K: Measure + Copy,
+{
+    let estimate_cost = RefCell::new(estimate_cost);
+    let violation = Cell::new(None);
+    let path = astar(
+        graph,
+        start,
+        |node| {
+            if violation.get().is_some() {
+                // Stop the search
+                return true;
+            }
+            let goal = is_goal(node);
+            if goal && (estimate_cost.borrow_mut())(node) != K::default() {
+                violation.set(Some((node, node)));
+            }
+            goal
+        },
+        |edge| {
+            let cost = edge_cost(edge);
+            let mut estimate_cost = estimate_cost.borrow_mut();
+            let (source, target) = (edge.source(), edge.target());
+            if violation.get().is_none() && estimate_cost(source) > cost + estimate_cost(target) {
+                violation.set(Some((source, target)));
+            }
+            cost
+        },
+        |node| (estimate_cost.borrow_mut())(node),
+    );
+    match violation.get() {
+        Some((source, target)) => Err(InconsistentHeuristic { source, target }),
+        None => Ok(path),
+    }
+}
+
 struct PathTracker<G>
 where
     G: GraphBase,
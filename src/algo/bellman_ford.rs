@@ -1,8 +1,12 @@
 //! Bellman-Ford algorithms.
 
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+
 use crate::prelude::*;
 
-use crate::visit::{IntoEdges, IntoNodeIdentifiers, NodeCount, NodeIndexable, VisitMap, Visitable};
+use crate::visit::{IntoEdges, IntoNodeIdentifiers, NodeCount, NodeIndexable, Visitable};
 
 use super::{FloatMeasure, NegativeCycle};
 
@@ -108,6 +112,160 @@ where
     })
 }
 
+/// \[Generic\] Compute shortest paths from node `source` to all other, with
+/// the queue-based variant of Bellman–Ford known as the
+/// [Shortest Path Faster Algorithm][spfa].
+///
+/// The result is the same as [`bellman_ford`]: negative edge costs are
+/// permitted, and an error is returned for a cycle of negative weights
+/// reachable from `source`. Instead of relaxing every edge in each round,
+/// only the edges out of the nodes whose distance changed are relaxed, and
+/// the search ends as soon as no distance changes. On graphs with few negative
+/// edges, this is usually much faster, though the worst case is still
+/// **O(|V|·|E|)**.
+///
+/// [spfa]: https://en.wikipedia.org/wiki/Shortest_path_faster_algorithm
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::bellman_ford::spfa;
+/// use petgraph::prelude::*;
+///
+/// let g = DiGraph::<(), f32>::from_edges(&[(0, 1, 2.), (0, 2, 4.), (1, 2, -3.), (2, 3, 1.)]);
+///
+/// let paths = spfa(&g, NodeIndex::new(0)).unwrap();
+/// assert_eq!(paths.distances, vec![0., 2., -1., 0.]);
+/// assert_eq!(paths.predecessors[2], Some(NodeIndex::new(1)));
+///
+/// let g = DiGraph::<(), f32>::from_edges(&[(0, 1, 1.), (1, 2, -2.), (2, 1, 1.)]);
+/// assert!(spfa(&g, NodeIndex::new(0)).is_err());
+/// ```
+pub fn spfa<G>(g: G, source: G::NodeId) -> Result<Paths<G::NodeId, G::EdgeWeight>, NegativeCycle>
+where
+    G: NodeCount + IntoEdges + NodeIndexable,
+    G::EdgeWeight: FloatMeasure,
+{
+    let ix = |i| g.to_index(i);
+    let mut predecessors = vec![None; g.node_bound()];
+    let mut distances = vec![<_>::infinite(); g.node_bound()];
+    // The number of edges of the shortest path found to each node: a path
+    // of |V| edges has a cycle, which must be negative
+    let mut lengths = vec![0; g.node_bound()];
+    let mut queued = vec![false; g.node_bound()];
+    let mut queue = VecDeque::new();
+    distances[ix(source)] = <_>::zero();
+    queued[ix(source)] = true;
+    queue.push_back(source);
+
+    while let Some(i) = queue.pop_front() {
+        queued[ix(i)] = false;
+        for edge in g.edges(i) {
+            let j = edge.target();
+            let w = *edge.weight();
+            if distances[ix(i)] + w < distances[ix(j)] {
+                distances[ix(j)] = distances[ix(i)] + w;
+                predecessors[ix(j)] = Some(i);
+                lengths[ix(j)] = lengths[ix(i)] + 1;
+                if lengths[ix(j)] >= g.node_count() {
+                    return Err(NegativeCycle(()));
+                }
+                if !queued[ix(j)] {
+                    queued[ix(j)] = true;
+                    queue.push_back(j);
+                }
+            }
+        }
+    }
+
+    Ok(Paths {
+        distances,
+        predecessors,
+    })
+}
+
+/// An algorithm error of [`bellman_ford_with_cycle`]: a cycle of negative
+/// weights was found in the graph, reachable from the source.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NegativeCycleError<N> {
+    cycle: Vec<N>,
+}
+
+impl<N> NegativeCycleError<N> {
+    /// Return the nodes of the negative cycle, in the order of its edges.
+    pub fn cycle(&self) -> &[N] {
+        &self.cycle
+    }
+
+    /// Return the nodes of the negative cycle, consuming the error.
+    pub fn into_cycle(self) -> Vec<N> {
+        self.cycle
+    }
+}
+
+impl<N> From<NegativeCycleError<N>> for NegativeCycle {
+    fn from(_: NegativeCycleError<N>) -> Self {
+        NegativeCycle(())
+    }
+}
+
+impl<N: fmt::Debug> fmt::Display for NegativeCycleError<N> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "negative cycle through nodes {:?}", self.cycle)
+    }
+}
+
+impl<N: fmt::Debug> Error for NegativeCycleError<N> {}
+
+/// \[Generic\] Compute shortest paths from node `source` to all other, like
+/// [`bellman_ford`], returning the nodes of a negative cycle on failure.
+///
+/// If the graph has a cycle of negative weights reachable from `source`, the
+/// error holds its nodes, in the order of its edges, as would be returned by
+/// [`find_negative_cycle`], without running the algorithm again.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::bellman_ford::bellman_ford_with_cycle;
+/// use petgraph::prelude::*;
+///
+/// let graph = DiGraph::<(), f32>::from_edges(&[(0, 1, 1.), (1, 2, 1.), (2, 3, -3.), (3, 1, 1.)]);
+///
+/// let error = bellman_ford_with_cycle(&graph, NodeIndex::new(0)).unwrap_err();
+/// let mut cycle = error.cycle().to_vec();
+/// cycle.sort();
+/// assert_eq!(cycle, vec![NodeIndex::new(1), NodeIndex::new(2), NodeIndex::new(3)]);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn bellman_ford_with_cycle<G>(
+    g: G,
+    source: G::NodeId,
+) -> Result<Paths<G::NodeId, G::EdgeWeight>, NegativeCycleError<G::NodeId>>
+where
+    G: NodeCount + IntoNodeIdentifiers + IntoEdges + NodeIndexable,
+    G::EdgeWeight: FloatMeasure,
+{
+    let ix = |i| g.to_index(i);
+
+    let (distances, predecessors) = bellman_ford_initialize_relax(g, source);
+
+    for i in g.node_identifiers() {
+        for edge in g.edges(i) {
+            let j = edge.target();
+            let w = *edge.weight();
+            if distances[ix(i)] + w < distances[ix(j)] {
+                return Err(NegativeCycleError {
+                    cycle: negative_cycle_from(g, i, j, &predecessors),
+                });
+            }
+        }
+    }
+
+    Ok(Paths {
+        distances,
+        predecessors,
+    })
+}
+
 /// \[Generic\] Find the path of a negative cycle reachable from node `source`.
 ///
 /// Using the [find_negative_cycle][nc]; will search the Graph for negative cycles using
@@ -147,63 +305,56 @@ where
     G::EdgeWeight: FloatMeasure,
 {
     let ix = |i| g.to_index(i);
-    let mut path = Vec::<G::NodeId>::new();
 
     // Step 1: initialize and relax
     let (distance, predecessor) = bellman_ford_initialize_relax(g, source);
 
     // Step 2: Check for negative weight cycle
-    'outer: for i in g.node_identifiers() {
+    for i in g.node_identifiers() {
         for edge in g.edges(i) {
             let j = edge.target();
             let w = *edge.weight();
             if distance[ix(i)] + w < distance[ix(j)] {
                 // Step 3: negative cycle found
-                let start = j;
-                let mut node = start;
-                let mut visited = g.visit_map();
-                // Go backward in the predecessor chain
-                loop {
-                    let ancestor = match predecessor[ix(node)] {
-                        Some(predecessor_node) => predecessor_node,
-                        None => node, // no predecessor, self cycle
-                    };
-                    // We have only 2 ways to find the cycle and break the loop:
-                    // 1. start is reached
-                    if ancestor == start {
-                        path.push(ancestor);
-                        break;
-                    }
-                    // 2. some node was reached twice
-                    else if visited.is_visited(&ancestor) {
-                        // Drop any node in path that is before the first ancestor
-                        let pos = path
-                            .iter()
-                            .position(|&p| p == ancestor)
-                            .expect("we should always have a position");
-                        path = path[pos..path.len()].to_vec();
-
-                        break;
-                    }
-
-                    // None of the above, some middle path node
-                    path.push(ancestor);
-                    visited.visit(ancestor);
-                    node = ancestor;
-                }
-                // We are done here
-                break 'outer;
+                return Some(negative_cycle_from(g, i, j, &predecessor));
             }
         }
     }
-    if !path.is_empty() {
-        // Users will probably need to follow the path of the negative cycle
-        // so it should be in the reverse order than it was found by the algorithm.
-        path.reverse();
-        Some(path)
-    } else {
-        None
+    None
+}
+
+/// Return the negative cycle found by following the predecessors from `j`,
+/// whose distance can still be decreased through the edge from `i` after
+/// relaxation.
+fn negative_cycle_from<G>(
+    g: G,
+    i: G::NodeId,
+    j: G::NodeId,
+    predecessor: &[Option<G::NodeId>],
+) -> Vec<G::NodeId>
+where
+    G: NodeCount + NodeIndexable,
+{
+    let ix = |i| g.to_index(i);
+    let mut predecessor = predecessor.to_vec();
+    predecessor[ix(j)] = Some(i);
+    // Every cycle of the predecessors has a negative cost, and the chain from
+    // a node relaxed after |V| - 1 rounds reaches one within |V| steps
+    let mut start = j;
+    for _ in 0..g.node_count() {
+        start = predecessor[ix(start)].expect("the predecessors of j form a cycle");
+    }
+    // Go backward in the predecessor chain, around the cycle
+    let mut path = vec![start];
+    let mut node = predecessor[ix(start)].unwrap();
+    while node != start {
+        path.push(node);
+        node = predecessor[ix(node)].unwrap();
     }
+    // Users will probably need to follow the path of the negative cycle
+    // so it should be in the reverse order than it was found by the algorithm.
+    path.reverse();
+    path
 }
 
 // Perform Step 1 and Step 2 of the Bellman-Ford algorithm.
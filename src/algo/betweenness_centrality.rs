@@ -0,0 +1,168 @@
+use std::collections::VecDeque;
+
+use crate::visit::{GraphProp, IntoNeighbors, IntoNodeIdentifiers, NodeIndexable};
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+/// \[Generic\] Betweenness centrality.
+///
+/// Computes the [betweenness centrality][bc] of every node of an unweighted
+/// graph with [Brandes' algorithm][brandes]: the centrality of a node `v` is
+/// the sum, over all pairs of distinct nodes `s` and `t` other than `v`, of the
+/// fraction of the shortest paths from `s` to `t` that go through `v`.
+///
+/// Returns a `Vec` container mapping each node index to its centrality. The
+/// centralities are not normalized. For undirected graphs, each pair of nodes
+/// is counted once.
+///
+/// # Complexity
+/// Time complexity is **O(|V||E|)**.
+/// Space complexity is **O(|V| + |E|)**.
+///
+/// [bc]: https://en.wikipedia.org/wiki/Betweenness_centrality
+/// [brandes]: https://doi.org/10.1080/0022250X.2001.9990249
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::betweenness_centrality;
+/// use petgraph::prelude::*;
+///
+/// // 0 - 1 - 2
+/// //      \  |
+/// //       - 3 - 4
+/// let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (1, 3), (2, 3), (3, 4)]);
+///
+/// let centrality = betweenness_centrality(&graph);
+/// assert_eq!(centrality, vec![0., 3., 0., 3., 0.]);
+/// ```
+pub fn betweenness_centrality<G>(graph: G) -> Vec<f64>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    let mut brandes = Brandes::new(graph);
+    for source in graph.node_identifiers() {
+        brandes.accumulate(graph, source);
+    }
+    brandes.finish(graph)
+}
+
+/// \[Generic\] Parallel betweenness centrality.
+///
+/// The sources of the shortest paths are split across threads, each with its
+/// own accumulator, and the accumulators are summed at the end.
+///
+/// See [`betweenness_centrality`].
+#[cfg(feature = "rayon")]
+pub fn parallel_betweenness_centrality<G>(graph: G) -> Vec<f64>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable + GraphProp + Sync,
+    G::NodeId: Send + Sync,
+{
+    let sources: Vec<_> = graph.node_identifiers().collect();
+    sources
+        .par_iter()
+        .fold(
+            || Brandes::new(graph),
+            |mut brandes, &source| {
+                brandes.accumulate(graph, source);
+                brandes
+            },
+        )
+        .map(|brandes| brandes.finish(graph))
+        .reduce(
+            || vec![0.; graph.node_bound()],
+            |mut left, right| {
+                for (l, r) in left.iter_mut().zip(right) {
+                    *l += r;
+                }
+                left
+            },
+        )
+}
+
+/// The state of Brandes' algorithm: the centrality accumulated so far and the
+/// workspace of the single-source computations, indexed by node index.
+struct Brandes {
+    centrality: Vec<f64>,
+    /// The number of shortest paths from the source to each node.
+    sigma: Vec<f64>,
+    /// The distance from the source, or `usize::MAX` if not reached.
+    distance: Vec<usize>,
+    /// The predecessors of each node on the shortest paths from the source.
+    predecessors: Vec<Vec<usize>>,
+    /// The dependency of the source on each node.
+    delta: Vec<f64>,
+    /// The nodes reached from the source, by increasing distance.
+    order: Vec<usize>,
+    queue: VecDeque<usize>,
+}
+
+impl Brandes {
+    fn new<G: NodeIndexable>(graph: G) -> Self {
+        let bound = graph.node_bound();
+        Brandes {
+            centrality: vec![0.; bound],
+            sigma: vec![0.; bound],
+            distance: vec![std::usize::MAX; bound],
+            predecessors: vec![Vec::new(); bound],
+            delta: vec![0.; bound],
+            order: Vec::new(),
+            queue: VecDeque::new(),
+        }
+    }
+
+    /// Add the dependencies of `source` on the other nodes to the centrality.
+    fn accumulate<G>(&mut self, graph: G, source: G::NodeId)
+    where
+        G: IntoNeighbors + NodeIndexable,
+    {
+        let s = graph.to_index(source);
+        self.sigma[s] = 1.;
+        self.distance[s] = 0;
+        self.queue.push_back(s);
+        while let Some(v) = self.queue.pop_front() {
+            self.order.push(v);
+            for w in graph.neighbors(graph.from_index(v)) {
+                let w = graph.to_index(w);
+                if self.distance[w] == std::usize::MAX {
+                    self.distance[w] = self.distance[v] + 1;
+                    self.queue.push_back(w);
+                }
+                if self.distance[w] == self.distance[v] + 1 {
+                    self.sigma[w] += self.sigma[v];
+                    self.predecessors[w].push(v);
+                }
+            }
+        }
+
+        // Back-propagate the dependencies, farthest nodes first
+        for &w in self.order.iter().rev() {
+            for &v in &self.predecessors[w] {
+                self.delta[v] += self.sigma[v] / self.sigma[w] * (1. + self.delta[w]);
+            }
+            if w != s {
+                self.centrality[w] += self.delta[w];
+            }
+        }
+
+        // Reset the workspace of the nodes that were reached
+        for &v in &self.order {
+            self.sigma[v] = 0.;
+            self.distance[v] = std::usize::MAX;
+            self.predecessors[v].clear();
+            self.delta[v] = 0.;
+        }
+        self.order.clear();
+    }
+
+    fn finish<G: GraphProp>(mut self, graph: G) -> Vec<f64> {
+        // Both directions of each pair were counted
+        if !graph.is_directed() {
+            for c in &mut self.centrality {
+                *c /= 2.;
+            }
+        }
+        self.centrality
+    }
+}
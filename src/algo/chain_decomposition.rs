@@ -0,0 +1,256 @@
+//! Chain decompositions and path covers of directed acyclic graphs.
+//!
+//! A *chain* of a DAG is a list of nodes, each reachable from the previous
+//! one. By [Dilworth's theorem](https://en.wikipedia.org/wiki/Dilworth%27s_theorem),
+//! the minimum number of chains covering the nodes, the *width* of the DAG,
+//! equals the maximum number of mutually unreachable nodes. Both are computed
+//! from a maximum matching of the bipartite graph with a copy of each node on
+//! either side and an edge `(a, b)` whenever `b` is reachable from `a`.
+
+use fixedbitset::FixedBitSet;
+
+use crate::algo::tred::transitive_closure;
+use crate::algo::{toposort, Cycle};
+use crate::visit::{IntoNeighbors, IntoNeighborsDirected, IntoNodeIdentifiers};
+use crate::visit::{NodeIndexable, Visitable};
+
+/// \[Generic\] Compute a minimum chain decomposition of a directed acyclic
+/// graph.
+///
+/// Returns a list of disjoint chains covering all the nodes of the graph:
+/// each node of a chain is reachable from the previous one, though not
+/// necessarily through a single edge. The number of chains is minimum, and
+/// equals the width of the DAG, the size of its largest set of mutually
+/// unreachable nodes.
+///
+/// Returns a [`Cycle`] error if the graph is not acyclic.
+///
+/// Computes in **O(|V|³ / 64 + |V| · |Ec|)** time, where **|Ec|** is the
+/// number of edges of the transitive closure of the graph.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::chain_decomposition;
+/// use petgraph::prelude::*;
+///
+/// //    1
+/// //  ↗   ↘
+/// // 0     3    4
+/// //  ↘   ↗
+/// //    2
+/// let mut graph = DiGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (2, 3)]);
+/// graph.add_node(());
+///
+/// let chains = chain_decomposition(&graph).unwrap();
+/// // {1, 2, 4} are mutually unreachable
+/// assert_eq!(chains.len(), 3);
+/// assert_eq!(chains.iter().map(|chain| chain.len()).sum::<usize>(), 5);
+///
+/// graph.add_edge(NodeIndex::new(3), NodeIndex::new(0), ());
+/// assert!(chain_decomposition(&graph).is_err());
+/// ```
+pub fn chain_decomposition<G>(g: G) -> Result<Vec<Vec<G::NodeId>>, Cycle<G::NodeId>>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let successors = closure_successors(g)?;
+    let next = bipartite_matching(&successors);
+    Ok(chains(g, &next))
+}
+
+/// \[Generic\] Compute a minimum path cover of a directed acyclic graph.
+///
+/// Returns a list of disjoint paths covering all the nodes of the graph: each
+/// node of a path is a successor of the previous one. The number of paths is
+/// minimum. Unlike [`chain_decomposition`], consecutive nodes must be
+/// adjacent, so that more paths may be needed than chains.
+///
+/// Returns a [`Cycle`] error if the graph is not acyclic.
+///
+/// Computes in **O(|V| · (|V| + |E|))** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::minimum_path_cover;
+/// use petgraph::prelude::*;
+///
+/// let graph = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (3, 1)]);
+/// let mut paths = minimum_path_cover(&graph).unwrap();
+/// paths.sort();
+///
+/// let n = NodeIndex::new;
+/// assert!(paths == vec![vec![n(0), n(1), n(2)], vec![n(3)]]
+///     || paths == vec![vec![n(0)], vec![n(3), n(1), n(2)]]);
+/// ```
+pub fn minimum_path_cover<G>(g: G) -> Result<Vec<Vec<G::NodeId>>, Cycle<G::NodeId>>
+where
+    G: IntoNeighborsDirected + IntoNodeIdentifiers + NodeIndexable + Visitable,
+{
+    toposort(g, None)?;
+    let mut successors = vec![Vec::new(); g.node_bound()];
+    for a in g.node_identifiers() {
+        successors[g.to_index(a)].extend(g.neighbors(a).map(|b| g.to_index(b)));
+    }
+    let next = bipartite_matching(&successors);
+    Ok(chains(g, &next))
+}
+
+/// \[Generic\] Compute a maximum antichain of a directed acyclic graph.
+///
+/// Returns a largest set of mutually unreachable nodes, in the order of
+/// [`IntoNodeIdentifiers::node_identifiers`]. By Dilworth's theorem, its size
+/// is the number of chains of a [`chain_decomposition`].
+///
+/// The antichain is derived from a minimum vertex cover of the bipartite
+/// reachability graph, given by König's theorem: a node is in the antichain if
+/// neither of its copies is in the cover.
+///
+/// Returns a [`Cycle`] error if the graph is not acyclic.
+///
+/// Computes in **O(|V|³ / 64 + |V| · |Ec|)** time, where **|Ec|** is the
+/// number of edges of the transitive closure of the graph.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::maximum_antichain;
+/// use petgraph::prelude::*;
+///
+/// //    1
+/// //  ↗   ↘
+/// // 0     3    4
+/// //  ↘   ↗
+/// //    2
+/// let mut graph = DiGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (2, 3)]);
+/// graph.add_node(());
+///
+/// let n = NodeIndex::new;
+/// assert_eq!(maximum_antichain(&graph), Ok(vec![n(1), n(2), n(4)]));
+/// ```
+pub fn maximum_antichain<G>(g: G) -> Result<Vec<G::NodeId>, Cycle<G::NodeId>>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let successors = closure_successors(g)?;
+    let next = bipartite_matching(&successors);
+    let mut previous = vec![None; next.len()];
+    for (a, &b) in next.iter().enumerate() {
+        if let Some(b) = b {
+            previous[b] = Some(a);
+        }
+    }
+
+    // The nodes reachable through alternating paths from the unmatched nodes
+    // of the left side.
+    let mut left = FixedBitSet::with_capacity(next.len());
+    let mut right = FixedBitSet::with_capacity(next.len());
+    let mut stack: Vec<_> = g
+        .node_identifiers()
+        .map(|a| g.to_index(a))
+        .filter(|&a| next[a].is_none())
+        .collect();
+    left.extend(stack.iter().copied());
+    while let Some(a) = stack.pop() {
+        for &b in &successors[a] {
+            if right.put(b) {
+                continue;
+            }
+            if let Some(c) = previous[b] {
+                if !left.put(c) {
+                    stack.push(c);
+                }
+            }
+        }
+    }
+
+    // The complement of the minimum vertex cover
+    Ok(g.node_identifiers()
+        .filter(|&a| left[g.to_index(a)] && !right[g.to_index(a)])
+        .collect())
+}
+
+/// The successors of each node in the transitive closure of `g`, by node
+/// index, or a cycle.
+fn closure_successors<G>(g: G) -> Result<Vec<Vec<usize>>, Cycle<G::NodeId>>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let closure = transitive_closure(g);
+    let mut successors = vec![Vec::new(); g.node_bound()];
+    for a in g.node_identifiers() {
+        if closure.is_reachable(a, a) {
+            return Err(Cycle(a));
+        }
+        successors[g.to_index(a)].extend(closure.reachable_from(a).map(|b| g.to_index(b)));
+    }
+    Ok(successors)
+}
+
+/// Split the nodes into chains, following the matched successor of each node.
+fn chains<G>(g: G, next: &[Option<usize>]) -> Vec<Vec<G::NodeId>>
+where
+    G: IntoNodeIdentifiers + NodeIndexable,
+{
+    let mut has_previous = FixedBitSet::with_capacity(g.node_bound());
+    has_previous.extend(next.iter().filter_map(|&b| b));
+    g.node_identifiers()
+        .filter(|&a| !has_previous[g.to_index(a)])
+        .map(|a| {
+            let mut chain = vec![a];
+            let mut i = g.to_index(a);
+            while let Some(j) = next[i] {
+                chain.push(g.from_index(j));
+                i = j;
+            }
+            chain
+        })
+        .collect()
+}
+
+/// Compute a maximum matching of the bipartite graph with edges `(a, b)` for
+/// `b` in `successors[a]`, with Kuhn's augmenting path algorithm.
+///
+/// Returns the node matched to each node on the left side, if any.
+fn bipartite_matching(successors: &[Vec<usize>]) -> Vec<Option<usize>> {
+    let n = successors.len();
+    let mut next = vec![None; n];
+    let mut previous = vec![None; n];
+    let mut visited = FixedBitSet::with_capacity(n);
+    for start in 0..n {
+        visited.clear();
+        // Depth-first search for an augmenting path: each entry holds a left
+        // node, the position of its next successor to try and the right node
+        // it was reached through.
+        let mut stack = vec![(start, 0, None)];
+        while let Some((a, pos, _)) = stack.last_mut() {
+            let a = *a;
+            let b = match successors[a][*pos..].iter().position(|&b| !visited[b]) {
+                Some(k) => {
+                    *pos += k + 1;
+                    successors[a][*pos - 1]
+                }
+                None => {
+                    stack.pop();
+                    continue;
+                }
+            };
+            visited.insert(b);
+            match previous[b] {
+                Some(c) => stack.push((c, 0, Some(b))),
+                None => {
+                    // Flip the matching along the path
+                    let mut b = b;
+                    while let Some((a, _, through)) = stack.pop() {
+                        next[a] = Some(b);
+                        previous[b] = Some(a);
+                        match through {
+                            Some(through) => b = through,
+                            None => break,
+                        }
+                    }
+                    break;
+                }
+            }
+        }
+    }
+    next
+}
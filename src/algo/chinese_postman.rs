@@ -0,0 +1,338 @@
+use std::collections::BinaryHeap;
+use std::ops::{Div, Sub};
+
+use crate::algo::matching::minimum_weight_perfect_matching;
+use crate::algo::Measure;
+use crate::scored::MinScored;
+use crate::unionfind::UnionFind;
+use crate::visit::{EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
+
+/// \[Generic\] Solve the
+/// [Chinese postman problem](https://en.wikipedia.org/wiki/Chinese_postman_problem),
+/// or route inspection problem: find a shortest closed walk traversing every
+/// edge of the graph at least once.
+///
+/// Edge costs are given by `edge_cost`, and must be non-negative. The walk
+/// repeats the edges of shortest paths between the nodes whose degree makes an
+/// Eulerian circuit impossible, and then follows an Eulerian circuit of the
+/// graph with the repeated edges:
+///
+/// - In undirected graphs, the nodes of odd degree are paired by a minimum
+///   weight perfect matching, computed with Edmonds' blossom algorithm.
+/// - In directed graphs, the shortest paths from the nodes with more incoming
+///   than outgoing edges to the nodes with more outgoing than incoming edges
+///   are chosen by a minimum cost flow.
+///
+/// Returns the cost of the walk and its edges, in order, or `None` if there is
+/// no closed walk through all edges: if the edges are not connected, or, in
+/// directed graphs, not strongly connected. Nodes without edges are ignored.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::chinese_postman;
+/// use petgraph::prelude::*;
+///
+/// // A square with a diagonal: 0 and 2 have odd degree
+/// let graph = UnGraph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 1), (2, 3, 1), (3, 0, 1), (0, 2, 3)]);
+///
+/// let (cost, walk) = chinese_postman(&graph, |e| *e.weight()).unwrap();
+/// // The cheapest way back from 2 to 0 is through 1 or 3
+/// assert_eq!(cost, 9);
+/// assert_eq!(walk.len(), 7);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn chinese_postman<G, F, K>(graph: G, mut edge_cost: F) -> Option<(K, Vec<G::EdgeId>)>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy + Sub<Output = K> + Div<Output = K> + From<u8>,
+{
+    let directed = graph.is_directed();
+    let n = graph.node_bound();
+    let edges: Vec<_> = graph
+        .edge_references()
+        .map(|e| {
+            let (a, b) = (graph.to_index(e.source()), graph.to_index(e.target()));
+            (a, b, e.id(), edge_cost(e))
+        })
+        .collect();
+    if edges.is_empty() {
+        return Some((K::default(), Vec::new()));
+    }
+
+    let mut components = UnionFind::new(n);
+    for &(a, b, _, _) in &edges {
+        components.union(a, b);
+    }
+    let root = components.find(edges[0].0);
+    if edges.iter().any(|&(a, _, _, _)| components.find(a) != root) {
+        return None;
+    }
+
+    // Adjacency, as (neighbor, edge) pairs
+    let mut adjacency = vec![Vec::new(); n];
+    for (i, &(a, b, _, _)) in edges.iter().enumerate() {
+        adjacency[a].push((b, i));
+        if !directed {
+            adjacency[b].push((a, i));
+        }
+    }
+    let cost = |i: usize| edges[i].3;
+
+    // The edges to repeat, as paths between pairs of nodes
+    let mut repeated = Vec::new();
+    if directed {
+        let mut balance = vec![0isize; n];
+        for &(a, b, _, _) in &edges {
+            balance[a] -= 1;
+            balance[b] += 1;
+        }
+        let sources: Vec<usize> = (0..n).filter(|&v| balance[v] > 0).collect();
+        let targets: Vec<usize> = (0..n).filter(|&v| balance[v] < 0).collect();
+        let trees: Vec<_> = sources
+            .iter()
+            .map(|&s| shortest_path_tree(&adjacency, s, cost))
+            .collect();
+        let supply: Vec<usize> = sources.iter().map(|&s| balance[s] as usize).collect();
+        let demand: Vec<usize> = targets.iter().map(|&t| (-balance[t]) as usize).collect();
+        let distance = |i: usize, j: usize| trees[i].0[targets[j]];
+        let flow = transportation(&supply, &demand, distance)?;
+        for (i, j, amount) in flow {
+            let path = tree_path(&trees[i].1, &edges, directed, targets[j]);
+            for _ in 0..amount {
+                repeated.extend_from_slice(&path);
+            }
+        }
+    } else {
+        let mut degree = vec![0; n];
+        for &(a, b, _, _) in &edges {
+            degree[a] += 1;
+            degree[b] += 1;
+        }
+        let odd: Vec<usize> = (0..n).filter(|&v| degree[v] % 2 == 1).collect();
+        let mut trees = vec![None; n];
+        for &v in &odd {
+            trees[v] = Some(shortest_path_tree(&adjacency, v, cost));
+        }
+        let distance = |a: usize, b: usize| trees[a].as_ref().unwrap().0[b].unwrap_or_default();
+        for (a, b) in minimum_weight_perfect_matching(&odd, &distance) {
+            let tree = &trees[a].as_ref().unwrap().1;
+            repeated.extend(tree_path(tree, &edges, directed, b));
+        }
+    }
+
+    // Eulerian circuit of the edges and the repeated edges, by Hierholzer's
+    // algorithm
+    let mut multigraph = vec![Vec::new(); n];
+    let copies: Vec<usize> = (0..edges.len()).chain(repeated).collect();
+    for (copy, &i) in copies.iter().enumerate() {
+        let (a, b, _, _) = edges[i];
+        multigraph[a].push((b, copy));
+        if !directed {
+            multigraph[b].push((a, copy));
+        }
+    }
+    let mut used = vec![false; copies.len()];
+    let mut walk = Vec::with_capacity(copies.len());
+    let mut stack = vec![(edges[0].0, None)];
+    while let Some(&(v, via)) = stack.last() {
+        match multigraph[v].pop() {
+            Some((_, copy)) if used[copy] => {}
+            Some((w, copy)) => {
+                used[copy] = true;
+                stack.push((w, Some(copy)));
+            }
+            None => {
+                stack.pop();
+                if let Some(copy) = via {
+                    walk.push(edges[copies[copy]].2);
+                }
+            }
+        }
+    }
+    walk.reverse();
+
+    let total = copies.iter().fold(K::default(), |acc, &i| acc + cost(i));
+    Some((total, walk))
+}
+
+/// The distances from `source` by Dijkstra's algorithm, and the edge to each
+/// node from its parent in the shortest path tree.
+fn shortest_path_tree<K, C>(
+    adjacency: &[Vec<(usize, usize)>],
+    source: usize,
+    cost: C,
+) -> (Vec<Option<K>>, Vec<Option<usize>>)
+where
+    K: Measure + Copy,
+    C: Fn(usize) -> K,
+{
+    let mut distance = vec![None; adjacency.len()];
+    let mut parent = vec![None; adjacency.len()];
+    let mut done = vec![false; adjacency.len()];
+    let mut heap = BinaryHeap::new();
+    distance[source] = Some(K::default());
+    heap.push(MinScored(K::default(), source));
+    while let Some(MinScored(d, v)) = heap.pop() {
+        if done[v] {
+            continue;
+        }
+        done[v] = true;
+        for &(w, i) in &adjacency[v] {
+            let next = d + cost(i);
+            if !done[w] && distance[w].map_or(true, |old| next < old) {
+                distance[w] = Some(next);
+                parent[w] = Some(i);
+                heap.push(MinScored(next, w));
+            }
+        }
+    }
+    (distance, parent)
+}
+
+/// The edges of the path from the root of a shortest path tree to `target`.
+fn tree_path<N, K>(
+    parent: &[Option<usize>],
+    edges: &[(usize, usize, N, K)],
+    directed: bool,
+    target: usize,
+) -> Vec<usize> {
+    let mut path = Vec::new();
+    let mut v = target;
+    while let Some(i) = parent[v] {
+        path.push(i);
+        let (a, b, _, _) = edges[i];
+        v = if directed || b == v { a } else { b };
+    }
+    path
+}
+
+/// Solve the transportation problem: send `supply[i]` units from each source
+/// `i` to the targets, which receive `demand[j]` units each, minimizing the
+/// total cost, where sending a unit from `i` to `j` costs `distance(i, j)`, or
+/// is impossible if `None`. Returns the amounts sent from sources to targets,
+/// or `None` if the demand cannot be met.
+///
+/// Successive shortest paths, with Dijkstra's algorithm on costs reduced by
+/// potentials so that they stay non-negative.
+fn transportation<K, D>(
+    supply: &[usize],
+    demand: &[usize],
+    distance: D,
+) -> Option<Vec<(usize, usize, usize)>>
+where
+    K: Measure + Copy + Sub<Output = K>,
+    D: Fn(usize, usize) -> Option<K>,
+{
+    let (s, t) = (supply.len(), demand.len());
+    // Nodes: the source, the sources, the targets, the sink
+    let sink = s + t + 1;
+    let mut flow = vec![0; s * t];
+    let mut supplied = vec![0; s];
+    let mut received = vec![0; t];
+    let mut potential = vec![K::default(); sink + 1];
+    let total: usize = demand.iter().sum();
+    let mut sent = 0;
+    while sent < total {
+        // Dense Dijkstra on the residual network with reduced costs
+        let mut dist: Vec<Option<K>> = vec![None; sink + 1];
+        let mut parent = vec![0; sink + 1];
+        let mut done = vec![false; sink + 1];
+        dist[0] = Some(K::default());
+        loop {
+            let u = (0..=sink).filter(|&u| !done[u] && dist[u].is_some()).fold(
+                None,
+                |best: Option<usize>, u| match best {
+                    Some(b) if dist[b] <= dist[u] => Some(b),
+                    _ => Some(u),
+                },
+            );
+            let u = match u {
+                Some(u) => u,
+                None => break,
+            };
+            done[u] = true;
+            let du = dist[u].unwrap();
+            let mut relax = |v: usize, reduced: K| {
+                let next = du + reduced;
+                if !done[v] && dist[v].map_or(true, |old| next < old) {
+                    dist[v] = Some(next);
+                    parent[v] = u;
+                }
+            };
+            if u == 0 {
+                for i in 0..s {
+                    if supplied[i] < supply[i] {
+                        relax(1 + i, potential[0] - potential[1 + i]);
+                    }
+                }
+            } else if u <= s {
+                let i = u - 1;
+                for j in 0..t {
+                    if let Some(c) = distance(i, j) {
+                        relax(1 + s + j, (c + potential[u]) - potential[1 + s + j]);
+                    }
+                }
+            } else if u < sink {
+                let j = u - 1 - s;
+                if received[j] < demand[j] {
+                    relax(sink, potential[u] - potential[sink]);
+                }
+                for i in 0..s {
+                    if flow[i * t + j] > 0 {
+                        let c = distance(i, j).unwrap();
+                        relax(1 + i, potential[u] - (c + potential[1 + i]));
+                    }
+                }
+            }
+        }
+        dist[sink]?;
+        for v in 0..=sink {
+            if let Some(d) = dist[v] {
+                potential[v] = potential[v] + d;
+            }
+        }
+
+        // Augment along the path, by the bottleneck of the source and sink arcs
+        // and of the reversed arcs
+        let mut path = vec![sink];
+        while *path.last().unwrap() != 0 {
+            path.push(parent[*path.last().unwrap()]);
+        }
+        path.reverse();
+        let mut amount = std::usize::MAX;
+        for w in path.windows(2) {
+            let (u, v) = (w[0], w[1]);
+            if u == 0 {
+                amount = amount.min(supply[v - 1] - supplied[v - 1]);
+            } else if v == sink {
+                amount = amount.min(demand[u - 1 - s] - received[u - 1 - s]);
+            } else if u > s {
+                amount = amount.min(flow[(v - 1) * t + (u - 1 - s)]);
+            }
+        }
+        for w in path.windows(2) {
+            let (u, v) = (w[0], w[1]);
+            if u == 0 {
+                supplied[v - 1] += amount;
+            } else if v == sink {
+                received[u - 1 - s] += amount;
+            } else if u <= s {
+                flow[(u - 1) * t + (v - 1 - s)] += amount;
+            } else {
+                flow[(v - 1) * t + (u - 1 - s)] -= amount;
+            }
+        }
+        sent += amount;
+    }
+
+    let mut amounts = Vec::new();
+    for i in 0..s {
+        for j in 0..t {
+            if flow[i * t + j] > 0 {
+                amounts.push((i, j, flow[i * t + j]));
+            }
+        }
+    }
+    Some(amounts)
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+use std::ops::{Div, Sub};
+
+use crate::algo::matching::minimum_weight_perfect_matching;
+use crate::algo::{min_spanning_tree, Measure};
+use crate::data::Element;
+use crate::graph::{NodeIndex, UnGraph};
+use crate::visit::{EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
+
+/// \[Generic\] Approximate a shortest
+/// [travelling salesman](https://en.wikipedia.org/wiki/Travelling_salesman_problem)
+/// tour with [Christofides' algorithm](https://en.wikipedia.org/wiki/Christofides_algorithm),
+/// refined by 2-opt.
+///
+/// The graph is treated as undirected and must be complete: every pair of
+/// distinct nodes must be joined by an edge, of cost given by `edge_cost`. The
+/// costs should be non-negative and satisfy the triangle inequality. The
+/// cheapest of parallel edges is used, and loops are ignored.
+///
+/// A minimum spanning tree is computed with
+/// [`min_spanning_tree`](fn@crate::algo::min_spanning_tree), and its nodes of
+/// odd degree are paired by a minimum weight perfect matching, computed with
+/// Edmonds' blossom algorithm. The Eulerian circuit of the tree and the
+/// matching is then shortcut to a tour, which is improved with 2-opt moves
+/// until no move shortens it.
+///
+/// For metric costs, the tour costs at most 1.5 times the optimum.
+///
+/// Computing the matching takes **O(|V|³)** time.
+///
+/// Returns the cost of the tour and its nodes, in order, without repeating the
+/// first node, or `None` if the graph is not complete.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::christofides;
+/// use petgraph::prelude::*;
+///
+/// // The corners and the center of a square, with Manhattan distances
+/// let points = [(0i32, 0i32), (0, 2), (2, 2), (2, 0), (1, 1)];
+/// let mut graph = UnGraph::<(), i32>::default();
+/// let nodes: Vec<_> = points.iter().map(|_| graph.add_node(())).collect();
+/// for i in 0..points.len() {
+///     for j in i + 1..points.len() {
+///         let (a, b) = (points[i], points[j]);
+///         let d = (a.0 - b.0).abs() + (a.1 - b.1).abs();
+///         graph.add_edge(nodes[i], nodes[j], d);
+///     }
+/// }
+///
+/// let (cost, tour) = christofides(&graph, |e| *e.weight()).unwrap();
+/// assert_eq!(cost, 10);
+/// assert_eq!(tour.len(), 5);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn christofides<G, F, K>(graph: G, mut edge_cost: F) -> Option<(K, Vec<G::NodeId>)>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy + Sub<Output = K> + Div<Output = K> + From<u8>,
+{
+    let nodes: Vec<_> = graph.node_identifiers().collect();
+    let n = nodes.len();
+    let compact: HashMap<_, _> = nodes
+        .iter()
+        .enumerate()
+        .map(|(i, &node)| (graph.to_index(node), i))
+        .collect();
+
+    let mut cost: Vec<Option<K>> = vec![None; n * n];
+    for edge in graph.edge_references() {
+        let a = compact[&graph.to_index(edge.source())];
+        let b = compact[&graph.to_index(edge.target())];
+        if a != b {
+            let c = edge_cost(edge);
+            for &index in &[a * n + b, b * n + a] {
+                match cost[index] {
+                    Some(old) if old <= c => {}
+                    _ => cost[index] = Some(c),
+                }
+            }
+        }
+    }
+    if (0..n).any(|a| (0..n).any(|b| a != b && cost[a * n + b].is_none())) {
+        return None;
+    }
+    let d = |a: usize, b: usize| cost[a * n + b].unwrap_or_default();
+
+    let tour_cost = |tour: &[usize]| {
+        (0..tour.len()).fold(K::default(), |acc, i| {
+            acc + d(tour[i], tour[(i + 1) % tour.len()])
+        })
+    };
+    if n <= 3 {
+        let tour: Vec<usize> = (0..n).collect();
+        return Some((tour_cost(&tour), nodes));
+    }
+
+    // Minimum spanning tree of the complete graph
+    let mut complete = UnGraph::<(), K>::with_capacity(n, n * (n - 1) / 2);
+    for _ in 0..n {
+        complete.add_node(());
+    }
+    for a in 0..n {
+        for b in a + 1..n {
+            complete.add_edge(NodeIndex::new(a), NodeIndex::new(b), d(a, b));
+        }
+    }
+    let mut edges = Vec::new();
+    for element in min_spanning_tree(&complete) {
+        if let Element::Edge { source, target, .. } = element {
+            edges.push((source, target));
+        }
+    }
+
+    // Pair the nodes of odd degree
+    let mut degree = vec![0; n];
+    for &(a, b) in &edges {
+        degree[a] += 1;
+        degree[b] += 1;
+    }
+    let odd: Vec<usize> = (0..n).filter(|&v| degree[v] % 2 == 1).collect();
+    edges.extend(minimum_weight_perfect_matching(&odd, &d));
+    let mut multigraph = vec![Vec::new(); n];
+    for (i, &(a, b)) in edges.iter().enumerate() {
+        multigraph[a].push((b, i));
+        multigraph[b].push((a, i));
+    }
+
+    // Eulerian circuit by Hierholzer's algorithm, shortcut to a tour
+    let mut used = vec![false; edges.len()];
+    let mut visited = vec![false; n];
+    let mut tour = Vec::with_capacity(n);
+    let mut stack = vec![0];
+    while let Some(&v) = stack.last() {
+        match multigraph[v].pop() {
+            Some((_, e)) if used[e] => {}
+            Some((w, e)) => {
+                used[e] = true;
+                stack.push(w);
+            }
+            None => {
+                stack.pop();
+                if !visited[v] {
+                    visited[v] = true;
+                    tour.push(v);
+                }
+            }
+        }
+    }
+
+    two_opt(&mut tour, &d);
+    Some((
+        tour_cost(&tour),
+        tour.into_iter().map(|v| nodes[v]).collect(),
+    ))
+}
+
+/// Improve a tour with 2-opt moves, reversing a section of the tour whenever
+/// that shortens it, until no move does.
+fn two_opt<K, D>(tour: &mut [usize], d: &D)
+where
+    K: Measure + Copy,
+    D: Fn(usize, usize) -> K,
+{
+    let n = tour.len();
+    let mut improved = true;
+    while improved {
+        improved = false;
+        for i in 0..n - 1 {
+            for j in i + 2..n {
+                if i == 0 && j == n - 1 {
+                    // The two edges are adjacent
+                    continue;
+                }
+                let (a, b, c, e) = (tour[i], tour[i + 1], tour[j], tour[(j + 1) % n]);
+                if d(a, c) + d(b, e) < d(a, b) + d(c, e) {
+                    tour[i + 1..=j].reverse();
+                    improved = true;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,322 @@
+//! Finding and enumerating the cycles of a graph.
+
+use fixedbitset::FixedBitSet;
+
+use crate::adj::List;
+use crate::algo::tarjan_scc;
+use crate::visit::{EdgeRef, IntoEdges, IntoNeighbors, IntoNodeIdentifiers, NodeIndexable};
+
+/// \[Generic\] Find a cycle of a directed graph.
+///
+/// Returns the nodes of a cycle, in order: each node has an edge to the next
+/// one, and the last one has an edge to the first one. A self loop is a cycle
+/// of a single node. Returns `None` if the graph is acyclic.
+///
+/// Unlike [`is_cyclic_directed`](super::is_cyclic_directed), this returns the
+/// cycle as a witness. The implementation is iterative.
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::find_cycle_directed;
+/// use petgraph::prelude::*;
+///
+/// let mut graph = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (0, 2)]);
+/// assert_eq!(find_cycle_directed(&graph), None);
+///
+/// graph.add_edge(NodeIndex::new(2), NodeIndex::new(1), ());
+/// let n = NodeIndex::new;
+/// assert_eq!(find_cycle_directed(&graph), Some(vec![n(2), n(1)]));
+/// ```
+pub fn find_cycle_directed<G>(g: G) -> Option<Vec<G::NodeId>>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    find_cycle(g, false)
+}
+
+/// \[Generic\] Find a cycle of an undirected graph.
+///
+/// Returns the nodes of a cycle, in order: each node is adjacent to the next
+/// one, and the last one to the first one, through distinct edges. A self
+/// loop is a cycle of a single node, and two parallel edges make a cycle of
+/// two nodes. Returns `None` if the graph is a forest.
+///
+/// Unlike [`is_cyclic_undirected`](super::is_cyclic_undirected), this returns
+/// the cycle as a witness. The graph must yield each edge from both of its
+/// endpoints, as undirected graphs do. The implementation is iterative.
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::find_cycle_undirected;
+/// use petgraph::prelude::*;
+///
+/// let mut graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (3, 2)]);
+/// assert_eq!(find_cycle_undirected(&graph), None);
+///
+/// graph.add_edge(NodeIndex::new(3), NodeIndex::new(1), ());
+/// let n = NodeIndex::new;
+/// assert_eq!(find_cycle_undirected(&graph), Some(vec![n(1), n(2), n(3)]));
+/// ```
+pub fn find_cycle_undirected<G>(g: G) -> Option<Vec<G::NodeId>>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    find_cycle(g, true)
+}
+
+/// Find a cycle by depth-first search: an edge to a node on the stack closes
+/// a cycle. For undirected graphs, the edge to the parent is skipped.
+fn find_cycle<G>(g: G, undirected: bool) -> Option<Vec<G::NodeId>>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    let mut discovered = FixedBitSet::with_capacity(g.node_bound());
+    let mut on_stack = FixedBitSet::with_capacity(g.node_bound());
+    for start in g.node_identifiers() {
+        if discovered.put(g.to_index(start)) {
+            continue;
+        }
+        on_stack.insert(g.to_index(start));
+        // Each entry holds a node, the edge it was reached through and its
+        // remaining edges.
+        let mut stack = vec![(start, None, g.edges(start))];
+        while let Some((node, via, edges)) = stack.last_mut() {
+            let edge = match edges.next() {
+                Some(edge) => edge,
+                None => {
+                    on_stack.set(g.to_index(*node), false);
+                    stack.pop();
+                    continue;
+                }
+            };
+            if undirected && Some(edge.id()) == *via {
+                continue;
+            }
+            let next = edge.target();
+            if on_stack[g.to_index(next)] {
+                let first = stack.iter().position(|&(n, ..)| n == next).unwrap();
+                return Some(stack[first..].iter().map(|&(n, ..)| n).collect());
+            }
+            if !discovered.put(g.to_index(next)) {
+                on_stack.insert(g.to_index(next));
+                stack.push((next, Some(edge.id()), g.edges(next)));
+            }
+        }
+    }
+    None
+}
+
+/// \[Generic\] Enumerate the simple cycles of a directed graph, with
+/// [Johnson's algorithm](https://doi.org/10.1137/0204007).
+///
+/// Returns an iterator over the elementary cycles of the graph, the cycles
+/// that do not visit a node twice. Each cycle is listed once, as its nodes in
+/// order, starting from its node of smallest index: each node has an edge to
+/// the next one, and the last one has an edge to the first one. A self loop is
+/// a cycle of a single node. Parallel edges do not make distinct cycles.
+///
+/// If `max_length` is not `None`, only the cycles of at most that many nodes
+/// are listed. The blocking rules of Johnson's algorithm do not apply to
+/// bounded searches, which may then take longer per cycle.
+///
+/// The cycles are computed lazily. Without a length bound, the time between
+/// two cycles is **O(|V| + |E|)**. A graph may have exponentially many cycles.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::simple_cycles;
+/// use petgraph::prelude::*;
+///
+/// let graph = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 0), (1, 2), (2, 0), (2, 2)]);
+/// let n = NodeIndex::new;
+///
+/// let mut cycles: Vec<_> = simple_cycles(&graph, None).collect();
+/// cycles.sort();
+/// assert_eq!(cycles, vec![vec![n(0), n(1)], vec![n(0), n(1), n(2)], vec![n(2)]]);
+///
+/// assert_eq!(simple_cycles(&graph, Some(2)).count(), 2);
+/// ```
+pub fn simple_cycles<G>(g: G, max_length: Option<usize>) -> SimpleCycles<G>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let mut successors = vec![Vec::new(); g.node_bound()];
+    for n in g.node_identifiers() {
+        let succ = &mut successors[g.to_index(n)];
+        succ.extend(g.neighbors(n).map(|m| g.to_index(m)));
+        succ.sort_unstable();
+        succ.dedup();
+    }
+    let components = tarjan_scc(g)
+        .into_iter()
+        .map(|scc| scc.into_iter().map(|n| g.to_index(n)).collect::<Vec<_>>())
+        .filter(|scc| has_cycle(&successors, scc))
+        .collect();
+    SimpleCycles {
+        graph: g,
+        max_length,
+        in_component: FixedBitSet::with_capacity(g.node_bound()),
+        blocked: FixedBitSet::with_capacity(g.node_bound()),
+        block_map: vec![Vec::new(); g.node_bound()],
+        successors,
+        components,
+        start: 0,
+        path: Vec::new(),
+        frames: Vec::new(),
+    }
+}
+
+/// An iterator over the simple cycles of a directed graph.
+///
+/// Created with [`simple_cycles`].
+#[derive(Clone, Debug)]
+pub struct SimpleCycles<G> {
+    graph: G,
+    max_length: Option<usize>,
+    /// The successors of each node, by node index, without duplicates.
+    successors: Vec<Vec<usize>>,
+    /// The strongly connected subgraphs left to search, each with a cycle.
+    components: Vec<Vec<usize>>,
+    /// The nodes of the subgraph being searched.
+    in_component: FixedBitSet,
+    /// The smallest node of the subgraph being searched, the first node of
+    /// the cycles found.
+    start: usize,
+    /// The path from `start`.
+    path: Vec<usize>,
+    /// For each node of the path, the position of its next successor to try
+    /// and whether a cycle was found through it.
+    frames: Vec<(usize, bool)>,
+    blocked: FixedBitSet,
+    /// The nodes to unblock when unblocking each node.
+    block_map: Vec<Vec<usize>>,
+}
+
+impl<G> SimpleCycles<G> {
+    /// Start the search for the cycles through the smallest node of the next
+    /// component, and schedule the rest of the component.
+    fn start_search(&mut self) -> bool {
+        let component = match self.components.pop() {
+            Some(component) => component,
+            None => return false,
+        };
+        let start = *component.iter().min().unwrap();
+        let rest: Vec<_> = component.iter().copied().filter(|&n| n != start).collect();
+        self.components
+            .extend(strong_components(&self.successors, &rest));
+
+        self.in_component.clear();
+        self.blocked.clear();
+        for &n in &component {
+            self.in_component.insert(n);
+            self.block_map[n].clear();
+        }
+        self.start = start;
+        self.path.push(start);
+        self.frames.push((0, false));
+        self.blocked.insert(start);
+        true
+    }
+
+    /// Unblock `node`, and the nodes that were waiting for it.
+    fn unblock(&mut self, node: usize) {
+        let mut stack = vec![node];
+        while let Some(n) = stack.pop() {
+            if self.blocked[n] {
+                self.blocked.set(n, false);
+                stack.append(&mut self.block_map[n]);
+            }
+        }
+    }
+}
+
+impl<G> Iterator for SimpleCycles<G>
+where
+    G: NodeIndexable,
+{
+    type Item = Vec<G::NodeId>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if self.path.is_empty() && !self.start_search() {
+                return None;
+            }
+            let node = *self.path.last().unwrap();
+            let in_component = &self.in_component;
+            let frame = self.frames.last_mut().unwrap();
+            let next = self.successors[node][frame.0..]
+                .iter()
+                .position(|&m| in_component[m]);
+            match next {
+                Some(k) => {
+                    frame.0 += k + 1;
+                    let next = self.successors[node][frame.0 - 1];
+                    let len = self.path.len();
+                    if next == self.start {
+                        if self.max_length.map_or(true, |max| len <= max) {
+                            frame.1 = true;
+                            let g = &self.graph;
+                            return Some(self.path.iter().map(|&n| g.from_index(n)).collect());
+                        }
+                    } else if !self.blocked[next] && self.max_length.map_or(true, |max| len < max) {
+                        self.path.push(next);
+                        self.frames.push((0, false));
+                        self.blocked.insert(next);
+                    }
+                }
+                None => {
+                    self.path.pop();
+                    let (_, closed) = self.frames.pop().unwrap();
+                    if self.max_length.is_some() {
+                        // Without blocking, only the nodes of the path are
+                        // excluded
+                        self.blocked.set(node, false);
+                    } else if closed {
+                        if let Some(frame) = self.frames.last_mut() {
+                            frame.1 = true;
+                        }
+                        self.unblock(node);
+                    } else {
+                        for &m in &self.successors[node] {
+                            if self.in_component[m] && !self.block_map[m].contains(&node) {
+                                self.block_map[m].push(node);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Whether a strongly connected set of nodes has a cycle: it has more than
+/// one node, or a self loop.
+fn has_cycle(successors: &[Vec<usize>], scc: &[usize]) -> bool {
+    scc.len() > 1 || successors[scc[0]].contains(&scc[0])
+}
+
+/// The strongly connected components of the subgraph induced by `nodes`
+/// that have a cycle.
+fn strong_components(successors: &[Vec<usize>], nodes: &[usize]) -> Vec<Vec<usize>> {
+    let mut local = vec![std::usize::MAX; successors.len()];
+    let mut subgraph = List::<(), usize>::with_capacity(nodes.len());
+    for &n in nodes {
+        local[n] = subgraph.add_node();
+    }
+    for &n in nodes {
+        for &m in &successors[n] {
+            if local[m] != std::usize::MAX {
+                subgraph.add_edge(local[n], local[m], ());
+            }
+        }
+    }
+    tarjan_scc(&subgraph)
+        .into_iter()
+        .map(|scc| scc.into_iter().map(|i| nodes[i]).collect::<Vec<_>>())
+        .filter(|scc| has_cycle(successors, scc))
+        .collect()
+}
@@ -0,0 +1,159 @@
+use crate::algo::{toposort, Cycle, Measure};
+use crate::visit::{EdgeRef, IntoEdges, IntoNeighborsDirected, IntoNodeIdentifiers};
+use crate::visit::{NodeIndexable, Visitable};
+
+/// The shortest or longest paths from a source node of a directed acyclic
+/// graph, as computed by [`dag_shortest_paths`] and [`dag_longest_paths`].
+///
+/// The distances and predecessors are indexed by
+/// [`NodeIndexable::to_index`]; the nodes not reachable from the source have
+/// neither.
+#[derive(Clone, Debug)]
+pub struct DagPaths<N, K> {
+    pub distances: Vec<Option<K>>,
+    pub predecessors: Vec<Option<N>>,
+}
+
+impl<N: Copy, K> DagPaths<N, K> {
+    /// Return the path from the source to `target`, from the source, or
+    /// `None` if `target` is not reachable.
+    pub fn path_to<G>(&self, graph: G, target: N) -> Option<Vec<N>>
+    where
+        G: NodeIndexable<NodeId = N>,
+    {
+        self.distances.get(graph.to_index(target))?.as_ref()?;
+        let mut path = vec![target];
+        while let Some(n) = self.predecessors[graph.to_index(*path.last().unwrap())] {
+            path.push(n);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+/// \[Generic\] Compute the shortest paths from node `source` of a directed
+/// acyclic graph.
+///
+/// The nodes are relaxed in topological order, so that edge costs may be
+/// negative, unlike with [`dijkstra`](fn@super::dijkstra), and the computation
+/// is faster than [`bellman_ford`](fn@super::bellman_ford).
+///
+/// If the graph contains a cycle, a [`Cycle`] error is returned. See
+/// [`Acyclic::shortest_paths`](crate::acyclic::Acyclic::shortest_paths) to
+/// reuse the topological order maintained by [`Acyclic`](crate::acyclic::Acyclic).
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::dag_shortest_paths;
+/// use petgraph::prelude::*;
+///
+/// let graph = DiGraph::<(), i32>::from_edges(&[(0, 1, 2), (0, 2, 5), (1, 2, -1), (3, 0, 1)]);
+/// let (a, c) = (NodeIndex::new(0), NodeIndex::new(2));
+///
+/// let paths = dag_shortest_paths(&graph, a, |e| *e.weight()).unwrap();
+/// assert_eq!(paths.distances, vec![Some(0), Some(2), Some(1), None]);
+/// assert_eq!(paths.path_to(&graph, c), Some(vec![a, NodeIndex::new(1), c]));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn dag_shortest_paths<G, F, K>(
+    graph: G,
+    source: G::NodeId,
+    edge_cost: F,
+) -> Result<DagPaths<G::NodeId, K>, Cycle<G::NodeId>>
+where
+    G: IntoEdges + IntoNeighborsDirected + IntoNodeIdentifiers + NodeIndexable + Visitable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure,
+{
+    let order = toposort(graph, None)?;
+    Ok(dag_paths_in_order(graph, order, source, edge_cost, false))
+}
+
+/// \[Generic\] Compute the longest paths from node `source` of a directed
+/// acyclic graph.
+///
+/// Longest paths are not well defined with cycles, and cannot be found with
+/// [`dijkstra`](fn@super::dijkstra) even with negated costs. The nodes are
+/// relaxed in topological order; edge costs may be negative. See
+/// [`longest_path`](fn@super::longest_path) for a longest path of the whole
+/// graph.
+///
+/// If the graph contains a cycle, a [`Cycle`] error is returned. See
+/// [`Acyclic::longest_paths`](crate::acyclic::Acyclic::longest_paths) to
+/// reuse the topological order maintained by [`Acyclic`](crate::acyclic::Acyclic).
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::dag_longest_paths;
+/// use petgraph::prelude::*;
+///
+/// let graph = DiGraph::<(), i32>::from_edges(&[(0, 1, 2), (0, 2, 5), (1, 2, -1), (3, 0, 1)]);
+/// let (a, c) = (NodeIndex::new(0), NodeIndex::new(2));
+///
+/// let paths = dag_longest_paths(&graph, a, |e| *e.weight()).unwrap();
+/// assert_eq!(paths.distances, vec![Some(0), Some(2), Some(5), None]);
+/// assert_eq!(paths.path_to(&graph, c), Some(vec![a, c]));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn dag_longest_paths<G, F, K>(
+    graph: G,
+    source: G::NodeId,
+    edge_cost: F,
+) -> Result<DagPaths<G::NodeId, K>, Cycle<G::NodeId>>
+where
+    G: IntoEdges + IntoNeighborsDirected + IntoNodeIdentifiers + NodeIndexable + Visitable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure,
+{
+    let order = toposort(graph, None)?;
+    Ok(dag_paths_in_order(graph, order, source, edge_cost, true))
+}
+
+/// Compute the shortest, or longest, paths from `source` of a directed
+/// acyclic graph, given a topological order of its nodes.
+pub(crate) fn dag_paths_in_order<G, I, F, K>(
+    graph: G,
+    order: I,
+    source: G::NodeId,
+    mut edge_cost: F,
+    longest: bool,
+) -> DagPaths<G::NodeId, K>
+where
+    G: IntoEdges + NodeIndexable,
+    I: IntoIterator<Item = G::NodeId>,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure,
+{
+    let mut distances: Vec<Option<K>> = vec![None; graph.node_bound()];
+    let mut predecessors = vec![None; graph.node_bound()];
+    distances[graph.to_index(source)] = Some(K::default());
+
+    for n in order {
+        let d = match &distances[graph.to_index(n)] {
+            Some(d) => d.clone(),
+            None => continue,
+        };
+        for edge in graph.edges(n) {
+            let target = graph.to_index(edge.target());
+            let candidate = d.clone() + edge_cost(edge);
+            let better = match &distances[target] {
+                None => true,
+                Some(old) if longest => candidate > *old,
+                Some(old) => candidate < *old,
+            };
+            if better {
+                distances[target] = Some(candidate);
+                predecessors[target] = Some(n);
+            }
+        }
+    }
+
+    DagPaths {
+        distances,
+        predecessors,
+    }
+}
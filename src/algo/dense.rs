@@ -0,0 +1,291 @@
+//! Dense all-pairs shortest paths and level-synchronous breadth-first search,
+//! computed by a pluggable [`DenseBackend`].
+
+use std::ops::Range;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::algo::NegativeCycle;
+use crate::visit::{
+    EdgeRef, GraphProp, IntoEdgeReferences, IntoNeighbors, NodeCompactIndexable, NodeIndexable,
+};
+
+/// The level of the nodes that a breadth-first search does not reach, in
+/// [`DenseBackend::bfs_levels`].
+pub const UNREACHED: u32 = std::u32::MAX;
+
+/// A backend running the dense kernels of [`dense_floyd_warshall`] and
+/// [`level_bfs`] on flat arrays, e.g. on the CPU with [`Cpu`], or on a GPU.
+///
+/// The kernels only see plain arrays of `f32` and indices, which can be
+/// copied to device memory as they are.
+pub trait DenseBackend {
+    /// Compute the all-pairs shortest paths in place, where `dist` is the
+    /// `n` × `n` matrix of costs stored row by row, with
+    /// `f32::INFINITY` for a missing edge.
+    ///
+    /// When the graph has a cycle of negative cost, the distances are
+    /// unspecified, but the diagonal must have a negative entry.
+    fn floyd_warshall(&self, n: usize, dist: &mut [f32]);
+
+    /// Return the breadth-first search level of each node from `source`, or
+    /// [`UNREACHED`], where the successors of node `i` are
+    /// `targets[offsets[i]..offsets[i + 1]]`.
+    fn bfs_levels(&self, offsets: &[usize], targets: &[usize], source: usize) -> Vec<u32>;
+}
+
+/// The CPU backend.
+///
+/// All-pairs shortest paths are computed by a tiled Floyd–Warshall, the same
+/// decomposition as GPU implementations, which keeps the working set in cache.
+/// With the `rayon` feature, the tiles are processed in parallel.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Cpu;
+
+/// The side of the tiles of the CPU Floyd–Warshall.
+const TILE: usize = 64;
+
+/// Relax `dist[i][j]` through the nodes `ks`, for `i` in `rows` and `j` in
+/// `cols`, in the order of Floyd–Warshall.
+fn relax(n: usize, dist: &mut [f32], rows: Range<usize>, cols: Range<usize>, ks: Range<usize>) {
+    for k in ks {
+        for i in rows.clone() {
+            let dist_ik = dist[i * n + k];
+            if dist_ik == std::f32::INFINITY {
+                continue;
+            }
+            for j in cols.clone() {
+                let through_k = dist_ik + dist[k * n + j];
+                if through_k < dist[i * n + j] {
+                    dist[i * n + j] = through_k;
+                }
+            }
+        }
+    }
+}
+
+impl DenseBackend for Cpu {
+    fn floyd_warshall(&self, n: usize, dist: &mut [f32]) {
+        assert_eq!(
+            dist.len(),
+            n * n,
+            "Cpu::floyd_warshall: not a square matrix"
+        );
+        for start in (0..n).step_by(TILE) {
+            let ks = start..std::cmp::min(start + TILE, n);
+            // The pivot tile, then the pivot rows and columns
+            relax(n, dist, ks.clone(), ks.clone(), ks.clone());
+            relax(n, dist, ks.clone(), 0..n, ks.clone());
+            relax(n, dist, 0..n, ks.clone(), ks.clone());
+
+            // All other rows, through the final pivot rows
+            let pivot = dist[ks.start * n..ks.end * n].to_vec();
+            let relax_row = |(i, row): (usize, &mut [f32])| {
+                if ks.contains(&i) {
+                    return;
+                }
+                for (k, pivot_row) in ks.clone().zip(pivot.chunks(n)) {
+                    let dist_ik = row[k];
+                    if dist_ik == std::f32::INFINITY {
+                        continue;
+                    }
+                    for (dist_ij, &dist_kj) in row.iter_mut().zip(pivot_row) {
+                        let through_k = dist_ik + dist_kj;
+                        if through_k < *dist_ij {
+                            *dist_ij = through_k;
+                        }
+                    }
+                }
+            };
+            #[cfg(feature = "rayon")]
+            dist.par_chunks_mut(n).enumerate().for_each(relax_row);
+            #[cfg(not(feature = "rayon"))]
+            dist.chunks_mut(n).enumerate().for_each(relax_row);
+        }
+    }
+
+    fn bfs_levels(&self, offsets: &[usize], targets: &[usize], source: usize) -> Vec<u32> {
+        let mut levels = vec![UNREACHED; offsets.len() - 1];
+        levels[source] = 0;
+        let mut frontier = vec![source];
+        let mut next = Vec::new();
+        let mut level = 0;
+        while !frontier.is_empty() {
+            level += 1;
+            for &u in &frontier {
+                for &v in &targets[offsets[u]..offsets[u + 1]] {
+                    if levels[v] == UNREACHED {
+                        levels[v] = level;
+                        next.push(v);
+                    }
+                }
+            }
+            std::mem::swap(&mut frontier, &mut next);
+            next.clear();
+        }
+        levels
+    }
+}
+
+/// The all-pairs distances of a graph, computed by [`dense_floyd_warshall`].
+///
+/// Nodes are identified by their index, as given by
+/// [`NodeIndexable::to_index`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct DistanceMatrix {
+    n: usize,
+    dist: Vec<f32>,
+}
+
+impl DistanceMatrix {
+    /// Return the number of nodes.
+    pub fn node_count(&self) -> usize {
+        self.n
+    }
+
+    /// Return the distance from node `i` to node `j`, or `None` if `j` is not
+    /// reachable from `i`.
+    ///
+    /// **Panics** if `i` or `j` is out of bounds.
+    pub fn get(&self, i: usize, j: usize) -> Option<f32> {
+        assert!(
+            i < self.n && j < self.n,
+            "DistanceMatrix::get: index out of bounds"
+        );
+        let d = self.dist[i * self.n + j];
+        if d == std::f32::INFINITY {
+            None
+        } else {
+            Some(d)
+        }
+    }
+
+    /// Return the distances from node `i`, with `f32::INFINITY` for the
+    /// unreachable nodes.
+    pub fn row(&self, i: usize) -> &[f32] {
+        &self.dist[i * self.n..(i + 1) * self.n]
+    }
+
+    /// Return the matrix of distances, stored row by row.
+    pub fn as_slice(&self) -> &[f32] {
+        &self.dist
+    }
+}
+
+/// \[Generic\] Compute the shortest paths between all pairs of nodes with
+/// the [Floyd–Warshall algorithm](https://en.wikipedia.org/wiki/Floyd%E2%80%93Warshall_algorithm),
+/// on an `f32` distance matrix computed by `backend`.
+///
+/// Unlike [`floyd_warshall`](fn@super::floyd_warshall), the distances are
+/// returned as a dense [`DistanceMatrix`], which fits graphs of many thousands
+/// of nodes, and the computation is offloaded to a [`DenseBackend`]. The cost
+/// of parallel edges is the smallest of their costs.
+///
+/// # Arguments
+/// * `graph`: graph with no negative cycle
+/// * `backend`: the backend computing the distances, e.g. [`Cpu`]
+/// * `edge_cost`: closure that returns cost of a particular edge
+///
+/// # Returns
+/// * `Ok`: the distance matrix, if the graph contains no negative cycle
+/// * `Err`: if graph contains negative cycle.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::dense::{dense_floyd_warshall, Cpu};
+/// use petgraph::prelude::*;
+///
+/// let mut graph = DiGraph::<(), f32>::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// graph.extend_with_edges(&[(a, b, 1.), (b, c, 2.), (a, c, 4.)]);
+///
+/// let dist = dense_floyd_warshall(&graph, Cpu, |e| *e.weight()).unwrap();
+/// assert_eq!(dist.get(a.index(), c.index()), Some(3.));
+/// assert_eq!(dist.get(c.index(), a.index()), None);
+/// ```
+pub fn dense_floyd_warshall<G, B, F>(
+    graph: G,
+    backend: B,
+    mut edge_cost: F,
+) -> Result<DistanceMatrix, NegativeCycle>
+where
+    G: NodeCompactIndexable + IntoEdgeReferences + GraphProp,
+    B: DenseBackend,
+    F: FnMut(G::EdgeRef) -> f32,
+{
+    let n = graph.node_count();
+    let mut dist = vec![std::f32::INFINITY; n * n];
+    for i in 0..n {
+        dist[i * n + i] = 0.;
+    }
+    for edge in graph.edge_references() {
+        let (i, j) = (graph.to_index(edge.source()), graph.to_index(edge.target()));
+        let cost = edge_cost(edge);
+        let mut update = |i: usize, j: usize| {
+            if cost < dist[i * n + j] {
+                dist[i * n + j] = cost;
+            }
+        };
+        update(i, j);
+        if !graph.is_directed() {
+            update(j, i);
+        }
+    }
+
+    backend.floyd_warshall(n, &mut dist);
+
+    if (0..n).any(|i| dist[i * n + i] < 0.) {
+        return Err(NegativeCycle(()));
+    }
+    Ok(DistanceMatrix { n, dist })
+}
+
+/// \[Generic\] Compute the breadth-first search level of each node from
+/// `source`, with a level-synchronous search run by `backend`.
+///
+/// Return a vector indexed by node index, as given by
+/// [`NodeIndexable::to_index`], holding the number of edges of a shortest path
+/// from `source`, or `None` for the unreachable nodes.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::dense::{level_bfs, Cpu};
+/// use petgraph::prelude::*;
+///
+/// let graph = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (0, 2), (3, 0)]);
+/// let levels = level_bfs(&graph, Cpu, NodeIndex::new(0));
+/// assert_eq!(levels, vec![Some(0), Some(1), Some(1), None]);
+/// ```
+pub fn level_bfs<G, B>(graph: G, backend: B, source: G::NodeId) -> Vec<Option<usize>>
+where
+    G: IntoNeighbors + NodeIndexable,
+    B: DenseBackend,
+{
+    let n = graph.node_bound();
+    let mut offsets = Vec::with_capacity(n + 1);
+    let mut targets = Vec::new();
+    offsets.push(0);
+    for i in 0..n {
+        targets.extend(
+            graph
+                .neighbors(graph.from_index(i))
+                .map(|v| graph.to_index(v)),
+        );
+        offsets.push(targets.len());
+    }
+
+    backend
+        .bfs_levels(&offsets, &targets, graph.to_index(source))
+        .into_iter()
+        .map(|level| {
+            if level == UNREACHED {
+                None
+            } else {
+                Some(level as usize)
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,55 @@
+use crate::visit::{IntoNeighbors, NodeIndexable, VisitMap, Visitable};
+
+/// The depth-first search spanning tree of the nodes reachable from a root.
+///
+/// The parent of a node is the node it was first discovered from, so that a
+/// graph that is not a tree, e.g. a DAG, is reduced to a tree, and the edges
+/// of an undirected tree back to the parent are ignored. All vectors but
+/// `preorder` are indexed by node index.
+pub(crate) struct DfsTree<N> {
+    pub(crate) parent: Vec<Option<N>>,
+    /// The depth of each node, or `None` if it is not reachable from the root.
+    pub(crate) depth: Vec<Option<usize>>,
+    /// The children of each node, in order of discovery.
+    pub(crate) children: Vec<Vec<N>>,
+    /// The nodes of the tree, parents before children.
+    pub(crate) preorder: Vec<N>,
+}
+
+/// Compute the depth-first search spanning tree rooted at `root`, with an
+/// iterative search.
+pub(crate) fn dfs_tree<G>(graph: G, root: G::NodeId) -> DfsTree<G::NodeId>
+where
+    G: IntoNeighbors + NodeIndexable + Visitable,
+{
+    let bound = graph.node_bound();
+    let mut parent = vec![None; bound];
+    let mut depth = vec![None; bound];
+    let mut children = vec![Vec::new(); bound];
+
+    let mut preorder = vec![root];
+    let mut discovered = graph.visit_map();
+    discovered.visit(root);
+    depth[graph.to_index(root)] = Some(0);
+    let mut stack = vec![(root, graph.neighbors(root))];
+    while let Some((node, neighbors)) = stack.last_mut() {
+        let node = *node;
+        if let Some(next) = neighbors.find(|&n| discovered.visit(n)) {
+            let (idx, node_idx) = (graph.to_index(next), graph.to_index(node));
+            parent[idx] = Some(node);
+            depth[idx] = depth[node_idx].map(|d| d + 1);
+            children[node_idx].push(next);
+            preorder.push(next);
+            stack.push((next, graph.neighbors(next)));
+        } else {
+            stack.pop();
+        }
+    }
+
+    DfsTree {
+        parent,
+        depth,
+        children,
+        preorder,
+    }
+}
@@ -1,11 +1,11 @@
 use std::collections::hash_map::Entry::{Occupied, Vacant};
-use std::collections::{BinaryHeap, HashMap};
+use std::collections::HashMap;
 
 use std::hash::Hash;
 
+use crate::algo::priority_queue::{BinaryQueue, PriorityQueue};
 use crate::algo::Measure;
-use crate::scored::MinScored;
-use crate::visit::{EdgeRef, IntoEdges, VisitMap, Visitable};
+use crate::visit::{EdgeRef, GraphBase, IntoEdges, VisitMap, Visitable};
 
 /// \[Generic\] Dijkstra's shortest path algorithm.
 ///
@@ -70,25 +70,62 @@ use crate::visit::{EdgeRef, IntoEdges, VisitMap, Visitable};
 /// // z is not inside res because there is not path from b to z.
 /// ```
 pub fn dijkstra<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    goal: Option<G::NodeId>,
+    edge_cost: F,
+) -> HashMap<G::NodeId, K>
+where
+    G: IntoEdges + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    dijkstra_with_queue(graph, start, goal, edge_cost, BinaryQueue::new())
+}
+
+/// \[Generic\] Dijkstra's shortest path algorithm, with a custom priority
+/// queue.
+///
+/// Same as [`dijkstra`], with the nodes to visit stored in `queue`, which
+/// should be empty. With unsigned integer costs, a
+/// [`RadixQueue`](crate::algo::priority_queue::RadixQueue) is usually the
+/// fastest.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::dijkstra::{dijkstra, dijkstra_with_queue};
+/// use petgraph::algo::priority_queue::DaryQueue;
+/// use petgraph::prelude::*;
+///
+/// let graph = DiGraph::<(), u32>::from_edges(&[(0, 1, 2), (1, 2, 2), (0, 2, 5)]);
+/// let start = NodeIndex::new(0);
+///
+/// let res = dijkstra_with_queue(&graph, start, None, |e| *e.weight(), DaryQueue::new(4));
+/// assert_eq!(res, dijkstra(&graph, start, None, |e| *e.weight()));
+/// assert_eq!(res[&NodeIndex::new(2)], 4);
+/// ```
+pub fn dijkstra_with_queue<G, F, K, Q>(
     graph: G,
     start: G::NodeId,
     goal: Option<G::NodeId>,
     mut edge_cost: F,
+    mut queue: Q,
 ) -> HashMap<G::NodeId, K>
 where
     G: IntoEdges + Visitable,
     G::NodeId: Eq + Hash,
     F: FnMut(G::EdgeRef) -> K,
     K: Measure + Copy,
+    Q: PriorityQueue<K, G::NodeId>,
 {
     let mut visited = graph.visit_map();
     let mut scores = HashMap::new();
     //let mut predecessor = HashMap::new();
-    let mut visit_next = BinaryHeap::new();
     let zero_score = K::default();
     scores.insert(start, zero_score);
-    visit_next.push(MinScored(zero_score, start));
-    while let Some(MinScored(node_score, node)) = visit_next.pop() {
+    queue.push(zero_score, start);
+    while let Some((node_score, node)) = queue.pop() {
         if visited.is_visited(&node) {
             continue;
         }
@@ -105,13 +142,13 @@ where
                 Occupied(ent) => {
                     if next_score < *ent.get() {
                         *ent.into_mut() = next_score;
-                        visit_next.push(MinScored(next_score, next));
+                        queue.push(next_score, next);
                         //predecessor.insert(next.clone(), node.clone());
                     }
                 }
                 Vacant(ent) => {
                     ent.insert(next_score);
-                    visit_next.push(MinScored(next_score, next));
+                    queue.push(next_score, next);
                     //predecessor.insert(next.clone(), node.clone());
                 }
             }
@@ -120,3 +157,218 @@ where
     }
     scores
 }
+
+/// \[Generic\] A resumable Dijkstra search, settling the nodes reachable from
+/// a start node one at a time, in order of distance.
+///
+/// Each call to [`next_settled`](Dijkstra::next_settled), or to `next` of its
+/// `Iterator` implementation, settles one more node and returns it with its
+/// distance: the search does only the work needed for the nodes requested, so
+/// that callers can stop at a radius, after the *k* nearest nodes, or at any
+/// other criterion, and resume later.
+///
+/// Edge costs must be non-negative.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::dijkstra::Dijkstra;
+/// use petgraph::prelude::*;
+///
+/// let graph = UnGraph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 1), (0, 3, 5), (2, 3, 1)]);
+/// let mut search = Dijkstra::new(&graph, NodeIndex::new(0), |e| *e.weight());
+///
+/// // The two nearest nodes
+/// assert_eq!(search.next_settled(), Some((NodeIndex::new(0), 0)));
+/// assert_eq!(search.next_settled(), Some((NodeIndex::new(1), 1)));
+/// assert!(!search.is_settled(NodeIndex::new(3)));
+///
+/// // Resume, up to a distance of 2, keeping the first node beyond it
+/// let mut within = Vec::new();
+/// let mut beyond = None;
+/// while let Some((node, d)) = search.next_settled() {
+///     if d > 2 {
+///         beyond = Some((node, d));
+///         break;
+///     }
+///     within.push(node);
+/// }
+/// assert_eq!(within, vec![NodeIndex::new(2)]);
+/// assert_eq!(beyond, Some((NodeIndex::new(3), 3)));
+/// assert_eq!(search.next_settled(), None);
+///
+/// // The nodes within a cost of 4, using at most 2 edges
+/// let bounded: Vec<_> = Dijkstra::new(&graph, NodeIndex::new(0), |e| *e.weight())
+///     .with_max_cost(4)
+///     .with_max_hops(2)
+///     .collect();
+/// assert_eq!(bounded, vec![(NodeIndex::new(0), 0), (NodeIndex::new(1), 1), (NodeIndex::new(2), 2)]);
+/// ```
+pub struct Dijkstra<G, F, K, Q = BinaryQueue<K, (<G as GraphBase>::NodeId, usize)>>
+where
+    G: IntoEdges + Visitable,
+{
+    graph: G,
+    edge_cost: F,
+    visited: G::Map,
+    scores: HashMap<G::NodeId, K>,
+    queue: Q,
+    max_cost: Option<K>,
+    max_hops: Option<usize>,
+    /// With a hop limit, the fewest edges of the paths followed from each
+    /// settled node.
+    hops: HashMap<G::NodeId, usize>,
+}
+
+impl<G, F, K> Dijkstra<G, F, K>
+where
+    G: IntoEdges + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    /// Create a search from `start`, where `edge_cost` returns the cost of an
+    /// edge.
+    pub fn new(graph: G, start: G::NodeId, edge_cost: F) -> Self {
+        Dijkstra::with_queue(graph, start, edge_cost, BinaryQueue::new())
+    }
+}
+
+impl<G, F, K, Q> Dijkstra<G, F, K, Q>
+where
+    G: IntoEdges + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+    Q: PriorityQueue<K, (G::NodeId, usize)>,
+{
+    /// Create a search from `start`, with the nodes to visit, and the number
+    /// of edges of the paths reaching them, stored in `queue`, which should be
+    /// empty.
+    pub fn with_queue(graph: G, start: G::NodeId, edge_cost: F, mut queue: Q) -> Self {
+        let zero_score = K::default();
+        let mut scores = HashMap::new();
+        scores.insert(start, zero_score);
+        queue.push(zero_score, (start, 0));
+        Dijkstra {
+            visited: graph.visit_map(),
+            graph,
+            edge_cost,
+            scores,
+            queue,
+            max_cost: None,
+            max_hops: None,
+            hops: HashMap::new(),
+        }
+    }
+
+    /// Only follow the paths of cost at most `max_cost`: the nodes further
+    /// away are never reached, and the search ends once the nodes within
+    /// that distance are settled.
+    pub fn with_max_cost(mut self, max_cost: K) -> Self {
+        self.max_cost = Some(max_cost);
+        self
+    }
+
+    /// Only follow the paths of at most `max_hops` edges: each node is
+    /// settled with the cost of its shortest path of at most `max_hops`
+    /// edges, and the nodes further away in edges are never reached.
+    ///
+    /// A node may then be expanded again when a costlier path with fewer
+    /// edges reaches it, so that the search takes up to
+    /// **O(max_hops · |E| log |E|)** time. Set the limit before settling any
+    /// node.
+    pub fn with_max_hops(mut self, max_hops: usize) -> Self {
+        self.max_hops = Some(max_hops);
+        self
+    }
+
+    /// Return `true` if a path of `hops` edges to the settled `node` may reach
+    /// nodes, under the hop limit, that the paths followed from it do not.
+    fn fewer_hops(&self, node: G::NodeId, hops: usize) -> bool {
+        self.max_hops.is_some() && self.hops.get(&node).map_or(true, |&h| hops < h)
+    }
+
+    /// Settle the nearest node not settled yet, and return it with its
+    /// distance from the start, or `None` if all the reachable nodes are
+    /// settled.
+    pub fn next_settled(&mut self) -> Option<(G::NodeId, K)> {
+        while let Some((node_score, (node, node_hops))) = self.queue.pop() {
+            let settled = self.visited.is_visited(&node);
+            if settled && !self.fewer_hops(node, node_hops) {
+                continue;
+            }
+            if self.max_hops.is_some() {
+                self.hops.insert(node, node_hops);
+            }
+            if self.max_hops.map_or(true, |max| node_hops < max) {
+                for edge in self.graph.edges(node) {
+                    let next = edge.target();
+                    if self.visited.is_visited(&next) && !self.fewer_hops(next, node_hops + 1) {
+                        continue;
+                    }
+                    let next_score = node_score + (self.edge_cost)(edge);
+                    if self.max_cost.map_or(false, |max| next_score > max) {
+                        continue;
+                    }
+                    match self.scores.entry(next) {
+                        Occupied(ent) => {
+                            if next_score < *ent.get() {
+                                *ent.into_mut() = next_score;
+                                self.queue.push(next_score, (next, node_hops + 1));
+                            } else if self.max_hops.is_some() {
+                                // A costlier path may have fewer edges
+                                self.queue.push(next_score, (next, node_hops + 1));
+                            }
+                        }
+                        Vacant(ent) => {
+                            ent.insert(next_score);
+                            self.queue.push(next_score, (next, node_hops + 1));
+                        }
+                    }
+                }
+            }
+            if !settled {
+                self.visited.visit(node);
+                return Some((node, node_score));
+            }
+        }
+        None
+    }
+
+    /// Return `true` if `node` is settled, i.e. if its distance is final.
+    pub fn is_settled(&self, node: G::NodeId) -> bool {
+        self.visited.is_visited(&node)
+    }
+
+    /// Return the distance of `node` if it is settled, or the cost of the
+    /// shortest path to it found so far, or `None` if it was not reached yet.
+    pub fn score(&self, node: G::NodeId) -> Option<K> {
+        self.scores.get(&node).copied()
+    }
+
+    /// Return the distances of the settled nodes, and the tentative costs of
+    /// the nodes reached but not settled yet.
+    pub fn scores(&self) -> &HashMap<G::NodeId, K> {
+        &self.scores
+    }
+
+    /// Return the scores, consuming the search.
+    pub fn into_scores(self) -> HashMap<G::NodeId, K> {
+        self.scores
+    }
+}
+
+impl<G, F, K, Q> Iterator for Dijkstra<G, F, K, Q>
+where
+    G: IntoEdges + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+    Q: PriorityQueue<K, (G::NodeId, usize)>,
+{
+    type Item = (G::NodeId, K);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.next_settled()
+    }
+}
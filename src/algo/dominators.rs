@@ -16,7 +16,10 @@ use std::cmp::Ordering;
 use std::collections::{hash_map::Iter, HashMap, HashSet};
 use std::hash::Hash;
 
-use crate::visit::{DfsPostOrder, GraphBase, IntoNeighbors, Visitable, Walker};
+use crate::visit::{
+    DfsPostOrder, GraphBase, GraphRef, IntoNeighbors, IntoNeighborsDirected, Visitable, Walker,
+};
+use crate::Direction::Incoming;
 
 /// The dominance relation for some graph and root.
 #[derive(Debug, Clone)]
@@ -240,6 +243,161 @@ where
     }
 }
 
+/// Compute the dominance frontier of every node reachable from the root.
+///
+/// The *dominance frontier* of a node **A** is the set of nodes **B** such
+/// that **A** dominates a predecessor of **B**, but does not strictly dominate
+/// **B**. These are the nodes where φ-functions are placed for definitions in
+/// **A** during SSA construction.
+///
+/// `dominators` must be the dominance relation of `graph`, as computed by
+/// [`simple_fast`]. The returned map has an entry, possibly empty, for every
+/// node reachable from the root.
+///
+/// This is the algorithm of Cooper et al. (see [`simple_fast`]), which
+/// computes the same frontiers as that of Cytron et al., in
+/// **O(|E| + Σ |DF|)** time.
+///
+/// ```
+/// use petgraph::algo::dominators;
+/// use petgraph::prelude::*;
+///
+/// // An if-then-else whose branches join in node 3.
+/// let graph = DiGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 3), (2, 3)]);
+/// let n = NodeIndex::new;
+/// let doms = dominators::simple_fast(&graph, n(0));
+/// let frontiers = dominators::dominance_frontiers(&graph, &doms);
+///
+/// assert_eq!(frontiers[&n(1)], vec![n(3)].into_iter().collect());
+/// assert!(frontiers[&n(0)].is_empty());
+/// ```
+pub fn dominance_frontiers<G>(
+    graph: G,
+    dominators: &Dominators<G::NodeId>,
+) -> HashMap<G::NodeId, HashSet<G::NodeId>>
+where
+    G: IntoNeighbors,
+    G::NodeId: Eq + Hash,
+{
+    let mut frontiers: HashMap<_, HashSet<_>> = dominators
+        .dominators
+        .keys()
+        .map(|&n| (n, HashSet::new()))
+        .collect();
+    for &p in dominators.dominators.keys() {
+        for b in graph.neighbors(p) {
+            // Walk up the dominator tree from the predecessor `p` of `b`,
+            // until reaching the immediate dominator of `b`.
+            let idom = dominators.immediate_dominator(b);
+            let mut runner = Some(p);
+            while let Some(r) = runner {
+                if Some(r) == idom {
+                    break;
+                }
+                frontiers.get_mut(&r).unwrap().insert(b);
+                runner = dominators.immediate_dominator(r);
+            }
+        }
+    }
+    frontiers
+}
+
+/// Compute the post-dominators of `graph` for the given exit nodes.
+///
+/// A node **A** *post-dominates* a node **B** iff every path from **B** to an
+/// exit contains **A**. The post-dominators are the dominators of the reversed
+/// graph, rooted at a virtual exit node whose successors are all the `exits`,
+/// for example the nodes without successors.
+///
+/// The virtual exit is represented by `None` and is the root of the returned
+/// dominance relation; every other node `n` is represented by `Some(n)`. A
+/// node whose immediate post-dominator is `None` is not post-dominated by any
+/// real node. Nodes that cannot reach any exit have no post-dominators.
+///
+/// ```
+/// use petgraph::algo::dominators;
+/// use petgraph::prelude::*;
+///
+/// // Node 2 may exit through either of the exits 3 and 4.
+/// let graph = DiGraph::<(), ()>::from_edges([(0, 1), (0, 2), (1, 3), (2, 3), (2, 4)]);
+/// let n = NodeIndex::new;
+/// let post_doms = dominators::post_dominators(&graph, [n(3), n(4)]);
+///
+/// assert_eq!(post_doms.immediate_dominator(Some(n(1))), Some(Some(n(3))));
+/// assert_eq!(post_doms.immediate_dominator(Some(n(0))), Some(None));
+/// ```
+///
+/// This uses the [`simple_fast`] algorithm, in **O(|V|²)** time.
+pub fn post_dominators<G, I>(graph: G, exits: I) -> Dominators<Option<G::NodeId>>
+where
+    G: IntoNeighborsDirected + Visitable,
+    G::NodeId: Eq + Hash,
+    I: IntoIterator<Item = G::NodeId>,
+{
+    let exits: Vec<_> = exits.into_iter().collect();
+    simple_fast(
+        VirtualExit {
+            graph,
+            exits: &exits,
+        },
+        None,
+    )
+}
+
+/// The reversed graph of `graph`, with a virtual root node `None` whose
+/// successors are the `exits`.
+struct VirtualExit<'a, G: GraphBase> {
+    graph: G,
+    exits: &'a [G::NodeId],
+}
+
+impl<'a, G: GraphBase + Copy> Clone for VirtualExit<'a, G> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<'a, G: GraphBase + Copy> Copy for VirtualExit<'a, G> {}
+
+impl<'a, G: GraphBase> GraphBase for VirtualExit<'a, G> {
+    type NodeId = Option<G::NodeId>;
+    type EdgeId = ();
+}
+
+impl<'a, G: GraphRef> GraphRef for VirtualExit<'a, G> {}
+
+impl<'a, G: IntoNeighborsDirected> IntoNeighbors for VirtualExit<'a, G> {
+    type Neighbors = std::vec::IntoIter<Option<G::NodeId>>;
+
+    fn neighbors(self, n: Self::NodeId) -> Self::Neighbors {
+        let neighbors: Vec<_> = match n {
+            None => self.exits.iter().copied().map(Some).collect(),
+            Some(n) => self
+                .graph
+                .neighbors_directed(n, Incoming)
+                .map(Some)
+                .collect(),
+        };
+        neighbors.into_iter()
+    }
+}
+
+impl<'a, G> Visitable for VirtualExit<'a, G>
+where
+    G: GraphBase,
+    G::NodeId: Eq + Hash,
+{
+    type Map = HashSet<Option<G::NodeId>>;
+
+    fn visit_map(&self) -> Self::Map {
+        HashSet::new()
+    }
+
+    fn reset_map(&self, map: &mut Self::Map) {
+        map.clear();
+    }
+}
+
 fn intersect(dominators: &[usize], mut finger1: usize, mut finger2: usize) -> usize {
     loop {
         match finger1.cmp(&finger2) {
@@ -0,0 +1,200 @@
+use std::collections::HashSet;
+
+use crate::visit::{IntoNeighbors, IntoNodeIdentifiers, NodeIndexable};
+
+/// \[Generic\] Finds a [feedback vertex set]: a set of nodes in the given directed graph, which
+/// when removed, make the graph acyclic.
+///
+/// Uses a heuristic to select a small number of nodes, but does not necessarily find the minimum
+/// feedback vertex set. The graph is first shrunk with the reduction rules of [Levy and Low]:
+///
+/// * nodes with no incoming or no outgoing edges are not on any cycle, and are removed,
+/// * nodes with a loop are part of the feedback vertex set,
+/// * nodes with a single predecessor or a single successor are bypassed, by connecting their
+///   predecessors directly to their successors.
+///
+/// When no rule applies, the node maximizing the product of its in and out degrees is added to
+/// the set, and the rules are applied again. Finally, the nodes that are not needed to break
+/// all cycles are dropped from the set, so that it is minimal for inclusion.
+///
+/// Does not consider node/edge weights when selecting nodes. Time complexity is
+/// **O(|V| · (|V| + |E|))** in the worst case, for an input graph with nodes **V** and edges
+/// **E**.
+///
+/// # Example
+///
+/// ```
+/// use petgraph::algo::{greedy_feedback_vertex_set, is_cyclic_directed};
+/// use petgraph::prelude::*;
+///
+/// let mut g = StableDiGraph::<(), ()>::from_edges(&[
+///     (0, 1),
+///     (1, 2),
+///     (2, 0),
+///     (2, 3),
+///     (3, 4),
+///     (4, 2),
+///     (4, 4),
+/// ]);
+///
+/// let fvs = greedy_feedback_vertex_set(&g);
+/// // The loop on 4 must be broken, and another node of the cycle 0 -> 1 -> 2 -> 0
+/// assert_eq!(fvs.len(), 2);
+/// assert!(fvs.contains(&NodeIndex::new(4)));
+///
+/// for node in fvs {
+///     g.remove_node(node);
+/// }
+/// assert!(!is_cyclic_directed(&g));
+/// ```
+///
+/// [feedback vertex set]: https://en.wikipedia.org/wiki/Feedback_vertex_set
+/// [Levy and Low]: https://doi.org/10.1109/31.1748
+pub fn greedy_feedback_vertex_set<G>(g: G) -> Vec<G::NodeId>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let mut reduction = Reduction::new(g);
+    let mut fvs = Vec::new();
+    loop {
+        reduction.reduce(&mut fvs);
+        let best = reduction
+            .alive()
+            .max_by_key(|&v| reduction.pred[v].len() * reduction.succ[v].len());
+        match best {
+            Some(v) => {
+                fvs.push(v);
+                reduction.remove(v);
+            }
+            None => break,
+        }
+    }
+
+    // Drop the nodes whose cycles are all broken by the others, most recently
+    // selected first
+    let mut in_fvs = vec![false; g.node_bound()];
+    for &v in &fvs {
+        in_fvs[v] = true;
+    }
+    for i in (0..fvs.len()).rev() {
+        in_fvs[fvs[i]] = false;
+        if is_cyclic_without(g, &in_fvs) {
+            in_fvs[fvs[i]] = true;
+        }
+    }
+    fvs.into_iter()
+        .filter(|&v| in_fvs[v])
+        .map(|v| g.from_index(v))
+        .collect()
+}
+
+/// A graph shrunk by the reduction rules, by node index.
+struct Reduction {
+    pred: Vec<HashSet<usize>>,
+    succ: Vec<HashSet<usize>>,
+    alive: Vec<bool>,
+    /// The nodes to which the rules may newly apply.
+    worklist: Vec<usize>,
+}
+
+impl Reduction {
+    fn new<G>(g: G) -> Self
+    where
+        G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+    {
+        let mut reduction = Reduction {
+            pred: vec![HashSet::new(); g.node_bound()],
+            succ: vec![HashSet::new(); g.node_bound()],
+            alive: vec![false; g.node_bound()],
+            worklist: Vec::new(),
+        };
+        for a in g.node_identifiers() {
+            let a = g.to_index(a);
+            reduction.alive[a] = true;
+            reduction.worklist.push(a);
+        }
+        for a in g.node_identifiers() {
+            for b in g.neighbors(a) {
+                reduction.add_edge(g.to_index(a), g.to_index(b));
+            }
+        }
+        reduction
+    }
+
+    fn alive(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.alive.len()).filter(move |&v| self.alive[v])
+    }
+
+    fn add_edge(&mut self, a: usize, b: usize) {
+        self.succ[a].insert(b);
+        self.pred[b].insert(a);
+    }
+
+    fn remove(&mut self, v: usize) {
+        self.alive[v] = false;
+        for w in std::mem::take(&mut self.succ[v]) {
+            self.pred[w].remove(&v);
+            self.worklist.push(w);
+        }
+        for u in std::mem::take(&mut self.pred[v]) {
+            self.succ[u].remove(&v);
+            self.worklist.push(u);
+        }
+    }
+
+    /// Apply the reduction rules until none applies, adding the nodes with a
+    /// loop to `fvs`.
+    fn reduce(&mut self, fvs: &mut Vec<usize>) {
+        while let Some(v) = self.worklist.pop() {
+            if !self.alive[v] {
+                continue;
+            }
+            if self.succ[v].contains(&v) {
+                fvs.push(v);
+                self.remove(v);
+            } else if self.pred[v].is_empty() || self.succ[v].is_empty() {
+                self.remove(v);
+            } else if self.pred[v].len() == 1 || self.succ[v].len() == 1 {
+                let pred: Vec<_> = self.pred[v].iter().copied().collect();
+                let succ: Vec<_> = self.succ[v].iter().copied().collect();
+                self.remove(v);
+                for &u in &pred {
+                    for &w in &succ {
+                        self.add_edge(u, w);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Whether the graph without the nodes in `removed` has a cycle, by Kahn's
+/// algorithm.
+fn is_cyclic_without<G>(g: G, removed: &[bool]) -> bool
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    let mut in_degree = vec![0; g.node_bound()];
+    let mut remaining = 0;
+    for a in g.node_identifiers().filter(|&a| !removed[g.to_index(a)]) {
+        remaining += 1;
+        for b in g.neighbors(a) {
+            in_degree[g.to_index(b)] += 1;
+        }
+    }
+    let mut sources: Vec<_> = g
+        .node_identifiers()
+        .filter(|&a| !removed[g.to_index(a)] && in_degree[g.to_index(a)] == 0)
+        .collect();
+    while let Some(a) = sources.pop() {
+        remaining -= 1;
+        for b in g.neighbors(a) {
+            let b_ix = g.to_index(b);
+            in_degree[b_ix] -= 1;
+            if in_degree[b_ix] == 0 && !removed[b_ix] {
+                sources.push(b);
+            }
+        }
+    }
+    remaining > 0
+}
@@ -89,29 +89,42 @@ where
     K: BoundedMeasure + Copy,
 {
     let num_of_nodes = graph.node_count();
+    let inf = K::max();
 
-    // |V|x|V| matrix
-    let mut dist = vec![vec![K::max(); num_of_nodes]; num_of_nodes];
+    // |V|x|V| matrix, stored row by row
+    let mut dist = vec![inf; num_of_nodes * num_of_nodes];
 
     // init distances of paths with no intermediate nodes
     for edge in graph.edge_references() {
-        dist[graph.to_index(edge.source())][graph.to_index(edge.target())] = edge_cost(edge);
+        let (i, j) = (graph.to_index(edge.source()), graph.to_index(edge.target()));
+        dist[i * num_of_nodes + j] = edge_cost(edge);
         if !graph.is_directed() {
-            dist[graph.to_index(edge.target())][graph.to_index(edge.source())] = edge_cost(edge);
+            dist[j * num_of_nodes + i] = edge_cost(edge);
         }
     }
 
     // distance of each node to itself is 0(default value)
     for node in graph.node_identifiers() {
-        dist[graph.to_index(node)][graph.to_index(node)] = K::default();
+        let i = graph.to_index(node);
+        dist[i * num_of_nodes + i] = K::default();
     }
 
+    // `K::max()` stands for a missing path: it is never extended, so that
+    // adding a negative cost to it does not yield a finite distance.
+    // The inner loop runs over two contiguous rows, so that it can be
+    // vectorized by the compiler.
+    let mut row_k = vec![inf; num_of_nodes];
     for k in 0..num_of_nodes {
-        for i in 0..num_of_nodes {
-            for j in 0..num_of_nodes {
-                let (result, overflow) = dist[i][k].overflowing_add(dist[k][j]);
-                if !overflow && dist[i][j] > result {
-                    dist[i][j] = result;
+        row_k.copy_from_slice(&dist[k * num_of_nodes..(k + 1) * num_of_nodes]);
+        for row_i in dist.chunks_exact_mut(num_of_nodes) {
+            let dist_ik = row_i[k];
+            if dist_ik == inf {
+                continue;
+            }
+            for (dist_ij, &dist_kj) in row_i.iter_mut().zip(&row_k) {
+                let (result, overflow) = dist_ik.overflowing_add(dist_kj);
+                if dist_kj != inf && !overflow && *dist_ij > result {
+                    *dist_ij = result;
                 }
             }
         }
@@ -119,7 +132,7 @@ where
 
     // value less than 0(default value) indicates a negative cycle
     for i in 0..num_of_nodes {
-        if dist[i][i] < K::default() {
+        if dist[i * num_of_nodes + i] < K::default() {
             return Err(NegativeCycle(()));
         }
     }
@@ -129,7 +142,10 @@ where
 
     for i in 0..num_of_nodes {
         for j in 0..num_of_nodes {
-            distance_map.insert((graph.from_index(i), graph.from_index(j)), dist[i][j]);
+            distance_map.insert(
+                (graph.from_index(i), graph.from_index(j)),
+                dist[i * num_of_nodes + j],
+            );
         }
     }
 
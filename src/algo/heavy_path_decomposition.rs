@@ -0,0 +1,281 @@
+use std::ops::Range;
+
+use crate::algo::dfs_tree::{dfs_tree, DfsTree};
+use crate::visit::{GraphBase, IntoNeighbors, NodeIndexable, Visitable};
+
+/// Computed
+/// [*heavy path decomposition*](https://en.wikipedia.org/wiki/Heavy_path_decomposition)
+/// of a rooted tree.
+///
+/// The nodes of the tree are assigned distinct positions `0..n` such that
+/// every heavy path occupies a contiguous range of positions, from its
+/// topmost node downwards, and the subtree of every node occupies a contiguous
+/// range of positions starting at the node. Any path of the tree is then
+/// covered by **O(log |V|)** ranges of positions, so that path queries can be
+/// answered with a range query structure (prefix sums, sparse table, segment
+/// tree, ...) built over values laid out by position.
+///
+/// Created with [`heavy_path_decomposition`].
+#[derive(Debug, Clone)]
+pub struct HeavyPathDecomposition<G: GraphBase> {
+    graph: G,
+    root: G::NodeId,
+    /// The parent of each node, indexed by node index.
+    parent: Vec<Option<G::NodeId>>,
+    /// The depth of each node, or `None` if it is not reachable from the root.
+    depth: Vec<Option<usize>>,
+    /// The size of the subtree of each node.
+    size: Vec<usize>,
+    /// The topmost node of the heavy path of each node.
+    head: Vec<Option<G::NodeId>>,
+    /// The position of each node.
+    position: Vec<usize>,
+    /// The nodes, ordered by position.
+    order: Vec<G::NodeId>,
+}
+
+impl<G> HeavyPathDecomposition<G>
+where
+    G: NodeIndexable,
+{
+    /// Get the root of the tree.
+    pub fn root(&self) -> G::NodeId {
+        self.root
+    }
+
+    /// Get the number of nodes of the tree, i.e. the number of positions.
+    pub fn len(&self) -> usize {
+        self.order.len()
+    }
+
+    /// Whether the tree is empty. Always `false`, as the tree contains at
+    /// least its root.
+    pub fn is_empty(&self) -> bool {
+        self.order.is_empty()
+    }
+
+    /// Get the parent of a node in the tree.
+    ///
+    /// Returns `None` for the root, and for nodes that are not in the tree.
+    pub fn parent(&self, node: G::NodeId) -> Option<G::NodeId> {
+        self.parent.get(self.graph.to_index(node)).and_then(|&p| p)
+    }
+
+    /// Get the depth of a node in the tree, i.e. its distance from the root.
+    ///
+    /// Returns `None` for nodes that are not in the tree.
+    pub fn depth(&self, node: G::NodeId) -> Option<usize> {
+        self.depth.get(self.graph.to_index(node)).and_then(|&d| d)
+    }
+
+    /// Get the topmost node of the heavy path containing `node`.
+    ///
+    /// Returns `None` for nodes that are not in the tree.
+    pub fn head(&self, node: G::NodeId) -> Option<G::NodeId> {
+        self.head.get(self.graph.to_index(node)).and_then(|&h| h)
+    }
+
+    /// Get the position of a node.
+    ///
+    /// Returns `None` for nodes that are not in the tree.
+    pub fn position(&self, node: G::NodeId) -> Option<usize> {
+        self.depth(node)?;
+        Some(self.position[self.graph.to_index(node)])
+    }
+
+    /// Get the node at a position, if the position is in `0..self.len()`.
+    pub fn node_at(&self, position: usize) -> Option<G::NodeId> {
+        self.order.get(position).copied()
+    }
+
+    /// Get the nodes of the tree, ordered by position.
+    pub fn nodes(&self) -> &[G::NodeId] {
+        &self.order
+    }
+
+    /// Get the range of positions of the subtree of `node`.
+    ///
+    /// Returns `None` for nodes that are not in the tree.
+    pub fn subtree_range(&self, node: G::NodeId) -> Option<Range<usize>> {
+        let position = self.position(node)?;
+        Some(position..position + self.size[self.graph.to_index(node)])
+    }
+
+    /// Get the lowest common ancestor of `a` and `b`.
+    ///
+    /// Returns `None` if either node is not in the tree.
+    ///
+    /// Computes in **O(log |V|)** time.
+    pub fn lca(&self, a: G::NodeId, b: G::NodeId) -> Option<G::NodeId> {
+        let mut lca = None;
+        self.walk_path(a, b, |_| (), |top| lca = Some(top))?;
+        lca
+    }
+
+    /// Get the ranges of positions covering the nodes of the path between `a`
+    /// and `b`, both included.
+    ///
+    /// The path consists of at most **O(log |V|)** ranges, in no particular
+    /// order. Returns `None` if either node is not in the tree.
+    pub fn path_ranges(&self, a: G::NodeId, b: G::NodeId) -> Option<Vec<Range<usize>>> {
+        let mut ranges = Vec::new();
+        self.walk_path(a, b, |range| ranges.push(range), |_| ())?;
+        Some(ranges)
+    }
+
+    /// Get the ranges of positions covering the edges of the path between `a`
+    /// and `b`.
+    ///
+    /// The edge between a node and its parent is represented by the position
+    /// of the node, so that the root has no edge. The path consists of at
+    /// most **O(log |V|)** non-empty ranges, in no particular order. Returns
+    /// `None` if either node is not in the tree.
+    pub fn path_edge_ranges(&self, a: G::NodeId, b: G::NodeId) -> Option<Vec<Range<usize>>> {
+        let mut ranges = Vec::new();
+        self.walk_path(a, b, |range| ranges.push(range), |_| ())?;
+        // The last range starts at the lowest common ancestor, whose edge to
+        // its parent is not on the path.
+        if let Some(last) = ranges.last_mut() {
+            last.start += 1;
+            if last.start == last.end {
+                ranges.pop();
+            }
+        }
+        Some(ranges)
+    }
+
+    /// Walk up the heavy paths from `a` and `b` until they meet, calling
+    /// `range` on the ranges of positions covering the path, and `top` on the
+    /// lowest common ancestor. The last range starts at the lowest common
+    /// ancestor.
+    fn walk_path(
+        &self,
+        mut a: G::NodeId,
+        mut b: G::NodeId,
+        mut range: impl FnMut(Range<usize>),
+        top: impl FnOnce(G::NodeId),
+    ) -> Option<()> {
+        self.depth(a)?;
+        self.depth(b)?;
+        let mut head_a = self.head(a)?;
+        let mut head_b = self.head(b)?;
+        while head_a != head_b {
+            // Move up from the node whose heavy path starts deeper
+            if self.depth(head_a) < self.depth(head_b) {
+                std::mem::swap(&mut a, &mut b);
+                std::mem::swap(&mut head_a, &mut head_b);
+            }
+            range(self.position[self.graph.to_index(head_a)]..self.position(a)? + 1);
+            a = self.parent(head_a)?;
+            head_a = self.head(a)?;
+        }
+        if self.depth(a) > self.depth(b) {
+            std::mem::swap(&mut a, &mut b);
+        }
+        range(self.position(a)?..self.position(b)? + 1);
+        top(a);
+        Some(())
+    }
+}
+
+/// \[Generic\] Compute the heavy path (heavy-light) decomposition of a rooted
+/// tree.
+///
+/// The tree is that of [`tree_fold`](super::tree_fold): the nodes reachable
+/// from `root`, in their depth-first search spanning tree.
+///
+/// The *heavy child* of a node is a child with the largest subtree; the other
+/// children are *light*. The heavy paths follow the heavy children, so that any
+/// path from a node to the root crosses at most **O(log |V|)** heavy paths.
+/// See [`HeavyPathDecomposition`] for how to answer path queries.
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::heavy_path_decomposition;
+/// use petgraph::prelude::*;
+///
+/// // A tree with a value on each node.
+/// //
+/// //        0 (5)
+/// //       /     \
+/// //    1 (1)   2 (2)
+/// //    /   \
+/// // 3 (4)  4 (3)
+/// let tree = UnGraph::<u32, ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (1, 4)]);
+/// let values = [5, 1, 2, 4, 3];
+/// let n = NodeIndex::new;
+/// let hpd = heavy_path_decomposition(&tree, n(0));
+///
+/// // Lay out the values by position, and compute their prefix sums.
+/// let mut prefix_sums = vec![0];
+/// for node in hpd.nodes() {
+///     prefix_sums.push(prefix_sums.last().unwrap() + values[node.index()]);
+/// }
+/// let path_sum = |a, b| -> u32 {
+///     hpd.path_ranges(a, b)
+///         .unwrap()
+///         .into_iter()
+///         .map(|range| prefix_sums[range.end] - prefix_sums[range.start])
+///         .sum()
+/// };
+///
+/// assert_eq!(path_sum(n(3), n(2)), 4 + 1 + 5 + 2);
+/// assert_eq!(path_sum(n(3), n(4)), 4 + 1 + 3);
+/// assert_eq!(hpd.lca(n(4), n(3)), Some(n(1)));
+/// ```
+pub fn heavy_path_decomposition<G>(graph: G, root: G::NodeId) -> HeavyPathDecomposition<G>
+where
+    G: IntoNeighbors + NodeIndexable + Visitable,
+{
+    let bound = graph.node_bound();
+    let DfsTree {
+        parent,
+        depth,
+        children,
+        preorder,
+    } = dfs_tree(graph, root);
+
+    // Subtree sizes, children before parents
+    let mut size = vec![0; bound];
+    for &node in preorder.iter().rev() {
+        let idx = graph.to_index(node);
+        size[idx] = 1 + children[idx]
+            .iter()
+            .map(|&c| size[graph.to_index(c)])
+            .sum::<usize>();
+    }
+
+    // Assign the positions along each heavy path, then to the light subtrees
+    let mut head = vec![None; bound];
+    let mut position = vec![0; bound];
+    let mut order = Vec::with_capacity(preorder.len());
+    let mut heads = vec![root];
+    while let Some(top) = heads.pop() {
+        let mut node = Some(top);
+        while let Some(n) = node {
+            let idx = graph.to_index(n);
+            head[idx] = Some(top);
+            position[idx] = order.len();
+            order.push(n);
+            let heavy = children[idx]
+                .iter()
+                .copied()
+                .max_by_key(|&c| size[graph.to_index(c)]);
+            heads.extend(children[idx].iter().copied().filter(|&c| Some(c) != heavy));
+            node = heavy;
+        }
+    }
+
+    HeavyPathDecomposition {
+        graph,
+        root,
+        parent,
+        depth,
+        size,
+        head,
+        position,
+        order,
+    }
+}
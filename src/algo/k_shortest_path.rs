@@ -1,10 +1,16 @@
-use std::collections::{BinaryHeap, HashMap};
-
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::hash::Hash;
+use std::ops::Sub;
+use std::rc::Rc;
 
-use crate::algo::Measure;
+use crate::algo::{astar, Measure};
 use crate::scored::MinScored;
-use crate::visit::{EdgeRef, IntoEdges, NodeCount, NodeIndexable, Visitable};
+use crate::visit::{
+    EdgeFiltered, EdgeRef, GraphBase, IntoEdges, IntoEdgesDirected, NodeCount, NodeIndexable,
+    Visitable,
+};
+use crate::Direction::Incoming;
 
 /// \[Generic\] k'th shortest path algorithm.
 ///
@@ -113,3 +119,495 @@ where
     }
     scores
 }
+
+/// \[Generic\] Yen's algorithm for the *k* shortest simple paths.
+///
+/// Return an iterator over the simple paths from `start` to `goal`, i.e.
+/// without repeated nodes, by increasing cost, with their cost. The paths are
+/// computed lazily: taking *k* paths runs *O(k |V|)* shortest path searches,
+/// and the iteration can stop at any cost threshold.
+///
+/// Paths are sequences of nodes, so that parallel edges do not yield
+/// distinct paths: the cost of a step is the cost of the cheapest edge between
+/// its nodes. The function `edge_cost` should return the cost for a
+/// particular edge. Edge costs must be non-negative.
+///
+/// See [`shortest_walks`] for paths that may repeat nodes.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::shortest_simple_paths;
+/// use petgraph::prelude::*;
+///
+/// let graph = DiGraph::<(), u32>::from_edges(&[
+///     (0, 1, 1),
+///     (1, 3, 1),
+///     (0, 2, 2),
+///     (2, 3, 1),
+///     (1, 2, 1),
+///     (0, 3, 5),
+/// ]);
+/// let (a, d) = (NodeIndex::new(0), NodeIndex::new(3));
+///
+/// let paths: Vec<_> = shortest_simple_paths(&graph, a, d, |e| *e.weight())
+///     .map(|(cost, path)| (cost, path.iter().map(|n| n.index()).collect::<Vec<_>>()))
+///     .collect();
+/// assert_eq!(
+///     paths,
+///     vec![
+///         (2, vec![0, 1, 3]),
+///         (3, vec![0, 2, 3]),
+///         (3, vec![0, 1, 2, 3]),
+///         (5, vec![0, 3]),
+///     ]
+/// );
+/// ```
+pub fn shortest_simple_paths<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    goal: G::NodeId,
+    edge_cost: F,
+) -> ShortestSimplePaths<G, F, K>
+where
+    G: IntoEdges + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    ShortestSimplePaths {
+        graph,
+        start,
+        goal,
+        edge_cost,
+        returned: Vec::new(),
+        last_costs: Vec::new(),
+        candidates: BinaryHeap::new(),
+        found: HashSet::new(),
+    }
+}
+
+/// An iterator over the simple paths between two nodes by increasing cost.
+///
+/// Created with [`shortest_simple_paths`].
+pub struct ShortestSimplePaths<G, F, K>
+where
+    G: GraphBase,
+{
+    graph: G,
+    start: G::NodeId,
+    goal: G::NodeId,
+    edge_cost: F,
+    /// The paths returned so far, and the cost of each prefix of the last one.
+    returned: Vec<Vec<G::NodeId>>,
+    last_costs: Vec<K>,
+    candidates: BinaryHeap<MinScored<K, Vec<G::NodeId>>>,
+    /// The paths returned or in the candidates.
+    found: HashSet<Vec<G::NodeId>>,
+}
+
+impl<G, F, K> ShortestSimplePaths<G, F, K>
+where
+    G: IntoEdges + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    /// Return the shortest path from `from` to the goal avoiding the nodes
+    /// `banned_nodes` and the steps `banned_steps`.
+    fn spur_path(
+        &mut self,
+        from: G::NodeId,
+        banned_nodes: &HashSet<G::NodeId>,
+        banned_steps: &HashSet<(G::NodeId, G::NodeId)>,
+    ) -> Option<(K, Vec<G::NodeId>)> {
+        let filtered = EdgeFiltered::from_fn(self.graph, |e: G::EdgeRef| {
+            !banned_nodes.contains(&e.target()) && !banned_steps.contains(&(e.source(), e.target()))
+        });
+        let goal = self.goal;
+        astar(
+            &filtered,
+            from,
+            |n| n == goal,
+            &mut self.edge_cost,
+            |_| K::default(),
+        )
+    }
+
+    /// Return the cost of each prefix of `path`.
+    fn prefix_costs(&mut self, path: &[G::NodeId]) -> Vec<K> {
+        let mut costs = vec![K::default()];
+        for step in path.windows(2) {
+            let cost = self
+                .graph
+                .edges(step[0])
+                .filter(|e| e.target() == step[1])
+                .map(&mut self.edge_cost)
+                .fold(None, |min: Option<K>, c| match min {
+                    Some(m) if m <= c => Some(m),
+                    _ => Some(c),
+                })
+                .unwrap();
+            costs.push(*costs.last().unwrap() + cost);
+        }
+        costs
+    }
+
+    fn push_candidate(&mut self, cost: K, path: Vec<G::NodeId>) {
+        if self.found.insert(path.clone()) {
+            self.candidates.push(MinScored(cost, path));
+        }
+    }
+}
+
+impl<G, F, K> Iterator for ShortestSimplePaths<G, F, K>
+where
+    G: IntoEdges + Visitable,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy,
+{
+    type Item = (K, Vec<G::NodeId>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match self.returned.last().cloned() {
+            None => {
+                if !self.found.is_empty() {
+                    return None;
+                }
+                let start = self.start;
+                if let Some((cost, path)) = self.spur_path(start, &HashSet::new(), &HashSet::new())
+                {
+                    self.push_candidate(cost, path);
+                }
+            }
+            Some(last) => {
+                // Deviate from the last path at each of its nodes
+                for i in 0..last.len() - 1 {
+                    let root = &last[..=i];
+                    let banned_nodes: HashSet<_> = root[..i].iter().cloned().collect();
+                    // The steps out of the root of the paths returned so far
+                    let banned_steps: HashSet<_> = self
+                        .returned
+                        .iter()
+                        .filter(|path| path.len() > i + 1 && path[..=i] == *root)
+                        .map(|path| (path[i], path[i + 1]))
+                        .collect();
+                    if let Some((cost, spur)) =
+                        self.spur_path(last[i], &banned_nodes, &banned_steps)
+                    {
+                        let mut path = root.to_vec();
+                        path.extend_from_slice(&spur[1..]);
+                        self.push_candidate(self.last_costs[i] + cost, path);
+                    }
+                }
+            }
+        }
+        let MinScored(cost, path) = self.candidates.pop()?;
+        self.last_costs = self.prefix_costs(&path);
+        self.returned.push(path.clone());
+        Some((cost, path))
+    }
+}
+
+/// \[Generic\] Eppstein's algorithm for the *k* shortest walks.
+///
+/// Return an iterator over the walks from `start` to `goal`, i.e. paths that
+/// may repeat nodes and edges, by increasing cost, with their cost. After a
+/// shortest path tree to `goal` is computed, in **O(|E| log |V|)** time, each
+/// walk is found in **O(log k + log |V|)** time, plus its length: the
+/// iteration is lazy, and can stop at any cost threshold.
+///
+/// Walks using different parallel edges are distinct, and may have the same
+/// sequence of nodes. The function `edge_cost` should return the cost for a
+/// particular edge. Edge costs must be non-negative; with edges of zero cost
+/// on a cycle, the iterator does not end.
+///
+/// See [`shortest_simple_paths`] for paths without repeated nodes.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::shortest_walks;
+/// use petgraph::prelude::*;
+///
+/// // A path with a cycle of cost 3 on node 1
+/// let graph = DiGraph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 1), (1, 3, 1), (3, 1, 2)]);
+/// let (a, c) = (NodeIndex::new(0), NodeIndex::new(2));
+///
+/// let walks: Vec<_> = shortest_walks(&graph, a, c, |e| *e.weight())
+///     .take_while(|&(cost, _)| cost <= 8)
+///     .collect();
+/// assert_eq!(walks.len(), 3);
+/// assert_eq!(walks[0], (2, vec![a, NodeIndex::new(1), c]));
+/// assert_eq!(walks[1].0, 5);
+/// assert_eq!(walks[2].1.len(), 7);
+/// ```
+pub fn shortest_walks<G, F, K>(
+    graph: G,
+    start: G::NodeId,
+    goal: G::NodeId,
+    mut edge_cost: F,
+) -> ShortestWalks<G, K>
+where
+    G: IntoEdgesDirected + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure + Copy + Sub<Output = K>,
+{
+    let ix = |n| graph.to_index(n);
+    let n = graph.node_bound();
+
+    // The shortest path tree to the goal
+    let mut distance = vec![None; n];
+    let mut next = vec![None; n];
+    let mut tree_edge = vec![None; n];
+    let mut settled = vec![false; n];
+    let mut order = Vec::new();
+    let mut queue = BinaryHeap::new();
+    distance[ix(goal)] = Some(K::default());
+    queue.push(MinScored(K::default(), goal));
+    while let Some(MinScored(d, v)) = queue.pop() {
+        if settled[ix(v)] {
+            continue;
+        }
+        settled[ix(v)] = true;
+        order.push(v);
+        for edge in graph.edges_directed(v, Incoming) {
+            let u = edge.source();
+            if settled[ix(u)] {
+                continue;
+            }
+            let du = d + edge_cost(edge);
+            if distance[ix(u)].map_or(true, |old| du < old) {
+                distance[ix(u)] = Some(du);
+                next[ix(u)] = Some(v);
+                tree_edge[ix(u)] = Some(edge.id());
+                queue.push(MinScored(du, u));
+            }
+        }
+    }
+
+    // The heaps of the sidetracks of the tree path from each node, built
+    // from the goal
+    let mut heaps = vec![None; n];
+    for v in order {
+        let dv = distance[ix(v)].unwrap();
+        let mut sidetracks = Vec::new();
+        for edge in graph.edges(v) {
+            let w = edge.target();
+            if Some(edge.id()) == tree_edge[ix(v)] {
+                continue;
+            }
+            if let Some(dw) = distance[ix(w)] {
+                sidetracks.push(Sidetrack {
+                    delta: edge_cost(edge) + dw - dv,
+                    source: v,
+                    target: w,
+                });
+            }
+        }
+        sidetracks.sort_by(|a, b| a.delta.partial_cmp(&b.delta).unwrap_or(Ordering::Equal));
+        let tail = next[ix(v)].and_then(|w| heaps[ix(w)].clone());
+        heaps[ix(v)] = if sidetracks.is_empty() {
+            tail
+        } else {
+            let single = SidetrackHeap {
+                sidetracks: Rc::new(sidetracks),
+                rank: 1,
+                left: None,
+                right: None,
+            };
+            merge(Some(Rc::new(single)), tail)
+        };
+    }
+
+    ShortestWalks {
+        graph,
+        start,
+        goal,
+        distance: distance[ix(start)],
+        next,
+        heaps,
+        queue: BinaryHeap::new(),
+        started: false,
+    }
+}
+
+/// An edge out of the shortest path tree, with its extra cost over the
+/// tree path.
+struct Sidetrack<N, K> {
+    delta: K,
+    source: N,
+    target: N,
+}
+
+/// A node of a persistent leftist heap of the sidetracks out of a tree
+/// path, keyed by the smallest sidetrack of each node of the path. Its
+/// `sidetracks` are those of one node, sorted.
+struct SidetrackHeap<N, K> {
+    sidetracks: Rc<Vec<Sidetrack<N, K>>>,
+    rank: usize,
+    left: Heap<N, K>,
+    right: Heap<N, K>,
+}
+
+/// A possibly empty sidetrack heap.
+type Heap<N, K> = Option<Rc<SidetrackHeap<N, K>>>;
+
+fn rank<N, K>(heap: &Heap<N, K>) -> usize {
+    heap.as_ref().map_or(0, |h| h.rank)
+}
+
+fn merge<N, K: PartialOrd>(a: Heap<N, K>, b: Heap<N, K>) -> Heap<N, K> {
+    match (a, b) {
+        (None, heap) | (heap, None) => heap,
+        (Some(a), Some(b)) => {
+            let (a, b) = if b.sidetracks[0].delta < a.sidetracks[0].delta {
+                (b, a)
+            } else {
+                (a, b)
+            };
+            let left = a.left.clone();
+            let right = merge(a.right.clone(), Some(b));
+            let (left, right) = if rank(&left) < rank(&right) {
+                (right, left)
+            } else {
+                (left, right)
+            };
+            Some(Rc::new(SidetrackHeap {
+                sidetracks: a.sidetracks.clone(),
+                rank: rank(&right) + 1,
+                left,
+                right,
+            }))
+        }
+    }
+}
+
+/// The sidetracks taken by a walk, from the last one.
+struct Taken<N> {
+    source: N,
+    target: N,
+    previous: Option<Rc<Taken<N>>>,
+}
+
+/// A walk to visit: the sidetracks `taken`, followed by sidetrack `index` of
+/// `heap`.
+struct WalkCandidate<N, K> {
+    heap: Rc<SidetrackHeap<N, K>>,
+    index: usize,
+    taken: Option<Rc<Taken<N>>>,
+}
+
+/// An iterator over the walks between two nodes by increasing cost.
+///
+/// Created with [`shortest_walks`].
+pub struct ShortestWalks<G, K>
+where
+    G: GraphBase,
+{
+    graph: G,
+    start: G::NodeId,
+    goal: G::NodeId,
+    /// The distance from `start` to `goal`, if it is reachable.
+    distance: Option<K>,
+    /// The next node on the shortest path to `goal`, by node index.
+    next: Vec<Option<G::NodeId>>,
+    heaps: Vec<Heap<G::NodeId, K>>,
+    queue: BinaryHeap<MinScored<K, WalkCandidate<G::NodeId, K>>>,
+    started: bool,
+}
+
+impl<G, K> ShortestWalks<G, K>
+where
+    G: NodeIndexable,
+{
+    /// Return the walk following the shortest path tree, except for the
+    /// sidetracks `taken`.
+    fn walk(&self, mut taken: Option<&Rc<Taken<G::NodeId>>>) -> Vec<G::NodeId> {
+        let mut sidetracks = Vec::new();
+        while let Some(t) = taken {
+            sidetracks.push((t.source, t.target));
+            taken = t.previous.as_ref();
+        }
+        let mut walk = vec![self.start];
+        let mut node = self.start;
+        for (source, target) in sidetracks.into_iter().rev() {
+            while node != source {
+                node = self.next[self.graph.to_index(node)].unwrap();
+                walk.push(node);
+            }
+            walk.push(target);
+            node = target;
+        }
+        while node != self.goal {
+            node = self.next[self.graph.to_index(node)].unwrap();
+            walk.push(node);
+        }
+        walk
+    }
+}
+
+impl<G, K> Iterator for ShortestWalks<G, K>
+where
+    G: NodeIndexable,
+    K: Measure + Copy + Sub<Output = K>,
+{
+    type Item = (K, Vec<G::NodeId>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if !self.started {
+            self.started = true;
+            let distance = self.distance?;
+            if let Some(heap) = &self.heaps[self.graph.to_index(self.start)] {
+                let candidate = WalkCandidate {
+                    heap: heap.clone(),
+                    index: 0,
+                    taken: None,
+                };
+                self.queue
+                    .push(MinScored(distance + heap.sidetracks[0].delta, candidate));
+            }
+            return Some((distance, self.walk(None)));
+        }
+
+        let MinScored(cost, candidate) = self.queue.pop()?;
+        let heap = &candidate.heap;
+        let sidetrack = &heap.sidetracks[candidate.index];
+        let base = cost - sidetrack.delta;
+        // Replace the last sidetrack by the next best ones
+        if candidate.index == 0 {
+            for child in heap.left.iter().chain(&heap.right) {
+                let next = WalkCandidate {
+                    heap: child.clone(),
+                    index: 0,
+                    taken: candidate.taken.clone(),
+                };
+                self.queue
+                    .push(MinScored(base + child.sidetracks[0].delta, next));
+            }
+        }
+        if let Some(following) = heap.sidetracks.get(candidate.index + 1) {
+            let next = WalkCandidate {
+                heap: heap.clone(),
+                index: candidate.index + 1,
+                taken: candidate.taken.clone(),
+            };
+            self.queue.push(MinScored(base + following.delta, next));
+        }
+        // Or add another sidetrack after it
+        let taken = Rc::new(Taken {
+            source: sidetrack.source,
+            target: sidetrack.target,
+            previous: candidate.taken.clone(),
+        });
+        if let Some(heap) = &self.heaps[self.graph.to_index(sidetrack.target)] {
+            let next = WalkCandidate {
+                heap: heap.clone(),
+                index: 0,
+                taken: Some(taken.clone()),
+            };
+            self.queue
+                .push(MinScored(cost + heap.sidetracks[0].delta, next));
+        }
+        Some((cost, self.walk(Some(&taken))))
+    }
+}
@@ -0,0 +1,117 @@
+use crate::algo::{toposort, Cycle, Measure};
+use crate::visit::{EdgeRef, IntoEdges, IntoNeighborsDirected, IntoNodeIdentifiers};
+use crate::visit::{NodeIndexable, Visitable};
+
+/// \[Generic\] Find a longest path of a directed acyclic graph.
+///
+/// The cost of a path is the sum of the costs of its edges, as given by
+/// `edge_cost`. Edge costs may be negative. Any path of maximum cost is
+/// returned, as its cost and the list of its nodes, from the first to the
+/// last. A single node is a path of cost zero, so that the returned cost is
+/// never negative. If the graph is empty, the returned path is empty.
+///
+/// If the graph contains a cycle, a [`Cycle`] error is returned, as longest
+/// paths are then not well defined. Self loops are also cycles.
+///
+/// This computes a topological order of the graph, then the longest path
+/// starting at each node in reverse topological order.
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::longest_path;
+/// use petgraph::prelude::*;
+///
+/// // A critical path analysis: the edge weights are task durations.
+/// let mut graph = DiGraph::<&str, u32>::new();
+/// let start = graph.add_node("start");
+/// let fetch = graph.add_node("fetch");
+/// let compile = graph.add_node("compile");
+/// let docs = graph.add_node("docs");
+/// let done = graph.add_node("done");
+/// graph.extend_with_edges(&[
+///     (start, fetch, 3),
+///     (fetch, compile, 10),
+///     (fetch, docs, 4),
+///     (compile, done, 2),
+///     (docs, done, 1),
+/// ]);
+///
+/// let (duration, path) = longest_path(&graph, |e| *e.weight()).unwrap();
+/// assert_eq!(duration, 15);
+/// assert_eq!(path, vec![start, fetch, compile, done]);
+///
+/// graph.add_edge(done, start, 0);
+/// assert!(longest_path(&graph, |e| *e.weight()).is_err());
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn longest_path<G, F, K>(
+    graph: G,
+    edge_cost: F,
+) -> Result<(K, Vec<G::NodeId>), Cycle<G::NodeId>>
+where
+    G: IntoEdges + IntoNeighborsDirected + IntoNodeIdentifiers + NodeIndexable + Visitable,
+    F: FnMut(G::EdgeRef) -> K,
+    K: Measure,
+{
+    let order = toposort(graph, None)?;
+    Ok(longest_path_in_order(
+        graph,
+        &order,
+        |_| K::default(),
+        edge_cost,
+    ))
+}
+
+/// Find a longest path of a directed acyclic graph, given a topological order
+/// of its nodes. The cost of a path is the sum of the costs of its nodes and
+/// edges.
+pub(crate) fn longest_path_in_order<G, K, F, H>(
+    graph: G,
+    order: &[G::NodeId],
+    mut node_cost: F,
+    mut edge_cost: H,
+) -> (K, Vec<G::NodeId>)
+where
+    G: IntoEdges + NodeIndexable,
+    K: Measure,
+    F: FnMut(G::NodeId) -> K,
+    H: FnMut(G::EdgeRef) -> K,
+{
+    // For each node, the cost of the longest path starting at the node and
+    // the next node on that path.
+    let mut cost = vec![K::default(); graph.node_bound()];
+    let mut next = vec![None; graph.node_bound()];
+    let mut start: Option<(K, G::NodeId)> = None;
+
+    for &n in order.iter().rev() {
+        let mut tail = K::default();
+        let mut next_n = None;
+        for edge in graph.edges(n) {
+            let target = edge.target();
+            let tail_cost = edge_cost(edge) + cost[graph.to_index(target)].clone();
+            if tail_cost > tail || (next_n.is_none() && tail_cost >= tail) {
+                tail = tail_cost;
+                next_n = Some(target);
+            }
+        }
+        let total = node_cost(n) + tail;
+        if start.as_ref().map_or(true, |(best, _)| total >= *best) {
+            start = Some((total.clone(), n));
+        }
+        cost[graph.to_index(n)] = total;
+        next[graph.to_index(n)] = next_n;
+    }
+
+    match start {
+        None => (K::default(), Vec::new()),
+        Some((total, n)) => {
+            let mut path = vec![n];
+            while let Some(n) = next[graph.to_index(*path.last().unwrap())] {
+                path.push(n);
+            }
+            (total, path)
+        }
+    }
+}
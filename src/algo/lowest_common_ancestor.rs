@@ -0,0 +1,170 @@
+use crate::algo::dfs_tree::dfs_tree;
+use crate::visit::{GraphBase, IntoNeighbors, NodeIndexable, Visitable};
+
+/// Precomputed
+/// [*lowest common ancestors*](https://en.wikipedia.org/wiki/Lowest_common_ancestor)
+/// of a rooted tree.
+///
+/// Created with [`lowest_common_ancestors`].
+#[derive(Debug, Clone)]
+pub struct LowestCommonAncestors<G: GraphBase> {
+    graph: G,
+    root: G::NodeId,
+    /// The parent of each node in the tree, indexed by node index.
+    parent: Vec<Option<G::NodeId>>,
+    /// The depth of each node in the tree, or `None` if the node is not
+    /// reachable from the root.
+    depth: Vec<Option<usize>>,
+    /// The index of the first occurrence of each node in the Euler tour.
+    first: Vec<usize>,
+    /// `table[k][i]` is the node of smallest depth among the nodes at
+    /// positions `i..i + 2^k` of the Euler tour.
+    table: Vec<Vec<G::NodeId>>,
+}
+
+impl<G> LowestCommonAncestors<G>
+where
+    G: NodeIndexable,
+{
+    /// Get the root of the tree.
+    pub fn root(&self) -> G::NodeId {
+        self.root
+    }
+
+    /// Get the parent of a node in the tree.
+    ///
+    /// Returns `None` for the root, and for nodes that are not reachable from
+    /// the root.
+    pub fn parent(&self, node: G::NodeId) -> Option<G::NodeId> {
+        self.parent.get(self.graph.to_index(node)).and_then(|&p| p)
+    }
+
+    /// Get the depth of a node in the tree, i.e. its distance from the root.
+    ///
+    /// Returns `None` for nodes that are not reachable from the root.
+    pub fn depth(&self, node: G::NodeId) -> Option<usize> {
+        self.depth.get(self.graph.to_index(node)).and_then(|&d| d)
+    }
+
+    /// Get the lowest common ancestor of `a` and `b`, i.e. the deepest node
+    /// that is an ancestor of both. A node is an ancestor of itself.
+    ///
+    /// Returns `None` if either node is not reachable from the root.
+    ///
+    /// Computes in **O(1)** time.
+    pub fn lca(&self, a: G::NodeId, b: G::NodeId) -> Option<G::NodeId> {
+        self.depth(a)?;
+        self.depth(b)?;
+        let (i, j) = (
+            self.first[self.graph.to_index(a)],
+            self.first[self.graph.to_index(b)],
+        );
+        let (i, j) = if i <= j { (i, j) } else { (j, i) };
+
+        // Cover `i..=j` with two (overlapping) ranges of length 2^k
+        let k = usize_log2(j - i + 1);
+        let left = self.table[k][i];
+        let right = self.table[k][j + 1 - (1 << k)];
+        Some(self.shallowest(left, right))
+    }
+
+    /// Get the distance between `a` and `b` in the tree.
+    ///
+    /// Returns `None` if either node is not reachable from the root.
+    pub fn distance(&self, a: G::NodeId, b: G::NodeId) -> Option<usize> {
+        let lca = self.lca(a, b)?;
+        Some(self.depth(a)? + self.depth(b)? - 2 * self.depth(lca)?)
+    }
+
+    fn shallowest(&self, a: G::NodeId, b: G::NodeId) -> G::NodeId {
+        if self.depth(b) < self.depth(a) {
+            b
+        } else {
+            a
+        }
+    }
+}
+
+fn usize_log2(n: usize) -> usize {
+    debug_assert!(n > 0);
+    std::mem::size_of::<usize>() * 8 - 1 - n.leading_zeros() as usize
+}
+
+/// \[Generic\] Preprocess a rooted tree for lowest common ancestor queries.
+///
+/// The tree is that of [`tree_fold`](super::tree_fold): the nodes reachable
+/// from `root`, in their depth-first search spanning tree. In a DAG, the
+/// ancestors of a node are thus those on the path it was discovered by.
+///
+/// This builds an Euler tour of the tree and a sparse table of the node of
+/// minimum depth over the ranges of the tour, so that
+/// [`LowestCommonAncestors::lca`] runs in **O(1)** time.
+///
+/// Computes in **O(|V| log |V| + |E|)** time and uses **O(|V| log |V|)**
+/// space.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::lowest_common_ancestors;
+/// use petgraph::prelude::*;
+///
+/// //      0
+/// //     / \
+/// //    1   2
+/// //   / \
+/// //  3   4
+/// let tree = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (1, 4)]);
+/// let n = NodeIndex::new;
+/// let lca = lowest_common_ancestors(&tree, n(0));
+///
+/// assert_eq!(lca.lca(n(3), n(4)), Some(n(1)));
+/// assert_eq!(lca.lca(n(3), n(2)), Some(n(0)));
+/// assert_eq!(lca.lca(n(1), n(4)), Some(n(1)));
+/// assert_eq!(lca.distance(n(3), n(2)), Some(3));
+/// ```
+pub fn lowest_common_ancestors<G>(graph: G, root: G::NodeId) -> LowestCommonAncestors<G>
+where
+    G: IntoNeighbors + NodeIndexable + Visitable,
+{
+    let tree = dfs_tree(graph, root);
+    let mut first = vec![0; graph.node_bound()];
+
+    // Euler tour of the tree
+    let mut tour = vec![root];
+    let mut stack = vec![(root, tree.children[graph.to_index(root)].iter())];
+    while let Some((_, children)) = stack.last_mut() {
+        if let Some(&child) = children.next() {
+            first[graph.to_index(child)] = tour.len();
+            tour.push(child);
+            stack.push((child, tree.children[graph.to_index(child)].iter()));
+        } else {
+            stack.pop();
+            if let Some(&(up, _)) = stack.last() {
+                tour.push(up);
+            }
+        }
+    }
+
+    let mut lca = LowestCommonAncestors {
+        graph,
+        root,
+        parent: tree.parent,
+        depth: tree.depth,
+        first,
+        table: Vec::new(),
+    };
+
+    // Sparse table over the tour
+    let mut table = vec![tour];
+    let mut width = 1;
+    while 2 * width <= table[0].len() {
+        let last = table.last().unwrap();
+        let level = (0..=table[0].len() - 2 * width)
+            .map(|i| lca.shallowest(last[i], last[i + width]))
+            .collect();
+        table.push(level);
+        width *= 2;
+    }
+    lca.table = table;
+    lca
+}
@@ -1,6 +1,9 @@
+use std::cmp::Ordering;
 use std::collections::VecDeque;
 use std::hash::Hash;
+use std::ops::{Div, Sub};
 
+use crate::algo::Measure;
 use crate::visit::{
     EdgeRef, GraphBase, IntoEdges, IntoNeighbors, IntoNodeIdentifiers, NodeCount, NodeIndexable,
     VisitMap, Visitable,
@@ -609,3 +612,728 @@ fn augment_path<G>(
         panic!("Unexpected label when augmenting path");
     }
 }
+
+/// The maximum number of nodes matched by dynamic programming over their
+/// subsets, rather than by the blossom algorithm.
+const MAX_SUBSET_MATCHING: usize = 10;
+
+/// Pair up `nodes`, of even number, minimizing the total cost `d` of the pairs.
+///
+/// Every pair of nodes may be matched, and `d` must be symmetric. The matching
+/// is computed by dynamic programming over the subsets of nodes for up to
+/// [`MAX_SUBSET_MATCHING`] nodes, otherwise by Edmonds' blossom algorithm, in
+/// **O(|nodes|³)** time.
+pub(crate) fn minimum_weight_perfect_matching<K, D>(nodes: &[usize], d: &D) -> Vec<(usize, usize)>
+where
+    K: Measure + Copy + Sub<Output = K> + Div<Output = K> + From<u8>,
+    D: Fn(usize, usize) -> K,
+{
+    if nodes.len() <= MAX_SUBSET_MATCHING {
+        subset_matching(nodes, d)
+    } else {
+        blossom_matching(nodes, d)
+    }
+}
+
+/// Minimum weight perfect matching by dynamic programming over the subsets of
+/// `nodes`.
+fn subset_matching<K, D>(nodes: &[usize], d: &D) -> Vec<(usize, usize)>
+where
+    K: Measure + Copy,
+    D: Fn(usize, usize) -> K,
+{
+    let m = nodes.len();
+    // best[mask]: the cheapest matching of the nodes in mask, pairing its
+    // lowest node with choice[mask]
+    let mut best: Vec<Option<K>> = vec![None; 1 << m];
+    let mut choice = vec![0; 1 << m];
+    best[0] = Some(K::default());
+    for mask in 1..1usize << m {
+        if mask.count_ones() % 2 == 1 {
+            continue;
+        }
+        let i = mask.trailing_zeros() as usize;
+        for j in i + 1..m {
+            if mask & (1 << j) == 0 {
+                continue;
+            }
+            let rest = mask & !(1 << i) & !(1 << j);
+            if let Some(c) = best[rest] {
+                let c = c + d(nodes[i], nodes[j]);
+                if best[mask].map_or(true, |b| c < b) {
+                    best[mask] = Some(c);
+                    choice[mask] = j;
+                }
+            }
+        }
+    }
+    let mut pairs = Vec::with_capacity(m / 2);
+    let mut mask = (1usize << m) - 1;
+    while mask != 0 {
+        let (i, j) = (mask.trailing_zeros() as usize, choice[mask]);
+        pairs.push((nodes[i], nodes[j]));
+        mask &= !(1 << i) & !(1 << j);
+    }
+    pairs
+}
+
+/// Minimum weight perfect matching by Edmonds' blossom algorithm.
+///
+/// The costs are turned into positive weights `(max - cost) + (max - min)`, so
+/// that a maximum weight matching of the complete graph is perfect, and of
+/// minimum cost.
+fn blossom_matching<K, D>(nodes: &[usize], d: &D) -> Vec<(usize, usize)>
+where
+    K: Measure + Copy + Sub<Output = K> + Div<Output = K> + From<u8>,
+    D: Fn(usize, usize) -> K,
+{
+    let m = nodes.len();
+    let mut costs = Vec::with_capacity(m * m.saturating_sub(1) / 2);
+    for i in 0..m {
+        for j in i + 1..m {
+            costs.push((i, j, d(nodes[i], nodes[j])));
+        }
+    }
+    let (min, max) = match costs.first() {
+        Some(&(_, _, c)) => costs.iter().fold((c, c), |(min, max), &(_, _, c)| {
+            (if c < min { c } else { min }, if c > max { c } else { max })
+        }),
+        None => return Vec::new(),
+    };
+    if min.partial_cmp(&max) != Some(Ordering::Less) {
+        // All pairings cost the same
+        return nodes.chunks(2).map(|pair| (pair[0], pair[1])).collect();
+    }
+    let edges = costs
+        .into_iter()
+        .map(|(i, j, c)| (i, j, (max - c) + (max - min)))
+        .collect();
+    let mut blossoms = Blossoms::new(m, edges);
+    blossoms.solve();
+    (0..m)
+        .filter_map(|v| {
+            let w = blossoms.endpoint(blossoms.mate[v]?);
+            if v < w {
+                Some((nodes[v], nodes[w]))
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+/// The search state of Edmonds' blossom algorithm for a maximum weight
+/// matching, following Van Rantwijk's implementation: the dual variables of
+/// the vertices, the slacks of the edges and the deltas are doubled.
+///
+/// Vertices are `0..n` and blossoms `n..2 * n`. An edge `k` has endpoints
+/// `2 * k` and `2 * k + 1`, and `mate` and `label_end` refer to the remote
+/// endpoints of matched and labelling edges. Labels are `0` for free, `1` for
+/// S (outer) and `2` for T (inner), with bit `4` marking the blossoms visited
+/// by [`scan_blossom`](Blossoms::scan_blossom).
+struct Blossoms<K> {
+    n: usize,
+    edges: Vec<(usize, usize, K)>,
+    /// The remote endpoints of the edges of each vertex.
+    neighbors: Vec<Vec<usize>>,
+    mate: Vec<Option<usize>>,
+    label: Vec<u8>,
+    label_end: Vec<Option<usize>>,
+    /// The top-level blossom containing each vertex.
+    in_blossom: Vec<usize>,
+    parent: Vec<Option<usize>>,
+    /// The sub-blossoms of each blossom, starting from the one containing its
+    /// base, and the endpoints of the edges joining them.
+    children: Vec<Vec<usize>>,
+    child_endpoints: Vec<Vec<usize>>,
+    base: Vec<Option<usize>>,
+    /// The least slack edge to an S-blossom, from each free vertex or
+    /// S-blossom.
+    best_edge: Vec<Option<usize>>,
+    /// The least slack edges to the other S-blossoms, from each S-blossom.
+    blossom_best_edges: Vec<Option<Vec<usize>>>,
+    unused: Vec<usize>,
+    dual: Vec<K>,
+    allowed: Vec<bool>,
+    queue: Vec<usize>,
+}
+
+/// The least of the changes of dual variables that keep them feasible.
+enum Delta {
+    /// A vertex dual variable reaches zero: the matching is optimal.
+    Vertex,
+    /// An edge from an S-vertex to a free vertex becomes tight.
+    Free(usize),
+    /// An edge between S-blossoms becomes tight.
+    Outer(usize),
+    /// The dual variable of a T-blossom reaches zero.
+    Expand(usize),
+}
+
+impl<K> Blossoms<K>
+where
+    K: Measure + Copy + Sub<Output = K> + Div<Output = K> + From<u8>,
+{
+    fn new(n: usize, edges: Vec<(usize, usize, K)>) -> Self {
+        let mut neighbors = vec![Vec::new(); n];
+        let mut max = K::default();
+        for (k, &(i, j, w)) in edges.iter().enumerate() {
+            neighbors[i].push(2 * k + 1);
+            neighbors[j].push(2 * k);
+            if w > max {
+                max = w;
+            }
+        }
+        let mut dual = vec![max; n];
+        dual.resize(2 * n, K::default());
+        Blossoms {
+            n,
+            allowed: vec![false; edges.len()],
+            edges,
+            neighbors,
+            mate: vec![None; n],
+            label: vec![0; 2 * n],
+            label_end: vec![None; 2 * n],
+            in_blossom: (0..n).collect(),
+            parent: vec![None; 2 * n],
+            children: vec![Vec::new(); 2 * n],
+            child_endpoints: vec![Vec::new(); 2 * n],
+            base: (0..n).map(Some).chain((0..n).map(|_| None)).collect(),
+            best_edge: vec![None; 2 * n],
+            blossom_best_edges: vec![None; 2 * n],
+            unused: (n..2 * n).collect(),
+            dual,
+            queue: Vec::new(),
+        }
+    }
+
+    fn endpoint(&self, p: usize) -> usize {
+        let (i, j, _) = self.edges[p / 2];
+        if p % 2 == 0 {
+            i
+        } else {
+            j
+        }
+    }
+
+    fn slack(&self, k: usize) -> K {
+        let (i, j, w) = self.edges[k];
+        (self.dual[i] + self.dual[j]) - (w + w)
+    }
+
+    /// Whether edge `k` has less slack than edge `other`, if any.
+    fn less_slack(&self, k: usize, other: Option<usize>) -> bool {
+        other.map_or(true, |other| self.slack(k) < self.slack(other))
+    }
+
+    /// The vertices of blossom `b`.
+    fn leaves(&self, b: usize) -> Vec<usize> {
+        let mut leaves = Vec::new();
+        let mut stack = vec![b];
+        while let Some(b) = stack.pop() {
+            if b < self.n {
+                leaves.push(b);
+            } else {
+                stack.extend(self.children[b].iter().rev());
+            }
+        }
+        leaves
+    }
+
+    /// Label the top-level blossom of vertex `w` with `label`, reached through
+    /// endpoint `p`; the mate of a T-blossom is labelled S in turn.
+    fn assign_label(&mut self, mut w: usize, mut label: u8, mut p: Option<usize>) {
+        loop {
+            let b = self.in_blossom[w];
+            self.label[w] = label;
+            self.label[b] = label;
+            self.label_end[w] = p;
+            self.label_end[b] = p;
+            self.best_edge[w] = None;
+            self.best_edge[b] = None;
+            if label == 1 {
+                let leaves = self.leaves(b);
+                self.queue.extend(leaves);
+                return;
+            }
+            let mate = self.mate[self.base[b].unwrap()].unwrap();
+            w = self.endpoint(mate);
+            label = 1;
+            p = Some(mate ^ 1);
+        }
+    }
+
+    /// Trace back from S-vertices `v` and `w` to the root of their
+    /// alternating trees, returning the base of the new blossom if they meet,
+    /// or `None` if they lead to an augmenting path.
+    fn scan_blossom(&mut self, v: usize, w: usize) -> Option<usize> {
+        let mut path = Vec::new();
+        let mut base = None;
+        let (mut v, mut w) = (Some(v), Some(w));
+        while let Some(x) = v {
+            let b = self.in_blossom[x];
+            if self.label[b] & 4 != 0 {
+                base = self.base[b];
+                break;
+            }
+            path.push(b);
+            self.label[b] = 5;
+            v = self.label_end[b].map(|p| {
+                let t = self.in_blossom[self.endpoint(p)];
+                self.endpoint(self.label_end[t].unwrap())
+            });
+            if w.is_some() {
+                std::mem::swap(&mut v, &mut w);
+            }
+        }
+        for b in path {
+            self.label[b] = 1;
+        }
+        base
+    }
+
+    /// Make a new S-blossom of base `base` from the cycle closed by edge `k`.
+    fn add_blossom(&mut self, base: usize, k: usize) {
+        let (v, w, _) = self.edges[k];
+        let bb = self.in_blossom[base];
+        let mut bv = self.in_blossom[v];
+        let mut bw = self.in_blossom[w];
+        let b = self.unused.pop().unwrap();
+        self.base[b] = Some(base);
+        self.parent[b] = None;
+        self.parent[bb] = Some(b);
+        let mut path = Vec::new();
+        let mut endpoints = Vec::new();
+        while bv != bb {
+            self.parent[bv] = Some(b);
+            path.push(bv);
+            let p = self.label_end[bv].unwrap();
+            endpoints.push(p);
+            bv = self.in_blossom[self.endpoint(p)];
+        }
+        path.push(bb);
+        path.reverse();
+        endpoints.reverse();
+        endpoints.push(2 * k);
+        while bw != bb {
+            self.parent[bw] = Some(b);
+            path.push(bw);
+            let p = self.label_end[bw].unwrap();
+            endpoints.push(p ^ 1);
+            bw = self.in_blossom[self.endpoint(p)];
+        }
+        self.label[b] = 1;
+        self.label_end[b] = self.label_end[bb];
+        self.dual[b] = K::default();
+        self.children[b] = path.clone();
+        self.child_endpoints[b] = endpoints;
+        for v in self.leaves(b) {
+            if self.label[self.in_blossom[v]] == 2 {
+                // The T-vertex becomes an S-vertex in the new blossom
+                self.queue.push(v);
+            }
+            self.in_blossom[v] = b;
+        }
+
+        let mut best_edge_to = vec![None; 2 * self.n];
+        for bv in path {
+            let edges = match self.blossom_best_edges[bv].take() {
+                Some(edges) => edges,
+                None => self
+                    .leaves(bv)
+                    .into_iter()
+                    .flat_map(|v| self.neighbors[v].iter().map(|&p| p / 2))
+                    .collect(),
+            };
+            for k in edges {
+                let (i, j, _) = self.edges[k];
+                let j = if self.in_blossom[j] == b { i } else { j };
+                let bj = self.in_blossom[j];
+                if bj != b && self.label[bj] == 1 && self.less_slack(k, best_edge_to[bj]) {
+                    best_edge_to[bj] = Some(k);
+                }
+            }
+            self.best_edge[bv] = None;
+        }
+        let best_edges: Vec<usize> = best_edge_to.into_iter().flatten().collect();
+        self.best_edge[b] = None;
+        for &k in &best_edges {
+            if self.less_slack(k, self.best_edge[b]) {
+                self.best_edge[b] = Some(k);
+            }
+        }
+        self.blossom_best_edges[b] = Some(best_edges);
+    }
+
+    /// The direction to walk around blossom `b` from its child at `index` to
+    /// its base: the signed start index, the step and the endpoint offset.
+    fn walk(&self, b: usize, index: usize) -> (isize, isize, usize) {
+        if index % 2 == 1 {
+            (index as isize - self.children[b].len() as isize, 1, 0)
+        } else {
+            (index as isize, -1, 1)
+        }
+    }
+
+    /// The child of blossom `b`, or the endpoint joining its children, at a
+    /// signed index.
+    fn at(list: &[usize], j: isize) -> usize {
+        list[j.rem_euclid(list.len() as isize) as usize]
+    }
+
+    /// Replace blossom `b` by its sub-blossoms, relabelling them if `b` is a
+    /// T-blossom during a stage.
+    fn expand_blossom(&mut self, b: usize, end_stage: bool) {
+        let children = self.children[b].clone();
+        for &s in &children {
+            self.parent[s] = None;
+            if s < self.n {
+                self.in_blossom[s] = s;
+            } else if end_stage && self.dual[s] == K::default() {
+                self.expand_blossom(s, end_stage);
+            } else {
+                for v in self.leaves(s) {
+                    self.in_blossom[v] = s;
+                }
+            }
+        }
+
+        if !end_stage && self.label[b] == 2 {
+            // Relabel the sub-blossoms from the one through which b got its
+            // label to the base
+            let endpoints = self.child_endpoints[b].clone();
+            let entry = self.in_blossom[self.endpoint(self.label_end[b].unwrap() ^ 1)];
+            let index = children.iter().position(|&c| c == entry).unwrap();
+            let (mut j, step, trick) = self.walk(b, index);
+            let mut p = self.label_end[b].unwrap();
+            while j != 0 {
+                let q = Self::at(&endpoints, j - trick as isize);
+                let (x, y) = (self.endpoint(p ^ 1), self.endpoint(q ^ trick ^ 1));
+                self.label[x] = 0;
+                self.label[y] = 0;
+                self.assign_label(x, 2, Some(p));
+                self.allowed[q / 2] = true;
+                j += step;
+                p = Self::at(&endpoints, j - trick as isize) ^ trick;
+                self.allowed[p / 2] = true;
+                j += step;
+            }
+            // The base sub-blossom is labelled T without labelling its mate
+            let bv = Self::at(&children, j);
+            let x = self.endpoint(p ^ 1);
+            self.label[x] = 2;
+            self.label[bv] = 2;
+            self.label_end[x] = Some(p);
+            self.label_end[bv] = Some(p);
+            self.best_edge[bv] = None;
+            j += step;
+            while Self::at(&children, j) != entry {
+                let bv = Self::at(&children, j);
+                j += step;
+                if self.label[bv] == 1 {
+                    continue;
+                }
+                // A sub-blossom reached from an S-vertex outside b is
+                // labelled T
+                if let Some(v) = self.leaves(bv).into_iter().find(|&v| self.label[v] != 0) {
+                    self.label[v] = 0;
+                    let mate = self.mate[self.base[bv].unwrap()].unwrap();
+                    let x = self.endpoint(mate);
+                    self.label[x] = 0;
+                    self.assign_label(v, 2, self.label_end[v]);
+                }
+            }
+        }
+
+        self.label[b] = 0;
+        self.label_end[b] = None;
+        self.children[b].clear();
+        self.child_endpoints[b].clear();
+        self.base[b] = None;
+        self.best_edge[b] = None;
+        self.blossom_best_edges[b] = None;
+        self.unused.push(b);
+    }
+
+    /// Swap the matched and unmatched edges of blossom `b` along the path from
+    /// vertex `v` to its base, making `v` the new base.
+    fn augment_blossom(&mut self, b: usize, v: usize) {
+        let mut t = v;
+        while self.parent[t] != Some(b) {
+            t = self.parent[t].unwrap();
+        }
+        if t >= self.n {
+            self.augment_blossom(t, v);
+        }
+        let index = self.children[b].iter().position(|&c| c == t).unwrap();
+        let (mut j, step, trick) = self.walk(b, index);
+        while j != 0 {
+            j += step;
+            let t = Self::at(&self.children[b], j);
+            let p = Self::at(&self.child_endpoints[b], j - trick as isize) ^ trick;
+            if t >= self.n {
+                let x = self.endpoint(p);
+                self.augment_blossom(t, x);
+            }
+            j += step;
+            let t = Self::at(&self.children[b], j);
+            if t >= self.n {
+                let x = self.endpoint(p ^ 1);
+                self.augment_blossom(t, x);
+            }
+            let (x, y) = (self.endpoint(p), self.endpoint(p ^ 1));
+            self.mate[x] = Some(p ^ 1);
+            self.mate[y] = Some(p);
+        }
+        self.children[b].rotate_left(index);
+        self.child_endpoints[b].rotate_left(index);
+        self.base[b] = self.base[self.children[b][0]];
+    }
+
+    /// Augment the matching along the path through edge `k` between the roots
+    /// of two alternating trees.
+    fn augment_matching(&mut self, k: usize) {
+        let (v, w, _) = self.edges[k];
+        for &(s, p) in &[(v, 2 * k + 1), (w, 2 * k)] {
+            let (mut s, mut p) = (s, p);
+            loop {
+                let bs = self.in_blossom[s];
+                if bs >= self.n {
+                    self.augment_blossom(bs, s);
+                }
+                self.mate[s] = Some(p);
+                let t = match self.label_end[bs] {
+                    Some(q) => self.endpoint(q),
+                    None => break,
+                };
+                let bt = self.in_blossom[t];
+                let q = self.label_end[bt].unwrap();
+                s = self.endpoint(q);
+                let j = self.endpoint(q ^ 1);
+                if bt >= self.n {
+                    self.augment_blossom(bt, j);
+                }
+                self.mate[j] = Some(q);
+                p = q ^ 1;
+            }
+        }
+    }
+
+    /// Grow alternating trees from the S-vertices in the queue, until an
+    /// augmenting path is found and the matching augmented.
+    fn scan(&mut self) -> bool {
+        while let Some(v) = self.queue.pop() {
+            for index in 0..self.neighbors[v].len() {
+                let p = self.neighbors[v][index];
+                let k = p / 2;
+                let w = self.endpoint(p);
+                if self.in_blossom[v] == self.in_blossom[w] {
+                    continue;
+                }
+                let mut slack = None;
+                if !self.allowed[k] {
+                    let s = self.slack(k);
+                    if s <= K::default() {
+                        self.allowed[k] = true;
+                    }
+                    slack = Some(s);
+                }
+                let bw = self.in_blossom[w];
+                if self.allowed[k] {
+                    if self.label[bw] == 0 {
+                        self.assign_label(w, 2, Some(p ^ 1));
+                    } else if self.label[bw] == 1 {
+                        match self.scan_blossom(v, w) {
+                            Some(base) => self.add_blossom(base, k),
+                            None => {
+                                self.augment_matching(k);
+                                return true;
+                            }
+                        }
+                    } else if self.label[w] == 0 {
+                        // w is in a T-blossom, but not yet reached itself
+                        self.label[w] = 2;
+                        self.label_end[w] = Some(p ^ 1);
+                    }
+                } else if self.label[bw] == 1 {
+                    let b = self.in_blossom[v];
+                    if self.best_edge[b].map_or(true, |e| slack.unwrap() < self.slack(e)) {
+                        self.best_edge[b] = Some(k);
+                    }
+                } else if self.label[w] == 0
+                    && self.best_edge[w].map_or(true, |e| slack.unwrap() < self.slack(e))
+                {
+                    self.best_edge[w] = Some(k);
+                }
+            }
+        }
+        false
+    }
+
+    /// Compute the least change of dual variables, and apply it.
+    fn update_duals(&mut self) -> Delta {
+        let n = self.n;
+        let mut kind = Delta::Vertex;
+        let mut delta = self.dual[..n]
+            .iter()
+            .fold(self.dual[0], |min, &u| if u < min { u } else { min });
+        for v in 0..n {
+            if let (0, Some(k)) = (self.label[self.in_blossom[v]], self.best_edge[v]) {
+                let slack = self.slack(k);
+                if slack < delta {
+                    delta = slack;
+                    kind = Delta::Free(k);
+                }
+            }
+        }
+        let two = K::from(2);
+        for b in 0..2 * n {
+            if let (None, 1, Some(k)) = (self.parent[b], self.label[b], self.best_edge[b]) {
+                let slack = self.slack(k) / two;
+                if slack < delta {
+                    delta = slack;
+                    kind = Delta::Outer(k);
+                }
+            }
+        }
+        for b in n..2 * n {
+            if self.base[b].is_some()
+                && self.parent[b].is_none()
+                && self.label[b] == 2
+                && self.dual[b] < delta
+            {
+                delta = self.dual[b];
+                kind = Delta::Expand(b);
+            }
+        }
+
+        for v in 0..n {
+            match self.label[self.in_blossom[v]] {
+                1 => self.dual[v] = self.dual[v] - delta,
+                2 => self.dual[v] = self.dual[v] + delta,
+                _ => {}
+            }
+        }
+        for b in n..2 * n {
+            if self.base[b].is_some() && self.parent[b].is_none() {
+                match self.label[b] {
+                    1 => self.dual[b] = self.dual[b] + delta,
+                    2 => self.dual[b] = self.dual[b] - delta,
+                    _ => {}
+                }
+            }
+        }
+        kind
+    }
+
+    /// Run the stages of the algorithm, each augmenting the matching, until
+    /// it is maximum.
+    fn solve(&mut self) {
+        let n = self.n;
+        for _ in 0..n {
+            self.label.iter_mut().for_each(|l| *l = 0);
+            self.best_edge.iter_mut().for_each(|e| *e = None);
+            self.blossom_best_edges[n..]
+                .iter_mut()
+                .for_each(|e| *e = None);
+            self.allowed.iter_mut().for_each(|a| *a = false);
+            self.queue.clear();
+            for v in 0..n {
+                if self.mate[v].is_none() && self.label[self.in_blossom[v]] == 0 {
+                    self.assign_label(v, 1, None);
+                }
+            }
+
+            let augmented = loop {
+                if self.scan() {
+                    break true;
+                }
+                match self.update_duals() {
+                    Delta::Vertex => break false,
+                    Delta::Free(k) => {
+                        self.allowed[k] = true;
+                        let (i, j, _) = self.edges[k];
+                        let i = if self.label[self.in_blossom[i]] == 0 {
+                            j
+                        } else {
+                            i
+                        };
+                        self.queue.push(i);
+                    }
+                    Delta::Outer(k) => {
+                        self.allowed[k] = true;
+                        self.queue.push(self.edges[k].0);
+                    }
+                    Delta::Expand(b) => self.expand_blossom(b, false),
+                }
+            };
+            if !augmented {
+                break;
+            }
+
+            // Expand the S-blossoms whose dual variable reached zero
+            for b in n..2 * n {
+                if self.parent[b].is_none()
+                    && self.base[b].is_some()
+                    && self.label[b] == 1
+                    && self.dual[b] == K::default()
+                {
+                    self.expand_blossom(b, true);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Pseudo-random costs in `0..range` between `m` nodes.
+    fn costs(m: usize, range: u32, seed: u64) -> Vec<u32> {
+        let mut state = seed;
+        let mut costs = vec![0; m * m];
+        for i in 0..m {
+            for j in i + 1..m {
+                state = state
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                let c = (state >> 33) as u32 % range;
+                costs[i * m + j] = c;
+                costs[j * m + i] = c;
+            }
+        }
+        costs
+    }
+
+    fn check(m: usize, pairs: &[(usize, usize)]) {
+        let mut matched: Vec<usize> = pairs.iter().flat_map(|&(a, b)| vec![a, b]).collect();
+        matched.sort_unstable();
+        assert_eq!(matched, (0..m).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn blossom_matching_is_minimum() {
+        for m in (2..=MAX_SUBSET_MATCHING + 2).step_by(2) {
+            for &range in &[3, 10, 1000] {
+                for seed in 0..20 {
+                    let costs = costs(m, range, seed);
+                    let d = |a: usize, b: usize| costs[a * m + b];
+                    let nodes: Vec<usize> = (0..m).collect();
+                    let blossom = blossom_matching(&nodes, &d);
+                    check(m, &blossom);
+                    let cost = |pairs: &[(usize, usize)]| -> u32 {
+                        pairs.iter().map(|&(a, b)| d(a, b)).sum()
+                    };
+                    assert_eq!(cost(&blossom), cost(&subset_matching(&nodes, &d)));
+
+                    let f = |a: usize, b: usize| costs[a * m + b] as f64 / 7.;
+                    let float = blossom_matching(&nodes, &f);
+                    check(m, &float);
+                    let float_cost: f64 = float.iter().map(|&(a, b)| f(a, b)).sum();
+                    assert!((float_cost - cost(&blossom) as f64 / 7.).abs() < 1e-9);
+                }
+            }
+        }
+    }
+}
@@ -6,18 +6,41 @@
 
 pub mod astar;
 pub mod bellman_ford;
+pub mod betweenness_centrality;
+pub mod chain_decomposition;
+pub mod chinese_postman;
+pub mod christofides;
+pub mod cycles;
+pub mod dag_paths;
+pub mod dense;
+mod dfs_tree;
 pub mod dijkstra;
 pub mod dominators;
 pub mod feedback_arc_set;
+pub mod feedback_vertex_set;
 pub mod floyd_warshall;
 pub mod ford_fulkerson;
+pub mod heavy_path_decomposition;
 pub mod isomorphism;
 pub mod k_shortest_path;
+pub mod longest_path;
+pub mod lowest_common_ancestor;
 pub mod matching;
 pub mod min_spanning_tree;
+pub mod neighborhood;
+pub mod ordering;
 pub mod page_rank;
+pub mod partition;
+pub mod percolation;
+pub mod priority_queue;
 pub mod simple_paths;
+#[cfg(feature = "nalgebra")]
+pub mod spectral_clustering;
+pub mod summary;
+pub mod temporal;
 pub mod tred;
+pub mod tree_dp;
+pub mod treewidth;
 
 use std::num::NonZeroUsize;
 
@@ -33,20 +56,38 @@ use super::EdgeType;
 use crate::visit::Walker;
 
 pub use astar::astar;
-pub use bellman_ford::{bellman_ford, find_negative_cycle};
+pub use bellman_ford::{bellman_ford, bellman_ford_with_cycle, find_negative_cycle, spfa};
+pub use betweenness_centrality::betweenness_centrality;
+pub use chain_decomposition::{chain_decomposition, maximum_antichain, minimum_path_cover};
+pub use chinese_postman::chinese_postman;
+pub use christofides::christofides;
+pub use cycles::{find_cycle_directed, find_cycle_undirected, simple_cycles, SimpleCycles};
+pub use dag_paths::{dag_longest_paths, dag_shortest_paths, DagPaths};
 pub use dijkstra::dijkstra;
 pub use feedback_arc_set::greedy_feedback_arc_set;
+pub use feedback_vertex_set::greedy_feedback_vertex_set;
 pub use floyd_warshall::floyd_warshall;
 pub use ford_fulkerson::ford_fulkerson;
+pub use heavy_path_decomposition::{heavy_path_decomposition, HeavyPathDecomposition};
 pub use isomorphism::{
     is_isomorphic, is_isomorphic_matching, is_isomorphic_subgraph, is_isomorphic_subgraph_matching,
     subgraph_isomorphisms_iter,
 };
-pub use k_shortest_path::k_shortest_path;
+pub use k_shortest_path::{k_shortest_path, shortest_simple_paths, shortest_walks};
+pub use longest_path::longest_path;
+pub use lowest_common_ancestor::{lowest_common_ancestors, LowestCommonAncestors};
 pub use matching::{greedy_matching, maximum_matching, Matching};
 pub use min_spanning_tree::min_spanning_tree;
+pub use ordering::{degree_ordering, reindex, reverse_cuthill_mckee};
 pub use page_rank::page_rank;
+pub use partition::{kernighan_lin_bisection, multilevel_partition};
+pub use percolation::{edge_percolation, node_percolation, Percolation, RemovalStrategy};
 pub use simple_paths::all_simple_paths;
+#[cfg(feature = "nalgebra")]
+pub use spectral_clustering::{spectral_clustering, SpectralClustering};
+pub use summary::{summary, GraphSummary};
+pub use tree_dp::{tree_centroid, tree_fold, tree_max_weight_independent_set, TreeFold};
+pub use treewidth::{treewidth_min_degree, treewidth_min_fill_in};
 
 /// \[Generic\] Return the number of connected components of the graph.
 ///
@@ -0,0 +1,100 @@
+//! Breadth-first levels and radius-bounded neighborhoods of a node.
+
+use crate::visit::{FilterNode, IntoNeighbors, NodeFiltered, VisitMap, Visitable};
+
+/// Group the nodes reached from `source` by breadth-first level, stopping
+/// after level `radius` if it is not `None`.
+fn levels<G>(graph: G, source: G::NodeId, radius: Option<usize>) -> (Vec<Vec<G::NodeId>>, G::Map)
+where
+    G: IntoNeighbors + Visitable,
+{
+    let mut discovered = graph.visit_map();
+    discovered.visit(source);
+    let mut levels = vec![vec![source]];
+    while radius.map_or(true, |r| levels.len() <= r) {
+        let mut next = Vec::new();
+        for &u in levels.last().unwrap() {
+            for v in graph.neighbors(u) {
+                if discovered.visit(v) {
+                    next.push(v);
+                }
+            }
+        }
+        if next.is_empty() {
+            break;
+        }
+        levels.push(next);
+    }
+    (levels, discovered)
+}
+
+/// \[Generic\] Return the nodes reachable from `source`, grouped by
+/// breadth-first level.
+///
+/// `levels_from(graph, source)[d]` holds the nodes at distance `d` from
+/// `source`, in number of edges, in the order of a breadth-first search; the
+/// first level is `[source]`. The edges are followed as given by
+/// `IntoNeighbors`: e.g. outgoing edges for a directed graph, which can be
+/// wrapped in [`Reversed`](crate::visit::Reversed) to follow incoming edges
+/// instead.
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::neighborhood::levels_from;
+/// use petgraph::prelude::*;
+///
+/// let graph = DiGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (2, 3), (4, 0)]);
+/// let levels = levels_from(&graph, NodeIndex::new(0));
+/// assert_eq!(
+///     levels,
+///     vec![
+///         vec![NodeIndex::new(0)],
+///         vec![NodeIndex::new(2), NodeIndex::new(1)],
+///         vec![NodeIndex::new(3)],
+///     ]
+/// );
+/// ```
+pub fn levels_from<G>(graph: G, source: G::NodeId) -> Vec<Vec<G::NodeId>>
+where
+    G: IntoNeighbors + Visitable,
+{
+    levels(graph, source, None).0
+}
+
+/// \[Generic\] Return the neighborhood of radius `radius` around `node`: the
+/// subgraph induced by the nodes at distance at most `radius` from `node`,
+/// e.g. the ego network of `node` for a radius of 1.
+///
+/// The neighborhood is a [`NodeFiltered`] view of `graph`, which the
+/// algorithms of the crate run on as is. The edges are followed as in
+/// [`levels_from`], and only the nodes up to the given distance are
+/// explored.
+///
+/// To copy the neighborhood out of a [`Graph`](crate::Graph), pass its nodes
+/// to [`Graph::induced_subgraph`](crate::Graph::induced_subgraph).
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::neighborhood::neighborhood;
+/// use petgraph::prelude::*;
+/// use petgraph::visit::IntoNodeIdentifiers;
+///
+/// let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (0, 4), (1, 4)]);
+/// let ego = neighborhood(&graph, NodeIndex::new(0), 1);
+///
+/// let nodes: Vec<_> = ego.node_identifiers().collect();
+/// assert_eq!(nodes, vec![NodeIndex::new(0), NodeIndex::new(1), NodeIndex::new(4)]);
+///
+/// let (sub, node_map) = graph.induced_subgraph(nodes);
+/// assert_eq!(sub.edge_count(), 3);
+/// assert_eq!(node_map[2], NodeIndex::new(4));
+/// ```
+pub fn neighborhood<G>(graph: G, node: G::NodeId, radius: usize) -> NodeFiltered<G, G::Map>
+where
+    G: IntoNeighbors + Visitable,
+    G::Map: FilterNode<G::NodeId>,
+{
+    NodeFiltered(graph, levels(graph, node, Some(radius)).1)
+}
@@ -0,0 +1,408 @@
+//! Graph partitioning: splitting the nodes of a graph into parts of balanced
+//! sizes, cutting as few edges as possible.
+//!
+//! Edges are considered undirected, and their weights are given by an
+//! `edge_weight` closure. The *edge cut* of a partition is the total weight of
+//! the edges between nodes of different parts. Finding a partition of minimum
+//! edge cut is NP-hard: the functions of this module are heuristics.
+
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::scored::MinScored;
+use crate::visit::{EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
+
+/// \[Generic\] Split a graph into two halves with the
+/// [Kernighan–Lin algorithm](https://en.wikipedia.org/wiki/Kernighan%E2%80%93Lin_algorithm).
+///
+/// Starting from the first and second halves of the nodes in the order of
+/// [`IntoNodeIdentifiers::node_identifiers`], each pass moves single nodes to
+/// the other half, alternately from each side, each time picking the unmoved
+/// node of largest gain on that side. It then keeps the prefix of moves that
+/// reduces the edge cut the most, cut after a move from each side, so the
+/// sizes of the halves differ by at most one. Stops after `max_passes`
+/// passes, or when a pass does not improve the edge cut.
+///
+/// Each pass runs in **O((|V| + |E|) log |V|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::kernighan_lin_bisection;
+/// use petgraph::prelude::*;
+///
+/// // Two triangles joined by a single edge, with interleaved indices
+/// let graph = UnGraph::<(), ()>::from_edges(&[(0, 2), (2, 4), (4, 0), (1, 3), (3, 5), (5, 1), (0, 1)]);
+///
+/// let (mut a, mut b) = kernighan_lin_bisection(&graph, |_| 1., 10);
+/// a.sort();
+/// b.sort();
+/// let n = NodeIndex::new;
+/// assert_eq!((a, b), (vec![n(0), n(2), n(4)], vec![n(1), n(3), n(5)]));
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn kernighan_lin_bisection<G, F>(
+    graph: G,
+    edge_weight: F,
+    max_passes: usize,
+) -> (Vec<G::NodeId>, Vec<G::NodeId>)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    let (nodes, wgraph) = WeightedGraph::new(graph, edge_weight);
+    let half = nodes.len() / 2;
+    let mut side: Vec<_> = (0..nodes.len()).map(|v| v >= half).collect();
+    for _ in 0..max_passes {
+        if !kernighan_lin_pass(&wgraph, &mut side) {
+            break;
+        }
+    }
+    let (mut a, mut b) = (Vec::new(), Vec::new());
+    for (v, node) in nodes.into_iter().enumerate() {
+        if side[v] {
+            b.push(node);
+        } else {
+            a.push(node);
+        }
+    }
+    (a, b)
+}
+
+/// \[Generic\] Split a graph into `k` parts with a multilevel partitioner.
+///
+/// The graph is first *coarsened*, by repeatedly merging the endpoints of a
+/// heavy edge matching, until it is small. The coarsest graph is split by
+/// growing `k` regions of balanced weights in breadth-first order. The
+/// partition is then projected back onto the finer graphs, and refined at each
+/// level by moving boundary nodes to the neighboring part that reduces the
+/// edge cut the most, in the style of Kernighan–Lin and Fiduccia–Mattheyses.
+///
+/// Every part has at most `(1 + imbalance) * |V| / k` nodes, rounded up, unless
+/// the coarsening made that impossible, in which case the parts are as
+/// balanced as the refinement manages. Returns the `k` parts, some of which
+/// may be empty if the graph has fewer than `k` nodes.
+///
+/// **Panics** if `k` is zero.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::multilevel_partition;
+/// use petgraph::prelude::*;
+///
+/// // A 8x8 grid
+/// let mut graph = UnGraph::<(), ()>::default();
+/// let nodes: Vec<_> = (0..64).map(|_| graph.add_node(())).collect();
+/// for i in 0..64 {
+///     if i % 8 < 7 {
+///         graph.add_edge(nodes[i], nodes[i + 1], ());
+///     }
+///     if i < 56 {
+///         graph.add_edge(nodes[i], nodes[i + 8], ());
+///     }
+/// }
+///
+/// let parts = multilevel_partition(&graph, 4, 0.1, |_| 1.);
+/// assert_eq!(parts.len(), 4);
+/// assert!(parts.iter().all(|part| part.len() <= 18));
+/// assert_eq!(parts.iter().map(|part| part.len()).sum::<usize>(), 64);
+/// ```
+pub fn multilevel_partition<G, F>(
+    graph: G,
+    k: usize,
+    imbalance: f64,
+    edge_weight: F,
+) -> Vec<Vec<G::NodeId>>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    assert!(k > 0, "the number of parts must be positive");
+    let (nodes, wgraph) = WeightedGraph::new(graph, edge_weight);
+    let max_weight = ((1. + imbalance.max(0.)) * nodes.len() as f64 / k as f64).ceil() as usize;
+
+    // Coarsen, keeping the map from each level to the next
+    let mut levels = vec![wgraph];
+    let mut maps = Vec::new();
+    loop {
+        let fine = levels.last().unwrap();
+        if fine.len() <= COARSEST.max(2 * k) {
+            break;
+        }
+        let (coarse, map) = fine.coarsen(max_weight);
+        if coarse.len() * 10 > fine.len() * 9 {
+            break;
+        }
+        levels.push(coarse);
+        maps.push(map);
+    }
+
+    let mut part = levels.last().unwrap().grow_regions(k);
+    levels.last().unwrap().refine(&mut part, k, max_weight);
+    while let Some(map) = maps.pop() {
+        levels.pop();
+        part = map.iter().map(|&c| part[c]).collect();
+        levels.last().unwrap().refine(&mut part, k, max_weight);
+    }
+
+    let mut parts = vec![Vec::new(); k];
+    for (v, node) in nodes.into_iter().enumerate() {
+        parts[part[v]].push(node);
+    }
+    parts
+}
+
+/// Stop coarsening below this number of nodes.
+const COARSEST: usize = 20;
+
+/// An undirected graph with weighted nodes and edges, on compact indices.
+struct WeightedGraph {
+    /// The number of original nodes merged into each node.
+    node_weight: Vec<usize>,
+    /// The neighbors of each node, with the total weight of the edges to them.
+    adjacency: Vec<Vec<(usize, f64)>>,
+}
+
+impl WeightedGraph {
+    /// Build the weighted graph of `graph`, merging parallel edges and dropping
+    /// loops. Returns the nodes of `graph` by compact index.
+    fn new<G, F>(graph: G, mut edge_weight: F) -> (Vec<G::NodeId>, Self)
+    where
+        G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+        F: FnMut(G::EdgeRef) -> f64,
+    {
+        let nodes: Vec<_> = graph.node_identifiers().collect();
+        let mut compact = vec![0; graph.node_bound()];
+        for (i, &node) in nodes.iter().enumerate() {
+            compact[graph.to_index(node)] = i;
+        }
+        let mut edges = HashMap::new();
+        for edge in graph.edge_references() {
+            let a = compact[graph.to_index(edge.source())];
+            let b = compact[graph.to_index(edge.target())];
+            if a != b {
+                *edges.entry((a.min(b), a.max(b))).or_insert(0.) += edge_weight(edge);
+            }
+        }
+        let mut adjacency = vec![Vec::new(); nodes.len()];
+        for ((a, b), w) in edges {
+            adjacency[a].push((b, w));
+            adjacency[b].push((a, w));
+        }
+        let wgraph = WeightedGraph {
+            node_weight: vec![1; nodes.len()],
+            adjacency,
+        };
+        (nodes, wgraph)
+    }
+
+    fn len(&self) -> usize {
+        self.node_weight.len()
+    }
+
+    /// Merge the endpoints of a heavy edge matching, leaving nodes that would
+    /// weigh more than `max_weight` unmatched. Returns the coarse graph and
+    /// the coarse node of each node.
+    fn coarsen(&self, max_weight: usize) -> (Self, Vec<usize>) {
+        const UNMATCHED: usize = std::usize::MAX;
+        let mut map = vec![UNMATCHED; self.len()];
+        let mut node_weight = Vec::new();
+        for v in 0..self.len() {
+            if map[v] != UNMATCHED {
+                continue;
+            }
+            let mate = self.adjacency[v]
+                .iter()
+                .filter(|&&(u, _)| {
+                    map[u] == UNMATCHED && self.node_weight[u] + self.node_weight[v] <= max_weight
+                })
+                .fold(None, |best: Option<(usize, f64)>, &(u, w)| match best {
+                    Some((_, best_w)) if best_w >= w => best,
+                    _ => Some((u, w)),
+                });
+            map[v] = node_weight.len();
+            let mut weight = self.node_weight[v];
+            if let Some((u, _)) = mate {
+                map[u] = node_weight.len();
+                weight += self.node_weight[u];
+            }
+            node_weight.push(weight);
+        }
+
+        let mut adjacency = vec![Vec::new(); node_weight.len()];
+        let mut edges = HashMap::new();
+        for v in 0..self.len() {
+            for &(u, w) in &self.adjacency[v] {
+                let (a, b) = (map[v], map[u]);
+                if a < b {
+                    *edges.entry((a, b)).or_insert(0.) += w;
+                }
+            }
+        }
+        for ((a, b), w) in edges {
+            adjacency[a].push((b, w));
+            adjacency[b].push((a, w));
+        }
+        (
+            WeightedGraph {
+                node_weight,
+                adjacency,
+            },
+            map,
+        )
+    }
+
+    /// Split the nodes into `k` regions of about the same weight, grown in
+    /// breadth-first order.
+    fn grow_regions(&self, k: usize) -> Vec<usize> {
+        const UNASSIGNED: usize = std::usize::MAX;
+        let total: usize = self.node_weight.iter().sum();
+        let mut part = vec![UNASSIGNED; self.len()];
+        let mut next_seed = 0;
+        let mut assigned = 0;
+        for p in 0..k {
+            // The target weight of the parts left, so that rounding errors do
+            // not accumulate on the last part
+            let target = (total - assigned + (k - p) - 1) / (k - p);
+            let mut weight = 0;
+            let mut queue = std::collections::VecDeque::new();
+            while weight < target {
+                let v = match queue.pop_front() {
+                    Some(v) => v,
+                    None => {
+                        while next_seed < self.len() && part[next_seed] != UNASSIGNED {
+                            next_seed += 1;
+                        }
+                        if next_seed == self.len() {
+                            break;
+                        }
+                        part[next_seed] = p;
+                        next_seed
+                    }
+                };
+                weight += self.node_weight[v];
+                for &(u, _) in &self.adjacency[v] {
+                    if part[u] == UNASSIGNED {
+                        part[u] = p;
+                        queue.push_back(u);
+                    }
+                }
+            }
+            // Release the nodes queued past the target
+            for v in queue {
+                part[v] = UNASSIGNED;
+            }
+            assigned += weight;
+        }
+        for p in &mut part {
+            if *p == UNASSIGNED {
+                *p = k - 1;
+            }
+        }
+        part
+    }
+
+    /// Move nodes to the neighboring part that reduces the edge cut the most,
+    /// while parts weigh at most `max_weight`, until no move improves.
+    /// Nodes of overweight parts are moved even if the edge cut increases.
+    fn refine(&self, part: &mut [usize], k: usize, max_weight: usize) {
+        let mut part_weight = vec![0; k];
+        for v in 0..self.len() {
+            part_weight[part[v]] += self.node_weight[v];
+        }
+        let mut connectivity = vec![0.; k];
+        for _ in 0..MAX_REFINEMENT_PASSES {
+            let mut moved = false;
+            for v in 0..self.len() {
+                let (own, weight) = (part[v], self.node_weight[v]);
+                for &(u, w) in &self.adjacency[v] {
+                    connectivity[part[u]] += w;
+                }
+                let overweight = part_weight[own] > max_weight;
+                let best = (0..k)
+                    .filter(|&q| {
+                        q != own
+                            && part_weight[q] + weight <= max_weight
+                            && (overweight || connectivity[q] > 0.)
+                    })
+                    .fold(None, |best: Option<usize>, q| match best {
+                        Some(b)
+                            if connectivity[b] > connectivity[q]
+                                || (connectivity[b] == connectivity[q]
+                                    && part_weight[b] <= part_weight[q]) =>
+                        {
+                            best
+                        }
+                        _ => Some(q),
+                    });
+                if let Some(q) = best {
+                    let gain = connectivity[q] - connectivity[own];
+                    let balances = part_weight[q] + weight < part_weight[own];
+                    if overweight || gain > 0. || (gain == 0. && balances) {
+                        part[v] = q;
+                        part_weight[own] -= weight;
+                        part_weight[q] += weight;
+                        moved = true;
+                    }
+                }
+                for &(u, _) in &self.adjacency[v] {
+                    connectivity[part[u]] = 0.;
+                }
+            }
+            if !moved {
+                break;
+            }
+        }
+    }
+}
+
+/// The maximum number of refinement passes at each level.
+const MAX_REFINEMENT_PASSES: usize = 10;
+
+/// Run a pass of the Kernighan–Lin algorithm. Returns `true` if the edge cut
+/// was reduced.
+fn kernighan_lin_pass(wgraph: &WeightedGraph, side: &mut [bool]) -> bool {
+    // gain[v]: the reduction of the edge cut when moving v to the other side
+    let mut gain = vec![0.; wgraph.len()];
+    for v in 0..wgraph.len() {
+        for &(u, w) in &wgraph.adjacency[v] {
+            gain[v] += if side[u] != side[v] { w } else { -w };
+        }
+    }
+    // Max-heaps of the nodes of each side by gain, with stale entries
+    let mut heaps = [BinaryHeap::new(), BinaryHeap::new()];
+    for v in 0..wgraph.len() {
+        heaps[side[v] as usize].push(MinScored(-gain[v], v));
+    }
+    let mut locked = vec![false; wgraph.len()];
+    let mut moves = Vec::new();
+    let (mut total, mut best_total, mut best_len) = (0., 0., 0);
+    'pass: loop {
+        // Move a node from each side in turn, so that the halves stay balanced
+        for s in 0..2 {
+            let v = loop {
+                match heaps[s].pop() {
+                    Some(MinScored(g, v)) if !locked[v] && -g == gain[v] => break v,
+                    Some(_) => continue,
+                    None => break 'pass,
+                }
+            };
+            total += gain[v];
+            locked[v] = true;
+            side[v] = !side[v];
+            moves.push(v);
+            for &(u, w) in &wgraph.adjacency[v] {
+                gain[u] += if side[u] == side[v] { -2. * w } else { 2. * w };
+                if !locked[u] {
+                    heaps[side[u] as usize].push(MinScored(-gain[u], u));
+                }
+            }
+        }
+        if total > best_total {
+            best_total = total;
+            best_len = moves.len();
+        }
+    }
+    for &v in &moves[best_len..] {
+        side[v] = !side[v];
+    }
+    best_len > 0
+}
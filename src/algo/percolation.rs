@@ -0,0 +1,273 @@
+//! Percolation and robustness analysis: how the connectivity of a graph
+//! degrades as its nodes or edges are removed.
+
+use std::cmp::Ordering;
+
+use crate::unionfind::UnionFind;
+use crate::visit::{
+    EdgeIndexable, EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable,
+};
+
+/// The order in which [`node_percolation`] and [`edge_percolation`] remove
+/// nodes or edges.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RemovalStrategy<'a> {
+    /// A random order, reproducible from the seed.
+    Random(u64),
+    /// By decreasing score, e.g. a centrality measure, with ties broken by
+    /// increasing index. The scores are indexed by node or edge index.
+    Ranked(&'a [f64]),
+}
+
+/// The connectivity of a graph along a sequence of removals of nodes or
+/// edges.
+///
+/// Created with [`node_percolation`] and [`edge_percolation`].
+#[derive(Debug, Clone)]
+pub struct Percolation<T> {
+    removed: Vec<T>,
+    largest_component: Vec<usize>,
+    component_count: Vec<usize>,
+}
+
+impl<T> Percolation<T> {
+    /// Get the nodes or edges, in the order they are removed.
+    pub fn removed(&self) -> &[T] {
+        &self.removed
+    }
+
+    /// Get the size of the largest connected component, i.e. the giant
+    /// component, after each number of removals: `largest_component()[i]` is
+    /// the size after removing the first `i` nodes or edges.
+    pub fn largest_component(&self) -> &[usize] {
+        &self.largest_component
+    }
+
+    /// Get the number of connected components after each number of removals:
+    /// `component_count()[i]` is the number after removing the first `i`
+    /// nodes or edges.
+    pub fn component_count(&self) -> &[usize] {
+        &self.component_count
+    }
+
+    /// Get the robustness index: the mean, over all removals, of the size of
+    /// the largest component after the removal, relative to the number of
+    /// nodes of the graph. For node removals, this is the robustness measure
+    /// `R` of Schneider et al., between `0` and `1/2`.
+    ///
+    /// Returns `0` if nothing is removed.
+    pub fn robustness(&self) -> f64 {
+        let n = self.largest_component[0].max(1) as f64;
+        let steps = self.removed.len();
+        if steps == 0 {
+            return 0.;
+        }
+        let total: usize = self.largest_component[1..].iter().sum();
+        total as f64 / (n * steps as f64)
+    }
+}
+
+/// \[Generic\] Remove all nodes of a graph one by one, tracking the size of
+/// the largest connected component and the number of components.
+///
+/// Edges are considered undirected, so components are weakly connected for
+/// directed graphs. The nodes are removed in the order given by `strategy`;
+/// rankings are not updated as nodes are removed.
+///
+/// Computes in **O(|V| log |V| + |E| α(|V|))** time, by adding the nodes back
+/// in reverse order.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::{betweenness_centrality, node_percolation, RemovalStrategy};
+/// use petgraph::prelude::*;
+///
+/// // Two triangles joined by the edge 2 - 3
+/// let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)]);
+///
+/// // Removing the most central node first splits the graph at once
+/// let centrality = betweenness_centrality(&graph);
+/// let attack = node_percolation(&graph, RemovalStrategy::Ranked(&centrality));
+/// assert_eq!(&attack.largest_component()[..3], &[6, 3, 2]);
+/// assert_eq!(&attack.component_count()[..3], &[1, 2, 2]);
+///
+/// let failures = node_percolation(&graph, RemovalStrategy::Random(42));
+/// assert!(failures.robustness() >= attack.robustness());
+/// ```
+pub fn node_percolation<G>(graph: G, strategy: RemovalStrategy) -> Percolation<G::NodeId>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+{
+    let nodes: Vec<_> = graph.node_identifiers().collect();
+    let removed = removal_order(nodes, strategy, |n| NodeIndexable::to_index(&graph, *n));
+
+    let bound = graph.node_bound();
+    let mut neighbors = vec![Vec::new(); bound];
+    for edge in graph.edge_references() {
+        let a = NodeIndexable::to_index(&graph, edge.source());
+        let b = NodeIndexable::to_index(&graph, edge.target());
+        neighbors[a].push(b);
+        neighbors[b].push(a);
+    }
+
+    let mut components = Components::new(bound);
+    let mut present = vec![false; bound];
+    let steps = removed.len();
+    let (mut largest_component, mut component_count) = (vec![0; steps + 1], vec![0; steps + 1]);
+    for (i, &node) in removed.iter().enumerate().rev() {
+        let v = NodeIndexable::to_index(&graph, node);
+        present[v] = true;
+        components.add();
+        for &w in &neighbors[v] {
+            if present[w] {
+                components.union(v, w);
+            }
+        }
+        largest_component[i] = components.largest;
+        component_count[i] = components.count;
+    }
+    Percolation {
+        removed,
+        largest_component,
+        component_count,
+    }
+}
+
+/// \[Generic\] Remove all edges of a graph one by one, tracking the size of
+/// the largest connected component and the number of components.
+///
+/// Edges are considered undirected, so components are weakly connected for
+/// directed graphs. The edges are removed in the order given by `strategy`;
+/// rankings are not updated as edges are removed.
+///
+/// Computes in **O(|V| + |E| log |E|)** time, by adding the edges back in
+/// reverse order.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::{edge_percolation, RemovalStrategy};
+/// use petgraph::prelude::*;
+///
+/// let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+///
+/// let percolation = edge_percolation(&graph, RemovalStrategy::Random(7));
+/// assert_eq!(percolation.removed().len(), 3);
+/// assert_eq!(percolation.largest_component()[0], 4);
+/// assert_eq!(percolation.component_count(), &[1, 2, 3, 4]);
+/// ```
+pub fn edge_percolation<G>(graph: G, strategy: RemovalStrategy) -> Percolation<G::EdgeId>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + EdgeIndexable,
+{
+    let edges: Vec<_> = graph
+        .edge_references()
+        .map(|e| {
+            let a = NodeIndexable::to_index(&graph, e.source());
+            let b = NodeIndexable::to_index(&graph, e.target());
+            (e.id(), (a, b))
+        })
+        .collect();
+    let mut endpoints = vec![(0, 0); graph.edge_bound()];
+    for &(e, ends) in &edges {
+        endpoints[EdgeIndexable::to_index(&graph, e)] = ends;
+    }
+    let ids = edges.into_iter().map(|(e, _)| e).collect();
+    let removed = removal_order(ids, strategy, |e| EdgeIndexable::to_index(&graph, *e));
+
+    let mut components = Components::new(graph.node_bound());
+    for _ in graph.node_identifiers() {
+        components.add();
+    }
+    let steps = removed.len();
+    let (mut largest_component, mut component_count) = (vec![0; steps + 1], vec![0; steps + 1]);
+    largest_component[steps] = components.largest;
+    component_count[steps] = components.count;
+    for (i, &edge) in removed.iter().enumerate().rev() {
+        let (a, b) = endpoints[EdgeIndexable::to_index(&graph, edge)];
+        components.union(a, b);
+        largest_component[i] = components.largest;
+        component_count[i] = components.count;
+    }
+    Percolation {
+        removed,
+        largest_component,
+        component_count,
+    }
+}
+
+/// Order `items` for removal, given their indices.
+fn removal_order<T, F>(mut items: Vec<T>, strategy: RemovalStrategy, index: F) -> Vec<T>
+where
+    F: Fn(&T) -> usize,
+{
+    match strategy {
+        RemovalStrategy::Random(seed) => {
+            // Fisher–Yates shuffle
+            let mut rng = SplitMix64(seed);
+            for i in (1..items.len()).rev() {
+                let j = (rng.next() % (i as u64 + 1)) as usize;
+                items.swap(i, j);
+            }
+        }
+        RemovalStrategy::Ranked(scores) => {
+            items.sort_by(|a, b| {
+                let (a, b) = (index(a), index(b));
+                scores[b]
+                    .partial_cmp(&scores[a])
+                    .unwrap_or(Ordering::Equal)
+                    .then(a.cmp(&b))
+            });
+        }
+    }
+    items
+}
+
+/// The [SplitMix64](https://prng.di.unimi.it/splitmix64.c) pseudorandom
+/// number generator.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Connected components under insertions, with their sizes.
+struct Components {
+    sets: UnionFind<usize>,
+    /// The size of each component, by root.
+    size: Vec<usize>,
+    largest: usize,
+    count: usize,
+}
+
+impl Components {
+    fn new(bound: usize) -> Self {
+        Components {
+            sets: UnionFind::new(bound),
+            size: vec![1; bound],
+            largest: 0,
+            count: 0,
+        }
+    }
+
+    /// Account for a new singleton component.
+    fn add(&mut self) {
+        self.count += 1;
+        self.largest = self.largest.max(1);
+    }
+
+    fn union(&mut self, a: usize, b: usize) {
+        let (ra, rb) = (self.sets.find_mut(a), self.sets.find_mut(b));
+        if self.sets.union(ra, rb) {
+            let root = self.sets.find_mut(ra);
+            self.size[root] = self.size[ra] + self.size[rb];
+            self.largest = self.largest.max(self.size[root]);
+            self.count -= 1;
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Priority queues for the shortest path algorithms.
+//!
+//! [`dijkstra_with_queue`](super::dijkstra::dijkstra_with_queue) and
+//! [`astar_with_queue`](super::astar::astar_with_queue) accept any
+//! [`PriorityQueue`], so that the queue can be picked to suit the graph and
+//! cost type: a [`BinaryQueue`], a [`DaryQueue`], or, for unsigned integer
+//! costs, a [`RadixQueue`].
+
+use std::collections::BinaryHeap;
+
+use crate::scored::MinScored;
+
+/// A min-priority queue of items of type `T` with priorities of type `K`.
+pub trait PriorityQueue<K, T> {
+    /// Insert `item` with the given priority.
+    fn push(&mut self, priority: K, item: T);
+
+    /// Remove and return an item of smallest priority, with its priority.
+    ///
+    /// Priorities that are not comparable, such as floating point NaN, are
+    /// returned last.
+    fn pop(&mut self) -> Option<(K, T)>;
+}
+
+/// A [`PriorityQueue`] backed by the standard library's `BinaryHeap`.
+///
+/// This is the queue used by [`dijkstra`](fn@super::dijkstra) and
+/// [`astar`](fn@super::astar).
+#[derive(Clone, Debug)]
+pub struct BinaryQueue<K, T> {
+    heap: BinaryHeap<MinScored<K, T>>,
+}
+
+impl<K: PartialOrd, T> BinaryQueue<K, T> {
+    /// Create a new empty queue.
+    pub fn new() -> Self {
+        BinaryQueue {
+            heap: BinaryHeap::new(),
+        }
+    }
+}
+
+impl<K: PartialOrd, T> Default for BinaryQueue<K, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: PartialOrd, T> PriorityQueue<K, T> for BinaryQueue<K, T> {
+    fn push(&mut self, priority: K, item: T) {
+        self.heap.push(MinScored(priority, item));
+    }
+
+    fn pop(&mut self) -> Option<(K, T)> {
+        self.heap
+            .pop()
+            .map(|MinScored(priority, item)| (priority, item))
+    }
+}
+
+/// A [`PriorityQueue`] backed by a [*d*-ary heap](https://en.wikipedia.org/wiki/D-ary_heap),
+/// whose nodes have `arity` children.
+///
+/// A larger arity makes pushing cheaper and popping more expensive, and the
+/// heap shallower and more cache friendly. As shortest path algorithms push
+/// more items than they pop, an arity of 4, the default, often beats a binary
+/// heap.
+#[derive(Clone, Debug)]
+pub struct DaryQueue<K, T> {
+    arity: usize,
+    heap: Vec<MinScored<K, T>>,
+}
+
+impl<K: PartialOrd, T> DaryQueue<K, T> {
+    /// Create a new empty queue whose heap nodes have `arity` children.
+    ///
+    /// **Panics** if `arity` is less than 2.
+    pub fn new(arity: usize) -> Self {
+        assert!(arity >= 2, "the arity of a heap must be at least 2");
+        DaryQueue {
+            arity,
+            heap: Vec::new(),
+        }
+    }
+}
+
+impl<K: PartialOrd, T> Default for DaryQueue<K, T> {
+    fn default() -> Self {
+        Self::new(4)
+    }
+}
+
+impl<K: PartialOrd, T> PriorityQueue<K, T> for DaryQueue<K, T> {
+    fn push(&mut self, priority: K, item: T) {
+        self.heap.push(MinScored(priority, item));
+        let mut i = self.heap.len() - 1;
+        while i > 0 {
+            let parent = (i - 1) / self.arity;
+            if self.heap[i] <= self.heap[parent] {
+                break;
+            }
+            self.heap.swap(i, parent);
+            i = parent;
+        }
+    }
+
+    fn pop(&mut self) -> Option<(K, T)> {
+        if self.heap.is_empty() {
+            return None;
+        }
+        let MinScored(priority, item) = self.heap.swap_remove(0);
+        let mut i = 0;
+        loop {
+            let first = self.arity * i + 1;
+            let last = (first + self.arity).min(self.heap.len());
+            if first >= last {
+                break;
+            }
+            let child = (first + 1..last).fold(first, |best, c| {
+                if self.heap[c] > self.heap[best] {
+                    c
+                } else {
+                    best
+                }
+            });
+            if self.heap[child] <= self.heap[i] {
+                break;
+            }
+            self.heap.swap(i, child);
+            i = child;
+        }
+        Some((priority, item))
+    }
+}
+
+/// Unsigned integer priorities of a [`RadixQueue`].
+pub trait RadixKey: Copy + Ord + Default {
+    /// The number of bits of the type.
+    const BITS: u32;
+
+    /// The position of the highest bit in which `self` and `other` differ,
+    /// counted from one, or zero if they are equal.
+    fn radix_distance(self, other: Self) -> u32;
+}
+
+macro_rules! impl_radix_key(
+    ( $( $t:ident ),* ) => {
+        $(
+            impl RadixKey for $t {
+                const BITS: u32 = (std::mem::size_of::<$t>() * 8) as u32;
+
+                fn radix_distance(self, other: Self) -> u32 {
+                    <Self as RadixKey>::BITS - (self ^ other).leading_zeros()
+                }
+            }
+        )*
+    };
+);
+
+impl_radix_key!(u8, u16, u32, u64, u128, usize);
+
+/// A monotone [`PriorityQueue`] for unsigned integer priorities, backed by a
+/// [radix heap](https://en.wikipedia.org/wiki/Radix_heap).
+///
+/// Items are kept in buckets by the highest bit in which their priority
+/// differs from the last priority popped, so that each item is moved between
+/// buckets at most once per bit of the priority type. This makes the queue
+/// faster than a heap for [`dijkstra`](fn@super::dijkstra) with integer costs,
+/// and for [`astar`](fn@super::astar) with a consistent heuristic.
+///
+/// The queue is monotone: items must not be pushed with a priority smaller
+/// than the last one popped, which holds in these algorithms as long as the
+/// costs are non-negative and the heuristic consistent.
+#[derive(Clone, Debug)]
+pub struct RadixQueue<K, T> {
+    last: K,
+    len: usize,
+    buckets: Vec<Vec<(K, T)>>,
+}
+
+impl<K: RadixKey, T> RadixQueue<K, T> {
+    /// Create a new empty queue.
+    pub fn new() -> Self {
+        RadixQueue {
+            last: K::default(),
+            len: 0,
+            buckets: (0..=K::BITS).map(|_| Vec::new()).collect(),
+        }
+    }
+}
+
+impl<K: RadixKey, T> Default for RadixQueue<K, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: RadixKey, T> PriorityQueue<K, T> for RadixQueue<K, T> {
+    /// **Panics** if `priority` is smaller than the last priority popped.
+    fn push(&mut self, priority: K, item: T) {
+        assert!(
+            priority >= self.last,
+            "a radix queue is monotone: priorities must not decrease"
+        );
+        let bucket = priority.radix_distance(self.last) as usize;
+        self.buckets[bucket].push((priority, item));
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<(K, T)> {
+        if self.len == 0 {
+            return None;
+        }
+        if self.buckets[0].is_empty() {
+            // Redistribute the first non-empty bucket around its smallest
+            // priority, which all fall into lower buckets
+            let first = self.buckets.iter().position(|b| !b.is_empty()).unwrap();
+            let items = std::mem::take(&mut self.buckets[first]);
+            self.last = items.iter().map(|&(priority, _)| priority).min().unwrap();
+            for (priority, item) in items {
+                let bucket = priority.radix_distance(self.last) as usize;
+                self.buckets[bucket].push((priority, item));
+            }
+        }
+        self.len -= 1;
+        self.buckets[0].pop()
+    }
+}
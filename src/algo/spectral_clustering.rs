@@ -0,0 +1,244 @@
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+use nalgebra::{DMatrix, SymmetricEigen};
+
+use crate::visit::{EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
+
+/// The result of [`spectral_clustering`].
+#[derive(Debug, Clone)]
+pub struct SpectralClustering<N> {
+    clusters: Vec<Vec<N>>,
+    eigenvalues: Vec<f64>,
+}
+
+impl<N> SpectralClustering<N> {
+    /// Get the clusters. There are exactly `k` clusters, some of which may be
+    /// empty.
+    pub fn clusters(&self) -> &[Vec<N>] {
+        &self.clusters
+    }
+
+    /// Take the clusters.
+    pub fn into_clusters(self) -> Vec<Vec<N>> {
+        self.clusters
+    }
+
+    /// Get all the eigenvalues of the normalized Laplacian, in ascending
+    /// order. They lie in `[0, 2]`, and the multiplicity of `0` is the number
+    /// of connected components of the graph.
+    pub fn eigenvalues(&self) -> &[f64] {
+        &self.eigenvalues
+    }
+
+    /// Get the eigen-gap of the clustering, i.e. the difference between the
+    /// `k + 1`-th and the `k`-th smallest eigenvalues, or `0` if the graph has
+    /// at most `k` nodes.
+    ///
+    /// A large eigen-gap indicates that the graph has `k` well separated
+    /// clusters.
+    pub fn eigen_gap(&self) -> f64 {
+        let k = self.clusters.len();
+        if k < self.eigenvalues.len() {
+            self.eigenvalues[k] - self.eigenvalues[k - 1]
+        } else {
+            0.
+        }
+    }
+
+    /// Get the number of clusters `k` with the largest eigen-gap, or `1` if the
+    /// graph has at most one node.
+    pub fn suggested_k(&self) -> usize {
+        (1..self.eigenvalues.len())
+            .fold((1, std::f64::NEG_INFINITY), |(best, best_gap), k| {
+                let gap = self.eigenvalues[k] - self.eigenvalues[k - 1];
+                if gap > best_gap {
+                    (k, gap)
+                } else {
+                    (best, best_gap)
+                }
+            })
+            .0
+    }
+}
+
+/// \[Generic\] Split a graph into `k` clusters by
+/// [spectral clustering](https://en.wikipedia.org/wiki/Spectral_clustering).
+///
+/// Edges are considered undirected, and their weights, given by
+/// `edge_weight`, must be non-negative. Following Ng, Jordan and Weiss, each
+/// node is embedded as its row of the eigenvectors of the `k` smallest
+/// eigenvalues of the normalized Laplacian `I - D^(-1/2) W D^(-1/2)`,
+/// normalized to unit length, and the embedded nodes are grouped by k-means,
+/// with a farthest-first initialization.
+///
+/// The eigenvalues are reported in the result, along with the eigen-gap to
+/// help choosing `k`.
+///
+/// Computes a dense eigendecomposition, in **O(|V|³)** time and
+/// **O(|V|²)** space.
+///
+/// Requires crate feature `"nalgebra"`.
+///
+/// **Panics** if `k` is zero.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::spectral_clustering;
+/// use petgraph::prelude::*;
+///
+/// // Two triangles joined by a single edge
+/// let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3), (2, 3)]);
+///
+/// let clustering = spectral_clustering(&graph, 2, |_| 1.);
+/// let mut clusters = clustering.clusters().to_vec();
+/// clusters.iter_mut().for_each(|c| c.sort());
+/// clusters.sort();
+/// let n = NodeIndex::new;
+/// assert_eq!(clusters, vec![vec![n(0), n(1), n(2)], vec![n(3), n(4), n(5)]]);
+/// assert_eq!(clustering.suggested_k(), 2);
+/// ```
+pub fn spectral_clustering<G, F>(
+    graph: G,
+    k: usize,
+    mut edge_weight: F,
+) -> SpectralClustering<G::NodeId>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    assert!(k > 0, "the number of clusters must be positive");
+    let nodes: Vec<_> = graph.node_identifiers().collect();
+    let n = nodes.len();
+    if n == 0 {
+        return SpectralClustering {
+            clusters: vec![Vec::new(); k],
+            eigenvalues: Vec::new(),
+        };
+    }
+    let mut compact = HashMap::with_capacity(n);
+    for (i, &node) in nodes.iter().enumerate() {
+        compact.insert(graph.to_index(node), i);
+    }
+
+    // Symmetric weight matrix, without loops
+    let mut laplacian = DMatrix::<f64>::zeros(n, n);
+    for edge in graph.edge_references() {
+        let a = compact[&graph.to_index(edge.source())];
+        let b = compact[&graph.to_index(edge.target())];
+        if a != b {
+            let w = edge_weight(edge);
+            laplacian[(a, b)] -= w;
+            laplacian[(b, a)] -= w;
+        }
+    }
+    let scale: Vec<f64> = (0..n)
+        .map(|i| {
+            let degree = -laplacian.row(i).sum();
+            if degree > 0. {
+                1. / degree.sqrt()
+            } else {
+                0.
+            }
+        })
+        .collect();
+    for i in 0..n {
+        for j in 0..n {
+            laplacian[(i, j)] *= scale[i] * scale[j];
+        }
+        // An isolated node is a component of its own, of eigenvalue 0
+        if scale[i] > 0. {
+            laplacian[(i, i)] = 1.;
+        }
+    }
+
+    let eigen = SymmetricEigen::new(laplacian);
+    let mut by_value: Vec<usize> = (0..n).collect();
+    by_value.sort_by(|&i, &j| cmp_f64(eigen.eigenvalues[i], eigen.eigenvalues[j]));
+    let eigenvalues = by_value.iter().map(|&i| eigen.eigenvalues[i]).collect();
+
+    // Embed the nodes, on the unit sphere
+    let dim = k.min(n);
+    let points: Vec<Vec<f64>> = (0..n)
+        .map(|v| {
+            let mut point: Vec<f64> = by_value[..dim]
+                .iter()
+                .map(|&i| eigen.eigenvectors[(v, i)])
+                .collect();
+            let norm = point.iter().map(|x| x * x).sum::<f64>().sqrt();
+            if norm > 0. {
+                point.iter_mut().for_each(|x| *x /= norm);
+            }
+            point
+        })
+        .collect();
+
+    let mut clusters = vec![Vec::new(); k];
+    for (v, c) in k_means(&points, k).into_iter().enumerate() {
+        clusters[c].push(nodes[v]);
+    }
+    SpectralClustering {
+        clusters,
+        eigenvalues,
+    }
+}
+
+/// Compare floats, with NaN equal to everything.
+fn cmp_f64(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+}
+
+/// The maximum number of iterations of k-means.
+const MAX_K_MEANS_ITERATIONS: usize = 100;
+
+/// Group `points` into `k` clusters by Lloyd's algorithm. Returns the cluster
+/// of each point.
+fn k_means(points: &[Vec<f64>], k: usize) -> Vec<usize> {
+    let distance =
+        |a: &[f64], b: &[f64]| -> f64 { a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum() };
+    let nearest = |centers: &[Vec<f64>], point: &[f64]| {
+        (0..centers.len())
+            .min_by(|&i, &j| cmp_f64(distance(&centers[i], point), distance(&centers[j], point)))
+            .unwrap_or(0)
+    };
+    if points.is_empty() {
+        return Vec::new();
+    }
+
+    // Farthest-first initialization
+    let mut centers = vec![points[0].clone()];
+    while centers.len() < k.min(points.len()) {
+        let farthest = points
+            .iter()
+            .max_by(|a, b| {
+                let da = distance(&centers[nearest(&centers, a)], a);
+                let db = distance(&centers[nearest(&centers, b)], b);
+                cmp_f64(da, db)
+            })
+            .unwrap();
+        centers.push(farthest.clone());
+    }
+
+    let mut assignment: Vec<usize> = points.iter().map(|p| nearest(&centers, p)).collect();
+    for _ in 0..MAX_K_MEANS_ITERATIONS {
+        let dim = points[0].len();
+        let mut sums = vec![vec![0.; dim]; centers.len()];
+        let mut counts = vec![0; centers.len()];
+        for (point, &c) in points.iter().zip(&assignment) {
+            counts[c] += 1;
+            sums[c].iter_mut().zip(point).for_each(|(s, x)| *s += x);
+        }
+        for (c, center) in centers.iter_mut().enumerate() {
+            // Empty clusters keep their center
+            if counts[c] > 0 {
+                *center = sums[c].iter().map(|s| s / counts[c] as f64).collect();
+            }
+        }
+        let next: Vec<usize> = points.iter().map(|p| nearest(&centers, p)).collect();
+        if next == assignment {
+            break;
+        }
+        assignment = next;
+    }
+    assignment
+}
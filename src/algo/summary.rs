@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+
+use crate::unionfind::UnionFind;
+use crate::visit::{EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
+
+/// Basic statistics of a graph, computed by [`summary`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct GraphSummary {
+    /// The number of nodes.
+    pub node_count: usize,
+    /// The number of edges, including loops and parallel edges.
+    pub edge_count: usize,
+    /// Whether the graph is directed.
+    pub directed: bool,
+    /// The number of edges divided by the number of possible edges between
+    /// distinct nodes, or `0` if there are fewer than two nodes.
+    pub density: f64,
+    /// The minimum degree, or `0` if the graph is empty.
+    pub min_degree: usize,
+    /// The mean degree, or `0` if the graph is empty.
+    pub mean_degree: f64,
+    /// The maximum degree, or `0` if the graph is empty.
+    pub max_degree: usize,
+    /// The number of connected components, weakly connected for directed
+    /// graphs.
+    pub component_count: usize,
+    /// Whether the graph is a directed acyclic graph. Always `false` for
+    /// undirected graphs.
+    pub is_dag: bool,
+    /// The number of loops, i.e. edges from a node to itself.
+    pub self_loop_count: usize,
+    /// The number of edges with the same endpoints as an other edge, not
+    /// counting the first of them.
+    pub parallel_edge_count: usize,
+}
+
+/// \[Generic\] Compute basic statistics of a graph, for quick sanity checks.
+///
+/// The degree of a node is its number of incident edges, both incoming and
+/// outgoing for directed graphs, where loops count twice.
+///
+/// Computes in **O(|V| + |E|)** expected time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::summary;
+/// use petgraph::prelude::*;
+///
+/// let mut graph = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (0, 1), (3, 3)]);
+/// graph.add_node(());
+///
+/// let summary = summary(&graph);
+/// assert_eq!(summary.node_count, 5);
+/// assert_eq!(summary.edge_count, 4);
+/// assert_eq!(summary.density, 4. / 20.);
+/// assert_eq!((summary.min_degree, summary.max_degree), (0, 3));
+/// assert_eq!(summary.mean_degree, 8. / 5.);
+/// assert_eq!(summary.component_count, 3);
+/// assert!(!summary.is_dag);
+/// assert_eq!(summary.self_loop_count, 1);
+/// assert_eq!(summary.parallel_edge_count, 1);
+/// ```
+pub fn summary<G>(graph: G) -> GraphSummary
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+{
+    let directed = graph.is_directed();
+    let bound = graph.node_bound();
+    let mut degree = vec![0; bound];
+    let mut in_degree = vec![0; bound];
+    let mut successors = vec![Vec::new(); bound];
+    let mut components = UnionFind::new(bound);
+    let mut endpoints = HashSet::new();
+    let (mut edge_count, mut self_loop_count, mut parallel_edge_count) = (0, 0, 0);
+    for edge in graph.edge_references() {
+        let (a, b) = (graph.to_index(edge.source()), graph.to_index(edge.target()));
+        edge_count += 1;
+        degree[a] += 1;
+        degree[b] += 1;
+        in_degree[b] += 1;
+        successors[a].push(b);
+        components.union(a, b);
+        if a == b {
+            self_loop_count += 1;
+        }
+        let key = if directed {
+            (a, b)
+        } else {
+            (a.min(b), a.max(b))
+        };
+        if !endpoints.insert(key) {
+            parallel_edge_count += 1;
+        }
+    }
+
+    let nodes: Vec<usize> = graph
+        .node_identifiers()
+        .map(|n| graph.to_index(n))
+        .collect();
+    let node_count = nodes.len();
+    let degrees = nodes.iter().map(|&v| degree[v]);
+    let min_degree = degrees.clone().min().unwrap_or(0);
+    let max_degree = degrees.max().unwrap_or(0);
+    let mean_degree = if node_count > 0 {
+        2. * edge_count as f64 / node_count as f64
+    } else {
+        0.
+    };
+    let pairs = node_count as f64 * node_count.saturating_sub(1) as f64;
+    let density = if node_count < 2 {
+        0.
+    } else if directed {
+        edge_count as f64 / pairs
+    } else {
+        2. * edge_count as f64 / pairs
+    };
+    let component_count = nodes
+        .iter()
+        .map(|&v| components.find(v))
+        .collect::<HashSet<_>>()
+        .len();
+
+    // Kahn's algorithm: the graph is acyclic if all nodes get removed
+    let is_dag = directed && {
+        let mut ready: Vec<usize> = nodes
+            .iter()
+            .copied()
+            .filter(|&v| in_degree[v] == 0)
+            .collect();
+        let mut removed = 0;
+        while let Some(v) = ready.pop() {
+            removed += 1;
+            for &w in &successors[v] {
+                in_degree[w] -= 1;
+                if in_degree[w] == 0 {
+                    ready.push(w);
+                }
+            }
+        }
+        removed == node_count
+    };
+
+    GraphSummary {
+        node_count,
+        edge_count,
+        directed,
+        density,
+        min_degree,
+        mean_degree,
+        max_degree,
+        component_count,
+        is_dag,
+        self_loop_count,
+        parallel_edge_count,
+    }
+}
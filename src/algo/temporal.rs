@@ -0,0 +1,279 @@
+//! Time-respecting paths in temporal graphs.
+//!
+//! In a temporal graph, each edge can only be traversed at a given time: it
+//! departs from its source at a *departure* time and reaches its target at an
+//! *arrival* time, given by an `edge_time` closure. Several contacts between
+//! the same nodes are represented by parallel edges. A *journey* is a path
+//! whose edges depart no earlier than the arrival of the previous edge, so
+//! that reachability is not symmetric nor transitive.
+//!
+//! The functions of this module follow the one-pass algorithms of Wu et al.,
+//! *Path Problems in Temporal Graphs* (VLDB 2014), which scan the edges by
+//! increasing departure time. Arrival times must be later than departure
+//! times; edges of zero duration departing at the same time may not be
+//! chained.
+//!
+//! A static snapshot of a temporal graph at a given time is a subgraph of its
+//! active edges, e.g. an [`EdgeFiltered`](crate::visit::EdgeFiltered) view or
+//! a copy made with [`Graph::filter_map`](crate::Graph::filter_map).
+
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::ops::Sub;
+
+use crate::algo::Measure;
+use crate::visit::{EdgeRef, GraphProp, IntoEdgeReferences};
+
+/// \[Generic\] Compute the earliest arrival time at every node reachable by a
+/// journey leaving `source` at `start_time` or later.
+///
+/// The edges of undirected graphs can be traversed both ways. See the
+/// [module documentation](self) for the temporal graph model.
+///
+/// Returns a `HashMap` from each reachable node to its earliest arrival time,
+/// `start_time` for `source`.
+///
+/// Computes in **O(|E| log |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::temporal::earliest_arrival;
+/// use petgraph::prelude::*;
+///
+/// // Edges weighted by (departure, arrival)
+/// let graph = DiGraph::<(), (u32, u32)>::from_edges(&[
+///     (0, 1, (1, 2)),
+///     (1, 2, (3, 5)),
+///     (0, 2, (2, 9)),
+///     // Departs before the arrival at 2: unusable
+///     (2, 3, (4, 6)),
+/// ]);
+///
+/// let arrival = earliest_arrival(&graph, NodeIndex::new(0), 0, |e| *e.weight());
+/// assert_eq!(arrival[&NodeIndex::new(2)], 5);
+/// assert!(!arrival.contains_key(&NodeIndex::new(3)));
+/// ```
+pub fn earliest_arrival<G, F, T>(
+    graph: G,
+    source: G::NodeId,
+    start_time: T,
+    edge_time: F,
+) -> HashMap<G::NodeId, T>
+where
+    G: IntoEdgeReferences + GraphProp,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> (T, T),
+    T: Measure + Copy,
+{
+    let mut arrival = HashMap::new();
+    arrival.insert(source, start_time);
+    for (u, v, departure, arrive) in contacts(graph, edge_time) {
+        let reached = arrival.get(&u).map_or(false, |&a| a <= departure);
+        if reached && arrival.get(&v).map_or(true, |&a| arrive < a) {
+            arrival.insert(v, arrive);
+        }
+    }
+    arrival
+}
+
+/// \[Generic\] Compute the minimum duration of a journey from `source` to
+/// every reachable node, leaving `source` at `start_time` or later.
+///
+/// The duration of a journey is the time between its departure from `source`
+/// and its arrival, including waiting times. The edges of undirected graphs
+/// can be traversed both ways. See the [module documentation](self) for the
+/// temporal graph model.
+///
+/// Returns a `HashMap` from each reachable node to its minimum duration, the
+/// default value (zero) for `source`.
+///
+/// Computes in **O(|E| log |E| + |E| p)** time, where `p` is the largest
+/// number of journeys to a node none of which departs later and arrives
+/// earlier than another.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::temporal::{earliest_arrival, fastest};
+/// use petgraph::prelude::*;
+///
+/// // Edges weighted by (departure, arrival)
+/// let graph = DiGraph::<(), (u32, u32)>::from_edges(&[
+///     (0, 1, (0, 1)),
+///     (1, 2, (8, 9)),
+///     (0, 1, (6, 7)),
+/// ]);
+/// let n = NodeIndex::new;
+///
+/// // Arriving at 9 either way, it is faster to leave at 6
+/// assert_eq!(earliest_arrival(&graph, n(0), 0, |e| *e.weight())[&n(2)], 9);
+/// assert_eq!(fastest(&graph, n(0), 0, |e| *e.weight())[&n(2)], 3);
+/// ```
+pub fn fastest<G, F, T>(
+    graph: G,
+    source: G::NodeId,
+    start_time: T,
+    edge_time: F,
+) -> HashMap<G::NodeId, T>
+where
+    G: IntoEdgeReferences + GraphProp,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> (T, T),
+    T: Measure + Copy + Sub<Output = T>,
+{
+    // The journeys to each node, as (departure from source, arrival), by
+    // increasing departure and arrival
+    let mut journeys: HashMap<G::NodeId, Vec<(T, T)>> = HashMap::new();
+    let mut duration = HashMap::new();
+    duration.insert(source, T::default());
+    for (u, v, departure, arrive) in contacts(graph, edge_time) {
+        let start = if u == source {
+            if departure < start_time {
+                continue;
+            }
+            departure
+        } else {
+            // The latest departure among the journeys arriving in time
+            let list = match journeys.get(&u) {
+                Some(list) => list,
+                None => continue,
+            };
+            let i = partition_point(list, |&(_, a)| a <= departure);
+            if i == 0 {
+                continue;
+            }
+            list[i - 1].0
+        };
+        if v == source {
+            continue;
+        }
+        let list = journeys.entry(v).or_insert_with(Vec::new);
+        // Skip journeys that depart earlier and arrive later than another
+        if list.iter().any(|&(s, a)| s >= start && a <= arrive) {
+            continue;
+        }
+        list.retain(|&(s, a)| !(s <= start && a >= arrive));
+        let i = partition_point(list, |&(s, _)| s < start);
+        list.insert(i, (start, arrive));
+        let d = arrive - start;
+        if duration.get(&v).map_or(true, |&best| d < best) {
+            duration.insert(v, d);
+        }
+    }
+    duration
+}
+
+/// \[Generic\] Compute the minimum total travel time of a journey from
+/// `source` to every reachable node, leaving `source` at `start_time` or later.
+///
+/// The travel time of a journey is the sum of the durations of its edges,
+/// arrival minus departure, excluding waiting times. The edges of undirected
+/// graphs can be traversed both ways. See the [module documentation](self) for
+/// the temporal graph model.
+///
+/// Returns a `HashMap` from each reachable node to its minimum travel time,
+/// the default value (zero) for `source`.
+///
+/// Computes in **O(|E| log |E| + |E| p)** time, where `p` is the largest
+/// number of journeys to a node none of which arrives earlier and travels
+/// less than another.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::temporal::shortest;
+/// use petgraph::prelude::*;
+///
+/// // Edges weighted by (departure, arrival)
+/// let graph = DiGraph::<(), (u32, u32)>::from_edges(&[
+///     (0, 1, (0, 5)),
+///     (0, 1, (10, 11)),
+///     (1, 2, (12, 13)),
+/// ]);
+///
+/// // Wait for the quick edge
+/// let travel = shortest(&graph, NodeIndex::new(0), 0, |e| *e.weight());
+/// assert_eq!(travel[&NodeIndex::new(2)], 2);
+/// ```
+pub fn shortest<G, F, T>(
+    graph: G,
+    source: G::NodeId,
+    start_time: T,
+    edge_time: F,
+) -> HashMap<G::NodeId, T>
+where
+    G: IntoEdgeReferences + GraphProp,
+    G::NodeId: Eq + Hash,
+    F: FnMut(G::EdgeRef) -> (T, T),
+    T: Measure + Copy + Sub<Output = T>,
+{
+    // The journeys to each node, as (arrival, travel time), by increasing
+    // arrival and decreasing travel time
+    let mut journeys: HashMap<G::NodeId, Vec<(T, T)>> = HashMap::new();
+    journeys.insert(source, vec![(start_time, T::default())]);
+    let mut travel = HashMap::new();
+    travel.insert(source, T::default());
+    for (u, v, departure, arrive) in contacts(graph, edge_time) {
+        // The least travel time among the journeys arriving in time
+        let list = match journeys.get(&u) {
+            Some(list) => list,
+            None => continue,
+        };
+        let i = partition_point(list, |&(a, _)| a <= departure);
+        if i == 0 {
+            continue;
+        }
+        let d = list[i - 1].1 + (arrive - departure);
+
+        let list = journeys.entry(v).or_insert_with(Vec::new);
+        // Skip journeys that arrive later and travel more than another
+        let i = partition_point(list, |&(a, _)| a <= arrive);
+        if i > 0 && list[i - 1].1 <= d {
+            continue;
+        }
+        list.retain(|&(a, t)| !(a >= arrive && t >= d));
+        let i = partition_point(list, |&(a, _)| a < arrive);
+        list.insert(i, (arrive, d));
+        if travel.get(&v).map_or(true, |&best| d < best) {
+            travel.insert(v, d);
+        }
+    }
+    travel
+}
+
+/// The number of leading elements of `list` satisfying `pred`, assuming they
+/// are followed by elements that do not.
+fn partition_point<P>(list: &[P], mut pred: impl FnMut(&P) -> bool) -> usize {
+    list.binary_search_by(|x| {
+        if pred(x) {
+            Ordering::Less
+        } else {
+            Ordering::Greater
+        }
+    })
+    .unwrap_or_else(|i| i)
+}
+
+/// The contacts of the graph, as (source, target, departure, arrival), by
+/// increasing departure then arrival, in both directions for undirected
+/// graphs.
+fn contacts<G, F, T>(graph: G, mut edge_time: F) -> Vec<(G::NodeId, G::NodeId, T, T)>
+where
+    G: IntoEdgeReferences + GraphProp,
+    F: FnMut(G::EdgeRef) -> (T, T),
+    T: Measure + Copy,
+{
+    let mut contacts = Vec::new();
+    for edge in graph.edge_references() {
+        let (departure, arrival) = edge_time(edge);
+        contacts.push((edge.source(), edge.target(), departure, arrival));
+        if !graph.is_directed() {
+            contacts.push((edge.target(), edge.source(), departure, arrival));
+        }
+    }
+    contacts.sort_by(|x, y| {
+        x.2.partial_cmp(&y.2)
+            .unwrap_or(Ordering::Equal)
+            .then(x.3.partial_cmp(&y.3).unwrap_or(Ordering::Equal))
+    });
+    contacts
+}
@@ -9,13 +9,17 @@
 //! The transitive reduction is well-defined for acyclic graphs only.
 
 use crate::adj::{List, UnweightedList};
+use crate::algo::{tarjan_scc, toposort, Cycle};
 use crate::graph::IndexType;
 use crate::visit::{
-    GraphBase, IntoNeighbors, IntoNeighborsDirected, NodeCompactIndexable, NodeCount,
+    EdgeRef, GraphBase, IntoEdges, IntoNeighbors, IntoNeighborsDirected, IntoNodeIdentifiers,
+    NodeCompactIndexable, NodeCount, NodeIndexable, Visitable,
 };
 use crate::Direction;
 use fixedbitset::FixedBitSet;
 
+use std::fmt;
+
 /// Creates a representation of the same graph respecting topological order for use in `tred::dag_transitive_reduction_closure`.
 ///
 /// `toposort` must be a topological order on the node indices of `g` (for example obtained
@@ -139,6 +143,225 @@ pub fn dag_transitive_reduction_closure<E, Ix: IndexType>(
     (tred, tclos)
 }
 
+/// Computes the transitive reduction of a directed acyclic graph, as a
+/// partition of its edges.
+///
+/// Returns the pair `(kept, removed)` of edge ids: the edges in `kept` form
+/// the transitive reduction of `g`, i.e. the minimal set of edges with the
+/// same reachability as `g`, while `removed` lists the redundant edges. An
+/// edge `a -> b` is redundant if `b` can be reached from `a` through a longer
+/// path, or if it is parallel to a kept edge. Both lists are in no particular
+/// order.
+///
+/// Returns a [`Cycle`] error if the graph is not acyclic.
+///
+/// Unlike [`dag_transitive_reduction_closure`], this works directly on the
+/// edges of `g` and does not require a topologically sorted adjacency list.
+///
+/// ```
+/// use petgraph::prelude::*;
+/// use petgraph::algo::tred::transitive_reduction;
+///
+/// let mut g = DiGraph::<(), ()>::new();
+/// let (a, b, c) = (g.add_node(()), g.add_node(()), g.add_node(()));
+/// let ab = g.add_edge(a, b, ());
+/// let bc = g.add_edge(b, c, ());
+/// let ac = g.add_edge(a, c, ());
+///
+/// let (mut kept, removed) = transitive_reduction(&g).unwrap();
+/// kept.sort();
+/// assert_eq!(kept, vec![ab, bc]);
+/// assert_eq!(removed, vec![ac]);
+/// ```
+///
+/// Runtime: **O(|V| + |E| + |V| · |E| / 64)**, using one bit set of
+/// reachable nodes per node.
+///
+/// Space complexity: **O(|V|² / 64)**.
+#[allow(clippy::type_complexity)]
+pub fn transitive_reduction<G>(g: G) -> Result<(Vec<G::EdgeId>, Vec<G::EdgeId>), Cycle<G::NodeId>>
+where
+    G: IntoEdges + IntoNeighborsDirected + IntoNodeIdentifiers + NodeIndexable + Visitable,
+{
+    let toposort = toposort(g, None)?;
+    Ok(partition_redundant_edges(g, &toposort))
+}
+
+/// Partition the edges of a DAG into the edges of its transitive reduction
+/// and the redundant edges, given a topological order of its nodes.
+pub(crate) fn partition_redundant_edges<G>(
+    g: G,
+    toposort: &[G::NodeId],
+) -> (Vec<G::EdgeId>, Vec<G::EdgeId>)
+where
+    G: IntoEdges + NodeIndexable,
+{
+    let mut kept = Vec::new();
+    let mut removed = Vec::new();
+    // reachable[i]: the nodes reachable from node i through at least one edge
+    let mut reachable = vec![FixedBitSet::new(); g.node_bound()];
+    let mut successors = FixedBitSet::with_capacity(g.node_bound());
+    // the algorithm relies on the successors being processed first
+    for &a in toposort.iter().rev() {
+        // the nodes reachable through paths of length at least two
+        let mut indirect = FixedBitSet::with_capacity(g.node_bound());
+        for b in g.neighbors(a) {
+            indirect.union_with(&reachable[g.to_index(b)]);
+        }
+        for edge in g.edges(a) {
+            let b = g.to_index(edge.target());
+            if indirect[b] || successors[b] {
+                removed.push(edge.id());
+            } else {
+                successors.insert(b);
+                kept.push(edge.id());
+            }
+        }
+        indirect.union_with(&successors);
+        successors.clear();
+        reachable[g.to_index(a)] = indirect;
+    }
+    (kept, removed)
+}
+
+/// The transitive closure of a directed graph, as one bit set of reachable
+/// nodes per node.
+///
+/// A node `b` is reachable from `a` if there is a path of at least one edge
+/// from `a` to `b`. In particular, a node is reachable from itself only if it
+/// lies on a cycle.
+///
+/// Created with [`transitive_closure`].
+#[derive(Clone)]
+pub struct ReachabilityMatrix<G: GraphBase> {
+    graph: G,
+    /// The strongly connected component of each node, indexed by node index.
+    component: Vec<usize>,
+    /// The nodes reachable from each strongly connected component.
+    reachable: Vec<FixedBitSet>,
+}
+
+impl<G> ReachabilityMatrix<G>
+where
+    G: NodeIndexable,
+{
+    /// Return `true` if there is a path of at least one edge from `a` to `b`.
+    ///
+    /// Computes in **O(1)** time.
+    pub fn is_reachable(&self, a: G::NodeId, b: G::NodeId) -> bool {
+        self.reachable_set(a).map_or(false, |reachable| {
+            reachable.contains(self.graph.to_index(b))
+        })
+    }
+
+    /// Return an iterator over the nodes reachable from `a`, in order of node
+    /// index.
+    pub fn reachable_from(&self, a: G::NodeId) -> impl Iterator<Item = G::NodeId> + '_ {
+        self.reachable_set(a)
+            .into_iter()
+            .flat_map(|reachable| reachable.ones())
+            .map(move |i| self.graph.from_index(i))
+    }
+
+    /// Return the number of nodes reachable from `a`.
+    pub fn reachable_count(&self, a: G::NodeId) -> usize {
+        self.reachable_set(a)
+            .map_or(0, |reachable| reachable.count_ones(..))
+    }
+
+    fn reachable_set(&self, a: G::NodeId) -> Option<&FixedBitSet> {
+        let component = *self.component.get(self.graph.to_index(a))?;
+        self.reachable.get(component)
+    }
+}
+
+impl<G> fmt::Debug for ReachabilityMatrix<G>
+where
+    G: NodeIndexable,
+    G::NodeId: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut map = f.debug_map();
+        for (i, &component) in self.component.iter().enumerate() {
+            if let Some(reachable) = self.reachable.get(component) {
+                let targets: Vec<_> = reachable.ones().map(|j| self.graph.from_index(j)).collect();
+                map.entry(&self.graph.from_index(i), &targets);
+            }
+        }
+        map.finish()
+    }
+}
+
+/// \[Generic\] Compute the transitive closure of a directed graph.
+///
+/// Returns a [`ReachabilityMatrix`] answering whether a node can be reached
+/// from another in constant time.
+///
+/// The strongly connected components of the graph are processed in reverse
+/// topological order, so that the set of nodes reachable from a component is
+/// the union of the sets of its successors, computed a machine word at a time.
+/// The graph may contain cycles: all the nodes of a strongly connected
+/// component share the same set.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::tred::transitive_closure;
+/// use petgraph::prelude::*;
+///
+/// let graph = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 1), (3, 0)]);
+/// let n = NodeIndex::new;
+/// let closure = transitive_closure(&graph);
+///
+/// assert!(closure.is_reachable(n(3), n(2)));
+/// assert!(!closure.is_reachable(n(2), n(0)));
+/// // Nodes on a cycle reach themselves
+/// assert!(closure.is_reachable(n(1), n(1)));
+/// assert!(!closure.is_reachable(n(0), n(0)));
+/// assert_eq!(closure.reachable_from(n(0)).collect::<Vec<_>>(), vec![n(1), n(2)]);
+/// ```
+///
+/// Runtime: **O(|V| + |E| + |E| · |V| / 64)**.
+///
+/// Space complexity: **O(|V|² / 64)**.
+pub fn transitive_closure<G>(g: G) -> ReachabilityMatrix<G>
+where
+    G: IntoNeighbors + IntoNodeIdentifiers + NodeIndexable,
+{
+    const VACANT: usize = std::usize::MAX;
+    let mut component = vec![VACANT; g.node_bound()];
+    let mut reachable = Vec::new();
+    // the sccs are returned in reverse topological order
+    for (c, scc) in tarjan_scc(g).into_iter().enumerate() {
+        for &n in &scc {
+            component[g.to_index(n)] = c;
+        }
+        let mut set = FixedBitSet::with_capacity(g.node_bound());
+        let mut cyclic = scc.len() > 1;
+        for &n in &scc {
+            for succ in g.neighbors(n) {
+                let i = g.to_index(succ);
+                if component[i] == c {
+                    cyclic = true;
+                } else {
+                    set.union_with(&reachable[component[i]]);
+                    set.insert(i);
+                }
+            }
+        }
+        if cyclic {
+            for &n in &scc {
+                set.insert(g.to_index(n));
+            }
+        }
+        reachable.push(set);
+    }
+    ReachabilityMatrix {
+        graph: g,
+        component,
+        reachable,
+    }
+}
+
 #[cfg(test)]
 #[test]
 fn test_easy_tred() {
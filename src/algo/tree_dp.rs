@@ -0,0 +1,232 @@
+use crate::algo::dfs_tree::dfs_tree;
+use crate::algo::Measure;
+use crate::visit::{GraphBase, IntoNeighbors, NodeIndexable, Visitable};
+
+/// The result of a dynamic programming fold over a rooted tree, holding the
+/// value computed for each node of the tree.
+///
+/// Created with [`tree_fold`].
+#[derive(Debug, Clone)]
+pub struct TreeFold<G: GraphBase, T> {
+    graph: G,
+    root: G::NodeId,
+    /// The parent of each node, indexed by node index.
+    parent: Vec<Option<G::NodeId>>,
+    /// The value of each node, or `None` if it is not in the tree.
+    values: Vec<Option<T>>,
+    /// The nodes of the tree, parents before children.
+    preorder: Vec<G::NodeId>,
+}
+
+impl<G, T> TreeFold<G, T>
+where
+    G: NodeIndexable,
+{
+    /// Get the root of the tree.
+    pub fn root(&self) -> G::NodeId {
+        self.root
+    }
+
+    /// Get the value of the root, i.e. the result of the fold over the whole
+    /// tree.
+    pub fn root_value(&self) -> &T {
+        self.value(self.root).unwrap()
+    }
+
+    /// Get the value of a node, computed from its subtree.
+    ///
+    /// Returns `None` for nodes that are not in the tree.
+    pub fn value(&self, node: G::NodeId) -> Option<&T> {
+        self.values
+            .get(self.graph.to_index(node))
+            .and_then(|v| v.as_ref())
+    }
+
+    /// Get the parent of a node in the tree.
+    ///
+    /// Returns `None` for the root, and for nodes that are not in the tree.
+    pub fn parent(&self, node: G::NodeId) -> Option<G::NodeId> {
+        self.parent.get(self.graph.to_index(node)).and_then(|&p| p)
+    }
+
+    /// Get the nodes of the tree in depth-first preorder, parents before
+    /// children.
+    pub fn preorder(&self) -> &[G::NodeId] {
+        &self.preorder
+    }
+}
+
+/// \[Generic\] Compute a value for every node of a rooted tree, from the
+/// values of its children: a dynamic programming fold, in post-order.
+///
+/// The tree consists of the nodes reachable from `root`. If the graph is not a
+/// tree, the depth-first search spanning tree rooted at `root` is used: the
+/// parent of a node is the node it was first discovered from. For undirected
+/// trees, the edges back to the parent are ignored.
+///
+/// `combine` is called once for each node, after its children, with the node
+/// and its children paired with their values. Leaves get no children.
+///
+/// Computes in **O(|V| + |E|)** time, plus the time spent in `combine`.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::tree_fold;
+/// use petgraph::prelude::*;
+///
+/// //      0
+/// //     / \
+/// //    1   2
+/// //   / \
+/// //  3   4
+/// let tree = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (1, 4)]);
+/// let n = NodeIndex::new;
+///
+/// // The height of each subtree
+/// let heights = tree_fold(&tree, n(0), |_, children: &[(NodeIndex, &usize)]| {
+///     children.iter().map(|&(_, &h)| h + 1).max().unwrap_or(0)
+/// });
+/// assert_eq!(*heights.root_value(), 2);
+/// assert_eq!(heights.value(n(1)), Some(&1));
+/// assert_eq!(heights.value(n(4)), Some(&0));
+/// ```
+pub fn tree_fold<G, T, F>(graph: G, root: G::NodeId, mut combine: F) -> TreeFold<G, T>
+where
+    G: IntoNeighbors + NodeIndexable + Visitable,
+    F: FnMut(G::NodeId, &[(G::NodeId, &T)]) -> T,
+{
+    let tree = dfs_tree(graph, root);
+
+    // Fold, children before parents
+    let mut values: Vec<Option<T>> = (0..graph.node_bound()).map(|_| None).collect();
+    for &node in tree.preorder.iter().rev() {
+        let idx = graph.to_index(node);
+        let child_values: Vec<_> = tree.children[idx]
+            .iter()
+            .map(|&c| (c, values[graph.to_index(c)].as_ref().unwrap()))
+            .collect();
+        let value = combine(node, &child_values);
+        values[idx] = Some(value);
+    }
+
+    TreeFold {
+        graph,
+        root,
+        parent: tree.parent,
+        values,
+        preorder: tree.preorder,
+    }
+}
+
+/// \[Generic\] Compute a maximum weight independent set of a tree, i.e. a set
+/// of nodes, no two of which are adjacent, of maximum total weight.
+///
+/// The tree consists of the nodes reachable from `root`, as in [`tree_fold`].
+/// Node weights are given by `node_weight`; nodes of negative weight are never
+/// chosen.
+///
+/// Returns the total weight of the set and its nodes, in preorder.
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::tree_max_weight_independent_set;
+/// use petgraph::prelude::*;
+///
+/// // A path 0 - 1 - 2 - 3 with weights 3, 5, 1, 4
+/// let tree = UnGraph::<u32, ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+/// let weights = [3, 5, 1, 4];
+/// let n = NodeIndex::new;
+///
+/// let (weight, set) = tree_max_weight_independent_set(&tree, n(0), |v| weights[v.index()]);
+/// assert_eq!(weight, 9);
+/// assert_eq!(set, vec![n(1), n(3)]);
+/// ```
+pub fn tree_max_weight_independent_set<G, F, K>(
+    graph: G,
+    root: G::NodeId,
+    mut node_weight: F,
+) -> (K, Vec<G::NodeId>)
+where
+    G: IntoNeighbors + NodeIndexable + Visitable,
+    F: FnMut(G::NodeId) -> K,
+    K: Measure + Copy,
+{
+    // The best weights of the subtree with and without the node
+    let fold = tree_fold(graph, root, |node, children: &[(G::NodeId, &(K, K))]| {
+        let mut with = node_weight(node);
+        let mut without = K::default();
+        for &(_, &(child_with, child_without)) in children {
+            with = with + child_without;
+            without = without
+                + if child_with > child_without {
+                    child_with
+                } else {
+                    child_without
+                };
+        }
+        (with, without)
+    });
+
+    let mut chosen = vec![false; graph.node_bound()];
+    let mut set = Vec::new();
+    for &node in fold.preorder() {
+        let parent_chosen = fold
+            .parent(node)
+            .map_or(false, |p| chosen[graph.to_index(p)]);
+        let &(with, without) = fold.value(node).unwrap();
+        if !parent_chosen && with > without {
+            chosen[graph.to_index(node)] = true;
+            set.push(node);
+        }
+    }
+    let &(with, without) = fold.root_value();
+    let weight = if with > without { with } else { without };
+    (weight, set)
+}
+
+/// \[Generic\] Find the centroids of a tree: the nodes whose removal leaves
+/// components of at most half the number of nodes of the tree.
+///
+/// The tree consists of the nodes reachable from `root`, as in [`tree_fold`].
+/// Every tree has one centroid, or two adjacent centroids.
+///
+/// Computes in **O(|V| + |E|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::tree_centroid;
+/// use petgraph::prelude::*;
+///
+/// // A path 0 - 1 - 2 - 3 - 4, and a path 0 - 1 - 2 - 3
+/// let n = NodeIndex::new;
+/// let odd = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4)]);
+/// assert_eq!(tree_centroid(&odd, n(0)), (n(2), None));
+/// let even = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3)]);
+/// assert_eq!(tree_centroid(&even, n(0)), (n(1), Some(n(2))));
+/// ```
+pub fn tree_centroid<G>(graph: G, root: G::NodeId) -> (G::NodeId, Option<G::NodeId>)
+where
+    G: IntoNeighbors + NodeIndexable + Visitable,
+{
+    // The size of each subtree, and of its largest child subtree
+    let fold = tree_fold(
+        graph,
+        root,
+        |_, children: &[(G::NodeId, &(usize, usize))]| {
+            children
+                .iter()
+                .fold((1, 0), |(size, largest), &(_, &(child, _))| {
+                    (size + child, largest.max(child))
+                })
+        },
+    );
+    let n = fold.root_value().0;
+    let mut centroids = fold.preorder().iter().copied().filter(|&node| {
+        let &(size, largest) = fold.value(node).unwrap();
+        2 * largest.max(n - size) <= n
+    });
+    let first = centroids.next().unwrap();
+    (first, centroids.next())
+}
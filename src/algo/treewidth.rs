@@ -0,0 +1,155 @@
+use std::collections::HashSet;
+
+use crate::graph::{NodeIndex, UnGraph};
+use crate::visit::{EdgeRef, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
+
+/// \[Generic\] Compute a tree decomposition of a graph from a minimum degree
+/// elimination ordering, giving an upper bound on its
+/// [treewidth](https://en.wikipedia.org/wiki/Treewidth).
+///
+/// The graph is treated as undirected, and loops are ignored. Nodes are
+/// eliminated one by one, each time choosing a node of minimum degree: its
+/// neighbors are made a clique, and the node is removed. See
+/// [`treewidth_min_fill_in`] for the resulting decomposition.
+///
+/// Computes in **O(|V|² + |V| w²)** time, where `w` is the width of the
+/// decomposition.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::treewidth_min_degree;
+/// use petgraph::prelude::*;
+///
+/// // A cycle has treewidth 2
+/// let cycle = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+/// let (width, decomposition) = treewidth_min_degree(&cycle);
+/// assert_eq!(width, 2);
+/// assert_eq!(decomposition.node_count(), 5);
+/// ```
+pub fn treewidth_min_degree<G>(graph: G) -> (usize, UnGraph<Vec<G::NodeId>, ()>)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+{
+    tree_decomposition(graph, |adjacency, v| adjacency[v].len())
+}
+
+/// \[Generic\] Compute a tree decomposition of a graph from a minimum fill-in
+/// elimination ordering, giving an upper bound on its
+/// [treewidth](https://en.wikipedia.org/wiki/Treewidth).
+///
+/// The graph is treated as undirected, and loops are ignored. Nodes are
+/// eliminated one by one, each time choosing a node whose elimination adds the
+/// fewest edges: its neighbors are made a clique, and the node is removed.
+/// This is slower than [`treewidth_min_degree`], but often gives a smaller
+/// width.
+///
+/// Returns the width of the decomposition and the decomposition: a tree (a
+/// forest if the graph is empty) whose nodes are *bags* of nodes of the
+/// graph, such that the endpoints of every edge are together in a bag, and
+/// the bags containing any node form a subtree. The width is the size of the
+/// largest bag minus one, or `0` if the graph is empty. There is one bag per
+/// node of the graph, holding the node and its neighbors when it was
+/// eliminated.
+///
+/// Computes in **O(|V|² w²)** time, where `w` is the width of the
+/// decomposition.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::treewidth_min_fill_in;
+/// use petgraph::prelude::*;
+///
+/// // A 3x3 grid has treewidth 3
+/// let mut grid = UnGraph::<(), ()>::default();
+/// let nodes: Vec<_> = (0..9).map(|_| grid.add_node(())).collect();
+/// for i in 0..9 {
+///     if i % 3 < 2 {
+///         grid.add_edge(nodes[i], nodes[i + 1], ());
+///     }
+///     if i < 6 {
+///         grid.add_edge(nodes[i], nodes[i + 3], ());
+///     }
+/// }
+/// let (width, decomposition) = treewidth_min_fill_in(&grid);
+/// assert_eq!(width, 3);
+/// assert_eq!(decomposition.edge_count(), 8);
+/// ```
+pub fn treewidth_min_fill_in<G>(graph: G) -> (usize, UnGraph<Vec<G::NodeId>, ()>)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+{
+    tree_decomposition(graph, |adjacency, v| {
+        let neighbors: Vec<_> = adjacency[v].iter().collect();
+        let mut fill = 0;
+        for (i, a) in neighbors.iter().enumerate() {
+            fill += neighbors[i + 1..]
+                .iter()
+                .filter(|b| !adjacency[**a].contains(b))
+                .count();
+        }
+        fill
+    })
+}
+
+/// Build a tree decomposition from the elimination ordering choosing, at each
+/// step, a remaining node minimizing `cost`.
+fn tree_decomposition<G, C>(graph: G, mut cost: C) -> (usize, UnGraph<Vec<G::NodeId>, ()>)
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable,
+    C: FnMut(&[HashSet<usize>], usize) -> usize,
+{
+    let nodes: Vec<_> = graph.node_identifiers().collect();
+    let n = nodes.len();
+    let mut compact = vec![0; graph.node_bound()];
+    for (i, &node) in nodes.iter().enumerate() {
+        compact[graph.to_index(node)] = i;
+    }
+    let mut adjacency = vec![HashSet::new(); n];
+    for edge in graph.edge_references() {
+        let a = compact[graph.to_index(edge.source())];
+        let b = compact[graph.to_index(edge.target())];
+        if a != b {
+            adjacency[a].insert(b);
+            adjacency[b].insert(a);
+        }
+    }
+
+    // Eliminate the nodes, recording the bag of each node
+    let mut eliminated = vec![false; n];
+    let mut position = vec![0; n];
+    let mut bags: Vec<(usize, Vec<usize>)> = Vec::with_capacity(n);
+    for step in 0..n {
+        let v = (0..n)
+            .filter(|&v| !eliminated[v])
+            .min_by_key(|&v| cost(&adjacency, v))
+            .unwrap();
+        let neighbors: Vec<usize> = adjacency[v].iter().copied().collect();
+        for &a in &neighbors {
+            adjacency[a].remove(&v);
+            for &b in &neighbors {
+                if a != b {
+                    adjacency[a].insert(b);
+                }
+            }
+        }
+        adjacency[v].clear();
+        eliminated[v] = true;
+        position[v] = step;
+        bags.push((v, neighbors));
+    }
+
+    // Connect the bag of each node to the bag of its neighbor eliminated
+    // first, or to the last bag if it has no neighbors
+    let width = bags.iter().map(|(_, bag)| bag.len()).max().unwrap_or(0);
+    let mut decomposition = UnGraph::with_capacity(n, n.saturating_sub(1));
+    for (v, bag) in &bags {
+        let mut bag_nodes = vec![nodes[*v]];
+        bag_nodes.extend(bag.iter().map(|&u| nodes[u]));
+        decomposition.add_node(bag_nodes);
+    }
+    for (step, (_, bag)) in bags.iter().enumerate().take(n.saturating_sub(1)) {
+        let next = bag.iter().map(|&u| position[u]).min().unwrap_or(n - 1);
+        decomposition.add_edge(NodeIndex::new(step), NodeIndex::new(next), ());
+    }
+    (width, decomposition)
+}
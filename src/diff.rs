@@ -0,0 +1,503 @@
+//! Structural differences between graphs, as patches that can be replayed on
+//! another copy of a graph.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+
+use crate::data::{Build, DataMapMut};
+use crate::graph::{Graph, IndexType};
+use crate::visit::{
+    Data, EdgeRef, GraphBase, GraphProp, IntoEdges, IntoNodeIdentifiers, IntoNodeReferences,
+    NodeIndexable, NodeRef,
+};
+use crate::EdgeType;
+
+#[cfg(feature = "stable_graph")]
+use crate::stable_graph::StableGraph;
+
+/// A change of a graph, in a [`Patch`].
+///
+/// Nodes are identified by a key of type `K`, e.g. their id, or a name stored
+/// in their weight; edges by the keys of their endpoints.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-1", derive(Serialize, Deserialize))]
+pub enum Change<K, N, E> {
+    /// Remove an edge from `source` to `target` with weight `weight`.
+    RemoveEdge { source: K, target: K, weight: E },
+    /// Remove node `node`, and all its edges.
+    RemoveNode { node: K },
+    /// Add node `node` with weight `weight`.
+    AddNode { node: K, weight: N },
+    /// Set the weight of node `node`.
+    UpdateNode { node: K, weight: N },
+    /// Add an edge from `source` to `target` with weight `weight`.
+    AddEdge { source: K, target: K, weight: E },
+    /// Set the weight of the edge from `source` to `target`, the only edge
+    /// between them.
+    UpdateEdge { source: K, target: K, weight: E },
+}
+
+/// A sequence of changes turning a graph into another, computed by [`diff`]
+/// or [`diff_by_key`] and replayed by [`apply_patch`] or
+/// [`apply_patch_by_key`].
+///
+/// The changes are ordered so that they apply in sequence: first the removed
+/// edges, then the removed nodes, the added and updated nodes, and finally the
+/// added and updated edges. The edges of removed nodes are not listed.
+///
+/// With the `serde-1` feature, a patch can be serialized, e.g. to send it to
+/// another process instead of the whole graph.
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-1", derive(Serialize, Deserialize))]
+pub struct Patch<K, N, E> {
+    changes: Vec<Change<K, N, E>>,
+}
+
+impl<K, N, E> Patch<K, N, E> {
+    /// Create a patch from a sequence of changes.
+    pub fn from_changes(changes: Vec<Change<K, N, E>>) -> Self {
+        Patch { changes }
+    }
+
+    /// Return the changes of the patch, in order.
+    pub fn changes(&self) -> &[Change<K, N, E>] {
+        &self.changes
+    }
+
+    /// Return `true` if the patch has no changes, i.e. if the graphs are
+    /// equal.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Return the changes of the patch, consuming it.
+    pub fn into_changes(self) -> Vec<Change<K, N, E>> {
+        self.changes
+    }
+}
+
+/// An error applying a [`Patch`]: a change refers to a node or an edge that
+/// does not exist, or adds a node that already exists.
+///
+/// The changes before the failing one are applied.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PatchError<K> {
+    /// No node has this key.
+    MissingNode(K),
+    /// No edge between these nodes matches the change.
+    MissingEdge(K, K),
+    /// A node with this key already exists.
+    DuplicateNode(K),
+}
+
+impl<K: fmt::Debug> fmt::Display for PatchError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PatchError::MissingNode(k) => write!(f, "no node with key {:?}", k),
+            PatchError::MissingEdge(a, b) => {
+                write!(f, "no matching edge from {:?} to {:?}", a, b)
+            }
+            PatchError::DuplicateNode(k) => write!(f, "a node with key {:?} already exists", k),
+        }
+    }
+}
+
+impl<K: fmt::Debug> Error for PatchError<K> {}
+
+/// The nodes of a graph by key, and its edges grouped by the keys of their
+/// endpoints: in order for directed graphs, sorted for undirected graphs.
+#[allow(clippy::type_complexity)]
+fn keyed<G, K, F>(
+    graph: G,
+    node_key: &mut F,
+) -> (BTreeMap<K, G::NodeRef>, BTreeMap<(K, K), Vec<G::EdgeRef>>)
+where
+    G: IntoNodeReferences + IntoEdges + NodeIndexable + GraphProp,
+    K: Ord + Clone,
+    F: FnMut(G::NodeId, &G::NodeWeight) -> K,
+{
+    let mut nodes = BTreeMap::new();
+    let mut keys = vec![None; graph.node_bound()];
+    for node in graph.node_references() {
+        let key = node_key(node.id(), node.weight());
+        keys[graph.to_index(node.id())] = Some(key.clone());
+        assert!(
+            nodes.insert(key, node).is_none(),
+            "diff: two nodes have the same key"
+        );
+    }
+    let mut edges: BTreeMap<_, Vec<_>> = BTreeMap::new();
+    for node in graph.node_identifiers() {
+        let source = keys[graph.to_index(node)].as_ref().unwrap();
+        for edge in graph.edges(node) {
+            let target = keys[graph.to_index(edge.target())].as_ref().unwrap();
+            // Each undirected edge is listed from both endpoints
+            if !graph.is_directed() && source > target {
+                continue;
+            }
+            edges
+                .entry((source.clone(), target.clone()))
+                .or_default()
+                .push(edge);
+        }
+    }
+    (nodes, edges)
+}
+
+/// \[Generic\] Compute the [`Patch`] turning `old` into `new`, where nodes
+/// are matched by their id.
+///
+/// This is [`diff_by_key`] with the node ids as keys. Replaying the patch
+/// with [`apply_patch`] on a copy of `old` with the same node ids turns it into
+/// `new`: e.g. for a [`Graph`], whose node ids are compact, the nodes are
+/// removed from the highest id, and added in order of id.
+///
+/// # Example
+/// ```rust
+/// use petgraph::diff::{apply_patch, diff};
+/// use petgraph::prelude::*;
+///
+/// let old = Graph::<&str, u32>::from_edges(&[(0, 1, 1), (1, 2, 2)]);
+/// let mut new = old.clone();
+/// new.add_edge(NodeIndex::new(2), NodeIndex::new(0), 3);
+/// new.remove_edge(EdgeIndex::new(0));
+///
+/// let patch = diff(&old, &new);
+/// assert_eq!(patch.changes().len(), 2);
+///
+/// let mut copy = old.clone();
+/// apply_patch(&mut copy, &patch).unwrap();
+/// assert!(diff(&copy, &new).is_empty());
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn diff<G>(old: G, new: G) -> Patch<G::NodeId, G::NodeWeight, G::EdgeWeight>
+where
+    G: IntoNodeReferences + IntoEdges + NodeIndexable + GraphProp,
+    G::NodeId: Ord,
+    G::NodeWeight: Clone + PartialEq,
+    G::EdgeWeight: Clone + PartialEq,
+{
+    diff_by_key(old, new, |id, _| id)
+}
+
+/// \[Generic\] Compute the [`Patch`] turning `old` into `new`, where nodes
+/// are matched by the key `node_key(id, weight)`, and edges by the keys of
+/// their endpoints.
+///
+/// The keys must be unique in each graph. The nodes with a key of `old` only
+/// are removed, those with a key of `new` only are added, and the others
+/// are updated if their weight changed. A single edge between two nodes in
+/// both graphs is updated if its weight changed; otherwise, the parallel
+/// edges of `old` and `new` are matched by equal weights, and the others are
+/// removed or added.
+///
+/// Computes in **O((|V| + |E|) log |V|)** time, without parallel edges.
+///
+/// **Panics** if two nodes of a graph have the same key.
+///
+/// # Example
+/// ```rust
+/// use petgraph::diff::{apply_patch_by_key, diff_by_key, Change};
+/// use petgraph::prelude::*;
+///
+/// let mut old = UnGraph::<&str, u32>::default();
+/// let a = old.add_node("a");
+/// let b = old.add_node("b");
+/// let c = old.add_node("c");
+/// old.extend_with_edges(&[(a, b, 1), (b, c, 2)]);
+///
+/// // The same graph, built in another order, without `a`
+/// let mut new = UnGraph::<&str, u32>::default();
+/// let c = new.add_node("c");
+/// let b = new.add_node("b");
+/// new.add_edge(c, b, 5);
+///
+/// let by_name = |_, name: &&'static str| *name;
+/// let patch = diff_by_key(&old, &new, by_name);
+/// assert_eq!(
+///     patch.changes(),
+///     &[
+///         Change::RemoveNode { node: "a" },
+///         Change::UpdateEdge { source: "b", target: "c", weight: 5 },
+///     ]
+/// );
+///
+/// apply_patch_by_key(&mut old, &patch, by_name).unwrap();
+/// assert_eq!(old.node_count(), 2);
+/// assert!(diff_by_key(&old, &new, by_name).is_empty());
+/// ```
+pub fn diff_by_key<G, K, F>(
+    old: G,
+    new: G,
+    mut node_key: F,
+) -> Patch<K, G::NodeWeight, G::EdgeWeight>
+where
+    G: IntoNodeReferences + IntoEdges + NodeIndexable + GraphProp,
+    K: Ord + Clone,
+    F: FnMut(G::NodeId, &G::NodeWeight) -> K,
+    G::NodeWeight: Clone + PartialEq,
+    G::EdgeWeight: Clone + PartialEq,
+{
+    let (old_nodes, old_edges) = keyed(old, &mut node_key);
+    let (new_nodes, new_edges) = keyed(new, &mut node_key);
+
+    let mut removed_edges = Vec::new();
+    let mut removed_nodes = Vec::new();
+    let mut nodes = Vec::new();
+    let mut edges = Vec::new();
+
+    for (key, old_node) in &old_nodes {
+        match new_nodes.get(key) {
+            None => removed_nodes.push(Change::RemoveNode { node: key.clone() }),
+            Some(new_node) if new_node.weight() != old_node.weight() => {
+                nodes.push(Change::UpdateNode {
+                    node: key.clone(),
+                    weight: new_node.weight().clone(),
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    // Remove the nodes from the highest key, so that the nodes of compact
+    // graphs are not moved when matched by id
+    removed_nodes.reverse();
+    for (key, new_node) in &new_nodes {
+        if !old_nodes.contains_key(key) {
+            nodes.push(Change::AddNode {
+                node: key.clone(),
+                weight: new_node.weight().clone(),
+            });
+        }
+    }
+
+    let no_edges = Vec::new();
+    for (pair, old_pair_edges) in &old_edges {
+        let (source, target) = pair;
+        if !new_nodes.contains_key(source) || !new_nodes.contains_key(target) {
+            continue;
+        }
+        let new_pair_edges = new_edges.get(pair).unwrap_or(&no_edges);
+        if let ([old_edge], [new_edge]) = (&old_pair_edges[..], &new_pair_edges[..]) {
+            if old_edge.weight() != new_edge.weight() {
+                edges.push(Change::UpdateEdge {
+                    source: source.clone(),
+                    target: target.clone(),
+                    weight: new_edge.weight().clone(),
+                });
+            }
+            continue;
+        }
+        // Match the parallel edges by weight
+        let mut unmatched: Vec<_> = new_pair_edges.iter().map(Some).collect();
+        for old_edge in old_pair_edges {
+            let matched = unmatched
+                .iter_mut()
+                .find(|e| e.map_or(false, |e| e.weight() == old_edge.weight()));
+            match matched {
+                Some(e) => *e = None,
+                None => removed_edges.push(Change::RemoveEdge {
+                    source: source.clone(),
+                    target: target.clone(),
+                    weight: old_edge.weight().clone(),
+                }),
+            }
+        }
+        edges.extend(unmatched.into_iter().flatten().map(|e| Change::AddEdge {
+            source: source.clone(),
+            target: target.clone(),
+            weight: e.weight().clone(),
+        }));
+    }
+    for (pair, new_pair_edges) in &new_edges {
+        if !old_edges.contains_key(pair) {
+            edges.extend(new_pair_edges.iter().map(|e| Change::AddEdge {
+                source: pair.0.clone(),
+                target: pair.1.clone(),
+                weight: e.weight().clone(),
+            }));
+        }
+    }
+
+    removed_edges.extend(removed_nodes);
+    removed_edges.extend(nodes);
+    removed_edges.extend(edges);
+    Patch {
+        changes: removed_edges,
+    }
+}
+
+/// A graph that a [`Patch`] can be applied to.
+pub trait PatchTarget: Build + DataMapMut + NodeIndexable {
+    /// Remove node `a` and its edges. If another node is moved into the place
+    /// of `a`, like in a [`Graph`], return its previous id.
+    fn patch_remove_node(&mut self, a: Self::NodeId) -> Option<Self::NodeId>;
+
+    /// Remove edge `e`.
+    fn patch_remove_edge(&mut self, e: Self::EdgeId);
+}
+
+impl<N, E, Ty, Ix> PatchTarget for Graph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    fn patch_remove_node(&mut self, a: Self::NodeId) -> Option<Self::NodeId> {
+        self.remove_node(a);
+        if a.index() < self.node_count() {
+            Some(self.from_index(self.node_count()))
+        } else {
+            None
+        }
+    }
+
+    fn patch_remove_edge(&mut self, e: Self::EdgeId) {
+        self.remove_edge(e);
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+impl<N, E, Ty, Ix> PatchTarget for StableGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    fn patch_remove_node(&mut self, a: Self::NodeId) -> Option<Self::NodeId> {
+        self.remove_node(a);
+        None
+    }
+
+    fn patch_remove_edge(&mut self, e: Self::EdgeId) {
+        self.remove_edge(e);
+    }
+}
+
+/// \[Generic\] Apply `patch` to `graph`, where nodes are matched by their
+/// id, as computed by [`diff`].
+///
+/// See [`apply_patch_by_key`].
+pub fn apply_patch<G>(
+    graph: &mut G,
+    patch: &Patch<G::NodeId, G::NodeWeight, G::EdgeWeight>,
+) -> Result<(), PatchError<G::NodeId>>
+where
+    G: PatchTarget,
+    for<'a> &'a G: IntoNodeIdentifiers
+        + IntoEdges
+        + GraphBase<NodeId = G::NodeId, EdgeId = G::EdgeId>
+        + Data<EdgeWeight = G::EdgeWeight>,
+    G::NodeId: Ord,
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Clone + PartialEq,
+{
+    apply_patch_by_key(graph, patch, |id, _| id)
+}
+
+/// \[Generic\] Apply `patch` to `graph`, where nodes are matched by the key
+/// `node_key(id, weight)`, as computed by [`diff_by_key`].
+///
+/// The changes are applied in order; a removed edge is any edge between its
+/// endpoints with an equal weight. Return an error at the first change
+/// referring to a missing node or edge, or adding a node with an existing
+/// key: the changes before it are applied.
+///
+/// **Panics** if two nodes of `graph` have the same key.
+pub fn apply_patch_by_key<G, K, F>(
+    graph: &mut G,
+    patch: &Patch<K, G::NodeWeight, G::EdgeWeight>,
+    mut node_key: F,
+) -> Result<(), PatchError<K>>
+where
+    G: PatchTarget,
+    for<'a> &'a G: IntoNodeIdentifiers
+        + IntoEdges
+        + GraphBase<NodeId = G::NodeId, EdgeId = G::EdgeId>
+        + Data<EdgeWeight = G::EdgeWeight>,
+    K: Ord + Clone,
+    F: FnMut(G::NodeId, &G::NodeWeight) -> K,
+    G::NodeWeight: Clone,
+    G::EdgeWeight: Clone + PartialEq,
+{
+    // The nodes by key, and the keys by node index
+    let mut nodes = BTreeMap::new();
+    let mut keys = vec![None; graph.node_bound()];
+    for id in (&*graph).node_identifiers() {
+        let key = node_key(id, graph.node_weight(id).unwrap());
+        keys[graph.to_index(id)] = Some(key.clone());
+        assert!(
+            nodes.insert(key, id).is_none(),
+            "apply_patch: two nodes have the same key"
+        );
+    }
+    let node = |nodes: &BTreeMap<K, G::NodeId>, key: &K| {
+        nodes
+            .get(key)
+            .copied()
+            .ok_or_else(|| PatchError::MissingNode(key.clone()))
+    };
+
+    for change in &patch.changes {
+        match change {
+            Change::RemoveEdge {
+                source,
+                target,
+                weight,
+            } => {
+                let (a, b) = (node(&nodes, source)?, node(&nodes, target)?);
+                let edge = (&*graph)
+                    .edges(a)
+                    .find(|e| e.target() == b && e.weight() == weight)
+                    .map(|e| e.id());
+                let edge =
+                    edge.ok_or_else(|| PatchError::MissingEdge(source.clone(), target.clone()))?;
+                graph.patch_remove_edge(edge);
+            }
+            Change::RemoveNode { node: key } => {
+                let a = node(&nodes, key)?;
+                nodes.remove(key);
+                keys[graph.to_index(a)] = None;
+                if let Some(moved) = graph.patch_remove_node(a) {
+                    let moved_key = keys[graph.to_index(moved)].take().unwrap();
+                    nodes.insert(moved_key.clone(), a);
+                    keys[graph.to_index(a)] = Some(moved_key);
+                }
+            }
+            Change::AddNode { node: key, weight } => {
+                if nodes.contains_key(key) {
+                    return Err(PatchError::DuplicateNode(key.clone()));
+                }
+                let a = graph.add_node(weight.clone());
+                let i = graph.to_index(a);
+                if keys.len() <= i {
+                    keys.resize(i + 1, None);
+                }
+                keys[i] = Some(key.clone());
+                nodes.insert(key.clone(), a);
+            }
+            Change::UpdateNode { node: key, weight } => {
+                let a = node(&nodes, key)?;
+                *graph.node_weight_mut(a).unwrap() = weight.clone();
+            }
+            Change::AddEdge {
+                source,
+                target,
+                weight,
+            } => {
+                let (a, b) = (node(&nodes, source)?, node(&nodes, target)?);
+                graph.add_edge(a, b, weight.clone());
+            }
+            Change::UpdateEdge {
+                source,
+                target,
+                weight,
+            } => {
+                let (a, b) = (node(&nodes, source)?, node(&nodes, target)?);
+                let edge = (&*graph).edges(a).find(|e| e.target() == b).map(|e| e.id());
+                let edge =
+                    edge.ok_or_else(|| PatchError::MissingEdge(source.clone(), target.clone()))?;
+                *graph.edge_weight_mut(edge).unwrap() = weight.clone();
+            }
+        }
+    }
+    Ok(())
+}
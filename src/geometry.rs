@@ -0,0 +1,399 @@
+//! Geometric graphs, whose nodes carry coordinates: construction of
+//! *k*-nearest-neighbor and fixed-radius graphs, and Euclidean edge costs and
+//! A* heuristics.
+//!
+//! Requires crate feature `"geometry"`.
+
+use std::cmp::Ordering;
+
+use crate::data::DataMap;
+use crate::graph::{node_index, Graph};
+use crate::visit::{EdgeRef, IntoEdgeReferences};
+use crate::EdgeType;
+
+/// A point in Euclidean space, of any dimension.
+///
+/// Coordinates must not be NaN.
+pub trait Point {
+    /// Return the dimension of the space.
+    fn dim(&self) -> usize;
+
+    /// Return the coordinate of the point along `axis`, which is less than
+    /// `dim()`.
+    fn coord(&self, axis: usize) -> f64;
+}
+
+impl Point for [f64; 2] {
+    fn dim(&self) -> usize {
+        2
+    }
+    fn coord(&self, axis: usize) -> f64 {
+        self[axis]
+    }
+}
+
+impl Point for [f64; 3] {
+    fn dim(&self) -> usize {
+        3
+    }
+    fn coord(&self, axis: usize) -> f64 {
+        self[axis]
+    }
+}
+
+impl Point for (f64, f64) {
+    fn dim(&self) -> usize {
+        2
+    }
+    fn coord(&self, axis: usize) -> f64 {
+        match axis {
+            0 => self.0,
+            _ => self.1,
+        }
+    }
+}
+
+impl Point for (f64, f64, f64) {
+    fn dim(&self) -> usize {
+        3
+    }
+    fn coord(&self, axis: usize) -> f64 {
+        match axis {
+            0 => self.0,
+            1 => self.1,
+            _ => self.2,
+        }
+    }
+}
+
+impl<P: Point> Point for &P {
+    fn dim(&self) -> usize {
+        (**self).dim()
+    }
+    fn coord(&self, axis: usize) -> f64 {
+        (**self).coord(axis)
+    }
+}
+
+fn squared_distance<P: Point>(a: &P, b: &P) -> f64 {
+    (0..a.dim())
+        .map(|axis| {
+            let d = a.coord(axis) - b.coord(axis);
+            d * d
+        })
+        .sum()
+}
+
+/// Return the Euclidean distance between `a` and `b`.
+///
+/// **Panics** if the points have different dimensions.
+pub fn euclidean<P: Point>(a: &P, b: &P) -> f64 {
+    assert_eq!(
+        a.dim(),
+        b.dim(),
+        "euclidean: points of different dimensions"
+    );
+    squared_distance(a, b).sqrt()
+}
+
+fn compare(a: f64, b: f64) -> Ordering {
+    a.partial_cmp(&b).unwrap_or(Ordering::Equal)
+}
+
+/// A static k-d tree over a set of points, stored implicitly: the point of
+/// each subtree is the median of its slice of `order`, and its children are
+/// the two halves.
+struct KdTree<'a, P> {
+    points: &'a [P],
+    order: Vec<usize>,
+    dim: usize,
+}
+
+impl<'a, P: Point> KdTree<'a, P> {
+    fn new(points: &'a [P]) -> Self {
+        let dim = points.first().map_or(0, |p| p.dim());
+        assert!(
+            points.iter().all(|p| p.dim() == dim),
+            "geometry: points of different dimensions"
+        );
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        if dim > 0 {
+            Self::build(points, &mut order, 0, dim);
+        }
+        KdTree { points, order, dim }
+    }
+
+    fn build(points: &[P], order: &mut [usize], axis: usize, dim: usize) {
+        if order.len() <= 1 {
+            return;
+        }
+        order.sort_by(|&a, &b| compare(points[a].coord(axis), points[b].coord(axis)));
+        let mid = order.len() / 2;
+        let (left, right) = order.split_at_mut(mid);
+        Self::build(points, left, (axis + 1) % dim, dim);
+        Self::build(points, &mut right[1..], (axis + 1) % dim, dim);
+    }
+
+    /// Visit the subtree of `range` for a query at `query`, calling `visit`
+    /// on each point whose squared distance is at most `bound()`.
+    fn search<F, B>(&self, query: &P, range: (usize, usize), axis: usize, bound: &B, visit: &mut F)
+    where
+        F: FnMut(usize, f64),
+        B: Fn() -> f64,
+    {
+        let (start, end) = range;
+        if start >= end {
+            return;
+        }
+        let mid = start + (end - start) / 2;
+        let i = self.order[mid];
+        let d = squared_distance(query, &self.points[i]);
+        if d <= bound() {
+            visit(i, d);
+        }
+        if self.dim == 0 {
+            return;
+        }
+        let delta = query.coord(axis) - self.points[i].coord(axis);
+        let next_axis = (axis + 1) % self.dim;
+        let (near, far) = if delta < 0. {
+            ((start, mid), (mid + 1, end))
+        } else {
+            ((mid + 1, end), (start, mid))
+        };
+        self.search(query, near, next_axis, bound, visit);
+        if delta * delta <= bound() {
+            self.search(query, far, next_axis, bound, visit);
+        }
+    }
+
+    /// Return the `k` nearest points to point `i` other than itself, by
+    /// increasing distance, with their squared distances.
+    fn nearest(&self, i: usize, k: usize) -> Vec<(usize, f64)> {
+        let nearest = std::cell::RefCell::new(Vec::<(usize, f64)>::with_capacity(k + 1));
+        let bound = || {
+            let nearest = nearest.borrow();
+            if nearest.len() < k {
+                std::f64::INFINITY
+            } else {
+                nearest[k - 1].1
+            }
+        };
+        let mut visit = |j: usize, d: f64| {
+            if j == i {
+                return;
+            }
+            let mut nearest = nearest.borrow_mut();
+            let pos = nearest
+                .iter()
+                .position(|&(l, e)| compare(d, e).then(j.cmp(&l)) == Ordering::Less)
+                .unwrap_or(nearest.len());
+            nearest.insert(pos, (j, d));
+            nearest.truncate(k);
+        };
+        if k > 0 {
+            self.search(
+                &self.points[i],
+                (0, self.points.len()),
+                0,
+                &bound,
+                &mut visit,
+            );
+        }
+        nearest.into_inner()
+    }
+
+    /// Return the points at distance at most `radius` from point `i` other
+    /// than itself, with their squared distances.
+    fn within(&self, i: usize, radius: f64) -> Vec<(usize, f64)> {
+        let mut within = Vec::new();
+        let squared_radius = radius * radius;
+        self.search(
+            &self.points[i],
+            (0, self.points.len()),
+            0,
+            &|| squared_radius,
+            &mut |j, d| {
+                if j != i {
+                    within.push((j, d));
+                }
+            },
+        );
+        within.sort_by_key(|&(j, _)| j);
+        within
+    }
+}
+
+/// Build the *k*-nearest-neighbor graph of `points`, whose nodes are the
+/// points, in order, and whose edges join each point to its `k` nearest
+/// other points, weighted by their Euclidean distance.
+///
+/// In a directed graph, each node has edges to its `k` nearest neighbors, by
+/// increasing distance. In an undirected graph, two nodes are adjacent if
+/// either is among the `k` nearest neighbors of the other, with a single
+/// edge. Ties are broken by node index.
+///
+/// The nearest neighbors are found with a k-d tree, in
+/// **O(|V| k log |V|)** expected time for points in general position.
+///
+/// **Panics** if the points have different dimensions.
+///
+/// # Example
+/// ```rust
+/// use petgraph::geometry::knn_graph;
+/// use petgraph::prelude::*;
+///
+/// let points = vec![[0., 0.], [1., 0.], [0., 2.], [5., 5.]];
+/// let graph: DiGraph<_, f64> = knn_graph(points, 1);
+/// let nearest: Vec<_> = graph
+///     .node_indices()
+///     .map(|n| graph.neighbors(n).next().unwrap().index())
+///     .collect();
+/// assert_eq!(nearest, vec![1, 0, 0, 2]);
+/// ```
+pub fn knn_graph<P, Ty>(points: Vec<P>, k: usize) -> Graph<P, f64, Ty>
+where
+    P: Point,
+    Ty: EdgeType,
+{
+    let neighbors: Vec<_> = {
+        let tree = KdTree::new(&points);
+        (0..points.len()).map(|i| tree.nearest(i, k)).collect()
+    };
+    let mut graph = Graph::with_capacity(points.len(), points.len() * k);
+    for p in points {
+        graph.add_node(p);
+    }
+    let directed = graph.is_directed();
+    for (i, nearest) in neighbors.iter().enumerate() {
+        for &(j, d) in nearest {
+            // Keep a single edge between mutual neighbors
+            let mutual = || neighbors[j].iter().any(|&(l, _)| l == i);
+            if directed || i < j || !mutual() {
+                graph.add_edge(node_index(i), node_index(j), d.sqrt());
+            }
+        }
+    }
+    graph
+}
+
+/// Build the fixed-radius graph of `points`, whose nodes are the points, in
+/// order, and whose edges join each pair of points at distance at most
+/// `radius`, weighted by their distance.
+///
+/// An undirected graph has one edge per pair of points; a directed graph has
+/// an edge in each direction.
+///
+/// The neighbors are found with a k-d tree.
+///
+/// **Panics** if the points have different dimensions.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::connected_components;
+/// use petgraph::geometry::radius_graph;
+/// use petgraph::prelude::*;
+///
+/// let points = vec![(0., 0.), (1., 0.), (1., 1.), (4., 4.)];
+/// let graph: UnGraph<_, f64> = radius_graph(points, 1.5);
+/// assert_eq!(graph.edge_count(), 3);
+/// assert_eq!(connected_components(&graph), 2);
+/// ```
+pub fn radius_graph<P, Ty>(points: Vec<P>, radius: f64) -> Graph<P, f64, Ty>
+where
+    P: Point,
+    Ty: EdgeType,
+{
+    let edges: Vec<_> = {
+        let tree = KdTree::new(&points);
+        (0..points.len()).map(|i| tree.within(i, radius)).collect()
+    };
+    let mut graph = Graph::with_capacity(points.len(), 0);
+    for p in points {
+        graph.add_node(p);
+    }
+    let directed = graph.is_directed();
+    for (i, within) in edges.into_iter().enumerate() {
+        for (j, d) in within {
+            if directed || i < j {
+                graph.add_edge(node_index(i), node_index(j), d.sqrt());
+            }
+        }
+    }
+    graph
+}
+
+/// Return an edge cost function giving the Euclidean length of the edges of
+/// `graph`, between the points of their endpoints, for the shortest path
+/// algorithms.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::dijkstra;
+/// use petgraph::geometry::edge_length;
+/// use petgraph::prelude::*;
+///
+/// let mut graph = UnGraph::<[f64; 2], ()>::default();
+/// let a = graph.add_node([0., 0.]);
+/// let b = graph.add_node([3., 4.]);
+/// let c = graph.add_node([3., 0.]);
+/// graph.extend_with_edges(&[(a, b), (a, c), (c, b)]);
+///
+/// let costs = dijkstra(&graph, a, None, edge_length(&graph));
+/// assert_eq!(costs[&b], 5.);
+/// ```
+pub fn edge_length<G>(graph: G) -> impl Fn(G::EdgeRef) -> f64
+where
+    G: IntoEdgeReferences + DataMap,
+    G::NodeWeight: Point,
+{
+    move |edge| {
+        euclidean(
+            graph.node_weight(edge.source()).unwrap(),
+            graph.node_weight(edge.target()).unwrap(),
+        )
+    }
+}
+
+/// Return an A* heuristic for the shortest paths to `goal`: the Euclidean
+/// distance from the point of a node to the point of `goal`.
+///
+/// The heuristic is admissible and consistent if the cost of each edge is at
+/// least the distance between its endpoints, e.g. with [`edge_length`], or the
+/// weights of [`knn_graph`] and [`radius_graph`].
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::astar;
+/// use petgraph::geometry::{euclidean_heuristic, radius_graph};
+/// use petgraph::prelude::*;
+///
+/// let points: Vec<_> = (0..10)
+///     .flat_map(|x| (0..10).map(move |y| [f64::from(x), f64::from(y)]))
+///     .collect();
+/// let graph: UnGraph<_, f64> = radius_graph(points, 1.);
+/// let (start, goal) = (NodeIndex::new(0), NodeIndex::new(99));
+///
+/// let (cost, path) = astar(
+///     &graph,
+///     start,
+///     |n| n == goal,
+///     |e| *e.weight(),
+///     euclidean_heuristic(&graph, goal),
+/// )
+/// .unwrap();
+/// assert_eq!(cost, 18.);
+/// assert_eq!(path.len(), 19);
+/// ```
+pub fn euclidean_heuristic<G>(graph: G, goal: G::NodeId) -> impl Fn(G::NodeId) -> f64
+where
+    G: DataMap,
+    G::NodeWeight: Point,
+{
+    move |node| {
+        euclidean(
+            graph.node_weight(node).unwrap(),
+            graph.node_weight(goal).unwrap(),
+        )
+    }
+}
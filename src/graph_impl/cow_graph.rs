@@ -0,0 +1,1274 @@
+//! `CowGraph<N, E, Ty, Ix>` is a graph datastructure with copy-on-write,
+//! structurally shared storage.
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::size_of;
+use std::ops::{Index, IndexMut};
+use std::slice;
+use std::sync::Arc;
+
+use fixedbitset::FixedBitSet;
+
+use super::{
+    edge_index, DefaultIx, Edge, EdgeIndex, EdgeIndices, EdgeReference, Graph, IndexType, Node,
+    NodeIndex, NodeIndices, DIRECTIONS,
+};
+use crate::data::{Build, Create, DataMap, DataMapMut};
+use crate::iter_format::{DebugMap, IterFormatExt, NoPretty};
+use crate::visit;
+use crate::{Directed, Direction, EdgeType, Incoming, Outgoing, Undirected};
+
+/// The number of elements in each shared chunk, as a power of two.
+const CHUNK_BITS: usize = 7;
+const CHUNK_SIZE: usize = 1 << CHUNK_BITS;
+const CHUNK_MASK: usize = CHUNK_SIZE - 1;
+
+/// A vector split into fixed size chunks behind reference counted pointers.
+///
+/// Cloning only bumps the reference count of the spine; a mutation copies the
+/// spine and the touched chunk if they are shared, and nothing else.
+struct Chunks<T> {
+    chunks: Arc<Vec<Arc<Vec<T>>>>,
+    len: usize,
+}
+
+impl<T> Clone for Chunks<T> {
+    fn clone(&self) -> Self {
+        Chunks {
+            chunks: self.chunks.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T> Default for Chunks<T> {
+    fn default() -> Self {
+        Chunks {
+            chunks: Arc::new(Vec::new()),
+            len: 0,
+        }
+    }
+}
+
+impl<T> Chunks<T> {
+    fn with_capacity(capacity: usize) -> Self {
+        Chunks {
+            chunks: Arc::new(Vec::with_capacity((capacity + CHUNK_MASK) >> CHUNK_BITS)),
+            len: 0,
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn get(&self, i: usize) -> Option<&T> {
+        if i < self.len {
+            Some(&self.chunks[i >> CHUNK_BITS][i & CHUNK_MASK])
+        } else {
+            None
+        }
+    }
+
+    fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            chunks: self.chunks.iter(),
+            chunk: [].iter(),
+            index: 0,
+        }
+    }
+
+    /// Return `true` if `self` and `other` share the chunk of index `i`.
+    fn shares(&self, other: &Self, i: usize) -> bool {
+        match (self.chunks.get(i), other.chunks.get(i)) {
+            (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+            _ => false,
+        }
+    }
+
+    fn chunk_count(&self) -> usize {
+        self.chunks.len()
+    }
+}
+
+impl<T: Clone> Chunks<T> {
+    fn get_mut(&mut self, i: usize) -> Option<&mut T> {
+        if i < self.len {
+            let chunk = &mut Arc::make_mut(&mut self.chunks)[i >> CHUNK_BITS];
+            Some(&mut Arc::make_mut(chunk)[i & CHUNK_MASK])
+        } else {
+            None
+        }
+    }
+
+    fn push(&mut self, value: T) {
+        let chunks = Arc::make_mut(&mut self.chunks);
+        if self.len & CHUNK_MASK == 0 {
+            let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+            chunk.push(value);
+            chunks.push(Arc::new(chunk));
+        } else {
+            Arc::make_mut(chunks.last_mut().unwrap()).push(value);
+        }
+        self.len += 1;
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        if self.len == 0 {
+            return None;
+        }
+        let chunks = Arc::make_mut(&mut self.chunks);
+        let last = Arc::make_mut(chunks.last_mut().unwrap());
+        let value = last.pop();
+        if last.is_empty() {
+            chunks.pop();
+        }
+        self.len -= 1;
+        value
+    }
+
+    fn swap_remove(&mut self, i: usize) -> T {
+        assert!(i < self.len, "Chunks::swap_remove: index out of bounds");
+        let last = self.pop().unwrap();
+        match self.get_mut(i) {
+            Some(slot) => std::mem::replace(slot, last),
+            None => last,
+        }
+    }
+
+    fn into_vec(self) -> Vec<T> {
+        let chunks = Arc::try_unwrap(self.chunks).unwrap_or_else(|c| (*c).clone());
+        let mut vec = Vec::with_capacity(self.len);
+        for chunk in chunks {
+            match Arc::try_unwrap(chunk) {
+                Ok(chunk) => vec.extend(chunk),
+                Err(chunk) => vec.extend(chunk.iter().cloned()),
+            }
+        }
+        vec
+    }
+}
+
+impl<T> From<Vec<T>> for Chunks<T> {
+    fn from(vec: Vec<T>) -> Self {
+        let len = vec.len();
+        let mut chunks = Vec::with_capacity((len + CHUNK_MASK) >> CHUNK_BITS);
+        let mut iter = vec.into_iter();
+        while chunks.len() << CHUNK_BITS < len {
+            let mut chunk = Vec::with_capacity(CHUNK_SIZE);
+            chunk.extend(iter.by_ref().take(CHUNK_SIZE));
+            chunks.push(Arc::new(chunk));
+        }
+        Chunks {
+            chunks: Arc::new(chunks),
+            len,
+        }
+    }
+}
+
+/// Iterator over the elements of `Chunks`, with their index.
+struct Iter<'a, T> {
+    chunks: slice::Iter<'a, Arc<Vec<T>>>,
+    chunk: slice::Iter<'a, T>,
+    index: usize,
+}
+
+impl<'a, T> Clone for Iter<'a, T> {
+    clone_fields!(Iter, chunks, chunk, index,);
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = (usize, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(value) = self.chunk.next() {
+                self.index += 1;
+                return Some((self.index - 1, value));
+            }
+            self.chunk = self.chunks.next()?.iter();
+        }
+    }
+}
+
+/// `CowGraph<N, E, Ty, Ix>` is a graph datastructure using an adjacency list
+/// representation, like [`Graph`], with copy-on-write storage.
+///
+/// The nodes and edges are stored in fixed size chunks behind reference
+/// counted pointers, that are shared between clones of the graph: cloning a
+/// `CowGraph` is **O(1)**, and a mutation only copies the chunks it touches
+/// that are still shared with another clone. This makes it cheap to keep many
+/// versions of a large graph alive, e.g. to explore speculative edits in a
+/// search, where each branch clones the graph and edits a few nodes and edges.
+///
+/// `CowGraph` has the same indices and removal semantics as [`Graph`]: removing
+/// a node or an edge moves the last one into its place. It can be converted
+/// from and into a `Graph`. Mutations require `N: Clone` and `E: Clone`, to copy
+/// the shared chunks, and are thread safe: clones of a `CowGraph` can be sent
+/// to other threads.
+///
+/// The first mutation after a clone also copies the array of pointers to the
+/// chunks, whose size is about 1/128 of the graph.
+///
+/// # Example
+/// ```rust
+/// use petgraph::cow_graph::CowGraph;
+/// use petgraph::algo::has_path_connecting;
+///
+/// let mut graph = CowGraph::<&str, u32>::new();
+/// let a = graph.add_node("a");
+/// let b = graph.add_node("b");
+/// let c = graph.add_node("c");
+/// graph.add_edge(a, b, 1);
+/// let bc = graph.add_edge(b, c, 2);
+///
+/// // A speculative edit on a cheap clone leaves the original untouched
+/// let mut branch = graph.clone();
+/// branch.remove_edge(bc);
+/// assert!(!has_path_connecting(&branch, a, c, None));
+/// assert!(has_path_connecting(&graph, a, c, None));
+/// assert_eq!(graph.edge_count(), 2);
+/// ```
+pub struct CowGraph<N, E, Ty = Directed, Ix = DefaultIx> {
+    nodes: Chunks<Node<N, Ix>>,
+    edges: Chunks<Edge<E, Ix>>,
+    ty: PhantomData<Ty>,
+}
+
+impl<N, E, Ty, Ix> Clone for CowGraph<N, E, Ty, Ix> {
+    fn clone(&self) -> Self {
+        CowGraph {
+            nodes: self.nodes.clone(),
+            edges: self.edges.clone(),
+            ty: self.ty,
+        }
+    }
+}
+
+impl<N, E, Ty, Ix> fmt::Debug for CowGraph<N, E, Ty, Ix>
+where
+    N: fmt::Debug,
+    E: fmt::Debug,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let etype = if self.is_directed() {
+            "Directed"
+        } else {
+            "Undirected"
+        };
+        let mut fmt_struct = f.debug_struct("CowGraph");
+        fmt_struct.field("Ty", &etype);
+        fmt_struct.field("node_count", &self.node_count());
+        fmt_struct.field("edge_count", &self.edge_count());
+        if self.edge_count() > 0 {
+            fmt_struct.field(
+                "edges",
+                &self
+                    .edges
+                    .iter()
+                    .map(|(_, e)| NoPretty((e.source().index(), e.target().index())))
+                    .format(", "),
+            );
+        }
+        // skip weights if they are ZST!
+        if size_of::<N>() != 0 {
+            fmt_struct.field(
+                "node weights",
+                &DebugMap(|| self.nodes.iter().map(|(i, n)| (i, &n.weight))),
+            );
+        }
+        if size_of::<E>() != 0 {
+            fmt_struct.field(
+                "edge weights",
+                &DebugMap(|| self.edges.iter().map(|(i, e)| (i, &e.weight))),
+            );
+        }
+        fmt_struct.finish()
+    }
+}
+
+impl<N, E, Ty, Ix> Default for CowGraph<N, E, Ty, Ix> {
+    fn default() -> Self {
+        CowGraph {
+            nodes: Chunks::default(),
+            edges: Chunks::default(),
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<N, E> CowGraph<N, E, Directed> {
+    /// Create a new `CowGraph` with directed edges.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<N, E> CowGraph<N, E, Undirected> {
+    /// Create a new `CowGraph` with undirected edges.
+    pub fn new_undirected() -> Self {
+        Self::default()
+    }
+}
+
+impl<N, E, Ty, Ix> CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    /// Return the number of nodes (vertices) in the graph.
+    ///
+    /// Computes in **O(1)** time.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Return the number of edges in the graph.
+    ///
+    /// Computes in **O(1)** time.
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Whether the graph has directed edges or not.
+    #[inline]
+    pub fn is_directed(&self) -> bool {
+        Ty::is_directed()
+    }
+
+    /// Return the number of the storage chunks of the graph that are shared
+    /// with `other`, e.g. a clone of the graph.
+    ///
+    /// The chunks of nodes and edges are counted together. Right after a
+    /// clone, all chunks are shared; each mutation unshares at most a few.
+    pub fn shared_chunks(&self, other: &Self) -> usize {
+        let nodes = (0..self.nodes.chunk_count()).filter(|&i| self.nodes.shares(&other.nodes, i));
+        let edges = (0..self.edges.chunk_count()).filter(|&i| self.edges.shares(&other.edges, i));
+        nodes.count() + edges.count()
+    }
+
+    /// Access the weight for node `a`.
+    ///
+    /// If node `a` doesn't exist in the graph, return `None`.
+    /// Also available with indexing syntax: `&graph[a]`.
+    pub fn node_weight(&self, a: NodeIndex<Ix>) -> Option<&N> {
+        self.nodes.get(a.index()).map(|n| &n.weight)
+    }
+
+    /// Access the weight for edge `e`.
+    ///
+    /// If edge `e` doesn't exist in the graph, return `None`.
+    /// Also available with indexing syntax: `&graph[e]`.
+    pub fn edge_weight(&self, e: EdgeIndex<Ix>) -> Option<&E> {
+        self.edges.get(e.index()).map(|ed| &ed.weight)
+    }
+
+    /// Access the source and target nodes for `e`.
+    ///
+    /// If edge `e` doesn't exist in the graph, return `None`.
+    pub fn edge_endpoints(&self, e: EdgeIndex<Ix>) -> Option<(NodeIndex<Ix>, NodeIndex<Ix>)> {
+        self.edges
+            .get(e.index())
+            .map(|ed| (ed.source(), ed.target()))
+    }
+
+    /// Return an iterator of all nodes with an edge starting from `a`.
+    ///
+    /// - `Directed`: Outgoing edges from `a`.
+    /// - `Undirected`: All edges from or to `a`.
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `NodeIndex<Ix>`.
+    pub fn neighbors(&self, a: NodeIndex<Ix>) -> Neighbors<'_, E, Ix> {
+        self.neighbors_directed(a, Outgoing)
+    }
+
+    /// Return an iterator of all neighbors that have an edge between them and
+    /// `a`, in the specified direction.
+    /// If the graph's edges are undirected, this is equivalent to *.neighbors(a)*.
+    ///
+    /// - `Directed`, `Outgoing`: All edges from `a`.
+    /// - `Directed`, `Incoming`: All edges to `a`.
+    /// - `Undirected`: All edges from or to `a`.
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `NodeIndex<Ix>`.
+    pub fn neighbors_directed(&self, a: NodeIndex<Ix>, dir: Direction) -> Neighbors<'_, E, Ix> {
+        let mut iter = self.neighbors_undirected(a);
+        if self.is_directed() {
+            let k = dir.index();
+            iter.next[1 - k] = EdgeIndex::end();
+            iter.skip_start = NodeIndex::end();
+        }
+        iter
+    }
+
+    /// Return an iterator of all neighbors that have an edge between them and
+    /// `a`, in either direction.
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `NodeIndex<Ix>`.
+    pub fn neighbors_undirected(&self, a: NodeIndex<Ix>) -> Neighbors<'_, E, Ix> {
+        Neighbors {
+            skip_start: a,
+            edges: &self.edges,
+            next: self.first_edges(a),
+        }
+    }
+
+    /// Return an iterator of all edges of `a`.
+    ///
+    /// - `Directed`: Outgoing edges from `a`.
+    /// - `Undirected`: All edges connected to `a`.
+    ///
+    /// Produces an empty iterator if the node doesn't exist.<br>
+    /// Iterator element type is `EdgeReference<E, Ix>`.
+    pub fn edges(&self, a: NodeIndex<Ix>) -> Edges<'_, E, Ty, Ix> {
+        self.edges_directed(a, Outgoing)
+    }
+
+    /// Return an iterator of all edges of `a`, in the specified direction.
+    ///
+    /// - `Directed`, `Outgoing`: All edges from `a`.
+    /// - `Directed`, `Incoming`: All edges to `a`.
+    /// - `Undirected`, `Outgoing`: All edges connected to `a`, with `a` being the source of each
+    ///   edge.
+    /// - `Undirected`, `Incoming`: All edges connected to `a`, with `a` being the target of each
+    ///   edge.
+    ///
+    /// Produces an empty iterator if the node `a` doesn't exist.<br>
+    /// Iterator element type is `EdgeReference<E, Ix>`.
+    pub fn edges_directed(&self, a: NodeIndex<Ix>, dir: Direction) -> Edges<'_, E, Ty, Ix> {
+        Edges {
+            skip_start: a,
+            edges: &self.edges,
+            direction: dir,
+            next: self.first_edges(a),
+            ty: PhantomData,
+        }
+    }
+
+    fn first_edges(&self, a: NodeIndex<Ix>) -> [EdgeIndex<Ix>; 2] {
+        match self.nodes.get(a.index()) {
+            None => [EdgeIndex::end(), EdgeIndex::end()],
+            Some(n) => n.next,
+        }
+    }
+
+    /// Lookup if there is an edge from `a` to `b`.
+    ///
+    /// Computes in **O(e')** time, where **e'** is the number of edges
+    /// connected to `a` (and `b`, if the graph edges are undirected).
+    pub fn contains_edge(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> bool {
+        self.find_edge(a, b).is_some()
+    }
+
+    /// Lookup an edge from `a` to `b`.
+    ///
+    /// Computes in **O(e')** time, where **e'** is the number of edges
+    /// connected to `a` (and `b`, if the graph edges are undirected).
+    pub fn find_edge(&self, a: NodeIndex<Ix>, b: NodeIndex<Ix>) -> Option<EdgeIndex<Ix>> {
+        let dirs: &[Direction] = if self.is_directed() {
+            &[Outgoing]
+        } else {
+            &DIRECTIONS
+        };
+        let next = self.first_edges(a);
+        for &d in dirs {
+            let k = d.index();
+            let mut edix = next[k];
+            while let Some(edge) = self.edges.get(edix.index()) {
+                if edge.node[1 - k] == b {
+                    return Some(edix);
+                }
+                edix = edge.next[k];
+            }
+        }
+        None
+    }
+
+    /// Return an iterator over the node indices of the graph.
+    pub fn node_indices(&self) -> NodeIndices<Ix> {
+        NodeIndices {
+            r: 0..self.node_count(),
+            ty: PhantomData,
+        }
+    }
+
+    /// Return an iterator over the edge indices of the graph.
+    pub fn edge_indices(&self) -> EdgeIndices<Ix> {
+        EdgeIndices {
+            r: 0..self.edge_count(),
+            ty: PhantomData,
+        }
+    }
+
+    /// Create an iterator over all edges, in indexed order.
+    ///
+    /// Iterator element type is `EdgeReference<E, Ix>`.
+    pub fn edge_references(&self) -> EdgeReferences<'_, E, Ix> {
+        EdgeReferences {
+            iter: self.edges.iter(),
+        }
+    }
+}
+
+impl<N, E, Ty, Ix> CowGraph<N, E, Ty, Ix>
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    /// Create a new `CowGraph` with estimated capacity.
+    ///
+    /// The storage grows by chunks, so the capacity is only used to reserve
+    /// the array of pointers to the chunks.
+    pub fn with_capacity(nodes: usize, edges: usize) -> Self {
+        CowGraph {
+            nodes: Chunks::with_capacity(nodes),
+            edges: Chunks::with_capacity(edges),
+            ty: PhantomData,
+        }
+    }
+
+    /// Add a node (also called vertex) with associated data `weight` to the graph.
+    ///
+    /// Computes in **O(1)** time, plus the copy of the last chunk if it is shared.
+    ///
+    /// Return the index of the new node.
+    ///
+    /// **Panics** if the graph is at the maximum number of nodes for its index
+    /// type (N/A if usize).
+    pub fn add_node(&mut self, weight: N) -> NodeIndex<Ix> {
+        let node_idx = NodeIndex::new(self.node_count());
+        assert!(<Ix as IndexType>::max().index() == !0 || NodeIndex::end() != node_idx);
+        self.nodes.push(Node {
+            weight,
+            next: [EdgeIndex::end(), EdgeIndex::end()],
+        });
+        node_idx
+    }
+
+    /// Access the weight for node `a`, mutably.
+    ///
+    /// If node `a` doesn't exist in the graph, return `None`.
+    /// Also available with indexing syntax: `&mut graph[a]`.
+    pub fn node_weight_mut(&mut self, a: NodeIndex<Ix>) -> Option<&mut N> {
+        self.nodes.get_mut(a.index()).map(|n| &mut n.weight)
+    }
+
+    /// Access the weight for edge `e`, mutably.
+    ///
+    /// If edge `e` doesn't exist in the graph, return `None`.
+    /// Also available with indexing syntax: `&mut graph[e]`.
+    pub fn edge_weight_mut(&mut self, e: EdgeIndex<Ix>) -> Option<&mut E> {
+        self.edges.get_mut(e.index()).map(|ed| &mut ed.weight)
+    }
+
+    /// Add an edge from `a` to `b` to the graph, with its associated
+    /// data `weight`.
+    ///
+    /// Return the index of the new edge.
+    ///
+    /// Computes in **O(1)** time, plus the copy of the touched chunks if they
+    /// are shared.
+    ///
+    /// **Panics** if any of the nodes don't exist.<br>
+    /// **Panics** if the graph is at the maximum number of edges for its index
+    /// type (N/A if usize).
+    pub fn add_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E) -> EdgeIndex<Ix> {
+        let edge_idx = EdgeIndex::new(self.edge_count());
+        assert!(<Ix as IndexType>::max().index() == !0 || EdgeIndex::end() != edge_idx);
+        if self.nodes.get(a.index()).is_none() || self.nodes.get(b.index()).is_none() {
+            panic!("CowGraph::add_edge: node indices out of bounds");
+        }
+        let next = [
+            self.nodes.get(a.index()).unwrap().next[0],
+            self.nodes.get(b.index()).unwrap().next[1],
+        ];
+        self.nodes.get_mut(a.index()).unwrap().next[0] = edge_idx;
+        self.nodes.get_mut(b.index()).unwrap().next[1] = edge_idx;
+        self.edges.push(Edge {
+            weight,
+            node: [a, b],
+            next,
+        });
+        edge_idx
+    }
+
+    /// Add or update an edge from `a` to `b`.
+    /// If the edge already exists, its weight is updated.
+    ///
+    /// Return the index of the affected edge.
+    ///
+    /// Computes in **O(e')** time, where **e'** is the number of edges
+    /// connected to `a` (and `b`, if the graph edges are undirected).
+    ///
+    /// **Panics** if any of the nodes doesn't exist.
+    pub fn update_edge(&mut self, a: NodeIndex<Ix>, b: NodeIndex<Ix>, weight: E) -> EdgeIndex<Ix> {
+        if let Some(ix) = self.find_edge(a, b) {
+            if let Some(ed) = self.edge_weight_mut(ix) {
+                *ed = weight;
+                return ix;
+            }
+        }
+        self.add_edge(a, b, weight)
+    }
+
+    /// Remove `a` from the graph if it exists, and return its weight.
+    /// If it doesn't exist in the graph, return `None`.
+    ///
+    /// Like [`Graph::remove_node`], this invalidates the last node index in the
+    /// graph (that node will adopt the removed node index), and the edge
+    /// indices as they would be following the removal of each edge with an
+    /// endpoint in `a`.
+    ///
+    /// Computes in **O(e')** time, where **e'** is the number of affected
+    /// edges, plus the copy of the touched chunks if they are shared.
+    pub fn remove_node(&mut self, a: NodeIndex<Ix>) -> Option<N> {
+        self.nodes.get(a.index())?;
+        for d in &DIRECTIONS {
+            let k = d.index();
+
+            // Remove all edges from and to this node.
+            loop {
+                let next = self.nodes.get(a.index()).unwrap().next[k];
+                if next == EdgeIndex::end() {
+                    break;
+                }
+                let ret = self.remove_edge(next);
+                debug_assert!(ret.is_some());
+                let _ = ret;
+            }
+        }
+
+        // Use swap_remove -- only the swapped-in node is going to change
+        // NodeIndex<Ix>, so we only have to walk its edges and update them.
+        let node = self.nodes.swap_remove(a.index());
+        let swap_edges = match self.nodes.get(a.index()) {
+            None => return Some(node.weight),
+            Some(n) => n.next,
+        };
+
+        // The swapped element's old index
+        let old_index = NodeIndex::new(self.nodes.len());
+        for &d in &DIRECTIONS {
+            let k = d.index();
+            let mut edix = swap_edges[k];
+            while let Some(edge) = self.edges.get_mut(edix.index()) {
+                debug_assert!(edge.node[k] == old_index);
+                edge.node[k] = a;
+                edix = edge.next[k];
+            }
+        }
+        Some(node.weight)
+    }
+
+    /// For edge `e` with endpoints `edge_node`, replace links to it,
+    /// with links to `edge_next`.
+    fn change_edge_links(
+        &mut self,
+        edge_node: [NodeIndex<Ix>; 2],
+        e: EdgeIndex<Ix>,
+        edge_next: [EdgeIndex<Ix>; 2],
+    ) {
+        for &d in &DIRECTIONS {
+            let k = d.index();
+            let fst = match self.nodes.get(edge_node[k].index()) {
+                Some(node) => node.next[k],
+                None => {
+                    debug_assert!(
+                        false,
+                        "Edge's endpoint dir={:?} index={:?} not found",
+                        d, edge_node[k]
+                    );
+                    return;
+                }
+            };
+            if fst == e {
+                self.nodes.get_mut(edge_node[k].index()).unwrap().next[k] = edge_next[k];
+            } else {
+                // Walk the list without copying, and only copy the chunk of
+                // the edge linking to `e`.
+                let mut edix = fst;
+                while let Some(edge) = self.edges.get(edix.index()) {
+                    if edge.next[k] == e {
+                        self.edges.get_mut(edix.index()).unwrap().next[k] = edge_next[k];
+                        break; // the edge can only be present once in the list.
+                    }
+                    edix = edge.next[k];
+                }
+            }
+        }
+    }
+
+    /// Remove an edge and return its edge weight, or `None` if it didn't exist.
+    ///
+    /// Like [`Graph::remove_edge`], this invalidates the last edge index in the
+    /// graph (that edge will adopt the removed edge index).
+    ///
+    /// Computes in **O(e')** time, where **e'** is the size of four particular
+    /// edge lists, plus the copy of the touched chunks if they are shared.
+    pub fn remove_edge(&mut self, e: EdgeIndex<Ix>) -> Option<E> {
+        let (edge_node, edge_next) = match self.edges.get(e.index()) {
+            None => return None,
+            Some(x) => (x.node, x.next),
+        };
+        // Remove the edge from its in and out lists by replacing it with
+        // a link to the next in the list.
+        self.change_edge_links(edge_node, e, edge_next);
+
+        // swap_remove the edge -- only the edge swapped into place needs
+        // its links updated.
+        let edge = self.edges.swap_remove(e.index());
+        let swap = match self.edges.get(e.index()) {
+            None => return Some(edge.weight),
+            Some(ed) => ed.node,
+        };
+        let swapped_e = EdgeIndex::new(self.edges.len());
+        self.change_edge_links(swap, swapped_e, [e, e]);
+        Some(edge.weight)
+    }
+
+    /// Convert the graph into a [`Graph`], with the same indices.
+    ///
+    /// The chunks that are not shared with another clone are moved, and the
+    /// others are copied.
+    pub fn into_graph(self) -> Graph<N, E, Ty, Ix> {
+        Graph {
+            nodes: self.nodes.into_vec(),
+            edges: self.edges.into_vec(),
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<N, E, Ty, Ix> From<Graph<N, E, Ty, Ix>> for CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    /// Convert a [`Graph`] into a `CowGraph`, with the same indices.
+    ///
+    /// Computes in **O(|V| + |E|)** time.
+    fn from(graph: Graph<N, E, Ty, Ix>) -> Self {
+        CowGraph {
+            nodes: Chunks::from(graph.nodes),
+            edges: Chunks::from(graph.edges),
+            ty: PhantomData,
+        }
+    }
+}
+
+impl<N, E, Ty, Ix> From<CowGraph<N, E, Ty, Ix>> for Graph<N, E, Ty, Ix>
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    /// Convert a [`CowGraph`] into a `Graph`, with the same indices.
+    fn from(graph: CowGraph<N, E, Ty, Ix>) -> Self {
+        graph.into_graph()
+    }
+}
+
+/// Index the `CowGraph` by `NodeIndex` to access node weights.
+///
+/// **Panics** if the node doesn't exist.
+impl<N, E, Ty, Ix> Index<NodeIndex<Ix>> for CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type Output = N;
+    fn index(&self, index: NodeIndex<Ix>) -> &N {
+        self.node_weight(index).unwrap()
+    }
+}
+
+/// Index the `CowGraph` by `NodeIndex` to access node weights.
+///
+/// **Panics** if the node doesn't exist.
+impl<N, E, Ty, Ix> IndexMut<NodeIndex<Ix>> for CowGraph<N, E, Ty, Ix>
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    fn index_mut(&mut self, index: NodeIndex<Ix>) -> &mut N {
+        self.node_weight_mut(index).unwrap()
+    }
+}
+
+/// Index the `CowGraph` by `EdgeIndex` to access edge weights.
+///
+/// **Panics** if the edge doesn't exist.
+impl<N, E, Ty, Ix> Index<EdgeIndex<Ix>> for CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type Output = E;
+    fn index(&self, index: EdgeIndex<Ix>) -> &E {
+        self.edge_weight(index).unwrap()
+    }
+}
+
+/// Index the `CowGraph` by `EdgeIndex` to access edge weights.
+///
+/// **Panics** if the edge doesn't exist.
+impl<N, E, Ty, Ix> IndexMut<EdgeIndex<Ix>> for CowGraph<N, E, Ty, Ix>
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    fn index_mut(&mut self, index: EdgeIndex<Ix>) -> &mut E {
+        self.edge_weight_mut(index).unwrap()
+    }
+}
+
+/// Iterator over the neighbors of a node.
+///
+/// Iterator element type is `NodeIndex<Ix>`.
+///
+/// Created with [`.neighbors()`][1], [`.neighbors_directed()`][2] or
+/// [`.neighbors_undirected()`][3].
+///
+/// [1]: struct.CowGraph.html#method.neighbors
+/// [2]: struct.CowGraph.html#method.neighbors_directed
+/// [3]: struct.CowGraph.html#method.neighbors_undirected
+pub struct Neighbors<'a, E: 'a, Ix: 'a = DefaultIx> {
+    /// starting node to skip over
+    skip_start: NodeIndex<Ix>,
+    edges: &'a Chunks<Edge<E, Ix>>,
+    next: [EdgeIndex<Ix>; 2],
+}
+
+impl<'a, E, Ix> Iterator for Neighbors<'a, E, Ix>
+where
+    Ix: IndexType,
+{
+    type Item = NodeIndex<Ix>;
+
+    fn next(&mut self) -> Option<NodeIndex<Ix>> {
+        // First any outgoing edges
+        if let Some(edge) = self.edges.get(self.next[0].index()) {
+            self.next[0] = edge.next[0];
+            return Some(edge.node[1]);
+        }
+        // Then incoming edges, skipping the selfloops already counted in the
+        // outgoing list of an undirected iterator.
+        while let Some(edge) = self.edges.get(self.next[1].index()) {
+            self.next[1] = edge.next[1];
+            if edge.node[0] != self.skip_start {
+                return Some(edge.node[0]);
+            }
+        }
+        None
+    }
+}
+
+impl<'a, E, Ix> Clone for Neighbors<'a, E, Ix>
+where
+    Ix: IndexType,
+{
+    fn clone(&self) -> Self {
+        Neighbors {
+            skip_start: self.skip_start,
+            edges: self.edges,
+            next: self.next,
+        }
+    }
+}
+
+/// Iterator over the edges of from or to a node
+pub struct Edges<'a, E: 'a, Ty, Ix: 'a = DefaultIx>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    /// starting node to skip over
+    skip_start: NodeIndex<Ix>,
+    edges: &'a Chunks<Edge<E, Ix>>,
+
+    /// Next edge to visit.
+    next: [EdgeIndex<Ix>; 2],
+
+    /// For directed graphs: the direction to iterate in
+    /// For undirected graphs: the direction of edges
+    direction: Direction,
+    ty: PhantomData<Ty>,
+}
+
+impl<'a, E, Ty, Ix> Iterator for Edges<'a, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type Item = EdgeReference<'a, E, Ix>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // Directed graphs iterate over the list of `direction`, undirected
+        // graphs over both lists, with `a` as the endpoint of `direction`.
+        let (iterate_over, reverse) = if Ty::is_directed() {
+            (Some(self.direction), None)
+        } else {
+            (None, Some(self.direction.opposite()))
+        };
+
+        if iterate_over.unwrap_or(Outgoing) == Outgoing {
+            let i = self.next[0];
+            if let Some(edge) = self.edges.get(i.index()) {
+                self.next[0] = edge.next[0];
+                return Some(EdgeReference {
+                    index: i,
+                    node: if reverse == Some(Outgoing) {
+                        super::swap_pair(edge.node)
+                    } else {
+                        edge.node
+                    },
+                    weight: &edge.weight,
+                });
+            }
+        }
+
+        if iterate_over.unwrap_or(Incoming) == Incoming {
+            while let Some(edge) = self.edges.get(self.next[1].index()) {
+                let i = self.next[1];
+                self.next[1] = edge.next[1];
+                // In any of the "both" situations, self-loops would be iterated over twice.
+                // Skip them here.
+                if iterate_over.is_none() && edge.node[0] == self.skip_start {
+                    continue;
+                }
+                return Some(EdgeReference {
+                    index: i,
+                    node: if reverse == Some(Incoming) {
+                        super::swap_pair(edge.node)
+                    } else {
+                        edge.node
+                    },
+                    weight: &edge.weight,
+                });
+            }
+        }
+
+        None
+    }
+}
+
+impl<'a, E, Ty, Ix> Clone for Edges<'a, E, Ty, Ix>
+where
+    Ix: IndexType,
+    Ty: EdgeType,
+{
+    fn clone(&self) -> Self {
+        Edges {
+            skip_start: self.skip_start,
+            edges: self.edges,
+            next: self.next,
+            direction: self.direction,
+            ty: self.ty,
+        }
+    }
+}
+
+/// Iterator over all nodes of a graph.
+pub struct NodeReferences<'a, N: 'a, Ix: IndexType = DefaultIx> {
+    iter: Iter<'a, Node<N, Ix>>,
+}
+
+impl<'a, N, Ix> Iterator for NodeReferences<'a, N, Ix>
+where
+    Ix: IndexType,
+{
+    type Item = (NodeIndex<Ix>, &'a N);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter
+            .next()
+            .map(|(i, node)| (NodeIndex::new(i), &node.weight))
+    }
+}
+
+/// Iterator over all edges of a graph.
+pub struct EdgeReferences<'a, E: 'a, Ix: IndexType = DefaultIx> {
+    iter: Iter<'a, Edge<E, Ix>>,
+}
+
+impl<'a, E, Ix> Iterator for EdgeReferences<'a, E, Ix>
+where
+    Ix: IndexType,
+{
+    type Item = EdgeReference<'a, E, Ix>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next().map(|(i, edge)| EdgeReference {
+            index: edge_index(i),
+            node: edge.node,
+            weight: &edge.weight,
+        })
+    }
+}
+
+impl<N, E, Ty, Ix> visit::GraphBase for CowGraph<N, E, Ty, Ix>
+where
+    Ix: IndexType,
+{
+    type NodeId = NodeIndex<Ix>;
+    type EdgeId = EdgeIndex<Ix>;
+}
+
+impl<N, E, Ty, Ix> visit::Data for CowGraph<N, E, Ty, Ix>
+where
+    Ix: IndexType,
+{
+    type NodeWeight = N;
+    type EdgeWeight = E;
+}
+
+impl<N, E, Ty, Ix> DataMap for CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    fn node_weight(&self, id: Self::NodeId) -> Option<&Self::NodeWeight> {
+        self.node_weight(id)
+    }
+    fn edge_weight(&self, id: Self::EdgeId) -> Option<&Self::EdgeWeight> {
+        self.edge_weight(id)
+    }
+}
+
+impl<N, E, Ty, Ix> DataMapMut for CowGraph<N, E, Ty, Ix>
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    fn node_weight_mut(&mut self, id: Self::NodeId) -> Option<&mut Self::NodeWeight> {
+        self.node_weight_mut(id)
+    }
+    fn edge_weight_mut(&mut self, id: Self::EdgeId) -> Option<&mut Self::EdgeWeight> {
+        self.edge_weight_mut(id)
+    }
+}
+
+impl<N, E, Ty, Ix> Build for CowGraph<N, E, Ty, Ix>
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    fn add_node(&mut self, weight: Self::NodeWeight) -> Self::NodeId {
+        self.add_node(weight)
+    }
+    fn add_edge(
+        &mut self,
+        a: Self::NodeId,
+        b: Self::NodeId,
+        weight: Self::EdgeWeight,
+    ) -> Option<Self::EdgeId> {
+        Some(self.add_edge(a, b, weight))
+    }
+    fn update_edge(
+        &mut self,
+        a: Self::NodeId,
+        b: Self::NodeId,
+        weight: Self::EdgeWeight,
+    ) -> Self::EdgeId {
+        self.update_edge(a, b, weight)
+    }
+}
+
+impl<N, E, Ty, Ix> Create for CowGraph<N, E, Ty, Ix>
+where
+    N: Clone,
+    E: Clone,
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    fn with_capacity(nodes: usize, edges: usize) -> Self {
+        Self::with_capacity(nodes, edges)
+    }
+}
+
+impl<N, E, Ty, Ix> visit::GraphProp for CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type EdgeType = Ty;
+}
+
+impl<N, E, Ty, Ix> visit::Visitable for CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type Map = FixedBitSet;
+    fn visit_map(&self) -> FixedBitSet {
+        FixedBitSet::with_capacity(self.node_count())
+    }
+
+    fn reset_map(&self, map: &mut Self::Map) {
+        map.clear();
+        map.grow(self.node_count());
+    }
+}
+
+impl<N, E, Ty, Ix> visit::NodeCount for CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    fn node_count(&self) -> usize {
+        self.node_count()
+    }
+}
+
+impl<N, E, Ty, Ix> visit::EdgeCount for CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    #[inline]
+    fn edge_count(&self) -> usize {
+        self.edge_count()
+    }
+}
+
+impl<N, E, Ty, Ix> visit::NodeIndexable for CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    #[inline]
+    fn node_bound(&self) -> usize {
+        self.node_count()
+    }
+    #[inline]
+    fn to_index(&self, ix: NodeIndex<Ix>) -> usize {
+        ix.index()
+    }
+    #[inline]
+    fn from_index(&self, ix: usize) -> Self::NodeId {
+        NodeIndex::new(ix)
+    }
+}
+
+impl<N, E, Ty, Ix> visit::NodeCompactIndexable for CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+}
+
+impl<N, E, Ty, Ix> visit::EdgeIndexable for CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    fn edge_bound(&self) -> usize {
+        self.edge_count()
+    }
+
+    fn to_index(&self, ix: EdgeIndex<Ix>) -> usize {
+        ix.index()
+    }
+
+    fn from_index(&self, ix: usize) -> Self::EdgeId {
+        EdgeIndex::new(ix)
+    }
+}
+
+impl<'a, N, E: 'a, Ty, Ix> visit::IntoNodeIdentifiers for &'a CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type NodeIdentifiers = NodeIndices<Ix>;
+    fn node_identifiers(self) -> NodeIndices<Ix> {
+        CowGraph::node_indices(self)
+    }
+}
+
+impl<'a, N, E, Ty, Ix> visit::IntoNodeReferences for &'a CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type NodeRef = (NodeIndex<Ix>, &'a N);
+    type NodeReferences = NodeReferences<'a, N, Ix>;
+    fn node_references(self) -> Self::NodeReferences {
+        NodeReferences {
+            iter: self.nodes.iter(),
+        }
+    }
+}
+
+impl<'a, N, E: 'a, Ty, Ix> visit::IntoNeighbors for &'a CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type Neighbors = Neighbors<'a, E, Ix>;
+    fn neighbors(self, n: NodeIndex<Ix>) -> Neighbors<'a, E, Ix> {
+        CowGraph::neighbors(self, n)
+    }
+}
+
+impl<'a, N, E: 'a, Ty, Ix> visit::IntoNeighborsDirected for &'a CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type NeighborsDirected = Neighbors<'a, E, Ix>;
+    fn neighbors_directed(self, n: NodeIndex<Ix>, d: Direction) -> Neighbors<'a, E, Ix> {
+        CowGraph::neighbors_directed(self, n, d)
+    }
+}
+
+impl<'a, N: 'a, E: 'a, Ty, Ix> visit::IntoEdgeReferences for &'a CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type EdgeRef = EdgeReference<'a, E, Ix>;
+    type EdgeReferences = EdgeReferences<'a, E, Ix>;
+    fn edge_references(self) -> Self::EdgeReferences {
+        (*self).edge_references()
+    }
+}
+
+impl<'a, N, E, Ty, Ix> visit::IntoEdges for &'a CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type Edges = Edges<'a, E, Ty, Ix>;
+    fn edges(self, a: Self::NodeId) -> Self::Edges {
+        self.edges(a)
+    }
+}
+
+impl<'a, N, E, Ty, Ix> visit::IntoEdgesDirected for &'a CowGraph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+{
+    type EdgesDirected = Edges<'a, E, Ty, Ix>;
+    fn edges_directed(self, a: Self::NodeId, dir: Direction) -> Self::EdgesDirected {
+        self.edges_directed(a, dir)
+    }
+}
@@ -16,6 +16,9 @@ use crate::iter_format::{DebugMap, IterFormatExt, NoPretty};
 use crate::util::enumerate;
 use crate::visit;
 
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
 #[cfg(feature = "serde-1")]
 mod serialization;
 
@@ -1038,6 +1041,36 @@ where
         }
     }
 
+    /// Return a parallel iterator over all nodes of the graph, in indexed
+    /// order.
+    ///
+    /// Iterator element type is `(NodeIndex<Ix>, &N)`.
+    #[cfg(feature = "rayon")]
+    pub fn par_nodes(&self) -> ParNodes<'_, N, Ix>
+    where
+        N: Sync,
+        Ix: Send + Sync,
+    {
+        ParNodes {
+            iter: self.nodes.par_iter().enumerate(),
+        }
+    }
+
+    /// Return a parallel iterator over all edges of the graph, in indexed
+    /// order.
+    ///
+    /// Iterator element type is `EdgeReference<E, Ix>`.
+    #[cfg(feature = "rayon")]
+    pub fn par_edges(&self) -> ParEdges<'_, E, Ix>
+    where
+        E: Sync,
+        Ix: Send + Sync,
+    {
+        ParEdges {
+            iter: self.edges.par_iter().enumerate(),
+        }
+    }
+
     /// Return an iterator yielding immutable access to all edge weights.
     ///
     /// The order in which weights are yielded matches the order of their
@@ -1291,6 +1324,75 @@ where
         }
     }
 
+    /// Remove all nodes without edges, and return their weights, in the order
+    /// of their former indices. Loops count as edges.
+    ///
+    /// Node indices are invalidated as they would be following the removal of
+    /// each node.
+    ///
+    /// Computes in **O(|V| + |E|)** time.
+    pub fn remove_isolated_nodes(&mut self) -> Vec<N> {
+        self.peel_degree_below(1)
+    }
+
+    /// Remove all loops, i.e. edges from a node to itself, and return their
+    /// weights, in the order of their former indices.
+    ///
+    /// Edge indices are invalidated as they would be following the removal of
+    /// each edge.
+    pub fn remove_self_loops(&mut self) -> Vec<E> {
+        let loops: Vec<_> = self
+            .edge_indices()
+            .filter(|e| {
+                let edge = &self.edges[e.index()];
+                edge.source() == edge.target()
+            })
+            .collect();
+        // Remove from the last, so that the indices of the next are unchanged
+        let mut weights: Vec<_> = loops
+            .into_iter()
+            .rev()
+            .filter_map(|e| self.remove_edge(e))
+            .collect();
+        weights.reverse();
+        weights
+    }
+
+    /// Repeatedly remove the nodes of degree less than `k`, until all nodes
+    /// have degree at least `k`, and return the weights of the removed nodes,
+    /// in the order of their former indices. What remains is the *k-core* of
+    /// the graph.
+    ///
+    /// The degree of a node is its number of edges, both incoming and
+    /// outgoing, where loops count twice.
+    ///
+    /// Node indices are invalidated as they would be following the removal of
+    /// each node.
+    ///
+    /// ```
+    /// use petgraph::Graph;
+    ///
+    /// // A triangle with a tail of two nodes
+    /// let mut graph = Graph::<_, ()>::new();
+    /// let nodes: Vec<_> = (0..5).map(|i| graph.add_node(i)).collect();
+    /// graph.extend_with_edges(&[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4)]);
+    ///
+    /// assert_eq!(graph.peel_degree_below(2), vec![3, 4]);
+    /// assert_eq!(graph.node_count(), 3);
+    /// ```
+    pub fn peel_degree_below(&mut self, k: usize) -> Vec<N> {
+        let removed = peel_below(&*self, k);
+        let mut weights = Vec::new();
+        // Remove from the last, so that the indices of the next are unchanged
+        for index in self.node_indices().rev() {
+            if removed[index.index()] {
+                weights.extend(self.remove_node(index));
+            }
+        }
+        weights.reverse();
+        weights
+    }
+
     /// Create a new `Graph` from an iterable of edges.
     ///
     /// Node weights `N` are set to default values.
@@ -1418,6 +1520,57 @@ where
         g
     }
 
+    /// Create a new `Graph` from the subgraph induced by `nodes`, i.e. the
+    /// given nodes and all edges between them.
+    ///
+    /// Return the subgraph together with a map from its node indices to the
+    /// node indices in `self`: node `n` of the subgraph is node
+    /// `node_map[n.index()]` of `self`. Nodes keep their relative order.
+    /// Duplicate and out of bounds indices in `nodes` are ignored.
+    ///
+    /// For a lightweight view that does not copy the graph, use
+    /// [`NodeFiltered`](../visit/struct.NodeFiltered.html) with a set of nodes.
+    ///
+    /// Computes in **O(|V| + |E|)** time.
+    ///
+    /// ```
+    /// use petgraph::graph::{DiGraph, NodeIndex};
+    ///
+    /// let g = DiGraph::<&str, u32>::from_edges(&[(0, 1, 1), (1, 2, 2), (2, 3, 3), (3, 1, 4)]);
+    /// let (sub, node_map) = g.induced_subgraph(vec![NodeIndex::new(3), NodeIndex::new(1)]);
+    ///
+    /// assert_eq!(node_map, vec![NodeIndex::new(1), NodeIndex::new(3)]);
+    /// assert_eq!(sub.node_count(), 2);
+    /// assert_eq!(sub.edge_count(), 1);
+    /// assert_eq!(sub.edge_weights().copied().collect::<Vec<_>>(), vec![4]);
+    /// ```
+    pub fn induced_subgraph<I>(&self, nodes: I) -> (Graph<N, E, Ty, Ix>, Vec<NodeIndex<Ix>>)
+    where
+        I: IntoIterator<Item = NodeIndex<Ix>>,
+        N: Clone,
+        E: Clone,
+    {
+        let mut keep = FixedBitSet::with_capacity(self.node_count());
+        for n in nodes {
+            if n.index() < self.node_count() {
+                keep.insert(n.index());
+            }
+        }
+        let mut node_map = Vec::with_capacity(keep.count_ones(..));
+        let sub = self.filter_map(
+            |n, weight| {
+                if keep.contains(n.index()) {
+                    node_map.push(n);
+                    Some(weight.clone())
+                } else {
+                    None
+                }
+            },
+            |_, weight| Some(weight.clone()),
+        );
+        (sub, node_map)
+    }
+
     /// Convert the graph into either undirected or directed. No edge adjustments
     /// are done, so you may want to go over the result to remove or add edges.
     ///
@@ -2375,6 +2528,123 @@ where
 
 impl<'a, E, Ix> ExactSizeIterator for EdgeReferences<'a, E, Ix> where Ix: IndexType {}
 
+/// A [ParallelIterator] over the nodes of a graph.
+#[cfg(feature = "rayon")]
+pub struct ParNodes<'a, N: 'a, Ix: IndexType = DefaultIx> {
+    iter: rayon::iter::Enumerate<rayon::slice::Iter<'a, Node<N, Ix>>>,
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, N, Ix> ParallelIterator for ParNodes<'a, N, Ix>
+where
+    N: Sync,
+    Ix: IndexType + Send + Sync,
+{
+    type Item = (NodeIndex<Ix>, &'a N);
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: rayon::iter::plumbing::UnindexedConsumer<Self::Item>,
+    {
+        self.iter
+            .map(|(i, node)| (node_index(i), &node.weight))
+            .drive_unindexed(consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        self.iter.opt_len()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, N, Ix> IndexedParallelIterator for ParNodes<'a, N, Ix>
+where
+    N: Sync,
+    Ix: IndexType + Send + Sync,
+{
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: rayon::iter::plumbing::Consumer<Self::Item>,
+    {
+        self.iter
+            .map(|(i, node)| (node_index(i), &node.weight))
+            .drive(consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: rayon::iter::plumbing::ProducerCallback<Self::Item>,
+    {
+        self.iter
+            .map(|(i, node)| (node_index(i), &node.weight))
+            .with_producer(callback)
+    }
+}
+
+/// A [ParallelIterator] over the edges of a graph.
+#[cfg(feature = "rayon")]
+pub struct ParEdges<'a, E: 'a, Ix: IndexType = DefaultIx> {
+    iter: rayon::iter::Enumerate<rayon::slice::Iter<'a, Edge<E, Ix>>>,
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, E, Ix> ParallelIterator for ParEdges<'a, E, Ix>
+where
+    E: Sync,
+    Ix: IndexType + Send + Sync,
+{
+    type Item = EdgeReference<'a, E, Ix>;
+
+    fn drive_unindexed<C>(self, consumer: C) -> C::Result
+    where
+        C: rayon::iter::plumbing::UnindexedConsumer<Self::Item>,
+    {
+        self.iter.map(edge_reference).drive_unindexed(consumer)
+    }
+
+    fn opt_len(&self) -> Option<usize> {
+        self.iter.opt_len()
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<'a, E, Ix> IndexedParallelIterator for ParEdges<'a, E, Ix>
+where
+    E: Sync,
+    Ix: IndexType + Send + Sync,
+{
+    fn drive<C>(self, consumer: C) -> C::Result
+    where
+        C: rayon::iter::plumbing::Consumer<Self::Item>,
+    {
+        self.iter.map(edge_reference).drive(consumer)
+    }
+
+    fn len(&self) -> usize {
+        self.iter.len()
+    }
+
+    fn with_producer<CB>(self, callback: CB) -> CB::Output
+    where
+        CB: rayon::iter::plumbing::ProducerCallback<Self::Item>,
+    {
+        self.iter.map(edge_reference).with_producer(callback)
+    }
+}
+
+#[cfg(feature = "rayon")]
+fn edge_reference<E, Ix: IndexType>((i, edge): (usize, &Edge<E, Ix>)) -> EdgeReference<'_, E, Ix> {
+    EdgeReference {
+        index: edge_index(i),
+        node: edge.node,
+        weight: &edge.weight,
+    }
+}
+
 impl<N, E, Ty, Ix> visit::EdgeIndexable for Graph<N, E, Ty, Ix>
 where
     Ty: EdgeType,
@@ -2393,6 +2663,7 @@ where
     }
 }
 
+pub mod cow_graph;
 mod frozen;
 #[cfg(feature = "stable_graph")]
 pub mod stable_graph;
@@ -2409,3 +2680,44 @@ pub mod stable_graph;
 /// See indexing implementations and the traits `Data` and `DataMap`
 /// for read-write access to the graph's weights.
 pub struct Frozen<'a, G: 'a>(&'a mut G);
+
+/// Find the nodes removed by repeatedly removing the nodes of degree less
+/// than `k`, by node index.
+fn peel_below<G>(graph: G, k: usize) -> Vec<bool>
+where
+    G: visit::IntoEdgeReferences + visit::IntoNodeIdentifiers + visit::NodeIndexable,
+{
+    use crate::visit::EdgeRef;
+
+    let bound = graph.node_bound();
+    let mut degree = vec![0; bound];
+    let mut neighbors = vec![Vec::new(); bound];
+    for edge in graph.edge_references() {
+        let (a, b) = (graph.to_index(edge.source()), graph.to_index(edge.target()));
+        degree[a] += 1;
+        degree[b] += 1;
+        if a != b {
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+        }
+    }
+    let mut removed = vec![false; bound];
+    let mut stack: Vec<usize> = graph
+        .node_identifiers()
+        .map(|n| graph.to_index(n))
+        .filter(|&v| degree[v] < k)
+        .collect();
+    while let Some(v) = stack.pop() {
+        if removed[v] {
+            continue;
+        }
+        removed[v] = true;
+        for &w in &neighbors[v] {
+            degree[w] -= 1;
+            if !removed[w] && degree[w] < k {
+                stack.push(w);
+            }
+        }
+    }
+    removed
+}
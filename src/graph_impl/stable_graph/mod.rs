@@ -197,6 +197,65 @@ where
         self.g.capacity()
     }
 
+    /// Reserves capacity for at least `additional` more nodes to be inserted in
+    /// the graph. Graph may reserve more space to avoid frequent reallocations.
+    ///
+    /// **Panics** if the new capacity overflows `usize`.
+    pub fn reserve_nodes(&mut self, additional: usize) {
+        self.g.reserve_nodes(additional);
+    }
+
+    /// Reserves capacity for at least `additional` more edges to be inserted in
+    /// the graph. Graph may reserve more space to avoid frequent reallocations.
+    ///
+    /// **Panics** if the new capacity overflows `usize`.
+    pub fn reserve_edges(&mut self, additional: usize) {
+        self.g.reserve_edges(additional);
+    }
+
+    /// Reserves the minimum capacity for exactly `additional` more nodes to be
+    /// inserted in the graph. Does nothing if the capacity is already
+    /// sufficient.
+    ///
+    /// Prefer `reserve_nodes` if future insertions are expected.
+    ///
+    /// **Panics** if the new capacity overflows `usize`.
+    pub fn reserve_exact_nodes(&mut self, additional: usize) {
+        self.g.reserve_exact_nodes(additional);
+    }
+
+    /// Reserves the minimum capacity for exactly `additional` more edges to be
+    /// inserted in the graph.
+    /// Does nothing if the capacity is already sufficient.
+    ///
+    /// Prefer `reserve_edges` if future insertions are expected.
+    ///
+    /// **Panics** if the new capacity overflows `usize`.
+    pub fn reserve_exact_edges(&mut self, additional: usize) {
+        self.g.reserve_exact_edges(additional);
+    }
+
+    /// Shrinks the capacity of the underlying nodes collection as much as possible.
+    ///
+    /// Vacant node slots are kept, so that node indices remain stable.
+    pub fn shrink_to_fit_nodes(&mut self) {
+        self.g.shrink_to_fit_nodes();
+    }
+
+    /// Shrinks the capacity of the underlying edges collection as much as possible.
+    ///
+    /// Vacant edge slots are kept, so that edge indices remain stable.
+    pub fn shrink_to_fit_edges(&mut self) {
+        self.g.shrink_to_fit_edges();
+    }
+
+    /// Shrinks the capacity of the graph as much as possible.
+    ///
+    /// Vacant node and edge slots are kept, so that indices remain stable.
+    pub fn shrink_to_fit(&mut self) {
+        self.g.shrink_to_fit();
+    }
+
     /// Reverse the direction of all edges
     pub fn reverse(&mut self) {
         // swap edge endpoints,
@@ -834,6 +893,68 @@ where
         self.check_free_lists();
     }
 
+    /// Remove all nodes without edges, and return their indices and weights,
+    /// in the order of their indices. Loops count as edges.
+    ///
+    /// The node indices of the removed nodes are invalidated, but none other.
+    ///
+    /// Computes in **O(|V| + |E|)** time.
+    pub fn remove_isolated_nodes(&mut self) -> Vec<(NodeIndex<Ix>, N)> {
+        self.peel_degree_below(1)
+    }
+
+    /// Remove all loops, i.e. edges from a node to itself, and return their
+    /// indices and weights, in the order of their indices.
+    ///
+    /// The edge indices of the removed edges are invalidated, but none other.
+    pub fn remove_self_loops(&mut self) -> Vec<(EdgeIndex<Ix>, E)> {
+        let loops: Vec<_> = self
+            .edge_references()
+            .filter(|e| e.source() == e.target())
+            .map(|e| e.id())
+            .collect();
+        loops
+            .into_iter()
+            .filter_map(|e| Some((e, self.remove_edge(e)?)))
+            .collect()
+    }
+
+    /// Repeatedly remove the nodes of degree less than `k`, until all nodes
+    /// have degree at least `k`, and return the indices and weights of the
+    /// removed nodes, in the order of their indices. What remains is the
+    /// *k-core* of the graph.
+    ///
+    /// The degree of a node is its number of edges, both incoming and
+    /// outgoing, where loops count twice.
+    ///
+    /// The node indices of the removed nodes are invalidated, but none other.
+    ///
+    /// ```
+    /// use petgraph::stable_graph::{node_index, StableGraph};
+    ///
+    /// // A triangle with a tail of two nodes
+    /// let mut graph = StableGraph::<_, ()>::new();
+    /// let nodes: Vec<_> = (0..5).map(|i| graph.add_node(i)).collect();
+    /// graph.extend_with_edges(&[(0, 1), (1, 2), (2, 0), (2, 3), (3, 4)]);
+    ///
+    /// assert_eq!(
+    ///     graph.peel_degree_below(2),
+    ///     vec![(node_index(3), 3), (node_index(4), 4)]
+    /// );
+    /// assert_eq!(graph.node_count(), 3);
+    /// ```
+    pub fn peel_degree_below(&mut self, k: usize) -> Vec<(NodeIndex<Ix>, N)> {
+        let removed = super::peel_below(&*self, k);
+        let mut nodes = Vec::new();
+        for (i, &remove) in removed.iter().enumerate() {
+            if remove {
+                let ix = node_index(i);
+                nodes.extend(self.remove_node(ix).map(|weight| (ix, weight)));
+            }
+        }
+        nodes
+    }
+
     /// Create a new `StableGraph` from an iterable of edges.
     ///
     /// Node weights `N` are set to default values.
@@ -19,7 +19,7 @@ use std::slice::Iter;
 use crate::{Directed, Direction, EdgeType, Incoming, Outgoing, Undirected};
 
 use crate::graph::node_index;
-use crate::graph::Graph;
+use crate::graph::{Frozen, Graph};
 use crate::visit;
 use crate::IntoWeightedEdge;
 
@@ -217,6 +217,64 @@ where
         (self.nodes.capacity(), self.edges.capacity())
     }
 
+    /// Reserves capacity for at least `additional` more nodes to be inserted in
+    /// the graph. Graph may reserve more space to avoid frequent reallocations.
+    ///
+    /// **Panics** if the new capacity overflows `usize`.
+    pub fn reserve_nodes(&mut self, additional: usize) {
+        self.nodes.reserve(additional);
+    }
+
+    /// Reserves capacity for at least `additional` more edges to be inserted in
+    /// the graph. Graph may reserve more space to avoid frequent reallocations.
+    ///
+    /// **Panics** if the new capacity overflows `usize`.
+    pub fn reserve_edges(&mut self, additional: usize) {
+        self.edges.reserve(additional);
+    }
+
+    /// Reserves the minimum capacity for exactly `additional` more nodes to be
+    /// inserted in the graph. Does nothing if the capacity is already
+    /// sufficient.
+    ///
+    /// Prefer `reserve_nodes` if future insertions are expected.
+    ///
+    /// **Panics** if the new capacity overflows `usize`.
+    pub fn reserve_exact_nodes(&mut self, additional: usize) {
+        self.nodes.reserve_exact(additional);
+    }
+
+    /// Reserves the minimum capacity for exactly `additional` more edges to be
+    /// inserted in the graph.
+    /// Does nothing if the capacity is already sufficient.
+    ///
+    /// Prefer `reserve_edges` if future insertions are expected.
+    ///
+    /// **Panics** if the new capacity overflows `usize`.
+    pub fn reserve_exact_edges(&mut self, additional: usize) {
+        self.edges.reserve_exact(additional);
+    }
+
+    /// Shrinks the capacity of the underlying nodes collection, and of the
+    /// adjacency list of every node, as much as possible.
+    pub fn shrink_to_fit_nodes(&mut self) {
+        self.nodes.shrink_to_fit();
+        for neighbors in self.nodes.values_mut() {
+            neighbors.shrink_to_fit();
+        }
+    }
+
+    /// Shrinks the capacity of the underlying edges collection as much as possible.
+    pub fn shrink_to_fit_edges(&mut self) {
+        self.edges.shrink_to_fit();
+    }
+
+    /// Shrinks the capacity of the graph as much as possible.
+    pub fn shrink_to_fit(&mut self) {
+        self.shrink_to_fit_nodes();
+        self.shrink_to_fit_edges();
+    }
+
     /// Use their natural order to map the node pair (a, b) to a canonical edge id.
     #[inline]
     fn edge_key(a: N, b: N) -> (N, N) {
@@ -410,6 +468,76 @@ where
         self.edges.contains_key(&Self::edge_key(a, b))
     }
 
+    /// Keep all nodes that return `true` from the `visit` closure,
+    /// remove the others.
+    ///
+    /// `visit` is provided a proxy reference to the graph, so that
+    /// the graph can be walked and associated data modified.
+    ///
+    /// The order nodes are visited is not specified.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average), independently of the
+    /// number of removed nodes.
+    pub fn retain_nodes<F>(&mut self, mut visit: F)
+    where
+        F: FnMut(Frozen<Self>, N) -> bool,
+    {
+        let mut removed = HashSet::new();
+        for i in 0..self.nodes.len() {
+            let (&n, _) = self.nodes.get_index(i).unwrap();
+            if !visit(Frozen::new(self), n) {
+                removed.insert(n);
+            }
+        }
+        if removed.is_empty() {
+            return;
+        }
+        self.nodes.retain(|n, _| !removed.contains(n));
+        self.edges
+            .retain(|(a, b), _| !removed.contains(a) && !removed.contains(b));
+        for links in self.nodes.values_mut() {
+            links.retain(|(succ, _)| !removed.contains(succ));
+        }
+    }
+
+    /// Keep all edges that return `true` from the `visit` closure,
+    /// remove the others.
+    ///
+    /// `visit` is provided a proxy reference to the graph and the endpoints
+    /// of the edge, so that the graph can be walked and associated data
+    /// modified.
+    ///
+    /// The order edges are visited is not specified.
+    ///
+    /// Computes in **O(|V| + |E|)** time (average), independently of the
+    /// number of removed edges.
+    pub fn retain_edges<F>(&mut self, mut visit: F)
+    where
+        F: FnMut(Frozen<Self>, N, N) -> bool,
+    {
+        let mut removed = HashSet::new();
+        for i in 0..self.edges.len() {
+            let (&(a, b), _) = self.edges.get_index(i).unwrap();
+            if !visit(Frozen::new(self), a, b) {
+                removed.insert((a, b));
+            }
+        }
+        if removed.is_empty() {
+            return;
+        }
+        self.edges.retain(|edge, _| !removed.contains(edge));
+        for (&a, links) in self.nodes.iter_mut() {
+            links.retain(|&(b, dir)| {
+                let edge = if dir == CompactDirection::Outgoing {
+                    Self::edge_key(a, b)
+                } else {
+                    Self::edge_key(b, a)
+                };
+                !removed.contains(&edge)
+            });
+        }
+    }
+
     /// Return an iterator over the nodes of the graph.
     ///
     /// Iterator element type is `N`.
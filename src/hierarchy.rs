@@ -0,0 +1,246 @@
+//! A hierarchy of nested groups of nodes, overlaid on a flat graph.
+
+use std::collections::HashMap;
+
+use fixedbitset::FixedBitSet;
+
+use crate::algo::Cycle;
+use crate::graph::{DefaultIx, Graph, IndexType, NodeIndex};
+use crate::visit::{EdgeRef, GraphBase, GraphProp, IntoEdgeReferences, IntoNodeIdentifiers};
+
+/// A parent-pointer forest over the nodes of a graph, representing their
+/// nesting in groups, e.g. the modules of a dataflow graph.
+///
+/// Each node may have a parent node, and the group of a node consists of its
+/// descendants in the forest. The group nodes are nodes of the graph as well;
+/// the hierarchy only stores the parent and children of each node, and is kept
+/// separate from the graph, so that any graph indexed by [`NodeIndex`] can be
+/// used, e.g. a [`Graph`] or a `StableGraph`.
+///
+/// Groups can be [collapsed](Hierarchy::collapse): the
+/// [quotient](Hierarchy::quotient) of a graph then merges the nodes of each
+/// collapsed group into a single node, while the edges crossing the groups are
+/// kept and can be [routed](Hierarchy::route) through the hierarchy.
+///
+/// # Example
+/// ```rust
+/// use petgraph::hierarchy::Hierarchy;
+/// use petgraph::prelude::*;
+///
+/// // A module `m` containing `a` and `b`, used by `x`
+/// let mut graph = DiGraph::<&str, ()>::new();
+/// let m = graph.add_node("m");
+/// let a = graph.add_node("a");
+/// let b = graph.add_node("b");
+/// let x = graph.add_node("x");
+/// graph.extend_with_edges(&[(a, b), (x, a), (b, x)]);
+///
+/// let mut hierarchy = Hierarchy::new();
+/// hierarchy.set_parent(a, m).unwrap();
+/// hierarchy.set_parent(b, m).unwrap();
+/// assert!(hierarchy.set_parent(m, a).is_err());
+///
+/// // The edges crossing the boundary of the module
+/// assert_eq!(hierarchy.boundary_edges(&graph, m).len(), 2);
+///
+/// // Collapse the module: `a` and `b` are merged into `m`
+/// hierarchy.collapse(m);
+/// let view = hierarchy.quotient(&graph);
+/// assert_eq!(view.node_count(), 2);
+/// assert_eq!(view.edge_count(), 2);
+/// assert_eq!(hierarchy.representative(b), m);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Hierarchy<Ix = DefaultIx> {
+    parent: Vec<Option<NodeIndex<Ix>>>,
+    children: Vec<Vec<NodeIndex<Ix>>>,
+    collapsed: FixedBitSet,
+}
+
+impl<Ix: IndexType> Default for Hierarchy<Ix> {
+    fn default() -> Self {
+        Hierarchy {
+            parent: Vec::new(),
+            children: Vec::new(),
+            collapsed: FixedBitSet::default(),
+        }
+    }
+}
+
+impl<Ix: IndexType> Hierarchy<Ix> {
+    /// Create an empty hierarchy, where no node has a parent.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make `parent` the parent of `child`, detaching it from its previous
+    /// parent.
+    ///
+    /// Return an error, leaving the hierarchy unchanged, if `child` is
+    /// `parent` or one of its ancestors.
+    pub fn set_parent(
+        &mut self,
+        child: NodeIndex<Ix>,
+        parent: NodeIndex<Ix>,
+    ) -> Result<(), Cycle<NodeIndex<Ix>>> {
+        if self.contains(child, parent) {
+            return Err(Cycle(child));
+        }
+        self.detach(child);
+        let bound = std::cmp::max(child.index(), parent.index()) + 1;
+        if self.parent.len() < bound {
+            self.parent.resize(bound, None);
+            self.children.resize(bound, Vec::new());
+        }
+        self.parent[child.index()] = Some(parent);
+        self.children[parent.index()].push(child);
+        Ok(())
+    }
+
+    /// Detach `node` from its parent, and return the parent, if any.
+    ///
+    /// The descendants of `node` stay in its group.
+    pub fn detach(&mut self, node: NodeIndex<Ix>) -> Option<NodeIndex<Ix>> {
+        let parent = self.parent.get_mut(node.index())?.take()?;
+        self.children[parent.index()].retain(|&c| c != node);
+        Some(parent)
+    }
+
+    /// Return the parent of `node`, if any.
+    pub fn parent(&self, node: NodeIndex<Ix>) -> Option<NodeIndex<Ix>> {
+        self.parent.get(node.index()).and_then(|&p| p)
+    }
+
+    /// Return the children of `node`, in the order they were attached.
+    pub fn children(&self, node: NodeIndex<Ix>) -> &[NodeIndex<Ix>] {
+        self.children.get(node.index()).map_or(&[], |c| c)
+    }
+
+    /// Return an iterator over the ancestors of `node`, from its parent to the
+    /// root of its tree.
+    pub fn ancestors(&self, node: NodeIndex<Ix>) -> impl Iterator<Item = NodeIndex<Ix>> + '_ {
+        let mut next = self.parent(node);
+        std::iter::from_fn(move || {
+            let node = next?;
+            next = self.parent(node);
+            Some(node)
+        })
+    }
+
+    /// Return the descendants of `group`, excluding itself, in depth-first
+    /// preorder.
+    pub fn descendants(&self, group: NodeIndex<Ix>) -> Vec<NodeIndex<Ix>> {
+        let mut descendants = Vec::new();
+        let mut stack: Vec<_> = self.children(group).iter().rev().copied().collect();
+        while let Some(node) = stack.pop() {
+            descendants.push(node);
+            stack.extend(self.children(node).iter().rev());
+        }
+        descendants
+    }
+
+    /// Return `true` if `node` is in the group of `group`, i.e. if `group` is
+    /// `node` or one of its ancestors.
+    pub fn contains(&self, group: NodeIndex<Ix>, node: NodeIndex<Ix>) -> bool {
+        node == group || self.ancestors(node).any(|a| a == group)
+    }
+
+    /// Collapse `group`, so that its descendants are merged into it by
+    /// [`representative`](Hierarchy::representative) and
+    /// [`quotient`](Hierarchy::quotient).
+    pub fn collapse(&mut self, group: NodeIndex<Ix>) {
+        self.collapsed.grow(group.index() + 1);
+        self.collapsed.insert(group.index());
+    }
+
+    /// Expand `group`, undoing [`collapse`](Hierarchy::collapse).
+    pub fn expand(&mut self, group: NodeIndex<Ix>) {
+        if group.index() < self.collapsed.len() {
+            self.collapsed.set(group.index(), false);
+        }
+    }
+
+    /// Return `true` if `group` is collapsed.
+    pub fn is_collapsed(&self, group: NodeIndex<Ix>) -> bool {
+        self.collapsed.contains(group.index())
+    }
+
+    /// Return the node that `node` is merged into: its outermost collapsed
+    /// ancestor, or `node` itself if none is collapsed.
+    pub fn representative(&self, node: NodeIndex<Ix>) -> NodeIndex<Ix> {
+        self.ancestors(node)
+            .filter(|&a| self.is_collapsed(a))
+            .last()
+            .unwrap_or(node)
+    }
+
+    /// Return how to route an edge from `a` to `b` through the hierarchy: the
+    /// groups it exits, from the innermost group of `a`, and the groups it
+    /// enters, down to the innermost group of `b`.
+    ///
+    /// The groups containing both nodes are not crossed. A node is not crossed
+    /// when the edge leaves or reaches it, e.g. an edge from a group node to
+    /// one of its children crosses no group.
+    #[allow(clippy::type_complexity)]
+    pub fn route(
+        &self,
+        a: NodeIndex<Ix>,
+        b: NodeIndex<Ix>,
+    ) -> (Vec<NodeIndex<Ix>>, Vec<NodeIndex<Ix>>) {
+        let mut exits: Vec<_> = self.ancestors(a).collect();
+        let mut entries: Vec<_> = self.ancestors(b).collect();
+        // Drop the common ancestors, from the roots
+        while !exits.is_empty() && exits.last() == entries.last() {
+            exits.pop();
+            entries.pop();
+        }
+        // An edge between a group and its own members stays inside the group
+        exits.retain(|&g| g != b);
+        entries.retain(|&g| g != a);
+        entries.reverse();
+        (exits, entries)
+    }
+
+    /// Return the edges of `graph` crossing the boundary of `group`: those
+    /// with exactly one endpoint in the group of `group`.
+    pub fn boundary_edges<G>(&self, graph: G, group: NodeIndex<Ix>) -> Vec<G::EdgeId>
+    where
+        G: IntoEdgeReferences + GraphBase<NodeId = NodeIndex<Ix>>,
+    {
+        graph
+            .edge_references()
+            .filter(|e| self.contains(group, e.source()) != self.contains(group, e.target()))
+            .map(|e| e.id())
+            .collect()
+    }
+
+    /// Return the quotient of `graph` by the collapsed groups, where the nodes
+    /// of each collapsed group are merged into a single node.
+    ///
+    /// The quotient has a node for each
+    /// [`representative`](Hierarchy::representative) of the nodes of `graph`,
+    /// with the representative as weight, in the order of their first node in
+    /// `graph`. Each edge of `graph` between different representatives gives an
+    /// edge between them, with its id as weight; the edges inside a collapsed
+    /// group are dropped. With no collapsed group, the quotient is a copy of
+    /// the structure of `graph`.
+    pub fn quotient<G>(&self, graph: G) -> Graph<NodeIndex<Ix>, G::EdgeId, G::EdgeType, Ix>
+    where
+        G: IntoNodeIdentifiers + IntoEdgeReferences + GraphProp + GraphBase<NodeId = NodeIndex<Ix>>,
+    {
+        let mut quotient = Graph::default();
+        let mut nodes = HashMap::new();
+        for node in graph.node_identifiers() {
+            let r = self.representative(node);
+            nodes.entry(r).or_insert_with(|| quotient.add_node(r));
+        }
+        for edge in graph.edge_references() {
+            let a = nodes[&self.representative(edge.source())];
+            let b = nodes[&self.representative(edge.target())];
+            if a != b {
+                quotient.add_edge(a, b, edge.id());
+            }
+        }
+        quotient
+    }
+}
@@ -0,0 +1,283 @@
+//! A hypergraph, whose hyperedges connect arbitrary sets of nodes.
+
+use std::collections::BTreeMap;
+
+use crate::graph::{DefaultIx, EdgeIndex, Graph, IndexType, NodeIndex};
+use crate::Undirected;
+
+/// A node or a hyperedge of a [`Hypergraph`], as the nodes of its
+/// [star expansion](Hypergraph::star_expansion).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StarNode<N, E> {
+    /// A node of the hypergraph.
+    Node(N),
+    /// A hyperedge of the hypergraph.
+    Hyperedge(E),
+}
+
+/// `Hypergraph<N, E, Ix>` is a hypergraph datastructure: its hyperedges connect
+/// sets of any number of nodes, e.g. the participants of a meeting or the
+/// authors of a paper.
+///
+/// Nodes and hyperedges are identified by [`NodeIndex`] and [`EdgeIndex`], and
+/// carry a weight of type `N` and `E` respectively. The nodes of each hyperedge
+/// are stored as a sorted set, and every node records the hyperedges incident to
+/// it, so that both directions of the incidence relation can be walked in time
+/// proportional to their size.
+///
+/// The hypergraph can be flattened into an ordinary [`Graph`] with
+/// [`clique_expansion`](Hypergraph::clique_expansion) or
+/// [`star_expansion`](Hypergraph::star_expansion), to run the graph
+/// algorithms of the crate on it.
+///
+/// # Example
+/// ```rust
+/// use petgraph::hypergraph::Hypergraph;
+///
+/// let mut hypergraph = Hypergraph::<&str, &str>::new();
+/// let a = hypergraph.add_node("a");
+/// let b = hypergraph.add_node("b");
+/// let c = hypergraph.add_node("c");
+/// let meeting = hypergraph.add_hyperedge([a, b, c], "meeting");
+/// let call = hypergraph.add_hyperedge([b, c], "call");
+///
+/// assert_eq!(hypergraph.members(meeting), &[a, b, c]);
+/// assert_eq!(hypergraph.incident_hyperedges(c), &[meeting, call]);
+///
+/// // Link the nodes sharing a hyperedge, weighted by how many they share
+/// let graph = hypergraph.clique_expansion(|_, _, shared| shared.len());
+/// assert_eq!(graph.edge_count(), 3);
+/// let bc = graph.find_edge(b, c).unwrap();
+/// assert_eq!(graph[bc], 2);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Hypergraph<N, E, Ix = DefaultIx> {
+    nodes: Vec<HyperNode<N, Ix>>,
+    hyperedges: Vec<Hyperedge<E, Ix>>,
+}
+
+#[derive(Clone, Debug)]
+struct HyperNode<N, Ix> {
+    weight: N,
+    hyperedges: Vec<EdgeIndex<Ix>>,
+}
+
+#[derive(Clone, Debug)]
+struct Hyperedge<E, Ix> {
+    weight: E,
+    members: Vec<NodeIndex<Ix>>,
+}
+
+impl<N, E, Ix> Default for Hypergraph<N, E, Ix> {
+    fn default() -> Self {
+        Hypergraph {
+            nodes: Vec::new(),
+            hyperedges: Vec::new(),
+        }
+    }
+}
+
+impl<N, E> Hypergraph<N, E> {
+    /// Create a new, empty `Hypergraph`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<N, E, Ix: IndexType> Hypergraph<N, E, Ix> {
+    /// Create a new, empty `Hypergraph` with estimated capacity.
+    pub fn with_capacity(nodes: usize, hyperedges: usize) -> Self {
+        Hypergraph {
+            nodes: Vec::with_capacity(nodes),
+            hyperedges: Vec::with_capacity(hyperedges),
+        }
+    }
+
+    /// Return the number of nodes.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Return the number of hyperedges.
+    pub fn hyperedge_count(&self) -> usize {
+        self.hyperedges.len()
+    }
+
+    /// Add a node with weight `weight` and return its index.
+    ///
+    /// **Panics** if the hypergraph is at the maximum number of nodes for its
+    /// index type.
+    pub fn add_node(&mut self, weight: N) -> NodeIndex<Ix> {
+        let node = NodeIndex::new(self.nodes.len());
+        assert!(
+            <Ix as IndexType>::max().index() == !0 || NodeIndex::end() != node,
+            "Hypergraph::add_node: node index type is too small"
+        );
+        self.nodes.push(HyperNode {
+            weight,
+            hyperedges: Vec::new(),
+        });
+        node
+    }
+
+    /// Add a hyperedge over the nodes `members` with weight `weight` and return
+    /// its index.
+    ///
+    /// Repeated members are only counted once. A hyperedge may have any number
+    /// of members, including none or one.
+    ///
+    /// **Panics** if any member does not exist, or if the hypergraph is at the
+    /// maximum number of hyperedges for its index type.
+    pub fn add_hyperedge<I>(&mut self, members: I, weight: E) -> EdgeIndex<Ix>
+    where
+        I: IntoIterator<Item = NodeIndex<Ix>>,
+    {
+        let hyperedge = EdgeIndex::new(self.hyperedges.len());
+        assert!(
+            <Ix as IndexType>::max().index() == !0 || EdgeIndex::end() != hyperedge,
+            "Hypergraph::add_hyperedge: edge index type is too small"
+        );
+        let mut members: Vec<_> = members.into_iter().collect();
+        members.sort();
+        members.dedup();
+        // Check all the members before updating any node
+        for &node in &members {
+            assert!(
+                node.index() < self.nodes.len(),
+                "Hypergraph::add_hyperedge: node index {} does not exist",
+                node.index()
+            );
+        }
+        for &node in &members {
+            self.nodes[node.index()].hyperedges.push(hyperedge);
+        }
+        self.hyperedges.push(Hyperedge { weight, members });
+        hyperedge
+    }
+
+    /// Access the weight of node `a`.
+    ///
+    /// Return `None` if the node doesn't exist.
+    pub fn node_weight(&self, a: NodeIndex<Ix>) -> Option<&N> {
+        self.nodes.get(a.index()).map(|n| &n.weight)
+    }
+
+    /// Access the weight of node `a`, mutably.
+    ///
+    /// Return `None` if the node doesn't exist.
+    pub fn node_weight_mut(&mut self, a: NodeIndex<Ix>) -> Option<&mut N> {
+        self.nodes.get_mut(a.index()).map(|n| &mut n.weight)
+    }
+
+    /// Access the weight of hyperedge `e`.
+    ///
+    /// Return `None` if the hyperedge doesn't exist.
+    pub fn hyperedge_weight(&self, e: EdgeIndex<Ix>) -> Option<&E> {
+        self.hyperedges.get(e.index()).map(|h| &h.weight)
+    }
+
+    /// Access the weight of hyperedge `e`, mutably.
+    ///
+    /// Return `None` if the hyperedge doesn't exist.
+    pub fn hyperedge_weight_mut(&mut self, e: EdgeIndex<Ix>) -> Option<&mut E> {
+        self.hyperedges.get_mut(e.index()).map(|h| &mut h.weight)
+    }
+
+    /// Return the nodes of hyperedge `e`, in increasing index order.
+    ///
+    /// **Panics** if the hyperedge doesn't exist.
+    pub fn members(&self, e: EdgeIndex<Ix>) -> &[NodeIndex<Ix>] {
+        &self.hyperedges[e.index()].members
+    }
+
+    /// Return the hyperedges containing node `a`, in increasing index order.
+    ///
+    /// **Panics** if the node doesn't exist.
+    pub fn incident_hyperedges(&self, a: NodeIndex<Ix>) -> &[EdgeIndex<Ix>] {
+        &self.nodes[a.index()].hyperedges
+    }
+
+    /// Return the number of hyperedges containing node `a`.
+    ///
+    /// **Panics** if the node doesn't exist.
+    pub fn degree(&self, a: NodeIndex<Ix>) -> usize {
+        self.incident_hyperedges(a).len()
+    }
+
+    /// Return an iterator over the node indices of the hypergraph.
+    pub fn node_indices(&self) -> impl Iterator<Item = NodeIndex<Ix>> {
+        (0..self.node_count()).map(NodeIndex::new)
+    }
+
+    /// Return an iterator over the hyperedge indices of the hypergraph.
+    pub fn hyperedge_indices(&self) -> impl Iterator<Item = EdgeIndex<Ix>> {
+        (0..self.hyperedge_count()).map(EdgeIndex::new)
+    }
+
+    /// Create the clique expansion of the hypergraph: an undirected graph with
+    /// the same nodes, where two nodes are adjacent if they share at least one
+    /// hyperedge.
+    ///
+    /// Node indices are preserved. Each pair of adjacent nodes `a < b` gets a
+    /// single edge, weighted by `edge_weight(a, b, shared)` where `shared` are
+    /// the hyperedges containing both, in increasing index order.
+    ///
+    /// Computes in **O(|V| + Σ k²)** time, where `k` ranges over the sizes of
+    /// the hyperedges.
+    pub fn clique_expansion<W, F>(&self, mut edge_weight: F) -> Graph<N, W, Undirected, Ix>
+    where
+        N: Clone,
+        F: FnMut(NodeIndex<Ix>, NodeIndex<Ix>, &[EdgeIndex<Ix>]) -> W,
+    {
+        let mut shared: BTreeMap<_, Vec<_>> = BTreeMap::new();
+        for (i, hyperedge) in self.hyperedges.iter().enumerate() {
+            for (j, &a) in hyperedge.members.iter().enumerate() {
+                for &b in &hyperedge.members[j + 1..] {
+                    shared.entry((a, b)).or_default().push(EdgeIndex::new(i));
+                }
+            }
+        }
+
+        let mut graph = Graph::with_capacity(self.node_count(), shared.len());
+        for node in &self.nodes {
+            graph.add_node(node.weight.clone());
+        }
+        for ((a, b), hyperedges) in shared {
+            let weight = edge_weight(a, b, &hyperedges);
+            graph.add_edge(a, b, weight);
+        }
+        graph
+    }
+
+    /// Create the star expansion of the hypergraph: a bipartite undirected
+    /// graph with a node for each node and each hyperedge of the hypergraph,
+    /// where each hyperedge is adjacent to its members.
+    ///
+    /// The nodes keep their indices, and hyperedge `e` becomes node
+    /// `self.node_count() + e.index()`. Unlike the clique expansion, the star
+    /// expansion loses no information.
+    ///
+    /// Computes in **O(|V| + Σ k)** time, where `k` ranges over the sizes of
+    /// the hyperedges.
+    ///
+    /// **Panics** if the expansion has too many nodes for the index type.
+    pub fn star_expansion(&self) -> Graph<StarNode<N, E>, (), Undirected, Ix>
+    where
+        N: Clone,
+        E: Clone,
+    {
+        let incidences = self.hyperedges.iter().map(|h| h.members.len()).sum();
+        let mut graph =
+            Graph::with_capacity(self.node_count() + self.hyperedge_count(), incidences);
+        for node in &self.nodes {
+            graph.add_node(StarNode::Node(node.weight.clone()));
+        }
+        for hyperedge in &self.hyperedges {
+            let star = graph.add_node(StarNode::Hyperedge(hyperedge.weight.clone()));
+            for &member in &hyperedge.members {
+                graph.add_edge(member, star, ());
+            }
+        }
+        graph
+    }
+}
@@ -49,6 +49,9 @@
 //!   An adjacency list graph with arbitrary associated data.
 //! * [`StableGraph`](./stable_graph/struct.StableGraph.html) -
 //!   Similar to `Graph`, but it keeps indices stable across removals.
+//! * [`CowGraph`](./cow_graph/struct.CowGraph.html) -
+//!   Similar to `Graph`, but with copy-on-write storage shared between clones, making
+//!   cloning **O(1)**.
 //! * [`GraphMap`](./graphmap/struct.GraphMap.html) -
 //!   An adjacency list graph backed by a hash table. The node identifiers are the keys
 //!   into the table.
@@ -56,6 +59,11 @@
 //!   An adjacency matrix graph.
 //! * [`CSR`](./csr/struct.Csr.html) -
 //!   A sparse adjacency matrix graph with arbitrary associated data.
+//! * [`Hypergraph`](./hypergraph/struct.Hypergraph.html) -
+//!   A hypergraph whose hyperedges connect arbitrary sets of nodes, with conversions to
+//!   ordinary graphs.
+//! * [`PortGraph`](./port_graph/struct.PortGraph.html) -
+//!   A directed graph whose edges connect ordered, typed ports of the nodes.
 //!
 //! ### Generic parameters
 //!
@@ -102,6 +110,17 @@
 //!   Defaults on. Enables [`StableGraph`](./stable_graph/struct.StableGraph.html).
 //! * **matrix_graph** -
 //!   Defaults on. Enables [`MatrixGraph`](./matrix_graph/struct.MatrixGraph.html).
+//! * **nalgebra** -
+//!   Defaults off. Enables [`spectral_clustering`](./algo/fn.spectral_clustering.html),
+//!   using [`nalgebra`](https://crates.io/crates/nalgebra) for linear algebra. May
+//!   require a more recent version of Rust than petgraph alone.
+//! * **ndarray** -
+//!   Defaults off. Enables the export of adjacency and Laplacian matrices to
+//!   [`ndarray`](https://crates.io/crates/ndarray) in the `matrix` module. May
+//!   require a more recent version of Rust than petgraph alone.
+//! * **geometry** -
+//!   Defaults off. Enables the [`geometry`](./geometry/index.html) module, for
+//!   graphs whose nodes carry coordinates.
 //!
 #![doc(html_root_url = "https://docs.rs/petgraph/0.4/")]
 
@@ -137,27 +156,39 @@ pub mod acyclic;
 pub mod adj;
 pub mod algo;
 pub mod csr;
+pub mod diff;
 pub mod dot;
 #[cfg(feature = "generate")]
 pub mod generate;
+#[cfg(feature = "geometry")]
+pub mod geometry;
 pub mod graph6;
 mod graph_impl;
 #[cfg(feature = "graphmap")]
 pub mod graphmap;
+pub mod hierarchy;
+pub mod hypergraph;
 mod iter_format;
 mod iter_utils;
+#[cfg(feature = "ndarray")]
+pub mod matrix;
 #[cfg(feature = "matrix_graph")]
 pub mod matrix_graph;
 #[cfg(feature = "quickcheck")]
-mod quickcheck;
+pub mod quickcheck;
 #[cfg(feature = "serde-1")]
 mod serde_utils;
+pub mod simple;
 mod traits_graph;
 pub mod unionfind;
 mod util;
 
+pub mod observed;
 pub mod operator;
+pub mod port_graph;
 pub mod prelude;
+pub mod rewrite;
+pub mod shared;
 
 /// `Graph<N, E, Ty, Ix>` is a graph datastructure using an adjacency list representation.
 pub mod graph {
@@ -167,8 +198,11 @@ pub mod graph {
         GraphIndex, IndexType, Neighbors, Node, NodeIndex, NodeIndices, NodeReferences,
         NodeWeightsMut, UnGraph, WalkNeighbors,
     };
+    #[cfg(feature = "rayon")]
+    pub use crate::graph_impl::{ParEdges, ParNodes};
 }
 
+pub use crate::graph_impl::cow_graph;
 #[cfg(feature = "stable_graph")]
 pub use crate::graph_impl::stable_graph;
 
@@ -0,0 +1,120 @@
+//! Export of graphs to dense [`ndarray`](https://crates.io/crates/ndarray)
+//! matrices, for use with numeric and graph signal processing libraries.
+//!
+//! The rows and columns of the matrices follow the order of the nodes in
+//! [`IntoNodeIdentifiers::node_identifiers`], which for [`Graph`](crate::Graph)
+//! is the order of their indices.
+//!
+//! Requires crate feature `"ndarray"`.
+
+use std::collections::HashMap;
+
+use ndarray::Array2;
+
+use crate::visit::{EdgeRef, GraphProp, IntoEdgeReferences, IntoNodeIdentifiers, NodeIndexable};
+
+/// The normalization of a Laplacian matrix, see [`to_laplacian`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Normalization {
+    /// The combinatorial Laplacian `L = D - A`.
+    None,
+    /// The symmetric normalized Laplacian `D^(-1/2) L D^(-1/2)`.
+    Symmetric,
+    /// The random walk normalized Laplacian `D^(-1) L`.
+    RandomWalk,
+}
+
+/// Build the weighted adjacency matrix of a graph.
+///
+/// The entry at row `i` and column `j` is the total weight of the edges from
+/// node `i` to node `j`, as given by `edge_weight`. The matrix of an undirected
+/// graph is symmetric, and its loops are counted once.
+///
+/// # Example
+/// ```rust
+/// use ndarray::array;
+/// use petgraph::matrix::to_adjacency_matrix;
+/// use petgraph::prelude::*;
+///
+/// let graph = DiGraph::<(), f64>::from_edges(&[(0, 1, 2.), (1, 2, 0.5), (0, 1, 1.)]);
+/// let adjacency = to_adjacency_matrix(&graph, |e| *e.weight());
+/// assert_eq!(
+///     adjacency,
+///     array![[0., 3., 0.], [0., 0., 0.5], [0., 0., 0.]]
+/// );
+/// ```
+pub fn to_adjacency_matrix<G, F>(graph: G, mut edge_weight: F) -> Array2<f64>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    let compact: HashMap<_, _> = graph
+        .node_identifiers()
+        .enumerate()
+        .map(|(i, node)| (graph.to_index(node), i))
+        .collect();
+    let n = compact.len();
+    let mut adjacency = Array2::zeros((n, n));
+    for edge in graph.edge_references() {
+        let a = compact[&graph.to_index(edge.source())];
+        let b = compact[&graph.to_index(edge.target())];
+        let w = edge_weight(edge);
+        adjacency[[a, b]] += w;
+        if !graph.is_directed() && a != b {
+            adjacency[[b, a]] += w;
+        }
+    }
+    adjacency
+}
+
+/// Build the weighted Laplacian matrix of a graph, with the given
+/// normalization.
+///
+/// The Laplacian is `L = D - A`, where `A` is the matrix of
+/// [`to_adjacency_matrix`] and `D` is the diagonal matrix of the row sums of
+/// `A`, i.e. the weighted out-degrees of the nodes. The rows of nodes of degree
+/// zero are zero in the normalized Laplacians.
+///
+/// # Example
+/// ```rust
+/// use ndarray::array;
+/// use petgraph::matrix::{to_laplacian, Normalization};
+/// use petgraph::prelude::*;
+///
+/// let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+/// assert_eq!(
+///     to_laplacian(&graph, Normalization::None, |_| 1.),
+///     array![[1., -1., 0.], [-1., 2., -1.], [0., -1., 1.]]
+/// );
+/// assert_eq!(
+///     to_laplacian(&graph, Normalization::RandomWalk, |_| 1.),
+///     array![[1., -1., 0.], [-0.5, 1., -0.5], [0., -1., 1.]]
+/// );
+/// ```
+pub fn to_laplacian<G, F>(graph: G, normalization: Normalization, edge_weight: F) -> Array2<f64>
+where
+    G: IntoEdgeReferences + IntoNodeIdentifiers + NodeIndexable + GraphProp,
+    F: FnMut(G::EdgeRef) -> f64,
+{
+    let mut laplacian = -to_adjacency_matrix(graph, edge_weight);
+    let degree: Vec<f64> = laplacian.rows().into_iter().map(|row| -row.sum()).collect();
+    for (i, &d) in degree.iter().enumerate() {
+        laplacian[[i, i]] += d;
+    }
+    let inverse = |d: f64| if d != 0. { 1. / d } else { 0. };
+    match normalization {
+        Normalization::None => {}
+        Normalization::Symmetric => {
+            let scale: Vec<f64> = degree.iter().map(|&d| inverse(d).sqrt()).collect();
+            for ((i, j), x) in laplacian.indexed_iter_mut() {
+                *x *= scale[i] * scale[j];
+            }
+        }
+        Normalization::RandomWalk => {
+            for ((i, _), x) in laplacian.indexed_iter_mut() {
+                *x *= inverse(degree[i]);
+            }
+        }
+    }
+    laplacian
+}
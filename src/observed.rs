@@ -0,0 +1,513 @@
+//! A wrapper around graph types that notifies an observer of every mutation.
+
+use std::ops::Deref;
+
+use crate::{
+    data::{Build, Create, DataMap, DataMapMut},
+    graph::{EdgeIndex, Graph, IndexType, NodeIndex},
+    visit::{Data, EdgeCount, GraphBase, GraphProp, NodeCount},
+    Direction::{Incoming, Outgoing},
+    EdgeType,
+};
+
+#[cfg(feature = "stable_graph")]
+use crate::{stable_graph::StableGraph, visit::EdgeRef};
+
+/// Callbacks for the mutations of a graph of type `G`, wrapped in
+/// [`Observed`].
+///
+/// All methods do nothing by default, so that observers only implement the
+/// events they need. They are called right after the mutation, with the
+/// mutated graph.
+///
+/// Removing a node first removes its edges, one at a time, and some graph
+/// types, like [`Graph`], move the last node or edge into the place of the
+/// removed one: observers keeping data indexed by node or edge ids should then
+/// also implement [`on_node_moved`](GraphObserver::on_node_moved) and
+/// [`on_edge_moved`](GraphObserver::on_edge_moved).
+///
+/// Two observers can be combined into a pair, which notifies both in order.
+pub trait GraphObserver<G: Data> {
+    /// Node `node` was added.
+    fn on_node_added(&mut self, _graph: &G, _node: G::NodeId) {}
+
+    /// Edge `edge` from `source` to `target` was added.
+    fn on_edge_added(
+        &mut self,
+        _graph: &G,
+        _edge: G::EdgeId,
+        _source: G::NodeId,
+        _target: G::NodeId,
+    ) {
+    }
+
+    /// Node `node`, with weight `weight`, was removed. Its edges were removed
+    /// before.
+    fn on_node_removed(&mut self, _graph: &G, _node: G::NodeId, _weight: &G::NodeWeight) {}
+
+    /// Edge `edge` from `source` to `target`, with weight `weight`, was
+    /// removed.
+    fn on_edge_removed(
+        &mut self,
+        _graph: &G,
+        _edge: G::EdgeId,
+        _source: G::NodeId,
+        _target: G::NodeId,
+        _weight: &G::EdgeWeight,
+    ) {
+    }
+
+    /// The node with id `from` now has id `to`, after the removal of node `to`.
+    fn on_node_moved(&mut self, _graph: &G, _from: G::NodeId, _to: G::NodeId) {}
+
+    /// The edge with id `from` now has id `to`, after the removal of edge `to`.
+    fn on_edge_moved(&mut self, _graph: &G, _from: G::EdgeId, _to: G::EdgeId) {}
+}
+
+impl<G: Data> GraphObserver<G> for () {}
+
+impl<G: Data, A, B> GraphObserver<G> for (A, B)
+where
+    A: GraphObserver<G>,
+    B: GraphObserver<G>,
+{
+    fn on_node_added(&mut self, graph: &G, node: G::NodeId) {
+        self.0.on_node_added(graph, node);
+        self.1.on_node_added(graph, node);
+    }
+
+    fn on_edge_added(&mut self, graph: &G, edge: G::EdgeId, source: G::NodeId, target: G::NodeId) {
+        self.0.on_edge_added(graph, edge, source, target);
+        self.1.on_edge_added(graph, edge, source, target);
+    }
+
+    fn on_node_removed(&mut self, graph: &G, node: G::NodeId, weight: &G::NodeWeight) {
+        self.0.on_node_removed(graph, node, weight);
+        self.1.on_node_removed(graph, node, weight);
+    }
+
+    fn on_edge_removed(
+        &mut self,
+        graph: &G,
+        edge: G::EdgeId,
+        source: G::NodeId,
+        target: G::NodeId,
+        weight: &G::EdgeWeight,
+    ) {
+        self.0.on_edge_removed(graph, edge, source, target, weight);
+        self.1.on_edge_removed(graph, edge, source, target, weight);
+    }
+
+    fn on_node_moved(&mut self, graph: &G, from: G::NodeId, to: G::NodeId) {
+        self.0.on_node_moved(graph, from, to);
+        self.1.on_node_moved(graph, from, to);
+    }
+
+    fn on_edge_moved(&mut self, graph: &G, from: G::EdgeId, to: G::EdgeId) {
+        self.0.on_edge_moved(graph, from, to);
+        self.1.on_edge_moved(graph, from, to);
+    }
+}
+
+/// A graph whose mutations are reported to a [`GraphObserver`].
+///
+/// Wrap a graph to keep derived structures, such as caches or indices into
+/// the graph, consistent with it, without having to update them at every
+/// mutating call. The graph can only be modified through the wrapper: nodes
+/// and edges are added with the graph construction trait [`Build`], and
+/// removed with the `remove_node` and `remove_edge` methods of the wrapper for
+/// [`Graph`] and [`StableGraph`]. Node and edge weights can be modified
+/// without notification.
+///
+/// The wrapped graph is accessible through [`Deref`], to run algorithms on it.
+///
+/// # Example
+/// ```rust
+/// use petgraph::data::Build;
+/// use petgraph::observed::{GraphObserver, Observed};
+/// use petgraph::prelude::*;
+///
+/// // Keep the degree of each node, indexed by node index
+/// #[derive(Default)]
+/// struct Degrees(Vec<usize>);
+///
+/// impl GraphObserver<DiGraph<(), ()>> for Degrees {
+///     fn on_node_added(&mut self, _: &DiGraph<(), ()>, _: NodeIndex) {
+///         self.0.push(0);
+///     }
+///     fn on_edge_added(&mut self, _: &DiGraph<(), ()>, _: EdgeIndex, a: NodeIndex, b: NodeIndex) {
+///         self.0[a.index()] += 1;
+///         self.0[b.index()] += 1;
+///     }
+///     fn on_edge_removed(&mut self, _: &DiGraph<(), ()>, _: EdgeIndex, a: NodeIndex, b: NodeIndex, _: &()) {
+///         self.0[a.index()] -= 1;
+///         self.0[b.index()] -= 1;
+///     }
+///     fn on_node_removed(&mut self, _: &DiGraph<(), ()>, n: NodeIndex, _: &()) {
+///         // `Graph` moves the last node into the place of the removed one
+///         self.0.swap_remove(n.index());
+///     }
+/// }
+///
+/// let mut graph = Observed::new(DiGraph::new(), Degrees::default());
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// graph.add_edge(a, b, ());
+/// graph.add_edge(b, c, ());
+/// graph.add_edge(c, a, ());
+/// assert_eq!(graph.observer().0, [2, 2, 2]);
+///
+/// // Removes the edges of `a`, then moves `c` to index 0
+/// graph.remove_node(a);
+/// assert_eq!(graph.observer().0, [1, 1]);
+/// assert_eq!(graph.node_count(), 2);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Observed<G, O> {
+    /// The underlying graph, accessible through the `inner` method.
+    graph: G,
+    observer: O,
+}
+
+impl<G, O> Observed<G, O> {
+    /// Wrap `graph`, reporting its mutations to `observer`.
+    ///
+    /// The observer is not notified of the existing nodes and edges of the
+    /// graph.
+    pub fn new(graph: G, observer: O) -> Self {
+        Observed { graph, observer }
+    }
+
+    /// Get the underlying graph.
+    pub fn inner(&self) -> &G {
+        &self.graph
+    }
+
+    /// Get the observer.
+    pub fn observer(&self) -> &O {
+        &self.observer
+    }
+
+    /// Get the observer mutably.
+    pub fn observer_mut(&mut self) -> &mut O {
+        &mut self.observer
+    }
+
+    /// Consume the `Observed` wrapper and return the underlying graph and the
+    /// observer.
+    pub fn into_parts(self) -> (G, O) {
+        (self.graph, self.observer)
+    }
+}
+
+impl<G: GraphBase, O> GraphBase for Observed<G, O> {
+    type NodeId = G::NodeId;
+    type EdgeId = G::EdgeId;
+}
+
+impl<G: Data, O> Data for Observed<G, O> {
+    type NodeWeight = G::NodeWeight;
+    type EdgeWeight = G::EdgeWeight;
+}
+
+impl<G: NodeCount, O> NodeCount for Observed<G, O> {
+    fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+}
+
+impl<G: DataMap, O> DataMap for Observed<G, O> {
+    fn node_weight(&self, id: Self::NodeId) -> Option<&Self::NodeWeight> {
+        self.graph.node_weight(id)
+    }
+
+    fn edge_weight(&self, id: Self::EdgeId) -> Option<&Self::EdgeWeight> {
+        self.graph.edge_weight(id)
+    }
+}
+
+impl<G: DataMapMut, O> DataMapMut for Observed<G, O> {
+    fn node_weight_mut(&mut self, id: Self::NodeId) -> Option<&mut Self::NodeWeight> {
+        self.graph.node_weight_mut(id)
+    }
+
+    fn edge_weight_mut(&mut self, id: Self::EdgeId) -> Option<&mut Self::EdgeWeight> {
+        self.graph.edge_weight_mut(id)
+    }
+}
+
+impl<G, O> Build for Observed<G, O>
+where
+    G: Build + EdgeCount,
+    O: GraphObserver<G>,
+{
+    fn add_node(&mut self, weight: Self::NodeWeight) -> Self::NodeId {
+        let node = self.graph.add_node(weight);
+        self.observer.on_node_added(&self.graph, node);
+        node
+    }
+
+    fn add_edge(
+        &mut self,
+        a: Self::NodeId,
+        b: Self::NodeId,
+        weight: Self::EdgeWeight,
+    ) -> Option<Self::EdgeId> {
+        let edge = self.graph.add_edge(a, b, weight)?;
+        self.observer.on_edge_added(&self.graph, edge, a, b);
+        Some(edge)
+    }
+
+    /// Only reports the edge as added if it did not exist.
+    fn update_edge(
+        &mut self,
+        a: Self::NodeId,
+        b: Self::NodeId,
+        weight: Self::EdgeWeight,
+    ) -> Self::EdgeId {
+        let edge_count = self.graph.edge_count();
+        let edge = self.graph.update_edge(a, b, weight);
+        if self.graph.edge_count() > edge_count {
+            self.observer.on_edge_added(&self.graph, edge, a, b);
+        }
+        edge
+    }
+}
+
+impl<G, O> Create for Observed<G, O>
+where
+    G: Create + EdgeCount,
+    O: GraphObserver<G> + Default,
+{
+    fn with_capacity(nodes: usize, edges: usize) -> Self {
+        Observed {
+            graph: G::with_capacity(nodes, edges),
+            observer: O::default(),
+        }
+    }
+}
+
+impl<G: GraphProp, O> GraphProp for Observed<G, O> {
+    type EdgeType = G::EdgeType;
+}
+
+impl<G, O> Deref for Observed<G, O> {
+    type Target = G;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+impl<N, E, Ty, Ix, O> Observed<Graph<N, E, Ty, Ix>, O>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    O: GraphObserver<Graph<N, E, Ty, Ix>>,
+{
+    /// Remove an edge and return its edge weight, or None if it didn't exist.
+    ///
+    /// Reports the removal, then the move of the last edge into its place.
+    pub fn remove_edge(&mut self, e: EdgeIndex<Ix>) -> Option<E> {
+        let (a, b) = self.graph.edge_endpoints(e)?;
+        let last = EdgeIndex::new(self.graph.edge_count() - 1);
+        let weight = self.graph.remove_edge(e)?;
+        self.observer.on_edge_removed(&self.graph, e, a, b, &weight);
+        if e != last {
+            self.observer.on_edge_moved(&self.graph, last, e);
+        }
+        Some(weight)
+    }
+
+    /// Remove a node from the graph if it exists, and return its weight. If
+    /// it doesn't exist in the graph, return None.
+    ///
+    /// Reports the removal of its edges, then of the node, then the move of
+    /// the last node into its place.
+    pub fn remove_node(&mut self, n: NodeIndex<Ix>) -> Option<N> {
+        self.graph.node_weight(n)?;
+        while let Some(e) = self
+            .graph
+            .first_edge(n, Outgoing)
+            .or_else(|| self.graph.first_edge(n, Incoming))
+        {
+            self.remove_edge(e);
+        }
+        let last = NodeIndex::new(self.graph.node_count() - 1);
+        let weight = self.graph.remove_node(n)?;
+        self.observer.on_node_removed(&self.graph, n, &weight);
+        if n != last {
+            self.observer.on_node_moved(&self.graph, last, n);
+        }
+        Some(weight)
+    }
+}
+
+#[cfg(feature = "stable_graph")]
+impl<N, E, Ty, Ix, O> Observed<StableGraph<N, E, Ty, Ix>, O>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    O: GraphObserver<StableGraph<N, E, Ty, Ix>>,
+{
+    /// Remove an edge and return its edge weight, or None if it didn't exist.
+    pub fn remove_edge(&mut self, e: EdgeIndex<Ix>) -> Option<E> {
+        let (a, b) = self.graph.edge_endpoints(e)?;
+        let weight = self.graph.remove_edge(e)?;
+        self.observer.on_edge_removed(&self.graph, e, a, b, &weight);
+        Some(weight)
+    }
+
+    /// Remove a node from the graph if it exists, and return its weight. If
+    /// it doesn't exist in the graph, return None.
+    ///
+    /// Reports the removal of its edges, then of the node.
+    pub fn remove_node(&mut self, n: NodeIndex<Ix>) -> Option<N> {
+        self.graph.node_weight(n)?;
+        let mut edges: Vec<_> = self
+            .graph
+            .edges_directed(n, Outgoing)
+            .chain(self.graph.edges_directed(n, Incoming))
+            .map(|e| e.id())
+            .collect();
+        edges.sort();
+        edges.dedup();
+        for e in edges {
+            self.remove_edge(e);
+        }
+        let weight = self.graph.remove_node(n)?;
+        self.observer.on_node_removed(&self.graph, n, &weight);
+        Some(weight)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::dijkstra;
+    #[cfg(feature = "stable_graph")]
+    use crate::prelude::StableUnGraph;
+    use crate::prelude::{DiGraph, UnGraph};
+
+    /// Record the events as strings.
+    #[derive(Default)]
+    struct Log(Vec<String>);
+
+    impl<G: Data> GraphObserver<G> for Log
+    where
+        G::NodeId: std::fmt::Debug,
+        G::EdgeId: std::fmt::Debug,
+    {
+        fn on_node_added(&mut self, _: &G, node: G::NodeId) {
+            self.0.push(format!("+{:?}", node));
+        }
+
+        fn on_edge_added(&mut self, _: &G, edge: G::EdgeId, _: G::NodeId, _: G::NodeId) {
+            self.0.push(format!("+{:?}", edge));
+        }
+
+        fn on_node_removed(&mut self, _: &G, node: G::NodeId, _: &G::NodeWeight) {
+            self.0.push(format!("-{:?}", node));
+        }
+
+        fn on_edge_removed(
+            &mut self,
+            _: &G,
+            edge: G::EdgeId,
+            _: G::NodeId,
+            _: G::NodeId,
+            _: &G::EdgeWeight,
+        ) {
+            self.0.push(format!("-{:?}", edge));
+        }
+
+        fn on_node_moved(&mut self, _: &G, from: G::NodeId, to: G::NodeId) {
+            self.0.push(format!("{:?}>{:?}", from, to));
+        }
+
+        fn on_edge_moved(&mut self, _: &G, from: G::EdgeId, to: G::EdgeId) {
+            self.0.push(format!("{:?}>{:?}", from, to));
+        }
+    }
+
+    fn log<G, O>(graph: &mut Observed<G, (Log, O)>) -> String {
+        graph
+            .observer_mut()
+            .0
+             .0
+            .drain(..)
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn test_observed_graph() {
+        let mut graph = Observed::new(DiGraph::<u32, u32>::new(), (Log::default(), ()));
+        let a = graph.add_node(0);
+        let b = graph.add_node(1);
+        let c = graph.add_node(2);
+        graph.add_edge(a, b, 0);
+        graph.add_edge(b, c, 1);
+        graph.add_edge(c, c, 2);
+        assert_eq!(
+            log(&mut graph),
+            "+NodeIndex(0) +NodeIndex(1) +NodeIndex(2) \
+             +EdgeIndex(0) +EdgeIndex(1) +EdgeIndex(2)"
+        );
+
+        // Only new edges are reported
+        graph.update_edge(a, b, 3);
+        graph.update_edge(b, a, 4);
+        assert_eq!(log(&mut graph), "+EdgeIndex(3)");
+
+        assert_eq!(graph.remove_edge(EdgeIndex::new(0)), Some(3));
+        assert_eq!(log(&mut graph), "-EdgeIndex(0) EdgeIndex(3)>EdgeIndex(0)");
+
+        assert_eq!(graph.remove_node(b), Some(1));
+        assert_eq!(
+            log(&mut graph),
+            "-EdgeIndex(0) EdgeIndex(2)>EdgeIndex(0) -EdgeIndex(1) \
+             -NodeIndex(1) NodeIndex(2)>NodeIndex(1)"
+        );
+        assert_eq!(graph.remove_node(c), None);
+        assert_eq!(graph.edge_count(), 1);
+
+        // Algorithms run on the wrapped graph.
+        let (graph, _) = graph.into_parts();
+        assert_eq!(dijkstra(&graph, a, None, |_| 1).len(), 1);
+    }
+
+    #[test]
+    fn test_observed_undirected() {
+        let mut graph = Observed::new(UnGraph::<(), ()>::new_undirected(), (Log::default(), ()));
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        graph.add_edge(b, a, ());
+        graph.add_edge(a, a, ());
+        log(&mut graph);
+        graph.remove_node(a);
+        assert_eq!(
+            log(&mut graph),
+            "-EdgeIndex(1) -EdgeIndex(0) -NodeIndex(0) NodeIndex(1)>NodeIndex(0)"
+        );
+        assert_eq!(graph.node_count(), 1);
+    }
+
+    #[cfg(feature = "stable_graph")]
+    #[test]
+    fn test_observed_stable_graph() {
+        let mut graph = Observed::new(StableUnGraph::<(), ()>::default(), (Log::default(), ()));
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        let c = graph.add_node(());
+        graph.add_edge(c, a, ());
+        graph.add_edge(b, c, ());
+        graph.add_edge(a, b, ());
+        log(&mut graph);
+        graph.remove_node(a);
+        assert_eq!(log(&mut graph), "-EdgeIndex(0) -EdgeIndex(2) -NodeIndex(0)");
+        assert_eq!(graph.remove_edge(EdgeIndex::new(0)), None);
+        assert_eq!(graph.node_count(), 2);
+    }
+}
@@ -1,7 +1,12 @@
 //! Operators for creating new graphs from existings ones.
-use super::graph::{Graph, IndexType};
-use super::EdgeType;
-use crate::visit::IntoNodeReferences;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use fixedbitset::FixedBitSet;
+
+use super::graph::{EdgeIndex, Graph, IndexType, NodeIndex};
+use super::{EdgeType, Undirected};
+use crate::visit::{EdgeRef, IntoNodeReferences};
 
 /// \[Generic\] complement of the graph
 ///
@@ -81,3 +86,785 @@ pub fn complement<N, E, Ty, Ix>(
         }
     }
 }
+
+/// Index the edges of a graph by the keys of their endpoints, so that they
+/// can be matched against the edges of another graph.
+///
+/// Parallel edges are matched one to one, in the order of their indices.
+struct EdgeMatcher<K, Ix> {
+    /// Unmatched edges for each pair of endpoint keys, in reverse order.
+    edges: HashMap<(K, K), Vec<EdgeIndex<Ix>>>,
+    /// The edges that have already been matched.
+    matched: FixedBitSet,
+    directed: bool,
+}
+
+impl<K, Ix> EdgeMatcher<K, Ix>
+where
+    K: Clone + Eq + Hash,
+    Ix: IndexType,
+{
+    fn new<N, E, Ty: EdgeType>(graph: &Graph<N, E, Ty, Ix>, keys: &[K]) -> Self {
+        let mut edges = HashMap::<_, Vec<_>>::new();
+        for edge in graph.edge_references().rev() {
+            let pair = (
+                keys[edge.source().index()].clone(),
+                keys[edge.target().index()].clone(),
+            );
+            edges.entry(pair).or_default().push(edge.id());
+        }
+        EdgeMatcher {
+            edges,
+            matched: FixedBitSet::with_capacity(graph.edge_count()),
+            directed: graph.is_directed(),
+        }
+    }
+
+    /// Match an edge between the nodes with keys `a` and `b`, if there is an
+    /// unmatched one left.
+    fn take(&mut self, a: &K, b: &K) -> Option<EdgeIndex<Ix>> {
+        let edges = &mut self.edges;
+        let mut pop = |pair: (K, K)| edges.get_mut(&pair).and_then(Vec::pop);
+        let edge = match pop((a.clone(), b.clone())) {
+            None if !self.directed => pop((b.clone(), a.clone())),
+            edge => edge,
+        }?;
+        self.matched.insert(edge.index());
+        Some(edge)
+    }
+
+    fn is_matched(&self, edge: EdgeIndex<Ix>) -> bool {
+        self.matched.contains(edge.index())
+    }
+}
+
+/// Map each node of `graph` to its key, and each key to the first node of
+/// `graph` with that key.
+fn node_keys<N, E, Ty, Ix, K, F>(
+    graph: &Graph<N, E, Ty, Ix>,
+    key: &mut F,
+) -> (Vec<K>, HashMap<K, NodeIndex<Ix>>)
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    K: Clone + Eq + Hash,
+    F: FnMut(&N) -> K,
+{
+    let keys: Vec<K> = graph.node_weights().map(key).collect();
+    let mut first = HashMap::with_capacity(keys.len());
+    for (i, k) in keys.iter().enumerate() {
+        first.entry(k.clone()).or_insert_with(|| NodeIndex::new(i));
+    }
+    (keys, first)
+}
+
+/// \[Generic\] union of two graphs.
+///
+/// Nodes of `a` and `b` are identified by the key computed by the `key`
+/// closure. The resulting graph contains one node for each key of either
+/// graph and the union of the edges of both graphs, where edges are
+/// identified by the keys of their endpoints.
+///
+/// Nodes and edges present in both graphs get the weight returned by
+/// `merge_nodes` and `merge_edges`, called with the weights from `a` and
+/// from `b` respectively; the others keep their weight. Parallel edges are
+/// matched one to one in the order of their indices, so the edge
+/// multiplicity between two nodes is the maximum of the multiplicities in
+/// `a` and `b`.
+///
+/// The nodes of `a` come first, in their order in `a`, followed by the nodes
+/// only present in `b`. If several nodes of the same graph share a key, they
+/// are identified with the first of them. In particular, if the keys of `a`
+/// are distinct, its node indices are preserved.
+///
+/// Computes in **O(|V| + |E|)** time (average).
+///
+/// # Example
+/// ```rust
+/// use petgraph::operator::union;
+/// use petgraph::prelude::*;
+///
+/// let mut monday = DiGraph::<&str, u32>::new();
+/// let a = monday.add_node("a");
+/// let b = monday.add_node("b");
+/// monday.add_edge(a, b, 1);
+///
+/// let mut tuesday = DiGraph::<&str, u32>::new();
+/// let c = tuesday.add_node("c");
+/// let b = tuesday.add_node("b");
+/// let a = tuesday.add_node("a");
+/// tuesday.extend_with_edges(&[(a, b, 2), (b, c, 3)]);
+///
+/// let week = union(&monday, &tuesday, |&name| name, |&n, _| n, |x, y| x + y);
+/// assert_eq!(week.node_weights().copied().collect::<Vec<_>>(), vec!["a", "b", "c"]);
+/// let total: Vec<_> = week.edge_references().map(|e| *e.weight()).collect();
+/// assert_eq!(total, vec![3, 3]);
+/// ```
+pub fn union<N, E, Ty, Ix, K, F, NM, EM>(
+    a: &Graph<N, E, Ty, Ix>,
+    b: &Graph<N, E, Ty, Ix>,
+    mut key: F,
+    mut merge_nodes: NM,
+    mut merge_edges: EM,
+) -> Graph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    N: Clone,
+    E: Clone,
+    K: Clone + Eq + Hash,
+    F: FnMut(&N) -> K,
+    NM: FnMut(&N, &N) -> N,
+    EM: FnMut(&E, &E) -> E,
+{
+    let (keys_a, first_a) = node_keys(a, &mut key);
+    let (keys_b, first_b) = node_keys(b, &mut key);
+    let mut output = Graph::with_capacity(first_a.len(), a.edge_count());
+
+    // Map each key to its node in the output.
+    let mut nodes = HashMap::with_capacity(first_a.len() + first_b.len());
+    for (i, k) in keys_a.iter().enumerate() {
+        if first_a[k].index() == i {
+            let weight = match first_b.get(k) {
+                Some(&j) => merge_nodes(&a[NodeIndex::new(i)], &b[j]),
+                None => a[NodeIndex::new(i)].clone(),
+            };
+            nodes.insert(k.clone(), output.add_node(weight));
+        }
+    }
+    for (j, k) in keys_b.iter().enumerate() {
+        if !nodes.contains_key(k) {
+            nodes.insert(k.clone(), output.add_node(b[NodeIndex::new(j)].clone()));
+        }
+    }
+
+    let mut matcher = EdgeMatcher::new(b, &keys_b);
+    for edge in a.edge_references() {
+        let source = &keys_a[edge.source().index()];
+        let target = &keys_a[edge.target().index()];
+        let weight = match matcher.take(source, target) {
+            Some(e) => merge_edges(edge.weight(), &b[e]),
+            None => edge.weight().clone(),
+        };
+        output.add_edge(nodes[source], nodes[target], weight);
+    }
+    for edge in b.edge_references() {
+        if !matcher.is_matched(edge.id()) {
+            let source = nodes[&keys_b[edge.source().index()]];
+            let target = nodes[&keys_b[edge.target().index()]];
+            output.add_edge(source, target, edge.weight().clone());
+        }
+    }
+    output
+}
+
+/// \[Generic\] intersection of two graphs.
+///
+/// Nodes of `a` and `b` are identified by the key computed by the `key`
+/// closure. The resulting graph contains the nodes whose key is present in
+/// both graphs and the edges present in both graphs, where edges are
+/// identified by the keys of their endpoints.
+///
+/// The weights of the resulting nodes and edges are computed by
+/// `merge_nodes` and `merge_edges`, called with the weights from `a` and
+/// from `b` respectively. Parallel edges are matched one to one in the order
+/// of their indices, so the edge multiplicity between two nodes is the
+/// minimum of the multiplicities in `a` and `b`.
+///
+/// The nodes are in their order in `a`. If several nodes of the same graph
+/// share a key, they are identified with the first of them.
+///
+/// Computes in **O(|V| + |E|)** time (average).
+///
+/// # Example
+/// ```rust
+/// use petgraph::operator::intersection;
+/// use petgraph::prelude::*;
+///
+/// let mut monday = UnGraph::<&str, ()>::new_undirected();
+/// let a = monday.add_node("a");
+/// let b = monday.add_node("b");
+/// let c = monday.add_node("c");
+/// monday.extend_with_edges(&[(a, b), (b, c), (c, a)]);
+///
+/// let mut tuesday = UnGraph::<&str, ()>::new_undirected();
+/// let b = tuesday.add_node("b");
+/// let a = tuesday.add_node("a");
+/// let d = tuesday.add_node("d");
+/// tuesday.extend_with_edges(&[(a, b), (a, d)]);
+///
+/// let both = intersection(&monday, &tuesday, |&name| name, |&n, _| n, |_, _| ());
+/// assert_eq!(both.node_weights().copied().collect::<Vec<_>>(), vec!["a", "b"]);
+/// assert_eq!(both.edge_count(), 1);
+/// ```
+pub fn intersection<N, E, Ty, Ix, K, F, NM, EM>(
+    a: &Graph<N, E, Ty, Ix>,
+    b: &Graph<N, E, Ty, Ix>,
+    mut key: F,
+    mut merge_nodes: NM,
+    mut merge_edges: EM,
+) -> Graph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    K: Clone + Eq + Hash,
+    F: FnMut(&N) -> K,
+    NM: FnMut(&N, &N) -> N,
+    EM: FnMut(&E, &E) -> E,
+{
+    let (keys_a, first_a) = node_keys(a, &mut key);
+    let (keys_b, first_b) = node_keys(b, &mut key);
+    let mut output = Graph::with_capacity(0, 0);
+
+    let mut nodes = HashMap::new();
+    for (i, k) in keys_a.iter().enumerate() {
+        if first_a[k].index() == i {
+            if let Some(&j) = first_b.get(k) {
+                let weight = merge_nodes(&a[NodeIndex::new(i)], &b[j]);
+                nodes.insert(k.clone(), output.add_node(weight));
+            }
+        }
+    }
+
+    let mut matcher = EdgeMatcher::new(b, &keys_b);
+    for edge in a.edge_references() {
+        let source = &keys_a[edge.source().index()];
+        let target = &keys_a[edge.target().index()];
+        if let Some(e) = matcher.take(source, target) {
+            let weight = merge_edges(edge.weight(), &b[e]);
+            output.add_edge(nodes[source], nodes[target], weight);
+        }
+    }
+    output
+}
+
+/// \[Generic\] difference of two graphs.
+///
+/// Nodes of `a` and `b` are identified by the key computed by the `key`
+/// closure, and edges by the keys of their endpoints. The resulting graph
+/// contains all nodes of `a` and the edges of `a` that are not present in
+/// `b`. Parallel edges are matched one to one in the order of their
+/// indices, so the edge multiplicity between two nodes is the multiplicity
+/// in `a` minus the one in `b`, if positive.
+///
+/// The node indices of `a` are preserved.
+///
+/// Computes in **O(|V| + |E|)** time (average).
+///
+/// # Example
+/// ```rust
+/// use petgraph::operator::difference;
+/// use petgraph::prelude::*;
+///
+/// let mut monday = DiGraph::<&str, ()>::new();
+/// let a = monday.add_node("a");
+/// let b = monday.add_node("b");
+/// let c = monday.add_node("c");
+/// monday.extend_with_edges(&[(a, b), (b, c), (c, a)]);
+///
+/// let mut tuesday = DiGraph::<&str, ()>::new();
+/// let c2 = tuesday.add_node("c");
+/// let b2 = tuesday.add_node("b");
+/// let a2 = tuesday.add_node("a");
+/// tuesday.extend_with_edges(&[(b2, a2), (b2, c2)]);
+///
+/// let removed = difference(&monday, &tuesday, |&name| name);
+/// assert_eq!(removed.node_count(), 3);
+/// assert!(removed.contains_edge(a, b));
+/// assert!(removed.contains_edge(c, a));
+/// assert!(!removed.contains_edge(b, c));
+/// ```
+pub fn difference<N, E, Ty, Ix, K, F>(
+    a: &Graph<N, E, Ty, Ix>,
+    b: &Graph<N, E, Ty, Ix>,
+    mut key: F,
+) -> Graph<N, E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    N: Clone,
+    E: Clone,
+    K: Clone + Eq + Hash,
+    F: FnMut(&N) -> K,
+{
+    let (keys_a, _) = node_keys(a, &mut key);
+    let (keys_b, _) = node_keys(b, &mut key);
+
+    let mut matcher = EdgeMatcher::new(b, &keys_b);
+    a.filter_map(
+        |_, weight| Some(weight.clone()),
+        |e, weight| {
+            let (source, target) = a.edge_endpoints(e).unwrap();
+            match matcher.take(&keys_a[source.index()], &keys_a[target.index()]) {
+                Some(_) => None,
+                None => Some(weight.clone()),
+            }
+        },
+    )
+}
+
+/// Create a graph whose nodes are all pairs of nodes of `a` and `b`, without
+/// edges.
+///
+/// The node `(i, j)` has index [`product_index`]`(i, j, b.node_count())`.
+fn product_nodes<N1, N2, E1, E2, E, Ty, Ix>(
+    a: &Graph<N1, E1, Ty, Ix>,
+    b: &Graph<N2, E2, Ty, Ix>,
+    edges: usize,
+) -> Graph<(N1, N2), E, Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    N1: Clone,
+    N2: Clone,
+{
+    let mut output = Graph::with_capacity(a.node_count() * b.node_count(), edges);
+    for wa in a.node_weights() {
+        for wb in b.node_weights() {
+            output.add_node((wa.clone(), wb.clone()));
+        }
+    }
+    output
+}
+
+/// The index of the node `(i, j)` in a product graph.
+fn product_index<Ix: IndexType>(i: NodeIndex<Ix>, j: NodeIndex<Ix>, n: usize) -> NodeIndex<Ix> {
+    NodeIndex::new(i.index() * n + j.index())
+}
+
+/// Add the edges of the cartesian product of `a` and `b` to `output`.
+#[allow(clippy::type_complexity)]
+fn add_cartesian_edges<N1, N2, E1, E2, Ty, Ix>(
+    a: &Graph<N1, E1, Ty, Ix>,
+    b: &Graph<N2, E2, Ty, Ix>,
+    output: &mut Graph<(N1, N2), (Option<E1>, Option<E2>), Ty, Ix>,
+) where
+    Ty: EdgeType,
+    Ix: IndexType,
+    E1: Clone,
+    E2: Clone,
+{
+    let pair = |i, j| product_index(i, j, b.node_count());
+    for ea in a.edge_references() {
+        for j in b.node_indices() {
+            let weight = (Some(ea.weight().clone()), None);
+            output.add_edge(pair(ea.source(), j), pair(ea.target(), j), weight);
+        }
+    }
+    for i in a.node_indices() {
+        for eb in b.edge_references() {
+            let weight = (None, Some(eb.weight().clone()));
+            output.add_edge(pair(i, eb.source()), pair(i, eb.target()), weight);
+        }
+    }
+}
+
+/// Add the edges of the tensor product of `a` and `b` to `output`, with
+/// weights mapped by `weight`.
+fn add_tensor_edges<N1, N2, E1, E2, E, Ty, Ix, F>(
+    a: &Graph<N1, E1, Ty, Ix>,
+    b: &Graph<N2, E2, Ty, Ix>,
+    output: &mut Graph<(N1, N2), E, Ty, Ix>,
+    mut weight: F,
+) where
+    Ty: EdgeType,
+    Ix: IndexType,
+    F: FnMut(&E1, &E2) -> E,
+{
+    let pair = |i, j| product_index(i, j, b.node_count());
+    for ea in a.edge_references() {
+        for eb in b.edge_references() {
+            let (u, u2, v, v2) = (ea.source(), ea.target(), eb.source(), eb.target());
+            output.add_edge(pair(u, v), pair(u2, v2), weight(ea.weight(), eb.weight()));
+            // For undirected graphs, `u -- u2` and `v2 -- v` give rise to a
+            // second edge, unless one of them is a self-loop.
+            if !Ty::is_directed() && u != u2 && v != v2 {
+                output.add_edge(pair(u, v2), pair(u2, v), weight(ea.weight(), eb.weight()));
+            }
+        }
+    }
+}
+
+/// \[Generic\] cartesian product of two graphs.
+///
+/// The nodes of the product are all pairs `(x, y)` of a node `x` of `a` and
+/// a node `y` of `b`, with the pair of their weights as weight. The node
+/// `(x, y)` has index `x.index() * b.node_count() + y.index()`.
+///
+/// There is an edge from `(x, y)` to `(x2, y2)` if either `x == x2` and
+/// there is an edge from `y` to `y2` in `b`, or `y == y2` and there is an
+/// edge from `x` to `x2` in `a`. The edge weight is `(None, Some(w))` or
+/// `(Some(w), None)` respectively, where `w` is the weight of the edge in
+/// the operand.
+///
+/// If `a` and `b` are automata, the cartesian product models their
+/// asynchronous (interleaved) execution.
+///
+/// Computes in **O(|V1||V2| + |V1||E2| + |E1||V2|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::operator::cartesian_product;
+/// use petgraph::prelude::*;
+///
+/// // The cartesian product of two paths is a grid.
+/// let path = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+/// let grid = cartesian_product(&path, &path);
+/// assert_eq!(grid.node_count(), 9);
+/// assert_eq!(grid.edge_count(), 12);
+///
+/// // The center of the grid has four neighbors.
+/// assert_eq!(grid.neighbors(NodeIndex::new(1 * 3 + 1)).count(), 4);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn cartesian_product<N1, N2, E1, E2, Ty, Ix>(
+    a: &Graph<N1, E1, Ty, Ix>,
+    b: &Graph<N2, E2, Ty, Ix>,
+) -> Graph<(N1, N2), (Option<E1>, Option<E2>), Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    N1: Clone,
+    N2: Clone,
+    E1: Clone,
+    E2: Clone,
+{
+    let edges = a.edge_count() * b.node_count() + a.node_count() * b.edge_count();
+    let mut output = product_nodes(a, b, edges);
+    add_cartesian_edges(a, b, &mut output);
+    output
+}
+
+/// \[Generic\] tensor product (also called categorical or direct product)
+/// of two graphs.
+///
+/// The nodes of the product are all pairs `(x, y)` of a node `x` of `a` and
+/// a node `y` of `b`, with the pair of their weights as weight. The node
+/// `(x, y)` has index `x.index() * b.node_count() + y.index()`.
+///
+/// There is an edge from `(x, y)` to `(x2, y2)` if there is an edge from `x`
+/// to `x2` in `a` and an edge from `y` to `y2` in `b`. Its weight is the pair
+/// of the weights of these edges.
+///
+/// If `a` and `b` are automata, the tensor product models their synchronous
+/// execution.
+///
+/// Computes in **O(|V1||V2| + |E1||E2|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::operator::tensor_product;
+/// use petgraph::prelude::*;
+///
+/// // Two automata that must move in lockstep.
+/// let a = DiGraph::<(), char>::from_edges(&[(0, 1, 'a'), (1, 0, 'b')]);
+/// let b = DiGraph::<(), char>::from_edges(&[(0, 1, 'x'), (1, 1, 'y')]);
+/// let product = tensor_product(&a, &b);
+///
+/// assert_eq!(product.edge_count(), 4);
+/// let from_start: Vec<_> = product
+///     .edges(NodeIndex::new(0))
+///     .map(|e| (e.target().index(), *e.weight()))
+///     .collect();
+/// assert_eq!(from_start, vec![(3, ('a', 'x'))]);
+/// ```
+pub fn tensor_product<N1, N2, E1, E2, Ty, Ix>(
+    a: &Graph<N1, E1, Ty, Ix>,
+    b: &Graph<N2, E2, Ty, Ix>,
+) -> Graph<(N1, N2), (E1, E2), Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    N1: Clone,
+    N2: Clone,
+    E1: Clone,
+    E2: Clone,
+{
+    let mut output = product_nodes(a, b, a.edge_count() * b.edge_count());
+    add_tensor_edges(a, b, &mut output, |wa, wb| (wa.clone(), wb.clone()));
+    output
+}
+
+/// \[Generic\] strong product of two graphs.
+///
+/// The strong product is the union of the [`cartesian_product`] and the
+/// [`tensor_product`] of `a` and `b`: the nodes are all pairs `(x, y)` of a
+/// node `x` of `a` and a node `y` of `b`, and there is an edge from `(x, y)`
+/// to `(x2, y2)` if either of the products has one. Edges of the cartesian
+/// product have weight `(Some(w), None)` or `(None, Some(w))`, edges of the
+/// tensor product have weight `(Some(w1), Some(w2))`.
+///
+/// The node `(x, y)` has index `x.index() * b.node_count() + y.index()`.
+///
+/// Computes in **O(|V1||V2| + |V1||E2| + |E1||V2| + |E1||E2|)** time.
+///
+/// # Example
+/// ```rust
+/// use petgraph::operator::strong_product;
+/// use petgraph::prelude::*;
+///
+/// // The strong product of two paths is a grid with diagonals.
+/// let path = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+/// let grid = strong_product(&path, &path);
+/// assert_eq!(grid.node_count(), 9);
+/// assert_eq!(grid.edge_count(), 20);
+///
+/// // The center of the grid is adjacent to all other nodes.
+/// assert_eq!(grid.neighbors(NodeIndex::new(1 * 3 + 1)).count(), 8);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn strong_product<N1, N2, E1, E2, Ty, Ix>(
+    a: &Graph<N1, E1, Ty, Ix>,
+    b: &Graph<N2, E2, Ty, Ix>,
+) -> Graph<(N1, N2), (Option<E1>, Option<E2>), Ty, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    N1: Clone,
+    N2: Clone,
+    E1: Clone,
+    E2: Clone,
+{
+    let edges = a.edge_count() * b.node_count()
+        + a.node_count() * b.edge_count()
+        + a.edge_count() * b.edge_count();
+    let mut output = product_nodes(a, b, edges);
+    add_cartesian_edges(a, b, &mut output);
+    add_tensor_edges(a, b, &mut output, |wa, wb| {
+        (Some(wa.clone()), Some(wb.clone()))
+    });
+    output
+}
+
+/// \[Generic\] quotient graph of a graph by a partition of its nodes.
+///
+/// The nodes of `graph` are partitioned into blocks by the `block` closure:
+/// two nodes are in the same block if and only if `block` returns the same
+/// key for both. Each block is contracted into a single node of the
+/// quotient graph, whose weight is the list of the nodes of the block, in
+/// increasing index order. The blocks are ordered by their first node.
+///
+/// All edges between two blocks are merged into a single edge: its weight
+/// is the weight of the first edge (in index order), folded with the
+/// weights of the others using `reduce_edges`. Edges within a block become
+/// a self-loop of the contracted node; use [`Graph::retain_edges`] to
+/// remove them if they are not needed.
+///
+/// Return the quotient graph together with the map from the nodes of
+/// `graph` to the nodes of the quotient graph: node `n` is contracted into
+/// `node_map[n.index()]`.
+///
+/// Computes in **O(|V| + |E|)** time (average).
+///
+/// # Example
+/// ```rust
+/// use petgraph::operator::quotient;
+/// use petgraph::prelude::*;
+///
+/// let mut graph = UnGraph::<&str, u32>::new_undirected();
+/// let a = graph.add_node("a");
+/// let b = graph.add_node("b");
+/// let c = graph.add_node("c");
+/// let d = graph.add_node("d");
+/// graph.extend_with_edges(&[(a, b, 1), (b, c, 2), (c, a, 3), (c, d, 4)]);
+///
+/// // Contract the nodes `a` and `c` into a single node, summing up the
+/// // weights of parallel edges.
+/// let (q, node_map) = quotient(
+///     &graph,
+///     |n, _| if n == c { a } else { n },
+///     |x, &y| x + y,
+/// );
+/// assert_eq!(node_map[a.index()], node_map[c.index()]);
+/// assert_eq!(q[node_map[a.index()]], vec![a, c]);
+/// assert_eq!(q.node_count(), 3);
+///
+/// let ac_b = q.find_edge(node_map[a.index()], node_map[b.index()]).unwrap();
+/// assert_eq!(q[ac_b], 1 + 2);
+/// let ac_ac = q.find_edge(node_map[a.index()], node_map[c.index()]).unwrap();
+/// assert_eq!(q[ac_ac], 3);
+/// ```
+#[allow(clippy::type_complexity)]
+pub fn quotient<N, E, Ty, Ix, K, F, R>(
+    graph: &Graph<N, E, Ty, Ix>,
+    mut block: F,
+    mut reduce_edges: R,
+) -> (Graph<Vec<NodeIndex<Ix>>, E, Ty, Ix>, Vec<NodeIndex<Ix>>)
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    E: Clone,
+    K: Eq + Hash,
+    F: FnMut(NodeIndex<Ix>, &N) -> K,
+    R: FnMut(E, &E) -> E,
+{
+    let mut output = Graph::with_capacity(0, 0);
+
+    // Contract the nodes.
+    let mut blocks = HashMap::new();
+    let mut node_map = Vec::with_capacity(graph.node_count());
+    for (n, weight) in graph.node_references() {
+        let q = *blocks
+            .entry(block(n, weight))
+            .or_insert_with(|| output.add_node(Vec::new()));
+        output[q].push(n);
+        node_map.push(q);
+    }
+
+    // Merge the edges between blocks.
+    let mut positions = HashMap::<_, usize>::new();
+    let mut edges: Vec<(NodeIndex<Ix>, NodeIndex<Ix>, Option<E>)> = Vec::new();
+    for edge in graph.edge_references() {
+        let source = node_map[edge.source().index()];
+        let target = node_map[edge.target().index()];
+        let key = if Ty::is_directed() || source <= target {
+            (source, target)
+        } else {
+            (target, source)
+        };
+        match positions.get(&key) {
+            Some(&i) => {
+                let acc = edges[i].2.take().unwrap();
+                edges[i].2 = Some(reduce_edges(acc, edge.weight()));
+            }
+            None => {
+                positions.insert(key, edges.len());
+                edges.push((source, target, Some(edge.weight().clone())));
+            }
+        }
+    }
+    output.reserve_exact_edges(edges.len());
+    for (source, target, weight) in edges {
+        output.add_edge(source, target, weight.unwrap());
+    }
+    (output, node_map)
+}
+
+/// An edge of a bipartite projection, given to the `weight` closure of
+/// [`bipartite_projection`].
+#[derive(Debug, Clone, Copy)]
+pub struct ProjectedEdge<'a, Ix> {
+    /// The source node, in the bipartite graph.
+    pub source: NodeIndex<Ix>,
+    /// The target node, in the bipartite graph.
+    pub target: NodeIndex<Ix>,
+    /// The neighbors shared by `source` and `target` on the other side, in
+    /// increasing index order.
+    pub common: &'a [NodeIndex<Ix>],
+    /// The number of neighbors of `source` on the other side.
+    pub source_degree: usize,
+    /// The number of neighbors of `target` on the other side.
+    pub target_degree: usize,
+}
+
+impl<Ix> ProjectedEdge<'_, Ix> {
+    /// The Jaccard similarity of the neighborhoods of the endpoints: the number
+    /// of shared neighbors divided by the number of neighbors of either.
+    pub fn jaccard(&self) -> f64 {
+        let shared = self.common.len();
+        shared as f64 / (self.source_degree + self.target_degree - shared) as f64
+    }
+}
+
+/// \[Generic\] one-mode projection of a bipartite graph onto one of its sides.
+///
+/// The projection has a node for each node of `side`, in the same order, with
+/// the same weight, and an edge between two of them if they have a common
+/// neighbor outside `side`. Edges of `graph` are considered undirected. The
+/// weight of an edge is computed by the `weight` closure from a
+/// [`ProjectedEdge`], e.g. the number of common neighbors, or their
+/// [Jaccard similarity](ProjectedEdge::jaccard).
+///
+/// The nodes of `side` should be distinct. Nodes of `side` adjacent to each
+/// other are not considered neighbors, so `graph` need not be bipartite.
+///
+/// Computes in **O(|V| + Σ d(v)²)** time (average), summing over the nodes
+/// outside `side`.
+///
+/// # Example
+/// ```rust
+/// use petgraph::operator::bipartite_projection;
+/// use petgraph::prelude::*;
+///
+/// // Authors and the papers they wrote
+/// let mut graph = UnGraph::<&str, ()>::new_undirected();
+/// let alice = graph.add_node("alice");
+/// let bob = graph.add_node("bob");
+/// let carol = graph.add_node("carol");
+/// let p1 = graph.add_node("p1");
+/// let p2 = graph.add_node("p2");
+/// graph.extend_with_edges(&[(alice, p1), (bob, p1), (alice, p2), (bob, p2), (carol, p2)]);
+///
+/// // Co-authorship, weighted by the number of papers written together
+/// let coauthors = bipartite_projection(&graph, &[alice, bob, carol], |e| e.common.len());
+/// assert_eq!(coauthors.node_weights().copied().collect::<Vec<_>>(), vec!["alice", "bob", "carol"]);
+/// let a_b = coauthors.find_edge(NodeIndex::new(0), NodeIndex::new(1)).unwrap();
+/// assert_eq!(coauthors[a_b], 2);
+///
+/// // Similarity of the papers, by the Jaccard index of their authors
+/// let papers = bipartite_projection(&graph, &[p1, p2], |e| e.jaccard());
+/// assert_eq!(papers.edge_weights().copied().collect::<Vec<_>>(), vec![2. / 3.]);
+/// ```
+pub fn bipartite_projection<N, E, Ty, Ix, W, F>(
+    graph: &Graph<N, E, Ty, Ix>,
+    side: &[NodeIndex<Ix>],
+    mut weight: F,
+) -> Graph<N, W, Undirected, Ix>
+where
+    Ty: EdgeType,
+    Ix: IndexType,
+    N: Clone,
+    F: FnMut(ProjectedEdge<'_, Ix>) -> W,
+{
+    // The position of each node of the side, if any.
+    let mut position = vec![None; graph.node_count()];
+    for (i, &n) in side.iter().enumerate() {
+        position[n.index()] = Some(i);
+    }
+    let others = |n: NodeIndex<Ix>| {
+        let mut neighbors: Vec<_> = graph
+            .neighbors_undirected(n)
+            .filter(|m| position[m.index()].is_none())
+            .collect();
+        neighbors.sort();
+        neighbors.dedup();
+        neighbors
+    };
+    let neighbors: Vec<_> = side.iter().map(|&n| others(n)).collect();
+
+    let mut output = Graph::with_capacity(side.len(), 0);
+    for &n in side {
+        output.add_node(graph[n].clone());
+    }
+    let mut common = HashMap::new();
+    for (i, neighbors_i) in neighbors.iter().enumerate() {
+        // The neighbors shared with the next nodes of the side.
+        common.clear();
+        for &x in neighbors_i {
+            for j in graph
+                .neighbors_undirected(x)
+                .filter_map(|m| position[m.index()])
+                .filter(|&j| j > i)
+            {
+                let shared = common.entry(j).or_insert_with(Vec::new);
+                // Parallel edges give the same neighbor in a row.
+                if shared.last() != Some(&x) {
+                    shared.push(x);
+                }
+            }
+        }
+        let mut targets: Vec<_> = common.keys().copied().collect();
+        targets.sort_unstable();
+        for j in targets {
+            let w = weight(ProjectedEdge {
+                source: side[i],
+                target: side[j],
+                common: &common[&j],
+                source_degree: neighbors_i.len(),
+                target_degree: neighbors[j].len(),
+            });
+            output.add_edge(NodeIndex::new(i), NodeIndex::new(j), w);
+        }
+    }
+    output
+}
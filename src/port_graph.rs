@@ -0,0 +1,407 @@
+//! A graph whose edges connect ports of the nodes.
+
+use std::fmt;
+
+use crate::graph::{DefaultIx, DiGraph, EdgeIndex, IndexType, NodeIndex};
+use crate::visit::EdgeRef;
+use crate::Direction::{self, Incoming, Outgoing};
+
+/// A port of a [`PortGraph`]: the `offset`-th input (`Incoming`) or output
+/// (`Outgoing`) port of `node`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Port<Ix = DefaultIx> {
+    /// The node of the port.
+    pub node: NodeIndex<Ix>,
+    /// Whether the port is an input (`Incoming`) or an output (`Outgoing`).
+    pub direction: Direction,
+    /// The position of the port among the ports of the node in its direction.
+    pub offset: usize,
+}
+
+impl<Ix> Port<Ix> {
+    /// The `offset`-th input port of `node`.
+    pub fn input(node: NodeIndex<Ix>, offset: usize) -> Self {
+        Port {
+            node,
+            direction: Incoming,
+            offset,
+        }
+    }
+
+    /// The `offset`-th output port of `node`.
+    pub fn output(node: NodeIndex<Ix>, offset: usize) -> Self {
+        Port {
+            node,
+            direction: Outgoing,
+            offset,
+        }
+    }
+}
+
+/// The reason two ports of a [`PortGraph`] cannot be connected.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PortGraphError<Ix = DefaultIx> {
+    /// The port doesn't exist.
+    MissingPort(Port<Ix>),
+    /// The port is an input where an output is expected, or the converse.
+    WrongDirection(Port<Ix>),
+    /// The port is already connected by an edge.
+    PortInUse(Port<Ix>, EdgeIndex<Ix>),
+    /// The ports have different types.
+    TypeMismatch(Port<Ix>, Port<Ix>),
+}
+
+impl<Ix: IndexType> fmt::Display for PortGraphError<Ix> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = |port: &Port<Ix>| {
+            let kind = if port.direction == Incoming {
+                "input"
+            } else {
+                "output"
+            };
+            format!("{} {} of node {}", kind, port.offset, port.node.index())
+        };
+        match self {
+            PortGraphError::MissingPort(p) => write!(f, "{} does not exist", name(p)),
+            PortGraphError::WrongDirection(p) => {
+                write!(f, "{} is connected in the wrong direction", name(p))
+            }
+            PortGraphError::PortInUse(p, e) => {
+                write!(f, "{} is already connected by edge {}", name(p), e.index())
+            }
+            PortGraphError::TypeMismatch(a, b) => {
+                write!(f, "{} and {} have different types", name(a), name(b))
+            }
+        }
+    }
+}
+
+impl<Ix: IndexType> std::error::Error for PortGraphError<Ix> {}
+
+/// `PortGraph<N, E, P, Ix>` is a directed graph whose edges connect *ports* of
+/// the nodes, e.g. the operands of the operations of a dataflow graph or the
+/// qubits of the gates of a quantum circuit.
+///
+/// Every node has an ordered list of input ports and an ordered list of output
+/// ports, each with a type `P`. An edge connects an output port to an input
+/// port of the same type, and each port is connected by at most one edge, so
+/// that the order of the operands of a node is kept, even between the same
+/// nodes.
+///
+/// Nodes and edges are identified by [`NodeIndex`] and [`EdgeIndex`] and carry
+/// weights `N` and `E`. As for [`Graph`](crate::Graph), removing an edge moves
+/// the last edge into its place. A port graph can be converted to and from a
+/// [`DiGraph`] with the same indices, to run the graph algorithms of the crate
+/// on it.
+///
+/// # Example
+/// ```rust
+/// use petgraph::port_graph::{Port, PortGraph, PortGraphError};
+///
+/// // A quantum circuit with a CNOT between two qubits
+/// let mut circuit = PortGraph::<&str, (), &str>::new();
+/// let q0 = circuit.add_node("q0", [], ["qubit"]);
+/// let q1 = circuit.add_node("q1", [], ["qubit"]);
+/// let cx = circuit.add_node("cx", ["qubit", "qubit"], ["qubit", "qubit"]);
+/// let bit = circuit.add_node("measure", ["qubit"], ["bit"]);
+///
+/// circuit.connect(Port::output(q0, 0), Port::input(cx, 0), ()).unwrap();
+/// circuit.connect(Port::output(q1, 0), Port::input(cx, 1), ()).unwrap();
+/// circuit.connect(Port::output(cx, 1), Port::input(bit, 0), ()).unwrap();
+///
+/// assert_eq!(circuit.linked_port(Port::input(bit, 0)), Some(Port::output(cx, 1)));
+/// assert_eq!(
+///     circuit.connect(Port::output(q0, 0), Port::input(cx, 0), ()),
+///     Err(PortGraphError::PortInUse(Port::output(q0, 0), 0.into()))
+/// );
+///
+/// // The edges of the plain graph keep the offsets of their ports
+/// let graph = circuit.into_graph();
+/// assert_eq!(graph.edge_count(), 3);
+/// assert_eq!(graph[graph.find_edge(q1, cx).unwrap()].0, (0, 1));
+/// ```
+#[derive(Clone, Debug)]
+pub struct PortGraph<N, E, P = (), Ix = DefaultIx> {
+    nodes: Vec<PortNode<N, P, Ix>>,
+    edges: Vec<PortEdge<E, Ix>>,
+}
+
+#[derive(Clone, Debug)]
+struct PortNode<N, P, Ix> {
+    weight: N,
+    /// The type and edge of the output and input ports, by `Direction::index`.
+    ports: [Vec<(P, Option<EdgeIndex<Ix>>)>; 2],
+}
+
+#[derive(Clone, Debug)]
+struct PortEdge<E, Ix> {
+    weight: E,
+    source: Port<Ix>,
+    target: Port<Ix>,
+}
+
+impl<N, E, P, Ix> Default for PortGraph<N, E, P, Ix> {
+    fn default() -> Self {
+        PortGraph {
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+}
+
+impl<N, E, P> PortGraph<N, E, P> {
+    /// Create a new, empty `PortGraph`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<N, E, P, Ix: IndexType> PortGraph<N, E, P, Ix> {
+    /// Create a new, empty `PortGraph` with estimated capacity.
+    pub fn with_capacity(nodes: usize, edges: usize) -> Self {
+        PortGraph {
+            nodes: Vec::with_capacity(nodes),
+            edges: Vec::with_capacity(edges),
+        }
+    }
+
+    /// Return the number of nodes.
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Return the number of edges.
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    /// Add a node with weight `weight`, and input and output ports of the
+    /// given types, and return its index.
+    ///
+    /// **Panics** if the graph is at the maximum number of nodes for its index
+    /// type.
+    pub fn add_node<I, O>(&mut self, weight: N, inputs: I, outputs: O) -> NodeIndex<Ix>
+    where
+        I: IntoIterator<Item = P>,
+        O: IntoIterator<Item = P>,
+    {
+        let node = NodeIndex::new(self.nodes.len());
+        assert!(<Ix as IndexType>::max().index() == !0 || NodeIndex::end() != node);
+        self.nodes.push(PortNode {
+            weight,
+            ports: [
+                outputs.into_iter().map(|p| (p, None)).collect(),
+                inputs.into_iter().map(|p| (p, None)).collect(),
+            ],
+        });
+        node
+    }
+
+    /// Connect the output port `source` to the input port `target` with an
+    /// edge of weight `weight`, and return its index.
+    ///
+    /// Return an error, leaving the graph unchanged, if a port doesn't exist,
+    /// is in the wrong direction, or is already connected, or if the ports
+    /// have different types.
+    ///
+    /// **Panics** if the graph is at the maximum number of edges for its index
+    /// type.
+    pub fn connect(
+        &mut self,
+        source: Port<Ix>,
+        target: Port<Ix>,
+        weight: E,
+    ) -> Result<EdgeIndex<Ix>, PortGraphError<Ix>>
+    where
+        P: PartialEq,
+    {
+        for &(port, direction) in &[(source, Outgoing), (target, Incoming)] {
+            if port.direction != direction {
+                return Err(PortGraphError::WrongDirection(port));
+            }
+            match self.slot(port) {
+                None => return Err(PortGraphError::MissingPort(port)),
+                Some(&(_, Some(e))) => return Err(PortGraphError::PortInUse(port, e)),
+                Some(_) => {}
+            }
+        }
+        if self.port_type(source) != self.port_type(target) {
+            return Err(PortGraphError::TypeMismatch(source, target));
+        }
+
+        let edge = EdgeIndex::new(self.edges.len());
+        assert!(<Ix as IndexType>::max().index() == !0 || EdgeIndex::end() != edge);
+        self.edges.push(PortEdge {
+            weight,
+            source,
+            target,
+        });
+        self.slot_mut(source).1 = Some(edge);
+        self.slot_mut(target).1 = Some(edge);
+        Ok(edge)
+    }
+
+    /// Remove edge `e`, freeing its ports, and return its weight, or `None` if
+    /// it didn't exist.
+    ///
+    /// Apart from `e`, this invalidates the last edge index in the graph (that
+    /// edge will adopt the removed edge index).
+    pub fn remove_edge(&mut self, e: EdgeIndex<Ix>) -> Option<E> {
+        if e.index() >= self.edges.len() {
+            return None;
+        }
+        let edge = self.edges.swap_remove(e.index());
+        self.slot_mut(edge.source).1 = None;
+        self.slot_mut(edge.target).1 = None;
+        if let Some(moved) = self.edges.get(e.index()) {
+            let (source, target) = (moved.source, moved.target);
+            self.slot_mut(source).1 = Some(e);
+            self.slot_mut(target).1 = Some(e);
+        }
+        Some(edge.weight)
+    }
+
+    /// Access the weight of node `a`.
+    ///
+    /// Return `None` if the node doesn't exist.
+    pub fn node_weight(&self, a: NodeIndex<Ix>) -> Option<&N> {
+        self.nodes.get(a.index()).map(|n| &n.weight)
+    }
+
+    /// Access the weight of node `a`, mutably.
+    ///
+    /// Return `None` if the node doesn't exist.
+    pub fn node_weight_mut(&mut self, a: NodeIndex<Ix>) -> Option<&mut N> {
+        self.nodes.get_mut(a.index()).map(|n| &mut n.weight)
+    }
+
+    /// Access the weight of edge `e`.
+    ///
+    /// Return `None` if the edge doesn't exist.
+    pub fn edge_weight(&self, e: EdgeIndex<Ix>) -> Option<&E> {
+        self.edges.get(e.index()).map(|e| &e.weight)
+    }
+
+    /// Access the weight of edge `e`, mutably.
+    ///
+    /// Return `None` if the edge doesn't exist.
+    pub fn edge_weight_mut(&mut self, e: EdgeIndex<Ix>) -> Option<&mut E> {
+        self.edges.get_mut(e.index()).map(|e| &mut e.weight)
+    }
+
+    /// Return the output and input ports connected by edge `e`.
+    ///
+    /// Return `None` if the edge doesn't exist.
+    pub fn edge_endpoints(&self, e: EdgeIndex<Ix>) -> Option<(Port<Ix>, Port<Ix>)> {
+        self.edges.get(e.index()).map(|e| (e.source, e.target))
+    }
+
+    /// Return the number of input (`Incoming`) or output (`Outgoing`) ports of
+    /// node `a`.
+    ///
+    /// **Panics** if the node doesn't exist.
+    pub fn port_count(&self, a: NodeIndex<Ix>, direction: Direction) -> usize {
+        self.nodes[a.index()].ports[direction.index()].len()
+    }
+
+    /// Return an iterator over the input (`Incoming`) or output (`Outgoing`)
+    /// ports of node `a`, in order.
+    ///
+    /// **Panics** if the node doesn't exist.
+    pub fn ports(&self, a: NodeIndex<Ix>, direction: Direction) -> impl Iterator<Item = Port<Ix>> {
+        (0..self.port_count(a, direction)).map(move |offset| Port {
+            node: a,
+            direction,
+            offset,
+        })
+    }
+
+    /// Return the type of `port`, or `None` if it doesn't exist.
+    pub fn port_type(&self, port: Port<Ix>) -> Option<&P> {
+        self.slot(port).map(|(p, _)| p)
+    }
+
+    /// Return the edge connecting `port`, or `None` if it is not connected or
+    /// doesn't exist.
+    pub fn link(&self, port: Port<Ix>) -> Option<EdgeIndex<Ix>> {
+        self.slot(port).and_then(|&(_, e)| e)
+    }
+
+    /// Return the port connected to `port`, or `None` if it is not connected
+    /// or doesn't exist.
+    pub fn linked_port(&self, port: Port<Ix>) -> Option<Port<Ix>> {
+        let edge = &self.edges[self.link(port)?.index()];
+        Some(if port.direction == Outgoing {
+            edge.target
+        } else {
+            edge.source
+        })
+    }
+
+    /// Convert the port graph into a [`DiGraph`] with the same node and edge
+    /// indices, whose edge weights are the offsets of the output and input
+    /// ports of each edge, and its weight. The port types are dropped.
+    pub fn into_graph(self) -> DiGraph<N, ((usize, usize), E), Ix> {
+        let mut graph = DiGraph::with_capacity(self.nodes.len(), self.edges.len());
+        for node in self.nodes {
+            graph.add_node(node.weight);
+        }
+        for edge in self.edges {
+            let offsets = (edge.source.offset, edge.target.offset);
+            graph.add_edge(edge.source.node, edge.target.node, (offsets, edge.weight));
+        }
+        graph
+    }
+
+    /// Convert a [`DiGraph`] into a port graph with the same node and edge
+    /// indices.
+    ///
+    /// Each edge gets its own output port at its source and input port at its
+    /// target, both of type `port_type(edge)`; the ports of each node are in
+    /// the order of the edge indices.
+    pub fn from_graph<F>(graph: DiGraph<N, E, Ix>, mut port_type: F) -> Self
+    where
+        F: FnMut(&E) -> P,
+        P: Clone,
+    {
+        let mut port_graph = PortGraph::with_capacity(graph.node_count(), graph.edge_count());
+        let mut ports: Vec<[Vec<_>; 2]> = (0..graph.node_count())
+            .map(|_| [Vec::new(), Vec::new()])
+            .collect();
+        let mut endpoints = Vec::with_capacity(graph.edge_count());
+        for edge in graph.edge_references() {
+            let p = port_type(edge.weight());
+            let e = Some(edge.id());
+            let source = &mut ports[edge.source().index()][Outgoing.index()];
+            source.push((p.clone(), e));
+            let source = Port::output(edge.source(), source.len() - 1);
+            let target = &mut ports[edge.target().index()][Incoming.index()];
+            target.push((p, e));
+            let target = Port::input(edge.target(), target.len() - 1);
+            endpoints.push((source, target));
+        }
+        let (nodes, edges) = graph.into_nodes_edges();
+        for (node, ports) in nodes.into_iter().zip(ports) {
+            port_graph.nodes.push(PortNode {
+                weight: node.weight,
+                ports,
+            });
+        }
+        for (edge, (source, target)) in edges.into_iter().zip(endpoints) {
+            port_graph.edges.push(PortEdge {
+                weight: edge.weight,
+                source,
+                target,
+            });
+        }
+        port_graph
+    }
+
+    fn slot(&self, port: Port<Ix>) -> Option<&(P, Option<EdgeIndex<Ix>>)> {
+        self.nodes.get(port.node.index())?.ports[port.direction.index()].get(port.offset)
+    }
+
+    fn slot_mut(&mut self, port: Port<Ix>) -> &mut (P, Option<EdgeIndex<Ix>>) {
+        &mut self.nodes[port.node.index()].ports[port.direction.index()][port.offset]
+    }
+}
@@ -1,10 +1,18 @@
+//! `quickcheck` support: `Arbitrary` implementations for the graph types, and
+//! configurable generators of weighted graphs for testing shortest path
+//! algorithms.
+//!
+//! Requires crate feature `"quickcheck"`
+
 extern crate quickcheck;
+use std::fmt;
+
 use self::quickcheck::{Arbitrary, Gen};
 
 use crate::graph::{node_index, IndexType};
 #[cfg(feature = "stable_graph")]
 use crate::stable_graph::StableGraph;
-use crate::{EdgeType, Graph};
+use crate::{Directed, EdgeType, Graph};
 
 #[cfg(feature = "graphmap")]
 use crate::graphmap::{GraphMap, NodeTrait};
@@ -214,3 +222,236 @@ where
         gr
     }
 }
+
+/// A random weighted graph generated by a [`WeightedGraphConfig`], with the
+/// distances from node 0 when they are known by construction.
+pub struct WeightedGraph<E, Ty = Directed> {
+    /// The graph.
+    pub graph: Graph<(), E, Ty>,
+    /// The distance from node 0 to each node, by node index, if shortest paths
+    /// were [planted](WeightedGraphConfig::planted).
+    pub distances: Option<Vec<E>>,
+}
+
+impl<E: Clone, Ty> Clone for WeightedGraph<E, Ty> {
+    fn clone(&self) -> Self {
+        WeightedGraph {
+            graph: self.graph.clone(),
+            distances: self.distances.clone(),
+        }
+    }
+}
+
+impl<E: fmt::Debug, Ty: EdgeType> fmt::Debug for WeightedGraph<E, Ty> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("WeightedGraph")
+            .field("graph", &self.graph)
+            .field("distances", &self.distances)
+            .finish()
+    }
+}
+
+/// The knobs of the random weighted graphs generated for shortest path
+/// tests: the range of the weights, negative edges without negative cycles,
+/// connectivity, acyclicity, and planted shortest paths.
+///
+/// The default configuration generates graphs with weights in `0..=100`,
+/// self-loops possible and no parallel edges, like the `Arbitrary`
+/// implementation of `Graph`.
+///
+/// # Example
+/// ```rust
+/// use petgraph::algo::bellman_ford;
+/// use petgraph::quickcheck::{WeightedGraph, WeightedGraphConfig};
+/// use petgraph::Directed;
+/// use quickcheck::{quickcheck, Arbitrary, Gen};
+///
+/// // Graphs with negative edges but no negative cycles, and known distances
+/// #[derive(Clone, Debug)]
+/// struct Reweighted(WeightedGraph<f64, Directed>);
+///
+/// impl Arbitrary for Reweighted {
+///     fn arbitrary<G: Gen>(g: &mut G) -> Self {
+///         Reweighted(WeightedGraphConfig::new().planted(true).potentials(50).generate(g))
+///     }
+/// }
+///
+/// fn prop(Reweighted(w): Reweighted) -> bool {
+///     if w.graph.node_count() == 0 {
+///         return true;
+///     }
+///     let paths = bellman_ford(&w.graph, 0.into()).unwrap();
+///     paths.distances == w.distances.unwrap()
+/// }
+/// quickcheck(prop as fn(_) -> bool);
+/// ```
+#[derive(Clone, Debug)]
+pub struct WeightedGraphConfig {
+    weights: (i32, i32),
+    potentials: i32,
+    connected: bool,
+    acyclic: bool,
+    planted: bool,
+}
+
+impl Default for WeightedGraphConfig {
+    fn default() -> Self {
+        WeightedGraphConfig {
+            weights: (0, 100),
+            potentials: 0,
+            connected: false,
+            acyclic: false,
+            planted: false,
+        }
+    }
+}
+
+impl WeightedGraphConfig {
+    /// Create the default configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Draw the weights in `min..=max`, which may be negative: the graphs
+    /// may then have negative cycles.
+    ///
+    /// **Panics** if `min > max`.
+    pub fn weights(mut self, min: i32, max: i32) -> Self {
+        assert!(min <= max, "WeightedGraphConfig::weights: empty range");
+        self.weights = (min, max);
+        self
+    }
+
+    /// Give each node a potential `p` in `0..=max`, and add `p(u) - p(v)` to
+    /// the weight of each edge from `u` to `v`.
+    ///
+    /// The cost of every cycle is unchanged, and the shortest paths stay the
+    /// same, so that non-negative weights turn into negative edges without
+    /// negative cycles. Only for directed graphs.
+    pub fn potentials(mut self, max: i32) -> Self {
+        self.potentials = max;
+        self
+    }
+
+    /// Make every node reachable from node 0, through a random spanning tree.
+    pub fn connected(mut self, connected: bool) -> Self {
+        self.connected = connected;
+        self
+    }
+
+    /// Only add edges from a node to a node of higher index, so that node
+    /// index order is a topological order. Only for directed graphs.
+    pub fn acyclic(mut self, acyclic: bool) -> Self {
+        self.acyclic = acyclic;
+        self
+    }
+
+    /// Plant a shortest path tree from node 0, whose distances are returned
+    /// in [`WeightedGraph::distances`]: the weights of the other edges are
+    /// raised so that they are no shortcut. Implies
+    /// [`connected`](Self::connected).
+    ///
+    /// Requires non-negative [`weights`](Self::weights).
+    pub fn planted(mut self, planted: bool) -> Self {
+        self.planted = planted;
+        self
+    }
+
+    /// Generate a graph of this configuration, with at most `g.size()`
+    /// nodes.
+    ///
+    /// **Panics** if the configuration is invalid for the graph type.
+    pub fn generate<E, Ty, G>(&self, g: &mut G) -> WeightedGraph<E, Ty>
+    where
+        E: From<i32>,
+        Ty: EdgeType,
+        G: Gen,
+    {
+        let directed = Ty::is_directed();
+        assert!(
+            directed || self.potentials == 0 && !self.acyclic,
+            "WeightedGraphConfig: potentials and acyclic require a directed graph"
+        );
+        assert!(
+            !self.planted || self.weights.0 >= 0,
+            "WeightedGraphConfig: planted requires non-negative weights"
+        );
+        let (min, max) = self.weights;
+        let weight = |g: &mut G| min + (random_01(g) * (f64::from(max - min) + 1.)) as i32;
+
+        let nodes = usize::arbitrary(g);
+        let potentials: Vec<i32> = (0..nodes)
+            .map(|_| (random_01(g) * f64::from(self.potentials + 1)) as i32)
+            .collect();
+        // The planted distances, or the weights of the tree edges
+        let mut heights = vec![0; nodes];
+        let mut parents = vec![None; nodes];
+        let mut edges = Vec::new();
+        if self.connected || self.planted {
+            for i in 1..nodes {
+                let parent = (random_01(g) * i as f64) as usize;
+                let w = weight(g);
+                heights[i] = heights[parent] + w;
+                parents[i] = Some(parent);
+                edges.push((parent, i, w));
+            }
+        }
+        let edge_prob = random_01(g) * random_01(g);
+        for i in 0..nodes {
+            let first = if self.acyclic {
+                i + 1
+            } else if directed {
+                0
+            } else {
+                i
+            };
+            for j in first..nodes {
+                let tree_edge = parents[j] == Some(i) || !directed && parents[i] == Some(j);
+                if tree_edge || random_01(g) > edge_prob {
+                    continue;
+                }
+                let mut w = weight(g);
+                if self.planted {
+                    // No shortcut: the planted distances stay feasible
+                    let mut shortcut = heights[j] - heights[i];
+                    if !directed {
+                        shortcut = shortcut.abs();
+                    }
+                    w = std::cmp::max(w, shortcut);
+                }
+                edges.push((i, j, w));
+            }
+        }
+
+        let mut graph = Graph::with_capacity(nodes, edges.len());
+        for _ in 0..nodes {
+            graph.add_node(());
+        }
+        for (i, j, w) in edges {
+            let w = w + potentials[i] - potentials[j];
+            graph.add_edge(node_index(i), node_index(j), E::from(w));
+        }
+        let distances = if self.planted {
+            Some(
+                (0..nodes)
+                    .map(|i| E::from(heights[i] + potentials[0] - potentials[i]))
+                    .collect(),
+            )
+        } else {
+            None
+        };
+        WeightedGraph { graph, distances }
+    }
+}
+
+/// `Arbitrary` for `WeightedGraph` generates graphs with non-negative
+/// weights and planted shortest paths, see [`WeightedGraphConfig`].
+impl<E, Ty> Arbitrary for WeightedGraph<E, Ty>
+where
+    E: From<i32> + Clone + Send + 'static,
+    Ty: EdgeType + Send + 'static,
+{
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        WeightedGraphConfig::new().planted(true).generate(g)
+    }
+}
@@ -0,0 +1,595 @@
+//! Graph rewriting: find the occurrences of a pattern in a [`Graph`] and
+//! replace them.
+//!
+//! A [`Pattern`] is a small graph whose nodes and edges are predicates on the
+//! weights of the target graph. Its [`matches`](Pattern::matches) are found with
+//! [`subgraph_isomorphisms_iter`].
+//!
+//! A [`Rewrite`] records the nodes and edges to remove and to add, and how to
+//! reattach the edges at the boundary of the removed nodes. It is checked
+//! against the graph as a whole when applied, and either applies completely
+//! or leaves the graph untouched. Since removals in a [`Graph`] shift indices,
+//! [`Rewrite::apply`] returns a [`Rewritten`] map to the final position of
+//! every node.
+//!
+//! [`Graph::transaction`] and [`Acyclic::transaction`] build a rewrite with a
+//! closure that can abort it, and commit it atomically.
+//!
+//! # Example
+//! ```rust
+//! use petgraph::prelude::*;
+//! use petgraph::rewrite::{Pattern, Rewrite};
+//!
+//! // Fold constants: an addition of two literals becomes a literal
+//! let mut graph = DiGraph::<&str, ()>::new();
+//! let one = graph.add_node("1");
+//! let two = graph.add_node("2");
+//! let add = graph.add_node("+");
+//! let print = graph.add_node("print");
+//! graph.extend_with_edges(&[(one, add), (two, add), (add, print)]);
+//!
+//! let mut pattern = Pattern::new();
+//! let x = pattern.add_node(|w: &&str| w.parse::<i32>().is_ok());
+//! let y = pattern.add_node(|w: &&str| w.parse::<i32>().is_ok());
+//! let op = pattern.add_node(|w: &&str| *w == "+");
+//! pattern.add_edge(x, op, |_: &()| true);
+//! pattern.add_edge(y, op, |_: &()| true);
+//!
+//! let found = &pattern.matches(&graph)[0];
+//! let mut rewrite = Rewrite::new();
+//! for p in [x, y, op] {
+//!     rewrite.remove_node(found.node(p));
+//! }
+//! let three = rewrite.add_node("3");
+//! // Keep the edge to `print`, now from the literal
+//! rewrite.reattach(found.node(op), three);
+//! let rewritten = rewrite.apply(&mut graph).unwrap();
+//!
+//! let three = rewritten.node(three).unwrap();
+//! let print = rewritten.node(print).unwrap();
+//! assert_eq!(graph.node_count(), 2);
+//! assert_eq!(graph[three], "3");
+//! assert!(graph.contains_edge(three, print));
+//! ```
+
+use std::collections::HashMap;
+use std::fmt;
+
+use fixedbitset::FixedBitSet;
+
+use crate::acyclic::Acyclic;
+use crate::algo::{subgraph_isomorphisms_iter, toposort, Cycle};
+use crate::graph::{DefaultIx, EdgeIndex, Graph, IndexType, NodeIndex};
+use crate::visit::EdgeRef;
+use crate::{Directed, Direction, EdgeType};
+
+/// A predicate on the weights of a graph.
+type Predicate<W> = Box<dyn Fn(&W) -> bool>;
+
+/// A pattern to find in graphs with node weights `N`, edge weights `E` and
+/// edge type `Ty`.
+///
+/// Each node and edge of the pattern is a predicate that the weight of a
+/// matching node or edge must satisfy.
+pub struct Pattern<N, E, Ty = Directed> {
+    graph: Graph<Predicate<N>, Predicate<E>, Ty>,
+}
+
+impl<N, E, Ty: EdgeType> Default for Pattern<N, E, Ty> {
+    fn default() -> Self {
+        Pattern {
+            graph: Graph::default(),
+        }
+    }
+}
+
+impl<N, E, Ty: EdgeType> fmt::Debug for Pattern<N, E, Ty> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Pattern")
+            .field("node_count", &self.node_count())
+            .field("edges", &self.graph.raw_edges().len())
+            .finish()
+    }
+}
+
+impl<N, E, Ty: EdgeType> Pattern<N, E, Ty> {
+    /// Create an empty pattern.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a node matching the nodes whose weight satisfies `predicate`, and
+    /// return its index in the pattern.
+    pub fn add_node<F>(&mut self, predicate: F) -> NodeIndex
+    where
+        F: Fn(&N) -> bool + 'static,
+    {
+        self.graph.add_node(Box::new(predicate))
+    }
+
+    /// Add an edge from `a` to `b` matching the edges whose weight satisfies
+    /// `predicate`, and return its index in the pattern.
+    ///
+    /// The pattern should not have parallel edges.
+    ///
+    /// **Panics** if any of the nodes doesn't exist.
+    pub fn add_edge<F>(&mut self, a: NodeIndex, b: NodeIndex, predicate: F) -> EdgeIndex
+    where
+        F: Fn(&E) -> bool + 'static,
+    {
+        self.graph.add_edge(a, b, Box::new(predicate))
+    }
+
+    /// Return the number of nodes of the pattern.
+    pub fn node_count(&self) -> usize {
+        self.graph.node_count()
+    }
+
+    /// Find all the occurrences of the pattern in `graph`.
+    ///
+    /// As for [`subgraph_isomorphisms_iter`], the matches are node-induced: two
+    /// matched nodes are adjacent in `graph` if and only if their pattern nodes
+    /// are. Symmetric patterns match the same nodes several times, once for
+    /// each automorphism. The graph should not be a multigraph.
+    pub fn matches<Ix: IndexType>(&self, graph: &Graph<N, E, Ty, Ix>) -> Vec<Match<Ix>> {
+        let mut node_match = |p: &Predicate<N>, w: &N| p(w);
+        let mut edge_match = |p: &Predicate<E>, w: &E| p(w);
+        let pattern = &self.graph;
+        let mappings =
+            match subgraph_isomorphisms_iter(&pattern, &graph, &mut node_match, &mut edge_match) {
+                Some(mappings) => mappings,
+                None => return Vec::new(),
+            };
+        mappings
+            .map(|mapping| {
+                let nodes: Vec<NodeIndex<Ix>> = mapping.into_iter().map(NodeIndex::new).collect();
+                let edges = self
+                    .graph
+                    .raw_edges()
+                    .iter()
+                    .map(|e| {
+                        let (a, b) = (nodes[e.source().index()], nodes[e.target().index()]);
+                        graph.find_edge(a, b).unwrap()
+                    })
+                    .collect();
+                Match { nodes, edges }
+            })
+            .collect()
+    }
+}
+
+/// An occurrence of a [`Pattern`] in a graph.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Match<Ix = DefaultIx> {
+    nodes: Vec<NodeIndex<Ix>>,
+    edges: Vec<EdgeIndex<Ix>>,
+}
+
+impl<Ix: IndexType> Match<Ix> {
+    /// Return the node of the graph matching the pattern node `p`.
+    ///
+    /// **Panics** if the pattern node doesn't exist.
+    pub fn node(&self, p: NodeIndex) -> NodeIndex<Ix> {
+        self.nodes[p.index()]
+    }
+
+    /// Return the edge of the graph matching the pattern edge `p`.
+    ///
+    /// **Panics** if the pattern edge doesn't exist.
+    pub fn edge(&self, p: EdgeIndex) -> EdgeIndex<Ix> {
+        self.edges[p.index()]
+    }
+
+    /// Return the nodes of the graph matching the nodes of the pattern, by
+    /// pattern node index.
+    pub fn nodes(&self) -> &[NodeIndex<Ix>] {
+        &self.nodes
+    }
+
+    /// Return the edges of the graph matching the edges of the pattern, by
+    /// pattern edge index.
+    pub fn edges(&self) -> &[EdgeIndex<Ix>] {
+        &self.edges
+    }
+}
+
+/// A node referred to by a [`Rewrite`]: a node of the graph, or a node added by
+/// the rewrite.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum RewriteNode<Ix = DefaultIx> {
+    /// A node of the graph, before the rewrite.
+    Existing(NodeIndex<Ix>),
+    /// The node added by the `n`-th call to [`Rewrite::add_node`].
+    New(usize),
+}
+
+impl<Ix> From<NodeIndex<Ix>> for RewriteNode<Ix> {
+    fn from(node: NodeIndex<Ix>) -> Self {
+        RewriteNode::Existing(node)
+    }
+}
+
+/// The reason a [`Rewrite`] cannot be applied to a graph.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum RewriteError<Ix = DefaultIx> {
+    /// A node doesn't exist in the graph, or was not added by the rewrite.
+    MissingNode(RewriteNode<Ix>),
+    /// An edge doesn't exist in the graph.
+    MissingEdge(EdgeIndex<Ix>),
+    /// An edge is added or reattached to a node that is removed.
+    RemovedEndpoint(NodeIndex<Ix>),
+    /// The edges of a node are reattached but the node is not removed.
+    KeptNode(NodeIndex<Ix>),
+}
+
+impl<Ix: IndexType> fmt::Display for RewriteError<Ix> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RewriteError::MissingNode(RewriteNode::Existing(n)) => {
+                write!(f, "node {} does not exist", n.index())
+            }
+            RewriteError::MissingNode(RewriteNode::New(n)) => {
+                write!(f, "new node {} was not added", n)
+            }
+            RewriteError::MissingEdge(e) => write!(f, "edge {} does not exist", e.index()),
+            RewriteError::RemovedEndpoint(n) => {
+                write!(f, "node {} is used as an endpoint but removed", n.index())
+            }
+            RewriteError::KeptNode(n) => {
+                write!(f, "node {} is reattached but not removed", n.index())
+            }
+        }
+    }
+}
+
+impl<Ix: IndexType> std::error::Error for RewriteError<Ix> {}
+
+/// A transactional rewrite of a [`Graph`], with node weights `N` and edge
+/// weights `E`.
+///
+/// Nodes and edges of the graph are referred to by their indices *before* the
+/// rewrite, e.g. those of a [`Match`]; added nodes are referred to by the
+/// [`RewriteNode::New`] returned by [`add_node`](Rewrite::add_node). Removing a
+/// node removes all its edges, except those [reattached](Rewrite::reattach) to
+/// another node.
+///
+/// Nothing is modified until [`apply`](Rewrite::apply), which checks the whole
+/// rewrite first. See the [module documentation](self) for an example.
+#[derive(Clone, Debug)]
+pub struct Rewrite<N, E, Ix = DefaultIx> {
+    remove_nodes: Vec<NodeIndex<Ix>>,
+    remove_edges: Vec<EdgeIndex<Ix>>,
+    add_nodes: Vec<N>,
+    add_edges: Vec<(RewriteNode<Ix>, RewriteNode<Ix>, E)>,
+    reattach: Vec<(NodeIndex<Ix>, RewriteNode<Ix>)>,
+}
+
+impl<N, E, Ix> Default for Rewrite<N, E, Ix> {
+    fn default() -> Self {
+        Rewrite {
+            remove_nodes: Vec::new(),
+            remove_edges: Vec::new(),
+            add_nodes: Vec::new(),
+            add_edges: Vec::new(),
+            reattach: Vec::new(),
+        }
+    }
+}
+
+impl<N, E, Ix: IndexType> Rewrite<N, E, Ix> {
+    /// Create an empty rewrite.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remove node `a` and its edges. Removing a node twice has no further
+    /// effect.
+    pub fn remove_node(&mut self, a: NodeIndex<Ix>) -> &mut Self {
+        self.remove_nodes.push(a);
+        self
+    }
+
+    /// Remove edge `e`. Removing an edge twice has no further effect.
+    pub fn remove_edge(&mut self, e: EdgeIndex<Ix>) -> &mut Self {
+        self.remove_edges.push(e);
+        self
+    }
+
+    /// Add a node with weight `weight`, and return how to refer to it in the
+    /// rewrite.
+    pub fn add_node(&mut self, weight: N) -> RewriteNode<Ix> {
+        self.add_nodes.push(weight);
+        RewriteNode::New(self.add_nodes.len() - 1)
+    }
+
+    /// Add an edge from `a` to `b` with weight `weight`, between existing or
+    /// new nodes.
+    pub fn add_edge<A, B>(&mut self, a: A, b: B, weight: E) -> &mut Self
+    where
+        A: Into<RewriteNode<Ix>>,
+        B: Into<RewriteNode<Ix>>,
+    {
+        self.add_edges.push((a.into(), b.into(), weight));
+        self
+    }
+
+    /// Reattach the edges between the removed node `from` and the nodes that
+    /// are not removed to the node `to` instead, keeping their weight and
+    /// direction.
+    ///
+    /// Edges removed with [`remove_edge`](Rewrite::remove_edge) are not
+    /// reattached. If a node is reattached several times, the last time wins.
+    pub fn reattach<T>(&mut self, from: NodeIndex<Ix>, to: T) -> &mut Self
+    where
+        T: Into<RewriteNode<Ix>>,
+    {
+        self.reattach.push((from, to.into()));
+        self
+    }
+
+    /// The same rewrite without weights, to check it on the structure of a
+    /// graph.
+    fn structure(&self) -> Rewrite<(), (), Ix> {
+        Rewrite {
+            remove_nodes: self.remove_nodes.clone(),
+            remove_edges: self.remove_edges.clone(),
+            add_nodes: vec![(); self.add_nodes.len()],
+            add_edges: self.add_edges.iter().map(|&(a, b, _)| (a, b, ())).collect(),
+            reattach: self.reattach.clone(),
+        }
+    }
+
+    /// Apply the rewrite to `graph`.
+    ///
+    /// The edges are removed first, then the nodes and edges are added and the
+    /// boundary edges reattached, and finally the nodes are removed, with
+    /// [`Graph::remove_node`]. The indices of the remaining nodes may change
+    /// and are given by the returned [`Rewritten`]; edge indices are
+    /// invalidated.
+    ///
+    /// Return an error, leaving `graph` unchanged, if the rewrite refers to
+    /// nodes or edges that don't exist, adds or reattaches edges to removed
+    /// nodes, or reattaches the edges of a node that is not removed.
+    pub fn apply<Ty: EdgeType>(
+        self,
+        graph: &mut Graph<N, E, Ty, Ix>,
+    ) -> Result<Rewritten<Ix>, RewriteError<Ix>> {
+        let node_count = graph.node_count();
+        let new_count = self.add_nodes.len();
+        let mut removed = FixedBitSet::with_capacity(node_count);
+        for &a in &self.remove_nodes {
+            if a.index() >= node_count {
+                return Err(RewriteError::MissingNode(a.into()));
+            }
+            removed.insert(a.index());
+        }
+        let mut removed_edges = FixedBitSet::with_capacity(graph.edge_count());
+        for &e in &self.remove_edges {
+            if e.index() >= graph.edge_count() {
+                return Err(RewriteError::MissingEdge(e));
+            }
+            removed_edges.insert(e.index());
+        }
+        // The index of each endpoint once the new nodes are added
+        let resolve = |n: RewriteNode<Ix>| match n {
+            RewriteNode::Existing(a) if a.index() >= node_count => {
+                Err(RewriteError::MissingNode(n))
+            }
+            RewriteNode::Existing(a) if removed[a.index()] => Err(RewriteError::RemovedEndpoint(a)),
+            RewriteNode::Existing(a) => Ok(a),
+            RewriteNode::New(i) if i >= new_count => Err(RewriteError::MissingNode(n)),
+            RewriteNode::New(i) => Ok(NodeIndex::new(node_count + i)),
+        };
+        let mut reattach = HashMap::new();
+        for &(from, to) in &self.reattach {
+            if from.index() >= node_count {
+                return Err(RewriteError::MissingNode(from.into()));
+            }
+            if !removed[from.index()] {
+                return Err(RewriteError::KeptNode(from));
+            }
+            reattach.insert(from, resolve(to)?);
+        }
+        let mut add_edges = Vec::with_capacity(self.add_edges.len());
+        for (a, b, weight) in self.add_edges {
+            add_edges.push((resolve(a)?, resolve(b)?, weight));
+        }
+
+        // The boundary edges to reattach, with their new endpoints
+        let mut boundary = HashMap::new();
+        let directions: &[Direction] = if graph.is_directed() {
+            &[Direction::Outgoing, Direction::Incoming]
+        } else {
+            &[Direction::Outgoing]
+        };
+        for (&from, &to) in &reattach {
+            for &dir in directions {
+                for edge in graph.edges_directed(from, dir) {
+                    let (source, target, other) = if dir == Direction::Outgoing {
+                        (to, edge.target(), edge.target())
+                    } else {
+                        (edge.source(), to, edge.source())
+                    };
+                    if !removed[other.index()] && !removed_edges[edge.id().index()] {
+                        boundary.insert(edge.id(), (source, target));
+                    }
+                }
+            }
+        }
+        for &e in boundary.keys() {
+            removed_edges.insert(e.index());
+        }
+
+        // Remove the edges by decreasing index, so that the edge swapped into
+        // the place of a removed edge is never removed afterwards
+        let mut reattached = Vec::new();
+        for e in removed_edges.ones().collect::<Vec<_>>().into_iter().rev() {
+            let e = EdgeIndex::new(e);
+            let weight = graph.remove_edge(e).unwrap();
+            if let Some(&(source, target)) = boundary.get(&e) {
+                reattached.push((source, target, weight));
+            }
+        }
+
+        for weight in self.add_nodes {
+            graph.add_node(weight);
+        }
+        for (source, target, weight) in reattached.into_iter().rev().chain(add_edges) {
+            graph.add_edge(source, target, weight);
+        }
+
+        // Remove the nodes by decreasing index, tracking the node swapped into
+        // the place of each removed node
+        let mut origin: Vec<usize> = (0..node_count + new_count).collect();
+        for a in removed.ones().collect::<Vec<_>>().into_iter().rev() {
+            graph.remove_node(NodeIndex::new(a));
+            origin.swap_remove(a);
+        }
+        let mut position = vec![None; node_count + new_count];
+        for (i, &o) in origin.iter().enumerate() {
+            position[o] = Some(NodeIndex::new(i));
+        }
+        Ok(Rewritten {
+            node_count,
+            position,
+        })
+    }
+}
+
+/// The positions of the nodes of a graph after a [`Rewrite`].
+///
+/// Created with [`Rewrite::apply`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rewritten<Ix = DefaultIx> {
+    node_count: usize,
+    position: Vec<Option<NodeIndex<Ix>>>,
+}
+
+impl<Ix: IndexType> Rewritten<Ix> {
+    /// Return the index of node `a` after the rewrite, where `a` is a node of
+    /// the graph before the rewrite or a node added by the rewrite.
+    ///
+    /// Return `None` if the node was removed or doesn't exist.
+    pub fn node<A>(&self, a: A) -> Option<NodeIndex<Ix>>
+    where
+        A: Into<RewriteNode<Ix>>,
+    {
+        let i = match a.into() {
+            RewriteNode::Existing(a) if a.index() < self.node_count => a.index(),
+            RewriteNode::New(i) => self.node_count + i,
+            _ => return None,
+        };
+        self.position.get(i).copied().flatten()
+    }
+}
+
+/// The reason a transaction was not committed.
+#[derive(Clone, Debug, PartialEq)]
+pub enum TransactionError<Err, Ix = DefaultIx> {
+    /// The transaction was aborted with an error.
+    Aborted(Err),
+    /// The rewrite of the transaction cannot be applied.
+    Invalid(RewriteError<Ix>),
+    /// The rewrite would create a cycle in an [`Acyclic`] graph, through the
+    /// given node (as indexed after the rewrite).
+    Cycle(Cycle<NodeIndex<Ix>>),
+}
+
+impl<N, E, Ty: EdgeType, Ix: IndexType> Graph<N, E, Ty, Ix> {
+    /// Run a transaction on the graph: `f` records structural changes in a
+    /// [`Rewrite`], reading the graph as it is before the transaction, and
+    /// the changes are committed atomically if it succeeds.
+    ///
+    /// Return the value returned by `f` and the positions of the nodes after
+    /// the rewrite. Return an error, leaving the graph unchanged, if `f`
+    /// returns an error or if the rewrite cannot be applied; see
+    /// [`Rewrite::apply`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use petgraph::prelude::*;
+    /// use petgraph::rewrite::TransactionError;
+    ///
+    /// let mut graph = DiGraph::<u32, ()>::from_edges(&[(0, 1), (1, 2), (3, 2)]);
+    /// graph[NodeIndex::new(0)] = 1;
+    ///
+    /// // Remove the nodes of weight zero, unless one of them is a source
+    /// let result = graph.transaction(|graph, tx| {
+    ///     for n in graph.node_indices().filter(|&n| graph[n] == 0) {
+    ///         if graph.neighbors_directed(n, Incoming).next().is_none() {
+    ///             return Err(n);
+    ///         }
+    ///         tx.remove_node(n);
+    ///     }
+    ///     Ok(())
+    /// });
+    /// // Nodes 1 and 2 were recorded for removal, but nothing is done
+    /// assert_eq!(result.unwrap_err(), TransactionError::Aborted(NodeIndex::new(3)));
+    /// assert_eq!(graph.node_count(), 4);
+    ///
+    /// let (_, rewritten) = graph
+    ///     .transaction(|_, tx| {
+    ///         let n = tx.add_node(3);
+    ///         tx.add_edge(NodeIndex::new(2), n, ());
+    ///         Ok::<_, ()>(n)
+    ///     })
+    ///     .unwrap();
+    /// assert_eq!(graph.edge_count(), 4);
+    /// assert_eq!(rewritten.node(NodeIndex::new(0)), Some(NodeIndex::new(0)));
+    /// ```
+    #[allow(clippy::type_complexity)]
+    pub fn transaction<T, Err, F>(
+        &mut self,
+        f: F,
+    ) -> Result<(T, Rewritten<Ix>), TransactionError<Err, Ix>>
+    where
+        F: FnOnce(&Self, &mut Rewrite<N, E, Ix>) -> Result<T, Err>,
+    {
+        let mut rewrite = Rewrite::new();
+        let value = f(self, &mut rewrite).map_err(TransactionError::Aborted)?;
+        let rewritten = rewrite.apply(self).map_err(TransactionError::Invalid)?;
+        Ok((value, rewritten))
+    }
+}
+
+impl<N, E, Ix: IndexType> Acyclic<Graph<N, E, Directed, Ix>> {
+    /// Run a transaction on the acyclic graph, as [`Graph::transaction`].
+    ///
+    /// The rewrite is first checked on a copy of the structure of the graph,
+    /// and is not committed if it would create a cycle. Otherwise, the
+    /// topological order is recomputed from scratch after the rewrite, as is
+    /// the reachability index if it is enabled.
+    ///
+    /// Computes in **O(|V| + |E|)** time, in addition to the rewrite and the
+    /// reachability index.
+    #[allow(clippy::type_complexity)]
+    pub fn transaction<T, Err, F>(
+        &mut self,
+        f: F,
+    ) -> Result<(T, Rewritten<Ix>), TransactionError<Err, Ix>>
+    where
+        F: FnOnce(&Graph<N, E, Directed, Ix>, &mut Rewrite<N, E, Ix>) -> Result<T, Err>,
+    {
+        let mut rewrite = Rewrite::new();
+        let value = f(self.inner(), &mut rewrite).map_err(TransactionError::Aborted)?;
+
+        // The structure gets the same indices as the graph after the rewrite
+        let mut structure = self.inner().map(|_, _| (), |_, _| ());
+        rewrite
+            .structure()
+            .apply(&mut structure)
+            .map_err(TransactionError::Invalid)?;
+        let order = toposort(&structure, None).map_err(TransactionError::Cycle)?;
+
+        let reachability_index = self.has_reachability_index();
+        let mut graph = std::mem::replace(self, Acyclic::new()).into_inner();
+        let rewritten = rewrite
+            .apply(&mut graph)
+            .expect("the rewrite was checked on the structure");
+        *self = Acyclic::from_parts_unchecked(graph, order);
+        if reachability_index {
+            self.build_reachability_index();
+        }
+        Ok((value, rewritten))
+    }
+}
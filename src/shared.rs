@@ -0,0 +1,205 @@
+//! A graph shared between threads, with read-copy-update writes.
+
+use std::convert::Infallible;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, RwLock};
+
+/// A read-only snapshot of a [`SharedGraph`], as published by one of its
+/// updates.
+///
+/// A snapshot dereferences to the graph, so the algorithms of the crate run
+/// on `&*snapshot`. It stays valid and unchanged while the shared graph is
+/// updated, and is cheap to clone.
+pub struct Snapshot<G> {
+    graph: Arc<G>,
+    epoch: u64,
+}
+
+impl<G> Snapshot<G> {
+    /// Return the epoch of the snapshot: the number of updates published
+    /// before it.
+    pub fn epoch(&self) -> u64 {
+        self.epoch
+    }
+
+    /// Return the graph of the snapshot.
+    pub fn graph(&self) -> &Arc<G> {
+        &self.graph
+    }
+}
+
+impl<G> Clone for Snapshot<G> {
+    fn clone(&self) -> Self {
+        Snapshot {
+            graph: self.graph.clone(),
+            epoch: self.epoch,
+        }
+    }
+}
+
+impl<G> Deref for Snapshot<G> {
+    type Target = G;
+    fn deref(&self) -> &G {
+        &self.graph
+    }
+}
+
+/// A graph designed for many concurrent readers and occasional batched
+/// writes, using read-copy-update.
+///
+/// Readers take a [`Snapshot`] of the current version of the graph, which only
+/// holds a lock for the time of cloning an `Arc`, and then query it without
+/// any synchronization. A writer [updates](SharedGraph::update) a copy of the
+/// current version with a batch of edits, and publishes it atomically for the
+/// following snapshots; the snapshots taken before are unaffected, and the
+/// old version is freed once its last snapshot is dropped. Writers are
+/// serialized, so that no update is lost.
+///
+/// Each update clones the graph. With a [`CowGraph`], whose clones share their
+/// storage, an update only copies the parts of the graph it touches.
+///
+/// [`CowGraph`]: crate::cow_graph::CowGraph
+///
+/// # Example
+/// ```rust
+/// use std::sync::Arc;
+/// use std::thread;
+///
+/// use petgraph::algo::dijkstra;
+/// use petgraph::cow_graph::CowGraph;
+/// use petgraph::graph::node_index;
+/// use petgraph::shared::SharedGraph;
+/// use petgraph::visit::EdgeRef;
+///
+/// let mut graph = CowGraph::<(), u32>::new();
+/// let a = graph.add_node(());
+/// let b = graph.add_node(());
+/// let c = graph.add_node(());
+/// graph.add_edge(a, b, 1);
+/// graph.add_edge(b, c, 1);
+/// let shared = Arc::new(SharedGraph::new(graph));
+///
+/// // Readers answer queries from their snapshot
+/// let reader = {
+///     let shared = shared.clone();
+///     thread::spawn(move || {
+///         let snapshot = shared.snapshot();
+///         let costs = dijkstra(&*snapshot, node_index(0), None, |e| *e.weight());
+///         (snapshot.epoch(), costs[&node_index(2)])
+///     })
+/// };
+///
+/// // A writer applies a batch of updates
+/// shared.update(|graph| {
+///     let d = graph.add_node(());
+///     graph.add_edge(a, d, 1);
+///     graph.add_edge(d, c, 0);
+/// });
+///
+/// let (epoch, cost) = reader.join().unwrap();
+/// assert!(epoch == 0 && cost == 2 || epoch == 1 && cost == 1);
+/// let snapshot = shared.snapshot();
+/// assert_eq!(snapshot.epoch(), 1);
+/// assert_eq!(dijkstra(&*snapshot, a, Some(c), |e| *e.weight())[&c], 1);
+/// ```
+pub struct SharedGraph<G> {
+    current: RwLock<Snapshot<G>>,
+    writer: Mutex<()>,
+}
+
+impl<G> SharedGraph<G> {
+    /// Create a shared graph whose first version is `graph`, at epoch 0.
+    pub fn new(graph: G) -> Self {
+        SharedGraph {
+            current: RwLock::new(Snapshot {
+                graph: Arc::new(graph),
+                epoch: 0,
+            }),
+            writer: Mutex::new(()),
+        }
+    }
+
+    /// Return a snapshot of the current version of the graph.
+    pub fn snapshot(&self) -> Snapshot<G> {
+        // The snapshot is always consistent: a panicking writer never
+        // published its version.
+        let current = self.current.read().unwrap_or_else(|e| e.into_inner());
+        current.clone()
+    }
+
+    /// Return the epoch of the current version of the graph: the number of
+    /// updates published so far.
+    pub fn epoch(&self) -> u64 {
+        self.snapshot().epoch
+    }
+
+    /// Apply the edits of `f` to a copy of the current version of the graph,
+    /// publish it as the new version and return the result of `f`.
+    ///
+    /// The snapshots taken while `f` runs still see the previous version.
+    /// Updates from other threads wait for this one to be published. If `f`
+    /// panics, nothing is published.
+    pub fn update<F, T>(&self, f: F) -> T
+    where
+        G: Clone,
+        F: FnOnce(&mut G) -> T,
+    {
+        match self.try_update(|graph| Ok::<T, Infallible>(f(graph))) {
+            Ok(value) => value,
+            Err(never) => match never {},
+        }
+    }
+
+    /// Apply the edits of `f` to a copy of the current version of the graph,
+    /// and publish it as the new version if `f` succeeds.
+    ///
+    /// If `f` returns an error, the copy is discarded and nothing is
+    /// published, so that a batch of updates is applied entirely or not at
+    /// all.
+    pub fn try_update<F, T, E>(&self, f: F) -> Result<T, E>
+    where
+        G: Clone,
+        F: FnOnce(&mut G) -> Result<T, E>,
+    {
+        let _writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        let current = self.snapshot();
+        let mut graph = G::clone(&current.graph);
+        let value = f(&mut graph)?;
+        self.publish(graph, current.epoch + 1);
+        Ok(value)
+    }
+
+    /// Publish `graph` as the new version of the shared graph, and return the
+    /// snapshot of the previous version.
+    pub fn replace(&self, graph: G) -> Snapshot<G> {
+        let _writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        let previous = self.snapshot();
+        self.publish(graph, previous.epoch + 1);
+        previous
+    }
+
+    fn publish(&self, graph: G, epoch: u64) {
+        let next = Snapshot {
+            graph: Arc::new(graph),
+            epoch,
+        };
+        let previous = {
+            let mut current = self.current.write().unwrap_or_else(|e| e.into_inner());
+            std::mem::replace(&mut *current, next)
+        };
+        // Free the previous version, if it has no other snapshot, outside of
+        // the lock
+        drop(previous);
+    }
+
+    /// Return the current version of the graph, consuming the shared graph.
+    pub fn into_inner(self) -> Snapshot<G> {
+        self.current.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<G: Default> Default for SharedGraph<G> {
+    fn default() -> Self {
+        SharedGraph::new(G::default())
+    }
+}
@@ -0,0 +1,589 @@
+//! A wrapper around graph types that forbids self-loops and parallel edges.
+
+use std::{convert::TryFrom, ops::Deref};
+
+use fixedbitset::FixedBitSet;
+
+use crate::{
+    adj::IndexType,
+    data::{Build, Create, DataMap, DataMapMut},
+    graph::Graph,
+    visit::{
+        Data, EdgeCount, EdgeIndexable, EdgeRef, GetAdjacencyMatrix, GraphBase, GraphProp,
+        IntoEdgeReferences, IntoEdges, IntoEdgesDirected, IntoNeighbors, IntoNeighborsDirected,
+        IntoNodeIdentifiers, IntoNodeReferences, NodeCompactIndexable, NodeCount, NodeIndexable,
+        Visitable,
+    },
+    Direction, EdgeType,
+};
+
+#[cfg(feature = "stable_graph")]
+use crate::stable_graph::StableGraph;
+
+/// A simple graph: a graph without self-loops or parallel edges.
+///
+/// Wrap a graph and expose an API that ensures that no self-loops and no
+/// parallel edges are ever created. Several algorithms assume that their
+/// input is a simple graph and silently return wrong results on multigraphs;
+/// wrapping the graph in `Simple` makes such graphs fail fast at insertion
+/// time instead.
+///
+/// For undirected graphs, an edge `a -- b` is parallel to an existing edge
+/// `b -- a`. For directed graphs, `a -> b` and `b -> a` may coexist.
+///
+/// ## Graph traits
+/// All graph traits are delegated to the inner graph, with the exception of
+/// the graph construction trait [`Build`]. The wrapped graph can thus only
+/// be modified through the wrapped API that rejects invalid edges.
+///
+/// ## Behaviour on invalid edges
+/// Prefer the dedicated [`Simple::try_add_edge`] method whenever possible,
+/// it returns a [`SimpleEdgeError`] describing why an edge was rejected. The
+/// [`Build::add_edge`] method returns `None` if the edge cannot be added and
+/// [`Build::update_edge`] panics if it is attempted to add a self-loop.
+///
+/// # Example
+/// ```rust
+/// use petgraph::data::Build;
+/// use petgraph::prelude::*;
+/// use petgraph::simple::{Simple, SimpleEdgeError};
+///
+/// let mut graph = Simple::<UnGraph<&str, u32>>::new();
+/// let a = graph.add_node("a");
+/// let b = graph.add_node("b");
+///
+/// let ab = graph.try_add_edge(a, b, 1).unwrap();
+/// assert_eq!(graph.try_add_edge(b, a, 2), Err(SimpleEdgeError::ParallelEdge(ab)));
+/// assert_eq!(graph.try_add_edge(a, a, 3), Err(SimpleEdgeError::SelfLoop(a)));
+/// assert_eq!(graph.edge_count(), 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Simple<G> {
+    /// The underlying graph, accessible through the `inner` method.
+    graph: G,
+}
+
+/// An error that can occur during edge addition for simple graphs.
+#[derive(Clone, Debug, PartialEq)]
+pub enum SimpleEdgeError<N, E> {
+    /// The edge would create a self-loop on the given node.
+    SelfLoop(N),
+    /// The edge would be parallel to the given existing edge.
+    ParallelEdge(E),
+    /// Could not successfully add the edge to the underlying graph.
+    InvalidEdge,
+}
+
+impl<G> Simple<G> {
+    /// Create a new empty simple graph.
+    pub fn new() -> Self
+    where
+        G: Default,
+    {
+        Default::default()
+    }
+
+    /// Get the underlying graph.
+    pub fn inner(&self) -> &G {
+        &self.graph
+    }
+
+    /// Get the underlying graph mutably.
+    ///
+    /// This cannot be public because it might break the simplicity invariant.
+    fn inner_mut(&mut self) -> &mut G {
+        &mut self.graph
+    }
+
+    /// Consume the `Simple` wrapper and return the underlying graph.
+    pub fn into_inner(self) -> G {
+        self.graph
+    }
+}
+
+impl<G: GraphBase> Simple<G>
+where
+    for<'a> &'a G: IntoEdges + GraphBase<NodeId = G::NodeId, EdgeId = G::EdgeId>,
+{
+    /// Wrap a graph into a simple graph.
+    ///
+    /// Returns an error describing the first self-loop or parallel edge found
+    /// if the graph is not simple.
+    ///
+    /// The graph types [`Graph`] and [`StableGraph`] also implement
+    /// [`TryFrom`], which can be used instead of this method and have looser
+    /// type bounds.
+    pub fn try_from_graph(graph: G) -> Result<Self, SimpleEdgeError<G::NodeId, G::EdgeId>>
+    where
+        G: NodeIndexable,
+        for<'a> &'a G: IntoNodeIdentifiers,
+    {
+        check_simple(&graph)?;
+        Ok(Self { graph })
+    }
+
+    /// Add an edge to the graph using [`Build::add_edge`].
+    ///
+    /// Returns the id of the added edge, or a [`SimpleEdgeError`] if the edge
+    /// would create a self-loop, would be parallel to an existing edge or if
+    /// the edge addition failed in the underlying graph.
+    ///
+    /// Computes in **O(e')** time, where **e'** is the number of edges
+    /// connected to `a`.
+    pub fn try_add_edge(
+        &mut self,
+        a: G::NodeId,
+        b: G::NodeId,
+        weight: G::EdgeWeight,
+    ) -> Result<G::EdgeId, SimpleEdgeError<G::NodeId, G::EdgeId>>
+    where
+        G: Build,
+    {
+        self.check_edge(a, b)?;
+        self.graph
+            .add_edge(a, b, weight)
+            .ok_or(SimpleEdgeError::InvalidEdge)
+    }
+
+    /// Check if an edge would be valid, i.e. adding it would neither create a
+    /// self-loop nor a parallel edge.
+    pub fn is_valid_edge(&self, a: G::NodeId, b: G::NodeId) -> bool {
+        self.check_edge(a, b).is_ok()
+    }
+
+    /// Return an error if adding the edge `a -> b` would break simplicity.
+    fn check_edge(
+        &self,
+        a: G::NodeId,
+        b: G::NodeId,
+    ) -> Result<(), SimpleEdgeError<G::NodeId, G::EdgeId>> {
+        if a == b {
+            return Err(SimpleEdgeError::SelfLoop(a));
+        }
+        match self.existing_edge(a, b) {
+            Some(e) => Err(SimpleEdgeError::ParallelEdge(e)),
+            None => Ok(()),
+        }
+    }
+
+    /// Find the edge from `a` to `b`, if any.
+    fn existing_edge(&self, a: G::NodeId, b: G::NodeId) -> Option<G::EdgeId> {
+        self.graph
+            .edges(a)
+            .find(|edge| edge.target() == b)
+            .map(|edge| edge.id())
+    }
+}
+
+impl<G: GraphBase> GraphBase for Simple<G> {
+    type NodeId = G::NodeId;
+    type EdgeId = G::EdgeId;
+}
+
+impl<G: Build> Build for Simple<G>
+where
+    for<'a> &'a G: IntoEdges + GraphBase<NodeId = G::NodeId, EdgeId = G::EdgeId>,
+{
+    fn add_node(&mut self, weight: Self::NodeWeight) -> Self::NodeId {
+        self.graph.add_node(weight)
+    }
+
+    fn add_edge(
+        &mut self,
+        a: Self::NodeId,
+        b: Self::NodeId,
+        weight: Self::EdgeWeight,
+    ) -> Option<Self::EdgeId> {
+        self.try_add_edge(a, b, weight).ok()
+    }
+
+    fn update_edge(
+        &mut self,
+        a: Self::NodeId,
+        b: Self::NodeId,
+        weight: Self::EdgeWeight,
+    ) -> Self::EdgeId {
+        assert!(a != b, "Simple::update_edge: self-loops are not allowed");
+        self.graph.update_edge(a, b, weight)
+    }
+}
+
+impl<G: Create> Create for Simple<G>
+where
+    for<'a> &'a G: IntoEdges + GraphBase<NodeId = G::NodeId, EdgeId = G::EdgeId>,
+{
+    fn with_capacity(nodes: usize, edges: usize) -> Self {
+        Self {
+            graph: G::with_capacity(nodes, edges),
+        }
+    }
+}
+
+impl<G> Deref for Simple<G> {
+    type Target = G;
+
+    fn deref(&self) -> &Self::Target {
+        &self.graph
+    }
+}
+
+/// Return an error describing the first self-loop or parallel edge of `graph`,
+/// if any.
+fn check_simple<G>(graph: G) -> Result<(), SimpleEdgeError<G::NodeId, G::EdgeId>>
+where
+    G: IntoEdges + IntoNodeIdentifiers + NodeIndexable,
+{
+    let mut targets = FixedBitSet::with_capacity(graph.node_bound());
+    for a in graph.node_identifiers() {
+        targets.clear();
+        for edge in graph.edges(a) {
+            if edge.target() == a {
+                return Err(SimpleEdgeError::SelfLoop(a));
+            }
+            if targets.put(graph.to_index(edge.target())) {
+                return Err(SimpleEdgeError::ParallelEdge(edge.id()));
+            }
+        }
+    }
+    Ok(())
+}
+
+/////////////////////// Pass-through graph traits ///////////////////////
+// We implement all the following traits by delegating to the inner graph:
+// - Data
+// - DataMap
+// - DataMapMut
+// - EdgeCount
+// - EdgeIndexable
+// - GetAdjacencyMatrix
+// - GraphProp
+// - NodeCompactIndexable
+// - NodeCount
+// - NodeIndexable
+// - Visitable
+//
+// Furthermore, we also implement the `remove_node` and `remove_edge` methods,
+// as well as the following traits for `Graph` and `StableGraph` (these are
+// hard/impossible to implement generically):
+// - TryFrom
+// - IntoEdgeReferences
+// - IntoEdges
+// - IntoEdgesDirected
+// - IntoNeighbors
+// - IntoNeighborsDirected
+// - IntoNodeIdentifiers
+// - IntoNodeReferences
+
+impl<G: Data> Data for Simple<G> {
+    type NodeWeight = G::NodeWeight;
+    type EdgeWeight = G::EdgeWeight;
+}
+
+impl<G: DataMap> DataMap for Simple<G> {
+    fn node_weight(&self, id: Self::NodeId) -> Option<&Self::NodeWeight> {
+        self.inner().node_weight(id)
+    }
+
+    fn edge_weight(&self, id: Self::EdgeId) -> Option<&Self::EdgeWeight> {
+        self.inner().edge_weight(id)
+    }
+}
+
+impl<G: DataMapMut> DataMapMut for Simple<G> {
+    fn node_weight_mut(&mut self, id: Self::NodeId) -> Option<&mut Self::NodeWeight> {
+        self.inner_mut().node_weight_mut(id)
+    }
+
+    fn edge_weight_mut(&mut self, id: Self::EdgeId) -> Option<&mut Self::EdgeWeight> {
+        self.inner_mut().edge_weight_mut(id)
+    }
+}
+
+impl<G: EdgeCount> EdgeCount for Simple<G> {
+    fn edge_count(&self) -> usize {
+        self.inner().edge_count()
+    }
+}
+
+impl<G: EdgeIndexable> EdgeIndexable for Simple<G> {
+    fn edge_bound(&self) -> usize {
+        self.inner().edge_bound()
+    }
+
+    fn to_index(&self, a: Self::EdgeId) -> usize {
+        self.inner().to_index(a)
+    }
+
+    fn from_index(&self, i: usize) -> Self::EdgeId {
+        self.inner().from_index(i)
+    }
+}
+
+impl<G: GetAdjacencyMatrix> GetAdjacencyMatrix for Simple<G> {
+    type AdjMatrix = G::AdjMatrix;
+
+    fn adjacency_matrix(&self) -> Self::AdjMatrix {
+        self.inner().adjacency_matrix()
+    }
+
+    fn is_adjacent(&self, matrix: &Self::AdjMatrix, a: Self::NodeId, b: Self::NodeId) -> bool {
+        self.inner().is_adjacent(matrix, a, b)
+    }
+}
+
+impl<G: GraphProp> GraphProp for Simple<G> {
+    type EdgeType = G::EdgeType;
+}
+
+impl<G: NodeCompactIndexable> NodeCompactIndexable for Simple<G> {}
+
+impl<G: NodeCount> NodeCount for Simple<G> {
+    fn node_count(&self) -> usize {
+        self.inner().node_count()
+    }
+}
+
+impl<G: NodeIndexable> NodeIndexable for Simple<G> {
+    fn node_bound(&self) -> usize {
+        self.inner().node_bound()
+    }
+
+    fn to_index(&self, a: Self::NodeId) -> usize {
+        self.inner().to_index(a)
+    }
+
+    fn from_index(&self, i: usize) -> Self::NodeId {
+        self.inner().from_index(i)
+    }
+}
+
+impl<G: Visitable> Visitable for Simple<G> {
+    type Map = G::Map;
+
+    fn visit_map(&self) -> Self::Map {
+        self.inner().visit_map()
+    }
+
+    fn reset_map(&self, map: &mut Self::Map) {
+        self.inner().reset_map(map)
+    }
+}
+
+macro_rules! impl_graph_traits {
+    ($graph_type:ident) => {
+        // Remove edge and node methods (not available through traits)
+        impl<N, E, Ty: EdgeType, Ix: IndexType> Simple<$graph_type<N, E, Ty, Ix>> {
+            /// Remove an edge and return its edge weight, or None if it didn't exist.
+            ///
+            /// Pass through to underlying graph.
+            pub fn remove_edge(
+                &mut self,
+                e: <$graph_type<N, E, Ty, Ix> as GraphBase>::EdgeId,
+            ) -> Option<E> {
+                self.graph.remove_edge(e)
+            }
+
+            /// Remove a node from the graph if it exists, and return its
+            /// weight. If it doesn't exist in the graph, return None.
+            ///
+            /// Pass through to underlying graph.
+            pub fn remove_node(
+                &mut self,
+                n: <$graph_type<N, E, Ty, Ix> as GraphBase>::NodeId,
+            ) -> Option<N> {
+                self.graph.remove_node(n)
+            }
+        }
+
+        impl<N, E, Ty: EdgeType, Ix: IndexType> TryFrom<$graph_type<N, E, Ty, Ix>>
+            for Simple<$graph_type<N, E, Ty, Ix>>
+        {
+            type Error = SimpleEdgeError<
+                <$graph_type<N, E, Ty, Ix> as GraphBase>::NodeId,
+                <$graph_type<N, E, Ty, Ix> as GraphBase>::EdgeId,
+            >;
+
+            fn try_from(graph: $graph_type<N, E, Ty, Ix>) -> Result<Self, Self::Error> {
+                check_simple(&graph)?;
+                Ok(Self { graph })
+            }
+        }
+
+        impl<'a, N, E, Ty: EdgeType, Ix: IndexType> IntoEdgeReferences
+            for &'a Simple<$graph_type<N, E, Ty, Ix>>
+        {
+            type EdgeRef = <&'a $graph_type<N, E, Ty, Ix> as IntoEdgeReferences>::EdgeRef;
+            type EdgeReferences =
+                <&'a $graph_type<N, E, Ty, Ix> as IntoEdgeReferences>::EdgeReferences;
+
+            fn edge_references(self) -> Self::EdgeReferences {
+                self.inner().edge_references()
+            }
+        }
+
+        impl<'a, N, E, Ty: EdgeType, Ix: IndexType> IntoEdges
+            for &'a Simple<$graph_type<N, E, Ty, Ix>>
+        {
+            type Edges = <&'a $graph_type<N, E, Ty, Ix> as IntoEdges>::Edges;
+
+            fn edges(self, a: Self::NodeId) -> Self::Edges {
+                self.inner().edges(a)
+            }
+        }
+
+        impl<'a, N, E, Ty: EdgeType, Ix: IndexType> IntoEdgesDirected
+            for &'a Simple<$graph_type<N, E, Ty, Ix>>
+        {
+            type EdgesDirected =
+                <&'a $graph_type<N, E, Ty, Ix> as IntoEdgesDirected>::EdgesDirected;
+
+            fn edges_directed(self, a: Self::NodeId, dir: Direction) -> Self::EdgesDirected {
+                self.inner().edges_directed(a, dir)
+            }
+        }
+
+        impl<'a, N, E, Ty: EdgeType, Ix: IndexType> IntoNeighbors
+            for &'a Simple<$graph_type<N, E, Ty, Ix>>
+        {
+            type Neighbors = <&'a $graph_type<N, E, Ty, Ix> as IntoNeighbors>::Neighbors;
+
+            fn neighbors(self, a: Self::NodeId) -> Self::Neighbors {
+                self.inner().neighbors(a)
+            }
+        }
+
+        impl<'a, N, E, Ty: EdgeType, Ix: IndexType> IntoNeighborsDirected
+            for &'a Simple<$graph_type<N, E, Ty, Ix>>
+        {
+            type NeighborsDirected =
+                <&'a $graph_type<N, E, Ty, Ix> as IntoNeighborsDirected>::NeighborsDirected;
+
+            fn neighbors_directed(self, n: Self::NodeId, d: Direction) -> Self::NeighborsDirected {
+                self.inner().neighbors_directed(n, d)
+            }
+        }
+
+        impl<'a, N, E, Ty: EdgeType, Ix: IndexType> IntoNodeIdentifiers
+            for &'a Simple<$graph_type<N, E, Ty, Ix>>
+        {
+            type NodeIdentifiers =
+                <&'a $graph_type<N, E, Ty, Ix> as IntoNodeIdentifiers>::NodeIdentifiers;
+
+            fn node_identifiers(self) -> Self::NodeIdentifiers {
+                self.inner().node_identifiers()
+            }
+        }
+
+        impl<'a, N, E, Ty: EdgeType, Ix: IndexType> IntoNodeReferences
+            for &'a Simple<$graph_type<N, E, Ty, Ix>>
+        {
+            type NodeRef = <&'a $graph_type<N, E, Ty, Ix> as IntoNodeReferences>::NodeRef;
+            type NodeReferences =
+                <&'a $graph_type<N, E, Ty, Ix> as IntoNodeReferences>::NodeReferences;
+
+            fn node_references(self) -> Self::NodeReferences {
+                self.inner().node_references()
+            }
+        }
+    };
+}
+
+impl_graph_traits!(Graph);
+#[cfg(feature = "stable_graph")]
+impl_graph_traits!(StableGraph);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::algo::dijkstra;
+    #[cfg(feature = "stable_graph")]
+    use crate::prelude::StableDiGraph;
+    use crate::prelude::{DiGraph, UnGraph};
+
+    #[test]
+    fn test_simple_directed() {
+        let mut graph = Simple::<DiGraph<(), ()>>::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+
+        let ab = graph.try_add_edge(a, b, ()).unwrap();
+        assert!(graph.try_add_edge(b, a, ()).is_ok());
+        assert_eq!(
+            graph.try_add_edge(a, b, ()),
+            Err(SimpleEdgeError::ParallelEdge(ab))
+        );
+        assert_eq!(
+            graph.try_add_edge(b, b, ()),
+            Err(SimpleEdgeError::SelfLoop(b))
+        );
+        assert!(graph.add_edge(a, b, ()).is_none());
+        assert!(!graph.is_valid_edge(a, a));
+        assert_eq!(graph.edge_count(), 2);
+
+        // Algorithms run on the wrapper directly.
+        let dist = dijkstra(&graph, a, None, |_| 1);
+        assert_eq!(dist[&b], 1);
+    }
+
+    #[test]
+    fn test_simple_undirected() {
+        let mut graph = Simple::<UnGraph<(), u32>>::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+
+        let ab = graph.try_add_edge(a, b, 1).unwrap();
+        assert_eq!(
+            graph.try_add_edge(b, a, 2),
+            Err(SimpleEdgeError::ParallelEdge(ab))
+        );
+
+        // Updating an existing edge does not create a parallel edge.
+        assert_eq!(graph.update_edge(b, a, 3), ab);
+        assert_eq!(graph.edge_count(), 1);
+        assert_eq!(graph[ab], 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_simple_update_edge_self_loop() {
+        let mut graph = Simple::<DiGraph<(), ()>>::new();
+        let a = graph.add_node(());
+        graph.update_edge(a, a, ());
+    }
+
+    #[test]
+    fn test_simple_try_from() {
+        let mut graph = DiGraph::<(), ()>::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        graph.add_edge(a, b, ());
+        let graph = Simple::try_from(graph).unwrap().into_inner();
+
+        let mut multi = graph.clone();
+        multi.add_edge(a, b, ());
+        assert!(matches!(
+            Simple::try_from(multi),
+            Err(SimpleEdgeError::ParallelEdge(_))
+        ));
+
+        let mut looped = graph;
+        looped.add_edge(b, b, ());
+        assert_eq!(
+            Simple::try_from(looped).unwrap_err(),
+            SimpleEdgeError::SelfLoop(b)
+        );
+    }
+
+    #[cfg(feature = "stable_graph")]
+    #[test]
+    fn test_simple_stable_graph_remove() {
+        let mut graph = Simple::<StableDiGraph<(), ()>>::new();
+        let a = graph.add_node(());
+        let b = graph.add_node(());
+        let ab = graph.try_add_edge(a, b, ()).unwrap();
+        assert!(graph.remove_edge(ab).is_some());
+        assert!(graph.try_add_edge(a, b, ()).is_ok());
+        assert!(graph.remove_node(b).is_some());
+        assert_eq!(graph.edge_count(), 0);
+    }
+}
@@ -69,8 +69,10 @@ pub use self::reversed::*;
 mod macros;
 
 mod dfsvisit;
+mod timestamps;
 mod traversal;
 pub use self::dfsvisit::*;
+pub use self::timestamps::*;
 pub use self::traversal::*;
 
 use fixedbitset::FixedBitSet;
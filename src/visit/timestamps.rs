@@ -0,0 +1,164 @@
+use std::collections::VecDeque;
+
+use crate::visit::{depth_first_search, DfsEvent, Time};
+use crate::visit::{IntoNeighbors, NodeIndexable, VisitMap, Visitable};
+
+/// The discovery and finish times of the nodes of a traversal, with their
+/// parent in the traversal forest, as computed by [`dfs_times`] or
+/// [`bfs_times`].
+///
+/// Discovery and finish events share a single clock, like the
+/// [`Time`] of [`depth_first_search`]. The times and parents are stored in
+/// arrays indexed by [`NodeIndexable::to_index`], available as slices for the
+/// algorithms built on them; the nodes not reached by the traversal have
+/// neither times nor parent.
+#[derive(Clone, Debug)]
+pub struct TraversalTimes<N> {
+    discovery: Vec<Option<Time>>,
+    finish: Vec<Option<Time>>,
+    parent: Vec<Option<N>>,
+    order: Vec<N>,
+}
+
+impl<N: Copy> TraversalTimes<N> {
+    fn new(bound: usize) -> Self {
+        TraversalTimes {
+            discovery: vec![None; bound],
+            finish: vec![None; bound],
+            parent: vec![None; bound],
+            order: Vec::new(),
+        }
+    }
+
+    /// Return the discovery time of the node of index `i`, or `None` if it was
+    /// not reached.
+    pub fn discovery(&self, i: usize) -> Option<Time> {
+        self.discovery.get(i).and_then(|&t| t)
+    }
+
+    /// Return the finish time of the node of index `i`, or `None` if it was
+    /// not reached.
+    pub fn finish(&self, i: usize) -> Option<Time> {
+        self.finish.get(i).and_then(|&t| t)
+    }
+
+    /// Return the parent of the node of index `i` in the traversal forest, or
+    /// `None` if it is a root or was not reached.
+    pub fn parent(&self, i: usize) -> Option<N> {
+        self.parent.get(i).and_then(|&p| p)
+    }
+
+    /// Return the discovery times, by node index.
+    pub fn discovery_times(&self) -> &[Option<Time>] {
+        &self.discovery
+    }
+
+    /// Return the finish times, by node index.
+    pub fn finish_times(&self) -> &[Option<Time>] {
+        &self.finish
+    }
+
+    /// Return the parents, by node index.
+    pub fn parents(&self) -> &[Option<N>] {
+        &self.parent
+    }
+
+    /// Return the reached nodes, in order of discovery.
+    pub fn order(&self) -> &[N] {
+        &self.order
+    }
+}
+
+/// Return the discovery and finish times of a depth first search from the
+/// nodes of `starts`, with the parent of each node in the search forest.
+///
+/// The times are those of the `Discover` and `Finish` events of
+/// [`depth_first_search`]: the interval of a node contains those of its
+/// descendants in the search forest, and is disjoint from the others.
+///
+/// # Example
+/// ```
+/// use petgraph::prelude::*;
+/// use petgraph::visit::{dfs_times, Time};
+///
+/// let gr: Graph<(), ()> = Graph::from_edges(&[(0, 1), (1, 2), (0, 3)]);
+/// let times = dfs_times(&gr, Some(NodeIndex::new(0)));
+/// assert_eq!(times.discovery(1), Some(Time(3)));
+/// assert_eq!(times.finish(1), Some(Time(6)));
+/// assert_eq!(times.parent(2), Some(NodeIndex::new(1)));
+/// let order: Vec<_> = times.order().iter().map(|n| n.index()).collect();
+/// assert_eq!(order, vec![0, 3, 1, 2]);
+/// ```
+pub fn dfs_times<G, I>(graph: G, starts: I) -> TraversalTimes<G::NodeId>
+where
+    G: IntoNeighbors + Visitable + NodeIndexable,
+    I: IntoIterator<Item = G::NodeId>,
+{
+    let mut times = TraversalTimes::new(graph.node_bound());
+    depth_first_search(graph, starts, |event| match event {
+        DfsEvent::Discover(n, t) => {
+            times.discovery[graph.to_index(n)] = Some(t);
+            times.order.push(n);
+        }
+        DfsEvent::TreeEdge(u, v) => times.parent[graph.to_index(v)] = Some(u),
+        DfsEvent::Finish(n, t) => times.finish[graph.to_index(n)] = Some(t),
+        _ => {}
+    });
+    times
+}
+
+/// Return the discovery and finish times of a breadth first search from the
+/// nodes of `starts`, with the parent of each node in the search forest.
+///
+/// Each start node not yet reached starts a new search. A node is discovered
+/// when it is first reached and queued, and finished when all its neighbors
+/// have been discovered, after it is dequeued.
+///
+/// # Example
+/// ```
+/// use petgraph::prelude::*;
+/// use petgraph::visit::{bfs_times, Time};
+///
+/// let gr: Graph<(), ()> = Graph::from_edges(&[(0, 1), (1, 2), (0, 3)]);
+/// let times = bfs_times(&gr, Some(NodeIndex::new(0)));
+/// assert_eq!(times.discovery(1), Some(Time(2)));
+/// assert_eq!(times.finish(0), Some(Time(3)));
+/// assert_eq!(times.parent(2), Some(NodeIndex::new(1)));
+/// let order: Vec<_> = times.order().iter().map(|n| n.index()).collect();
+/// assert_eq!(order, vec![0, 3, 1, 2]);
+/// ```
+pub fn bfs_times<G, I>(graph: G, starts: I) -> TraversalTimes<G::NodeId>
+where
+    G: IntoNeighbors + Visitable + NodeIndexable,
+    I: IntoIterator<Item = G::NodeId>,
+{
+    let mut times = TraversalTimes::new(graph.node_bound());
+    let mut discovered = graph.visit_map();
+    let mut queue = VecDeque::new();
+    let mut time = 0;
+    let discover = |times: &mut TraversalTimes<_>, n, parent, time: &mut usize| {
+        let i = graph.to_index(n);
+        times.discovery[i] = Some(Time(*time));
+        times.parent[i] = parent;
+        times.order.push(n);
+        *time += 1;
+    };
+    for start in starts {
+        if !discovered.visit(start) {
+            continue;
+        }
+        discover(&mut times, start, None, &mut time);
+        queue.push_back(start);
+        while let Some(u) = queue.pop_front() {
+            for v in graph.neighbors(u) {
+                if discovered.visit(v) {
+                    discover(&mut times, v, Some(u), &mut time);
+                    queue.push_back(v);
+                }
+            }
+            times.finish[graph.to_index(u)] = Some(Time(time));
+            time += 1;
+        }
+    }
+    times
+}
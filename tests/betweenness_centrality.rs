@@ -0,0 +1,105 @@
+use petgraph::algo::betweenness_centrality;
+use petgraph::prelude::*;
+use petgraph::EdgeType;
+
+/// The distance and number of shortest paths from `s` to every node.
+fn bfs_counts<Ty: EdgeType>(graph: &Graph<(), (), Ty>, s: NodeIndex) -> Vec<Option<(usize, f64)>> {
+    let mut counts = vec![None; graph.node_count()];
+    counts[s.index()] = Some((0, 1.));
+    let mut frontier = vec![s];
+    let mut d = 0;
+    while !frontier.is_empty() {
+        d += 1;
+        let mut next = Vec::new();
+        for &v in &frontier {
+            let sigma = counts[v.index()].unwrap().1;
+            for w in graph.neighbors(v) {
+                match &mut counts[w.index()] {
+                    Some((dw, sw)) if *dw == d => *sw += sigma,
+                    Some(_) => {}
+                    c @ None => {
+                        *c = Some((d, sigma));
+                        next.push(w);
+                    }
+                }
+            }
+        }
+        frontier = next;
+    }
+    counts
+}
+
+fn naive_betweenness<Ty: EdgeType>(graph: &Graph<(), (), Ty>) -> Vec<f64> {
+    let counts: Vec<_> = graph.node_indices().map(|s| bfs_counts(graph, s)).collect();
+    let mut centrality = vec![0.; graph.node_count()];
+    for s in 0..graph.node_count() {
+        for t in 0..graph.node_count() {
+            let (dst, sst) = match counts[s][t] {
+                Some(c) if s != t => c,
+                _ => continue,
+            };
+            for v in (0..graph.node_count()).filter(|&v| v != s && v != t) {
+                if let (Some((dsv, ssv)), Some((dvt, svt))) = (counts[s][v], counts[v][t]) {
+                    if dsv + dvt == dst {
+                        centrality[v] += ssv * svt / sst;
+                    }
+                }
+            }
+        }
+    }
+    if !graph.is_directed() {
+        for c in &mut centrality {
+            *c /= 2.;
+        }
+    }
+    centrality
+}
+
+fn pseudo_random_edges(n: u32) -> Vec<(u32, u32)> {
+    (0..n)
+        .flat_map(|a| (0..n).map(move |b| (a, b)))
+        .filter(|&(a, b)| a != b && (a * 17 + b * 31) % 7 == 0)
+        .collect()
+}
+
+fn assert_close(left: &[f64], right: &[f64]) {
+    assert_eq!(left.len(), right.len());
+    for (l, r) in left.iter().zip(right) {
+        assert!((l - r).abs() < 1e-9, "{:?} != {:?}", left, right);
+    }
+}
+
+#[test]
+fn betweenness_matches_naive() {
+    let edges = pseudo_random_edges(20);
+    let directed = DiGraph::<(), ()>::from_edges(&edges);
+    assert_close(
+        &betweenness_centrality(&directed),
+        &naive_betweenness(&directed),
+    );
+    let undirected = UnGraph::<(), ()>::from_edges(&edges);
+    assert_close(
+        &betweenness_centrality(&undirected),
+        &naive_betweenness(&undirected),
+    );
+}
+
+#[test]
+fn betweenness_directed_cycle() {
+    // Every node is in the middle of the paths of length 2 and 3 through it
+    let cycle = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 3), (3, 0)]);
+    assert_eq!(betweenness_centrality(&cycle), vec![3.; 4]);
+    assert_eq!(betweenness_centrality(&DiGraph::<(), ()>::new()), vec![]);
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn parallel_betweenness_matches_sequential() {
+    use petgraph::algo::betweenness_centrality::parallel_betweenness_centrality;
+
+    let graph = UnGraph::<(), ()>::from_edges(pseudo_random_edges(50));
+    assert_close(
+        &parallel_betweenness_centrality(&graph),
+        &betweenness_centrality(&graph),
+    );
+}
@@ -0,0 +1,194 @@
+use petgraph::algo::{chinese_postman, floyd_warshall};
+use petgraph::prelude::*;
+use petgraph::EdgeType;
+
+/// Assert that `walk` is a closed walk through every edge of `graph`, and
+/// return its cost.
+fn check_walk<Ty: EdgeType>(graph: &Graph<(), u32, Ty>, walk: &[EdgeIndex]) -> u32 {
+    let mut covered = vec![false; graph.edge_count()];
+    // The possible current nodes, as undirected edges can be walked both ways
+    let (a, b) = graph.edge_endpoints(walk[0]).unwrap();
+    let starts = if Ty::is_directed() {
+        vec![a]
+    } else {
+        vec![a, b]
+    };
+    let mut found = false;
+    for &start in &starts {
+        let mut current = start;
+        let mut ok = true;
+        for &e in walk {
+            let (a, b) = graph.edge_endpoints(e).unwrap();
+            current = if a == current {
+                b
+            } else if b == current && !Ty::is_directed() {
+                a
+            } else {
+                ok = false;
+                break;
+            };
+        }
+        if ok && current == start {
+            found = true;
+        }
+    }
+    assert!(found, "not a closed walk: {:?}", walk);
+    for &e in walk {
+        covered[e.index()] = true;
+    }
+    assert!(covered.iter().all(|&c| c));
+    walk.iter().map(|&e| graph[e]).sum()
+}
+
+#[test]
+fn chinese_postman_undirected() {
+    // Two triangles sharing node 0, with a tail: 4 and 5 have odd degree
+    let graph = UnGraph::<(), u32>::from_edges(&[
+        (0, 1, 1),
+        (1, 2, 1),
+        (2, 0, 1),
+        (0, 3, 2),
+        (3, 4, 5),
+        (4, 0, 2),
+        (4, 5, 1),
+    ]);
+    let (cost, walk) = chinese_postman(&graph, |e| *e.weight()).unwrap();
+    assert_eq!(check_walk(&graph, &walk), cost);
+    // Repeat the tail 4-5
+    assert_eq!(cost, 13 + 1);
+}
+
+#[test]
+fn chinese_postman_eulerian() {
+    let graph = DiGraph::<(), u32>::from_edges(&[(0, 1, 3), (1, 2, 4), (2, 0, 5), (0, 0, 1)]);
+    let (cost, walk) = chinese_postman(&graph, |e| *e.weight()).unwrap();
+    assert_eq!(check_walk(&graph, &walk), cost);
+    assert_eq!((cost, walk.len()), (13, 4));
+}
+
+#[test]
+fn chinese_postman_directed() {
+    // 1 has one more incoming edge, 0 one more outgoing edge
+    let graph = DiGraph::<(), u32>::from_edges(&[
+        (0, 1, 1),
+        (1, 2, 1),
+        (2, 0, 10),
+        (0, 2, 1),
+        (2, 3, 1),
+        (3, 1, 1),
+    ]);
+    let (cost, walk) = chinese_postman(&graph, |e| *e.weight()).unwrap();
+    assert_eq!(check_walk(&graph, &walk), cost);
+    // Go back from 1 to 0 through 2
+    assert_eq!(cost, 15 + 11);
+
+    // Several sources and targets
+    let graph = DiGraph::<(), u32>::from_edges(&[
+        (0, 1, 1),
+        (0, 2, 1),
+        (0, 3, 1),
+        (1, 4, 1),
+        (2, 4, 1),
+        (3, 4, 1),
+        (4, 0, 1),
+    ]);
+    let (cost, walk) = chinese_postman(&graph, |e| *e.weight()).unwrap();
+    assert_eq!(check_walk(&graph, &walk), cost);
+    assert_eq!(cost, 7 + 2);
+}
+
+#[test]
+fn chinese_postman_impossible() {
+    let graph = DiGraph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 1)]);
+    assert_eq!(chinese_postman(&graph, |e| *e.weight()), None);
+
+    let graph = UnGraph::<(), u32>::from_edges(&[(0, 1, 1), (2, 3, 1)]);
+    assert_eq!(chinese_postman(&graph, |e| *e.weight()), None);
+}
+
+#[test]
+fn chinese_postman_trivial() {
+    let mut graph = UnGraph::<(), u32>::default();
+    assert_eq!(chinese_postman(&graph, |e| *e.weight()), Some((0, vec![])));
+
+    // Isolated nodes are ignored
+    graph.add_node(());
+    let a = graph.add_node(());
+    let b = graph.add_node(());
+    let e = graph.add_edge(a, b, 3);
+    assert_eq!(
+        chinese_postman(&graph, |e| *e.weight()),
+        Some((6, vec![e, e]))
+    );
+}
+
+#[test]
+fn chinese_postman_grid() {
+    // A 4x4 grid, with all border nodes except the corners of odd degree
+    let mut graph = UnGraph::<(), u32>::default();
+    let nodes: Vec<_> = (0..16).map(|_| graph.add_node(())).collect();
+    for i in 0..16 {
+        if i % 4 < 3 {
+            graph.add_edge(nodes[i], nodes[i + 1], 1);
+        }
+        if i < 12 {
+            graph.add_edge(nodes[i], nodes[i + 4], 1);
+        }
+    }
+    let (cost, walk) = chinese_postman(&graph, |e| *e.weight()).unwrap();
+    assert_eq!(check_walk(&graph, &walk), cost);
+    // The 8 odd nodes are paired along the border
+    assert_eq!(cost, 24 + 4);
+}
+
+#[test]
+fn chinese_postman_many_odd_nodes() {
+    // A cycle of 30 nodes with 10 chords, of pseudo-random costs: the 20 ends
+    // of the chords have odd degree
+    let n = 30;
+    let mut state = 7u64;
+    let mut next = || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        1 + (state >> 33) as u32 % 20
+    };
+    let mut graph = UnGraph::<(), u32>::default();
+    let nodes: Vec<_> = (0..n).map(|_| graph.add_node(())).collect();
+    for i in 0..n {
+        graph.add_edge(nodes[i], nodes[(i + 1) % n], next());
+    }
+    for i in 0..10 {
+        graph.add_edge(nodes[i], nodes[i + 15], next());
+    }
+    let odd: Vec<_> = graph
+        .node_indices()
+        .filter(|&v| graph.edges(v).count() % 2 == 1)
+        .collect();
+    assert_eq!(odd.len(), 20);
+
+    // The optimum repeats the shortest paths of a minimum weight perfect
+    // matching of the odd nodes, by dynamic programming over their subsets
+    let distance = floyd_warshall(&graph, |e| *e.weight()).unwrap();
+    let m = odd.len();
+    let mut best = vec![u32::MAX; 1 << m];
+    best[0] = 0;
+    for mask in 1..1usize << m {
+        if mask.count_ones() % 2 == 1 {
+            continue;
+        }
+        let i = mask.trailing_zeros() as usize;
+        for j in i + 1..m {
+            let rest = mask & !(1 << i) & !(1 << j);
+            if mask & (1 << j) != 0 && best[rest] != u32::MAX {
+                let c = best[rest] + distance[&(odd[i], odd[j])];
+                best[mask] = best[mask].min(c);
+            }
+        }
+    }
+    let total: u32 = graph.edge_weights().sum();
+
+    let (cost, walk) = chinese_postman(&graph, |e| *e.weight()).unwrap();
+    assert_eq!(check_walk(&graph, &walk), cost);
+    assert_eq!(cost, total + best[(1 << m) - 1]);
+}
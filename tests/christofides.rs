@@ -0,0 +1,108 @@
+use petgraph::algo::{christofides, min_spanning_tree};
+use petgraph::data::FromElements;
+use petgraph::prelude::*;
+
+/// A complete graph of `n` pseudo-random points, with Euclidean distances.
+fn euclidean(n: usize, seed: u64) -> UnGraph<(f64, f64), f64> {
+    let mut state = seed;
+    let mut next = || {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) as f64 / (1u64 << 31) as f64
+    };
+    let mut graph = UnGraph::default();
+    let nodes: Vec<_> = (0..n).map(|_| graph.add_node((next(), next()))).collect();
+    for i in 0..n {
+        for j in i + 1..n {
+            let (a, b) = (graph[nodes[i]], graph[nodes[j]]);
+            let d = ((a.0 - b.0).powi(2) + (a.1 - b.1).powi(2)).sqrt();
+            graph.add_edge(nodes[i], nodes[j], d);
+        }
+    }
+    graph
+}
+
+fn tour_cost(graph: &UnGraph<(f64, f64), f64>, tour: &[NodeIndex]) -> f64 {
+    (0..tour.len())
+        .map(|i| {
+            let e = graph
+                .find_edge(tour[i], tour[(i + 1) % tour.len()])
+                .unwrap();
+            graph[e]
+        })
+        .sum()
+}
+
+/// The cost of an optimal tour, by brute force over the permutations fixing the
+/// first node.
+fn optimal_cost(graph: &UnGraph<(f64, f64), f64>) -> f64 {
+    fn search(
+        graph: &UnGraph<(f64, f64), f64>,
+        tour: &mut Vec<NodeIndex>,
+        rest: &mut Vec<NodeIndex>,
+        best: &mut f64,
+    ) {
+        if rest.is_empty() {
+            *best = best.min(tour_cost(graph, tour));
+        }
+        for i in 0..rest.len() {
+            let node = rest.swap_remove(i);
+            tour.push(node);
+            search(graph, tour, rest, best);
+            tour.pop();
+            rest.push(node);
+            let last = rest.len() - 1;
+            rest.swap(i, last);
+        }
+    }
+    let mut rest: Vec<_> = graph.node_indices().skip(1).collect();
+    let mut best = std::f64::INFINITY;
+    search(graph, &mut vec![NodeIndex::new(0)], &mut rest, &mut best);
+    best
+}
+
+fn assert_is_tour(graph: &UnGraph<(f64, f64), f64>, tour: &[NodeIndex]) {
+    let mut sorted = tour.to_vec();
+    sorted.sort();
+    assert_eq!(sorted, graph.node_indices().collect::<Vec<_>>());
+}
+
+#[test]
+fn christofides_approximation() {
+    for n in 0..9 {
+        for seed in 0..5 {
+            let graph = euclidean(n, seed);
+            let (cost, tour) = christofides(&graph, |e| *e.weight()).unwrap();
+            assert_is_tour(&graph, &tour);
+            if n > 1 {
+                assert!((cost - tour_cost(&graph, &tour)).abs() < 1e-9);
+                assert!(cost <= 1.5 * optimal_cost(&graph) + 1e-9);
+            }
+        }
+    }
+}
+
+#[test]
+fn christofides_large() {
+    // Enough nodes for the matching of odd degree nodes to use the blossom
+    // algorithm
+    let graph = euclidean(100, 42);
+    let (cost, tour) = christofides(&graph, |e| *e.weight()).unwrap();
+    assert_is_tour(&graph, &tour);
+    assert!((cost - tour_cost(&graph, &tour)).abs() < 1e-9);
+    // The minimum spanning tree costs less than the optimal tour
+    let mst = UnGraph::<(f64, f64), f64>::from_elements(min_spanning_tree(&graph));
+    let mst_cost: f64 = mst.edge_weights().sum();
+    assert!(cost <= 1.5 * mst_cost);
+}
+
+#[test]
+fn christofides_incomplete() {
+    let graph = UnGraph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 1), (2, 3, 1)]);
+    assert_eq!(christofides(&graph, |e| *e.weight()), None);
+
+    let triangle = UnGraph::<(), u32>::from_edges(&[(0, 1, 1), (1, 2, 2), (2, 0, 3), (0, 1, 5)]);
+    let (cost, _) = christofides(&triangle, |e| *e.weight()).unwrap();
+    assert_eq!(cost, 6);
+}
@@ -0,0 +1,124 @@
+use petgraph::algo::{dijkstra, toposort};
+use petgraph::cow_graph::CowGraph;
+use petgraph::graph::{edge_index, node_index};
+use petgraph::prelude::*;
+
+/// A path of `n` nodes.
+fn path(n: usize) -> CowGraph<usize, usize> {
+    let mut graph = CowGraph::new();
+    let mut prev = graph.add_node(0);
+    for i in 1..n {
+        let next = graph.add_node(i);
+        graph.add_edge(prev, next, i);
+        prev = next;
+    }
+    graph
+}
+
+#[test]
+fn cow_graph_clone_shares_storage() {
+    let graph = path(1000);
+    // 8 chunks of nodes and 8 chunks of edges
+    let total = graph.shared_chunks(&graph);
+    assert_eq!(total, 16);
+
+    let mut branch = graph.clone();
+    assert_eq!(branch.shared_chunks(&graph), total);
+
+    // Changing a weight copies a single chunk
+    branch[node_index(500)] = 0;
+    assert_eq!(branch.shared_chunks(&graph), total - 1);
+    assert_eq!(graph[node_index(500)], 500);
+
+    // Removing an edge touches the chunks of the removed edge, of the last
+    // edge moved into its place, and of their endpoints
+    branch.remove_edge(edge_index(10));
+    assert_eq!(branch.shared_chunks(&graph), total - 5);
+    assert_eq!(branch.edge_count(), 998);
+    assert_eq!(graph.edge_count(), 999);
+    assert_eq!(
+        graph.edge_endpoints(edge_index(10)),
+        Some((node_index(10), node_index(11)))
+    );
+    assert_eq!(
+        branch.edge_endpoints(edge_index(10)),
+        Some((node_index(998), node_index(999)))
+    );
+}
+
+#[test]
+fn cow_graph_branches_are_independent() {
+    let graph = path(300);
+    let mut branches: Vec<_> = (0..3).map(|_| graph.clone()).collect();
+    branches[0].remove_node(node_index(0));
+    branches[1].add_edge(node_index(299), node_index(0), 0);
+    let n = branches[2].add_node(300);
+    branches[2].update_edge(node_index(299), n, 300);
+
+    assert_eq!(graph.node_count(), 300);
+    assert_eq!(graph.edge_count(), 299);
+    assert!(toposort(&graph, None).is_ok());
+
+    assert_eq!(branches[0].node_count(), 299);
+    assert_eq!(branches[0].edge_count(), 298);
+    // The last node took the place of the removed one
+    assert_eq!(branches[0][node_index(0)], 299);
+    assert_eq!(
+        branches[0]
+            .neighbors_directed(node_index(0), Incoming)
+            .collect::<Vec<_>>(),
+        vec![node_index(298)]
+    );
+
+    assert!(toposort(&branches[1], None).is_err());
+
+    let costs = dijkstra(&branches[2], node_index(0), None, |e| *e.weight());
+    assert_eq!(costs[&n], (1..=300).sum());
+    assert_eq!(
+        dijkstra(&graph, node_index(0), None, |e| *e.weight()).len(),
+        300
+    );
+}
+
+#[test]
+fn cow_graph_undirected() {
+    let mut graph = CowGraph::<(), (), Undirected>::new_undirected();
+    let a = graph.add_node(());
+    let b = graph.add_node(());
+    let c = graph.add_node(());
+    graph.add_edge(a, b, ());
+    graph.add_edge(c, a, ());
+    graph.add_edge(a, a, ());
+
+    // Like `Graph`, the self-loop is only listed once
+    let mut neighbors: Vec<_> = graph.neighbors(a).collect();
+    neighbors.sort();
+    assert_eq!(neighbors, vec![a, b, c]);
+    assert!(graph.edges(a).all(|e| e.source() == a));
+    assert!(graph.edges_directed(a, Incoming).all(|e| e.target() == a));
+    assert_eq!(graph.find_edge(a, c), Some(edge_index(1)));
+    assert_eq!(graph.find_edge(b, c), None);
+}
+
+#[test]
+fn cow_graph_graph_roundtrip() {
+    let mut graph = Graph::<&str, u32>::new();
+    let a = graph.add_node("a");
+    let b = graph.add_node("b");
+    let c = graph.add_node("c");
+    graph.extend_with_edges(&[(a, b, 1), (b, c, 2), (c, a, 3)]);
+
+    let cow = CowGraph::from(graph.clone());
+    let mut copy = cow.clone();
+    copy.remove_node(b);
+    assert_eq!(copy.node_count(), 2);
+
+    let back = cow.into_graph();
+    assert_eq!(back.node_count(), 3);
+    assert!(back
+        .edge_references()
+        .map(|e| (e.source(), e.target(), *e.weight()))
+        .eq(graph
+            .edge_references()
+            .map(|e| (e.source(), e.target(), *e.weight()))));
+}
@@ -0,0 +1,108 @@
+use petgraph::diff::{apply_patch, apply_patch_by_key, diff, Change, Patch, PatchError};
+use petgraph::prelude::*;
+
+#[test]
+fn diff_parallel_edges() {
+    let mut old = DiGraph::<(), char>::new();
+    let a = old.add_node(());
+    let b = old.add_node(());
+    old.extend_with_edges(&[(a, b, 'x'), (a, b, 'y'), (a, b, 'x')]);
+    let mut new = old.clone();
+    new.remove_edge(EdgeIndex::new(1));
+    new.add_edge(a, b, 'z');
+    new.add_edge(b, a, 'w');
+
+    let patch = diff(&old, &new);
+    assert_eq!(
+        patch.changes(),
+        &[
+            Change::RemoveEdge {
+                source: a,
+                target: b,
+                weight: 'y'
+            },
+            Change::AddEdge {
+                source: a,
+                target: b,
+                weight: 'z'
+            },
+            Change::AddEdge {
+                source: b,
+                target: a,
+                weight: 'w'
+            },
+        ]
+    );
+    apply_patch(&mut old, &patch).unwrap();
+    assert!(diff(&old, &new).is_empty());
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn diff_stable_graph_by_key() {
+    use petgraph::diff::diff_by_key;
+
+    // Two replicas with different indices, synced by node name
+    let mut old = StableGraph::<&str, u32>::new();
+    let x = old.add_node("x");
+    let a = old.add_node("a");
+    let b = old.add_node("b");
+    old.add_edge(a, b, 1);
+    old.remove_node(x);
+    let mut replica = StableGraph::<&str, u32>::new();
+    let b = replica.add_node("b");
+    let a = replica.add_node("a");
+    replica.add_edge(a, b, 1);
+
+    let mut new = old.clone();
+    let c = new.add_node("c");
+    new.add_edge(c, NodeIndex::new(1), 2);
+    new.remove_node(NodeIndex::new(2));
+
+    let by_name = |_, name: &&'static str| *name;
+    let patch = diff_by_key(&old, &new, by_name);
+    apply_patch_by_key(&mut replica, &patch, by_name).unwrap();
+    assert!(diff_by_key(&replica, &new, by_name).is_empty());
+    assert_eq!(replica.node_count(), 2);
+    assert_eq!(replica.edge_count(), 1);
+}
+
+#[test]
+fn apply_patch_errors() {
+    let mut graph = DiGraph::<&str, u32>::new();
+    let a = graph.add_node("a");
+    let b = graph.add_node("b");
+    graph.add_edge(a, b, 1);
+    let by_name = |_, name: &&'static str| *name;
+
+    let patch = Patch::from_changes(vec![Change::UpdateEdge {
+        source: "b",
+        target: "a",
+        weight: 2,
+    }]);
+    assert_eq!(
+        apply_patch_by_key(&mut graph, &patch, by_name),
+        Err(PatchError::MissingEdge("b", "a"))
+    );
+
+    let patch = Patch::from_changes(vec![
+        Change::RemoveNode { node: "a" },
+        Change::AddNode {
+            node: "b",
+            weight: "b",
+        },
+    ]);
+    assert_eq!(
+        apply_patch_by_key(&mut graph, &patch, by_name),
+        Err(PatchError::DuplicateNode("b"))
+    );
+    // The changes before the error are applied
+    assert_eq!(graph.node_count(), 1);
+    assert_eq!(graph[NodeIndex::new(0)], "b");
+
+    let patch = Patch::from_changes(vec![Change::RemoveNode { node: "a" }]);
+    assert_eq!(
+        apply_patch_by_key(&mut graph, &patch, by_name),
+        Err(PatchError::MissingNode("a"))
+    );
+}
@@ -282,3 +282,60 @@ fn floyd_warshall_negative_cycle() {
 
     assert!(res.is_err());
 }
+
+#[test]
+fn floyd_warshall_negative_weight_unreachable() {
+    let mut graph: Graph<(), i32, Directed> = Graph::new();
+    let a = graph.add_node(());
+    let b = graph.add_node(());
+    let c = graph.add_node(());
+    graph.extend_with_edges(&[(a, b, -1), (c, c, 0)]);
+
+    let inf = std::i32::MAX;
+    let res = floyd_warshall(&graph, |edge| *edge.weight()).unwrap();
+
+    // Missing paths are not extended through negative edges
+    assert_eq!(res[&(a, b)], -1);
+    assert_eq!(res[&(c, b)], inf);
+    assert_eq!(res[&(b, a)], inf);
+    assert_eq!(res[&(c, c)], 0);
+}
+
+#[test]
+fn dense_floyd_warshall_tiles() {
+    use petgraph::algo::dense::{dense_floyd_warshall, Cpu};
+
+    // Enough nodes for several tiles, with pseudo-random edges and costs
+    let mut graph: Graph<(), i32, Directed> = Graph::new();
+    let nodes: Vec<_> = (0..150).map(|_| graph.add_node(())).collect();
+    let mut state = 7u32;
+    let mut next = || {
+        state = state.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        (state >> 16) as usize
+    };
+    for _ in 0..600 {
+        let (a, b, cost) = (next() % 150, next() % 150, next() % 20);
+        if a != b && graph.find_edge(nodes[a], nodes[b]).is_none() {
+            graph.add_edge(nodes[a], nodes[b], cost as i32);
+        }
+    }
+
+    let expected = floyd_warshall(&graph, |e| *e.weight()).unwrap();
+    let dist = dense_floyd_warshall(&graph, Cpu, |e| *e.weight() as f32).unwrap();
+    for &a in &nodes {
+        for &b in &nodes {
+            let d = expected[&(a, b)];
+            let d = if d == std::i32::MAX {
+                None
+            } else {
+                Some(d as f32)
+            };
+            assert_eq!(dist.get(a.index(), b.index()), d);
+        }
+    }
+
+    // A negative cycle through two tiles
+    graph.add_edge(nodes[10], nodes[140], -1000);
+    graph.add_edge(nodes[140], nodes[10], 0);
+    assert!(dense_floyd_warshall(&graph, Cpu, |e| *e.weight() as f32).is_err());
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "geometry")]
+
+use petgraph::algo::astar;
+use petgraph::geometry::{edge_length, euclidean, euclidean_heuristic, knn_graph, radius_graph};
+use petgraph::prelude::*;
+
+/// Pseudo-random points in the unit cube.
+fn points(n: usize) -> Vec<[f64; 3]> {
+    let mut state = 0x2545_f491_u64;
+    let mut next = move || {
+        state = state
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (state >> 11) as f64 / (1u64 << 53) as f64
+    };
+    (0..n).map(|_| [next(), next(), next()]).collect()
+}
+
+#[test]
+fn knn_graph_matches_brute_force() {
+    let points = points(300);
+    let k = 5;
+    let graph: DiGraph<_, f64> = knn_graph(points.clone(), k);
+    assert_eq!(graph.node_count(), 300);
+    assert_eq!(graph.edge_count(), 300 * k);
+    for (i, p) in points.iter().enumerate() {
+        let mut expected: Vec<_> = (0..points.len()).filter(|&j| j != i).collect();
+        expected.sort_by(|&a, &b| {
+            euclidean(p, &points[a])
+                .partial_cmp(&euclidean(p, &points[b]))
+                .unwrap()
+        });
+        expected.truncate(k);
+        let mut found: Vec<_> = graph
+            .edges(NodeIndex::new(i))
+            .map(|e| {
+                assert_eq!(*e.weight(), euclidean(p, &points[e.target().index()]));
+                e.target().index()
+            })
+            .collect();
+        found.reverse();
+        assert_eq!(found, expected);
+    }
+
+    // The undirected graph is the symmetric closure, without parallel edges
+    let undirected: UnGraph<_, f64> = knn_graph(points, k);
+    for e in graph.edge_references() {
+        assert_eq!(
+            undirected.edges_connecting(e.source(), e.target()).count(),
+            1
+        );
+    }
+    assert!(undirected.edge_count() < graph.edge_count());
+}
+
+#[test]
+fn radius_graph_matches_brute_force() {
+    let points = points(200);
+    let radius = 0.2;
+    let graph: UnGraph<_, f64> = radius_graph(points.clone(), radius);
+    let mut expected = 0;
+    for i in 0..points.len() {
+        for j in i + 1..points.len() {
+            let close = euclidean(&points[i], &points[j]) <= radius;
+            expected += close as usize;
+            assert_eq!(
+                graph
+                    .find_edge(NodeIndex::new(i), NodeIndex::new(j))
+                    .is_some(),
+                close
+            );
+        }
+    }
+    assert_eq!(graph.edge_count(), expected);
+    let directed: DiGraph<_, f64> = radius_graph(points, radius);
+    assert_eq!(directed.edge_count(), 2 * expected);
+}
+
+#[test]
+fn euclidean_astar() {
+    let points = points(500);
+    let graph: UnGraph<_, f64> = knn_graph(points, 6);
+    let (start, goal) = (NodeIndex::new(0), NodeIndex::new(499));
+    let with_heuristic = astar(
+        &graph,
+        start,
+        |n| n == goal,
+        edge_length(&graph),
+        euclidean_heuristic(&graph, goal),
+    );
+    let without = astar(&graph, start, |n| n == goal, |e| *e.weight(), |_| 0.);
+    match (with_heuristic, without) {
+        (Some((a, _)), Some((b, _))) => assert!((a - b).abs() < 1e-9),
+        (a, b) => assert_eq!(a.is_none(), b.is_none()),
+    }
+}
+
+#[test]
+fn geometry_degenerate() {
+    let graph: UnGraph<(f64, f64), f64> = knn_graph(Vec::new(), 3);
+    assert_eq!(graph.node_count(), 0);
+    // Fewer points than neighbors, and duplicate points
+    let graph: DiGraph<_, f64> = knn_graph(vec![(0., 0.), (0., 0.), (1., 0.)], 5);
+    assert_eq!(graph.edge_count(), 6);
+    let graph: UnGraph<_, f64> = radius_graph(vec![(0., 0.), (0., 0.)], 0.);
+    assert_eq!(graph.edge_count(), 1);
+}
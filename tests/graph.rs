@@ -2133,6 +2133,49 @@ fn dfs_visit() {
     assert!(ret.break_value().is_none());
 }
 
+#[test]
+fn traversal_times() {
+    use petgraph::visit::{bfs_times, dfs_times, Time};
+    let gr: Graph<(), ()> = Graph::from_edges(&[(0, 1), (0, 2), (1, 3), (2, 3), (4, 0), (5, 6)]);
+
+    // Starting from 0 and then 4 and 5, every node is reached
+    let dfs = dfs_times(&gr, vec![n(0), n(4), n(5)]);
+    assert_eq!(dfs.order().len(), gr.node_count());
+    for e in gr.edge_references() {
+        let (u, v) = (e.source().index(), e.target().index());
+        // No back edges in a DAG: every edge ends before its source finishes
+        assert!(dfs.finish(v) < dfs.finish(u));
+    }
+    for v in gr.node_indices() {
+        let i = v.index();
+        assert!(dfs.discovery(i) < dfs.finish(i));
+        // A node's interval is nested in its parent's
+        if let Some(p) = dfs.parent(i) {
+            assert!(dfs.discovery(p.index()) < dfs.discovery(i));
+            assert!(dfs.finish(i) < dfs.finish(p.index()));
+        }
+    }
+    assert_eq!(dfs.parent(0), None);
+    assert_eq!(dfs.parent(4), None);
+    assert_eq!(dfs.parent(6), Some(n(5)));
+
+    let bfs = bfs_times(&gr, vec![n(0), n(4), n(5)]);
+    let order: Vec<_> = bfs.order().iter().map(|v| v.index()).collect();
+    assert_eq!(order, vec![0, 2, 1, 3, 4, 5, 6]);
+    assert_eq!(bfs.finish(0), Some(Time(3)));
+    assert_eq!(bfs.discovery(3), Some(Time(4)));
+    assert_eq!(bfs.parent(3), Some(n(2)));
+    assert_eq!(bfs.parent(4), None);
+    assert_eq!(bfs.finish_times().len(), gr.node_count());
+    assert!(bfs.finish_times().iter().all(|t| t.is_some()));
+
+    // Nodes not reached have no times
+    let partial = bfs_times(&gr, Some(n(1)));
+    assert_eq!(partial.order(), &[n(1), n(3)]);
+    assert_eq!(partial.discovery(0), None);
+    assert_eq!(partial.parents()[3], Some(n(1)));
+}
+
 #[test]
 fn filtered_post_order() {
     use petgraph::visit::NodeFiltered;
@@ -2450,3 +2493,192 @@ fn test_dominators_simple_fast() {
         "nodes that aren't reachable from the root do not have an idom"
     );
 }
+
+#[test]
+fn test_post_dominators() {
+    // A control-flow graph with two exits, `ret` and `throw`, and a node
+    // `spin` that loops forever:
+    //
+    //   entry -> cond -> then -> join -> ret
+    //            cond -> else -> join
+    //            else -> throw
+    //   entry -> spin -> spin
+    let mut graph = DiGraph::<_, ()>::new();
+    let entry = graph.add_node("entry");
+    let cond = graph.add_node("cond");
+    let then = graph.add_node("then");
+    let else_ = graph.add_node("else");
+    let join = graph.add_node("join");
+    let ret = graph.add_node("ret");
+    let throw = graph.add_node("throw");
+    let spin = graph.add_node("spin");
+    graph.extend_with_edges(&[
+        (entry, cond),
+        (cond, then),
+        (cond, else_),
+        (then, join),
+        (else_, join),
+        (join, ret),
+        (else_, throw),
+        (entry, spin),
+        (spin, spin),
+    ]);
+
+    let post_doms = dominators::post_dominators(&graph, vec![ret, throw]);
+    assert_eq!(post_doms.root(), None);
+    assert_eq!(post_doms.immediate_dominator(None), None);
+    assert_eq!(post_doms.immediate_dominator(Some(ret)), Some(None));
+    assert_eq!(post_doms.immediate_dominator(Some(then)), Some(Some(join)));
+    assert_eq!(post_doms.immediate_dominator(Some(join)), Some(Some(ret)));
+    assert_eq!(post_doms.immediate_dominator(Some(else_)), Some(None));
+    assert_eq!(post_doms.immediate_dominator(Some(cond)), Some(None));
+    assert_eq!(post_doms.immediate_dominator(Some(entry)), Some(Some(cond)));
+    assert_eq!(
+        post_doms
+            .dominators(Some(then))
+            .unwrap()
+            .collect::<Vec<_>>(),
+        vec![Some(then), Some(join), Some(ret), None]
+    );
+
+    // `spin` never reaches an exit.
+    assert!(post_doms.dominators(Some(spin)).is_none());
+
+    // With a single exit, `throw` is unreachable backwards.
+    let post_doms = dominators::post_dominators(&graph, Some(ret));
+    assert_eq!(post_doms.immediate_dominator(Some(else_)), Some(Some(join)));
+    assert_eq!(post_doms.immediate_dominator(Some(cond)), Some(Some(join)));
+    assert!(post_doms.dominators(Some(throw)).is_none());
+}
+
+#[test]
+fn test_dominance_frontiers() {
+    // A loop whose body branches and joins:
+    //
+    //   0 -> 1 -> 2 -> 4 -> 5
+    //        1 -> 3 -> 4 -> 1
+    let graph =
+        DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (1, 3), (2, 4), (3, 4), (4, 1), (4, 5)]);
+    let n = NodeIndex::new;
+    let doms = dominators::simple_fast(&graph, n(0));
+    let frontiers = dominators::dominance_frontiers(&graph, &doms);
+
+    let frontier = |i| {
+        let mut f: Vec<_> = frontiers[&n(i)].iter().map(|n| n.index()).collect();
+        f.sort();
+        f
+    };
+    assert_eq!(frontiers.len(), 6);
+    assert_eq!(frontier(0), vec![]);
+    assert_eq!(frontier(1), vec![1]);
+    assert_eq!(frontier(2), vec![4]);
+    assert_eq!(frontier(3), vec![4]);
+    assert_eq!(frontier(4), vec![1]);
+    assert_eq!(frontier(5), vec![]);
+
+    // The root is in its own frontier if it is the target of a back edge.
+    let graph = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 0), (2, 0)]);
+    let doms = dominators::simple_fast(&graph, n(0));
+    let frontiers = dominators::dominance_frontiers(&graph, &doms);
+    assert_eq!(frontiers.len(), 2);
+    assert!(frontiers[&n(0)].contains(&n(0)));
+    assert!(frontiers[&n(1)].contains(&n(0)));
+}
+
+#[test]
+fn induced_subgraph() {
+    let mut g = Graph::new_undirected();
+    let a = g.add_node("A");
+    let b = g.add_node("B");
+    let c = g.add_node("C");
+    let d = g.add_node("D");
+    g.add_edge(a, b, 1);
+    g.add_edge(b, c, 2);
+    g.add_edge(c, c, 3);
+    g.add_edge(c, d, 4);
+    g.add_edge(d, a, 5);
+
+    let (sub, node_map) = g.induced_subgraph(vec![d, c, d, n(17)]);
+    assert_eq!(node_map, vec![c, d]);
+    assert_eq!(
+        sub.node_weights().copied().collect::<Vec<_>>(),
+        vec!["C", "D"]
+    );
+    assert_eq!(sub.edge_count(), 2);
+    assert!(sub.contains_edge(n(0), n(0)));
+    assert_eq!(sub[sub.find_edge(n(1), n(0)).unwrap()], 4);
+
+    let (empty, node_map) = g.induced_subgraph(None);
+    assert_eq!(empty.node_count(), 0);
+    assert!(node_map.is_empty());
+}
+
+#[test]
+#[cfg(feature = "rayon")]
+fn test_parallel_iterator() {
+    use rayon::prelude::*;
+    let mut gr = DiGraph::<u32, u32>::new();
+    for i in 0..1000 {
+        gr.add_node(i);
+    }
+
+    let serial_sum: u32 = gr.node_weights().sum();
+    let parallel_sum: u32 = gr.par_nodes().map(|(_, &w)| w).sum();
+    assert_eq!(serial_sum, parallel_sum);
+    gr.par_nodes()
+        .enumerate()
+        .for_each(|(i, (n, &w))| assert_eq!((i, i as u32), (n.index(), w)));
+
+    for i in 0..1000 {
+        gr.add_edge(n(i / 2), n(i), (i + i / 2) as u32);
+    }
+
+    let serial_sum: u32 = gr.edge_weights().sum();
+    let parallel_sum: u32 = gr.par_edges().map(|e| *e.weight()).sum();
+    assert_eq!(serial_sum, parallel_sum);
+    let edges: Vec<_> = gr
+        .par_edges()
+        .map(|e| (e.id(), e.source(), e.target()))
+        .collect();
+    assert_eq!(
+        edges,
+        gr.edge_references()
+            .map(|e| (e.id(), e.source(), e.target()))
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_pruning() {
+    let mut graph = Graph::<_, _, Undirected>::new_undirected();
+    for i in 0..7 {
+        graph.add_node(i);
+    }
+    graph.extend_with_edges(&[
+        (0, 1, "a"),
+        (1, 2, "b"),
+        (2, 0, "c"),
+        (2, 2, "loop"),
+        (3, 4, "d"),
+        (4, 4, "loop"),
+    ]);
+
+    // 5 and 6 are isolated; 4 only has a loop and an edge
+    assert_eq!(graph.remove_isolated_nodes(), vec![5, 6]);
+    assert_eq!(graph.node_count(), 5);
+
+    assert_eq!(graph.remove_self_loops(), vec!["loop", "loop"]);
+    assert_eq!(graph.edge_count(), 4);
+    assert!(graph.raw_edges().iter().all(|e| e.source() != e.target()));
+
+    // The triangle is the 2-core
+    assert_eq!(graph.peel_degree_below(2), vec![3, 4]);
+    let mut remaining: Vec<_> = graph.node_weights().copied().collect();
+    remaining.sort();
+    assert_eq!(remaining, vec![0, 1, 2]);
+    assert_eq!(graph.edge_count(), 3);
+
+    // Peeling cascades through the whole triangle
+    assert_eq!(graph.peel_degree_below(3).len(), 3);
+    assert_eq!(graph.node_count(), 0);
+}
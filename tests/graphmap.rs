@@ -441,3 +441,82 @@ fn test_alternative_hasher() {
     assert!(gr.contains_edge("abc", "def"));
     assert!(!gr.contains_edge("abc", "ghi"));
 }
+
+#[test]
+fn retain_nodes() {
+    let mut gr = DiGraphMap::<_, ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (2, 3), (3, 3), (4, 0)]);
+
+    gr.retain_nodes(|g, n| n % 2 == 0 || g.contains_edge(n, n));
+
+    assert_eq!(
+        gr.nodes().collect::<HashSet<_>>(),
+        [0, 2, 3, 4].iter().copied().collect()
+    );
+    assert_eq!(gr.edge_count(), 4);
+    assert!(gr.contains_edge(2, 0));
+    assert!(gr.contains_edge(2, 3));
+    assert!(gr.contains_edge(3, 3));
+    assert!(gr.contains_edge(4, 0));
+    assert_eq!(gr.neighbors_directed(0, Incoming).count(), 2);
+    assert_eq!(gr.neighbors_directed(2, Incoming).count(), 0);
+    assert_eq!(gr.neighbors_directed(2, Outgoing).count(), 2);
+}
+
+#[test]
+fn retain_edges() {
+    let mut gr = UnGraphMap::<_, i32>::from_edges(&[(0, 1, 1), (1, 2, -2), (2, 0, 3), (2, 2, -4)]);
+
+    gr.retain_edges(|mut g, a, b| {
+        g[(a, b)] *= 10;
+        g[(a, b)] > 0
+    });
+
+    assert_eq!(gr.node_count(), 3);
+    assert_eq!(gr.edge_count(), 2);
+    assert_eq!(gr.edge_weight(1, 0), Some(&10));
+    assert_eq!(gr.edge_weight(0, 2), Some(&30));
+    assert!(!gr.contains_edge(2, 1));
+    assert!(!gr.contains_edge(2, 2));
+    assert_eq!(gr.neighbors(2).collect::<Vec<_>>(), vec![0]);
+    assert_eq!(gr.neighbors(1).collect::<Vec<_>>(), vec![0]);
+}
+
+#[test]
+fn shrink_to_fit() {
+    let mut gr = DiGraphMap::<u32, ()>::with_capacity(100, 100);
+    gr.extend(&[(0, 1), (1, 2), (2, 0)]);
+    assert!(gr.capacity().0 >= 100);
+    assert!(gr.capacity().1 >= 100);
+
+    gr.shrink_to_fit();
+    assert!(gr.capacity().0 < 100);
+    assert!(gr.capacity().1 < 100);
+    assert_eq!(gr.node_count(), 3);
+    assert!(gr.contains_edge(2, 0));
+    assert_eq!(
+        gr.neighbors_directed(0, Incoming).collect::<Vec<_>>(),
+        vec![2]
+    );
+}
+
+#[test]
+fn reserve_and_shrink_to_fit() {
+    let mut gr = DiGraphMap::<u32, ()>::new();
+    gr.reserve_nodes(100);
+    gr.reserve_edges(100);
+    gr.extend(&[(0, 1), (1, 2), (2, 0)]);
+    assert!(gr.capacity().0 >= 100);
+    assert!(gr.capacity().1 >= 100);
+
+    gr.shrink_to_fit_nodes();
+    assert!(gr.capacity().0 < 100);
+    assert!(gr.capacity().1 >= 100);
+    gr.shrink_to_fit_edges();
+    assert!(gr.capacity().1 < 100);
+
+    gr.reserve_exact_nodes(50);
+    gr.reserve_exact_edges(50);
+    assert!(gr.capacity().0 >= 53);
+    assert!(gr.capacity().1 >= 53);
+    assert!(gr.contains_edge(2, 0));
+}
@@ -0,0 +1,97 @@
+use petgraph::algo::heavy_path_decomposition;
+use petgraph::prelude::*;
+
+/// The nodes on the path from `a` to `b` in a tree given by its parents.
+fn naive_path(parent: &[Option<usize>], a: usize, b: usize) -> Vec<usize> {
+    let ancestors = |mut n: usize| {
+        let mut path = vec![n];
+        while let Some(p) = parent[n] {
+            path.push(p);
+            n = p;
+        }
+        path
+    };
+    let (up_a, up_b) = (ancestors(a), ancestors(b));
+    let lca = *up_a.iter().find(|n| up_b.contains(n)).unwrap();
+    let mut path: Vec<_> = up_a.into_iter().take_while(|&n| n != lca).collect();
+    path.push(lca);
+    path.extend(up_b.into_iter().take_while(|&n| n != lca));
+    path.sort_unstable();
+    path
+}
+
+fn covered(ranges: Vec<std::ops::Range<usize>>, nodes: &[NodeIndex]) -> Vec<usize> {
+    let mut covered: Vec<_> = ranges
+        .into_iter()
+        .flatten()
+        .map(|pos| nodes[pos].index())
+        .collect();
+    covered.sort_unstable();
+    covered
+}
+
+#[test]
+fn heavy_path_ranges_match_naive() {
+    // A tree where the parent of node i is a pseudo-random earlier node.
+    let n = 60;
+    let parent: Vec<_> = (0..n)
+        .map(|i| if i == 0 { None } else { Some((i * 11 + 5) % i) })
+        .collect();
+    let edges: Vec<_> = (1..n)
+        .map(|i| (parent[i].unwrap() as u32, i as u32))
+        .collect();
+    let tree = UnGraph::<(), ()>::from_edges(&edges);
+
+    let hpd = heavy_path_decomposition(&tree, NodeIndex::new(0));
+    assert_eq!(hpd.len(), n);
+    let nodes = hpd.nodes();
+    for a in 0..n {
+        let a_idx = NodeIndex::new(a);
+        assert_eq!(hpd.parent(a_idx), parent[a].map(NodeIndex::new));
+        assert_eq!(hpd.node_at(hpd.position(a_idx).unwrap()), Some(a_idx));
+
+        // Subtrees are contiguous
+        let mut subtree: Vec<_> = (0..n)
+            .filter(|&b| naive_path(&parent, 0, b).contains(&a))
+            .collect();
+        subtree.sort_unstable();
+        assert_eq!(
+            covered(vec![hpd.subtree_range(a_idx).unwrap()], nodes),
+            subtree
+        );
+
+        for b in 0..n {
+            let b_idx = NodeIndex::new(b);
+            let path = naive_path(&parent, a, b);
+            let ranges = hpd.path_ranges(a_idx, b_idx).unwrap();
+            assert!(ranges.len() <= 2 * 6 + 1);
+            assert_eq!(covered(ranges, nodes), path);
+
+            // Every node of the path but the lowest common ancestor has its edge
+            // to the parent on the path.
+            let lca = hpd.lca(a_idx, b_idx).unwrap();
+            let edges = hpd.path_edge_ranges(a_idx, b_idx).unwrap();
+            assert!(edges.iter().all(|range| !range.is_empty()));
+            let edge_nodes: Vec<_> = path.into_iter().filter(|&m| m != lca.index()).collect();
+            assert_eq!(covered(edges, nodes), edge_nodes);
+        }
+    }
+}
+
+#[test]
+fn heavy_path_unreachable() {
+    let mut graph = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2)]);
+    let isolated = graph.add_node(());
+    let n = NodeIndex::new;
+
+    let hpd = heavy_path_decomposition(&graph, n(0));
+    assert_eq!(hpd.root(), n(0));
+    assert_eq!(hpd.len(), 3);
+    assert_eq!(hpd.head(n(2)), Some(n(0)));
+    assert_eq!(hpd.depth(n(2)), Some(2));
+    assert_eq!(hpd.subtree_range(n(0)), Some(0..3));
+    assert_eq!(hpd.position(isolated), None);
+    assert_eq!(hpd.lca(isolated, n(1)), None);
+    assert_eq!(hpd.path_ranges(n(1), isolated), None);
+    assert_eq!(hpd.path_edge_ranges(n(0), n(0)), Some(vec![]));
+}
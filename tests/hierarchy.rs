@@ -0,0 +1,127 @@
+use petgraph::hierarchy::Hierarchy;
+use petgraph::prelude::*;
+
+//         0
+//       /   \
+//      1     2
+//     / \    |
+//    3   4   5
+fn example() -> Hierarchy {
+    let mut hierarchy = Hierarchy::new();
+    for &(child, parent) in &[(1, 0), (2, 0), (3, 1), (4, 1), (5, 2)] {
+        hierarchy
+            .set_parent(NodeIndex::new(child), NodeIndex::new(parent))
+            .unwrap();
+    }
+    hierarchy
+}
+
+#[test]
+fn hierarchy_structure() {
+    let mut hierarchy = example();
+    let n = NodeIndex::new;
+    assert_eq!(hierarchy.parent(n(4)), Some(n(1)));
+    assert_eq!(hierarchy.parent(n(0)), None);
+    assert_eq!(hierarchy.parent(n(9)), None);
+    assert_eq!(hierarchy.children(n(1)), &[n(3), n(4)]);
+    assert_eq!(hierarchy.children(n(9)), &[]);
+    assert_eq!(
+        hierarchy.ancestors(n(5)).collect::<Vec<_>>(),
+        vec![n(2), n(0)]
+    );
+    assert_eq!(
+        hierarchy.descendants(n(0)),
+        vec![n(1), n(3), n(4), n(2), n(5)]
+    );
+    assert!(hierarchy.contains(n(1), n(4)));
+    assert!(hierarchy.contains(n(4), n(4)));
+    assert!(!hierarchy.contains(n(2), n(4)));
+
+    assert_eq!(
+        hierarchy.set_parent(n(1), n(4)).unwrap_err().node_id(),
+        n(1)
+    );
+    assert_eq!(
+        hierarchy.set_parent(n(1), n(1)).unwrap_err().node_id(),
+        n(1)
+    );
+    assert_eq!(hierarchy.parent(n(1)), Some(n(0)));
+
+    // Move a subtree
+    hierarchy.set_parent(n(1), n(5)).unwrap();
+    assert_eq!(hierarchy.children(n(0)), &[n(2)]);
+    assert_eq!(hierarchy.ancestors(n(3)).count(), 4);
+    assert_eq!(hierarchy.detach(n(1)), Some(n(5)));
+    assert_eq!(hierarchy.detach(n(1)), None);
+    assert_eq!(hierarchy.children(n(5)), &[]);
+    assert_eq!(hierarchy.descendants(n(1)), vec![n(3), n(4)]);
+}
+
+#[test]
+fn hierarchy_route() {
+    let hierarchy = example();
+    let n = NodeIndex::new;
+    assert_eq!(hierarchy.route(n(3), n(5)), (vec![n(1)], vec![n(2)]));
+    assert_eq!(hierarchy.route(n(3), n(4)), (vec![], vec![]));
+    assert_eq!(hierarchy.route(n(3), n(0)), (vec![n(1)], vec![]));
+    assert_eq!(hierarchy.route(n(0), n(5)), (vec![], vec![n(2)]));
+    assert_eq!(hierarchy.route(n(1), n(3)), (vec![], vec![]));
+    assert_eq!(hierarchy.route(n(6), n(4)), (vec![], vec![n(0), n(1)]));
+}
+
+#[test]
+fn hierarchy_quotient() {
+    let mut hierarchy = example();
+    let n = NodeIndex::new;
+    let graph = DiGraph::<(), ()>::from_edges(&[(3, 4), (4, 5), (5, 3), (1, 2), (6, 3)]);
+    assert_eq!(
+        hierarchy.boundary_edges(&graph, n(1)),
+        vec![
+            EdgeIndex::new(1),
+            EdgeIndex::new(2),
+            EdgeIndex::new(3),
+            EdgeIndex::new(4)
+        ]
+    );
+    assert_eq!(
+        hierarchy.boundary_edges(&graph, n(0)),
+        vec![EdgeIndex::new(4)]
+    );
+
+    let quotient = hierarchy.quotient(&graph);
+    assert_eq!(quotient.node_count(), graph.node_count());
+    assert_eq!(quotient.edge_count(), graph.edge_count());
+
+    hierarchy.collapse(n(1));
+    hierarchy.collapse(n(5));
+    assert!(hierarchy.is_collapsed(n(1)));
+    assert_eq!(hierarchy.representative(n(4)), n(1));
+    assert_eq!(hierarchy.representative(n(2)), n(2));
+    let quotient = hierarchy.quotient(&graph);
+    // 0, 1 (with 3 and 4), 2, 5, 6
+    assert_eq!(quotient.node_count(), 5);
+    let weights: Vec<_> = quotient.node_weights().map(|n| n.index()).collect();
+    assert_eq!(weights, vec![0, 1, 2, 5, 6]);
+    let edges: Vec<_> = quotient
+        .edge_references()
+        .map(|e| {
+            (
+                quotient[e.source()].index(),
+                quotient[e.target()].index(),
+                e.weight().index(),
+            )
+        })
+        .collect();
+    assert_eq!(edges, vec![(1, 5, 1), (5, 1, 2), (1, 2, 3), (6, 1, 4)]);
+
+    // Collapsing the root merges everything but the detached node
+    hierarchy.collapse(n(0));
+    assert_eq!(hierarchy.representative(n(4)), n(0));
+    let quotient = hierarchy.quotient(&graph);
+    assert_eq!(quotient.node_count(), 2);
+    assert_eq!(quotient.edge_count(), 1);
+
+    hierarchy.expand(n(0));
+    hierarchy.expand(n(9));
+    assert_eq!(hierarchy.representative(n(4)), n(1));
+}
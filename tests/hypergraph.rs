@@ -0,0 +1,83 @@
+use petgraph::algo::connected_components;
+use petgraph::hypergraph::{Hypergraph, StarNode};
+use petgraph::prelude::*;
+
+fn example() -> Hypergraph<u32, char> {
+    let mut hypergraph = Hypergraph::new();
+    let n: Vec<_> = (0..5).map(|i| hypergraph.add_node(i)).collect();
+    hypergraph.add_hyperedge(vec![n[2], n[0], n[1], n[0]], 'a');
+    hypergraph.add_hyperedge(vec![n[1], n[2]], 'b');
+    hypergraph.add_hyperedge(vec![n[3]], 'c');
+    hypergraph.add_hyperedge(vec![], 'd');
+    hypergraph
+}
+
+#[test]
+fn hypergraph_incidence() {
+    let hypergraph = example();
+    let n = NodeIndex::new;
+    let e = EdgeIndex::new;
+    assert_eq!(hypergraph.node_count(), 5);
+    assert_eq!(hypergraph.hyperedge_count(), 4);
+    assert_eq!(hypergraph.members(e(0)), &[n(0), n(1), n(2)]);
+    assert_eq!(hypergraph.members(e(3)), &[]);
+    assert_eq!(hypergraph.incident_hyperedges(n(2)), &[e(0), e(1)]);
+    assert_eq!(hypergraph.degree(n(4)), 0);
+    assert_eq!(hypergraph.node_weight(n(3)), Some(&3));
+    assert_eq!(hypergraph.hyperedge_weight(e(2)), Some(&'c'));
+    assert_eq!(hypergraph.hyperedge_weight(e(4)), None);
+    assert_eq!(hypergraph.node_indices().count(), 5);
+    assert_eq!(hypergraph.hyperedge_indices().last(), Some(e(3)));
+}
+
+#[test]
+#[should_panic]
+fn hypergraph_missing_member() {
+    let mut hypergraph = example();
+    hypergraph.add_hyperedge(vec![NodeIndex::new(5)], 'e');
+}
+
+#[test]
+fn hypergraph_missing_member_leaves_hypergraph_unchanged() {
+    use std::panic::{catch_unwind, AssertUnwindSafe};
+
+    let mut hypergraph = example();
+    let n = NodeIndex::new;
+    let result = catch_unwind(AssertUnwindSafe(|| {
+        hypergraph.add_hyperedge(vec![n(0), n(2), n(5)], 'e');
+    }));
+    assert!(result.is_err());
+    assert_eq!(hypergraph.hyperedge_count(), 4);
+    assert_eq!(hypergraph.degree(n(0)), 1);
+    assert_eq!(hypergraph.degree(n(2)), 2);
+}
+
+#[test]
+fn hypergraph_clique_expansion() {
+    let hypergraph = example();
+    let n = NodeIndex::new;
+    let graph = hypergraph.clique_expansion(|_, _, shared| shared.to_vec());
+    assert_eq!(graph.node_count(), 5);
+    assert_eq!(graph[n(4)], 4);
+    assert_eq!(graph.edge_count(), 3);
+    let edge = |a, b| &graph[graph.find_edge(n(a), n(b)).unwrap()];
+    assert_eq!(edge(0, 1), &[EdgeIndex::new(0)]);
+    assert_eq!(edge(2, 1), &[EdgeIndex::new(0), EdgeIndex::new(1)]);
+    assert_eq!(connected_components(&graph), 3);
+}
+
+#[test]
+fn hypergraph_star_expansion() {
+    let hypergraph = example();
+    let n = NodeIndex::new;
+    let graph = hypergraph.star_expansion();
+    assert_eq!(graph.node_count(), 9);
+    assert_eq!(graph.edge_count(), 6);
+    assert_eq!(graph[n(1)], StarNode::Node(1));
+    assert_eq!(graph[n(6)], StarNode::Hyperedge('b'));
+    let mut neighbors: Vec<_> = graph.neighbors(n(5)).collect();
+    neighbors.sort();
+    assert_eq!(neighbors, vec![n(0), n(1), n(2)]);
+    assert_eq!(graph.neighbors(n(8)).count(), 0);
+    assert_eq!(connected_components(&graph), 4);
+}
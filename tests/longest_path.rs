@@ -0,0 +1,132 @@
+use petgraph::algo::{dag_longest_paths, dag_shortest_paths, longest_path};
+use petgraph::prelude::*;
+
+/// The cost of the longest path starting at `n`, by exhaustive search.
+fn naive_longest_from(graph: &DiGraph<(), i32>, n: NodeIndex) -> i32 {
+    graph
+        .edges(n)
+        .map(|e| e.weight() + naive_longest_from(graph, e.target()))
+        .fold(0, i32::max)
+}
+
+/// The costs of all paths from `n`, with their last node, by exhaustive
+/// search.
+fn naive_paths_from(
+    graph: &DiGraph<(), i32>,
+    n: NodeIndex,
+    cost: i32,
+    paths: &mut Vec<(NodeIndex, i32)>,
+) {
+    paths.push((n, cost));
+    for e in graph.edges(n) {
+        naive_paths_from(graph, e.target(), cost + e.weight(), paths);
+    }
+}
+
+/// A pseudo-random DAG, with edges following a shuffled order of the nodes,
+/// and negative weights.
+fn shuffled_dag() -> DiGraph<(), i32> {
+    let n = 12;
+    let mut graph = DiGraph::<(), i32>::new();
+    let nodes: Vec<_> = (0..n).map(|_| graph.add_node(())).collect();
+    for i in 0..n {
+        for j in i + 1..n {
+            if (i * 7 + j * 13) % 5 < 2 {
+                let weight = ((i * 31 + j * 17) % 11) as i32 - 4;
+                graph.add_edge(nodes[i * 5 % n], nodes[j * 5 % n], weight);
+            }
+        }
+    }
+    graph
+}
+
+#[test]
+fn longest_path_matches_naive() {
+    let graph = shuffled_dag();
+    let (cost, path) = longest_path(&graph, |e| *e.weight()).unwrap();
+    let expected = graph
+        .node_indices()
+        .map(|n| naive_longest_from(&graph, n))
+        .max()
+        .unwrap();
+    assert_eq!(cost, expected);
+
+    // The path is valid and has the returned cost
+    let path_cost: i32 = path
+        .windows(2)
+        .map(|w| {
+            graph
+                .edges_connecting(w[0], w[1])
+                .map(|e| *e.weight())
+                .max()
+                .unwrap()
+        })
+        .sum();
+    assert_eq!(path_cost, cost);
+}
+
+#[test]
+fn longest_path_edge_cases() {
+    let empty = DiGraph::<(), i32>::new();
+    assert_eq!(longest_path(&empty, |e| *e.weight()), Ok((0, vec![])));
+
+    // Only negative edges: a single node is the longest path
+    let graph = DiGraph::<(), i32>::from_edges(&[(0, 1, -1), (1, 2, -2)]);
+    let (cost, path) = longest_path(&graph, |e| *e.weight()).unwrap();
+    assert_eq!((cost, path.len()), (0, 1));
+
+    // Zero-cost edges extend the path
+    let graph = DiGraph::<(), i32>::from_edges(&[(0, 1, 0), (1, 2, 0)]);
+    let path: Vec<_> = [0, 1, 2].iter().map(|&i| NodeIndex::new(i)).collect();
+    assert_eq!(longest_path(&graph, |e| *e.weight()), Ok((0, path)));
+
+    // Cycles
+    let cyclic = DiGraph::<(), i32>::from_edges(&[(0, 1, 1), (1, 2, 1), (2, 0, 1)]);
+    assert!(longest_path(&cyclic, |e| *e.weight()).is_err());
+    let self_loop = DiGraph::<(), i32>::from_edges(&[(0, 1, 1), (1, 1, 1)]);
+    assert_eq!(
+        longest_path(&self_loop, |e| *e.weight()).map_err(|c| c.node_id()),
+        Err(NodeIndex::new(1))
+    );
+}
+
+#[test]
+fn dag_paths_match_naive() {
+    let graph = shuffled_dag();
+    for source in graph.node_indices() {
+        let mut paths = Vec::new();
+        naive_paths_from(&graph, source, 0, &mut paths);
+        let shortest = dag_shortest_paths(&graph, source, |e| *e.weight()).unwrap();
+        let longest = dag_longest_paths(&graph, source, |e| *e.weight()).unwrap();
+        for target in graph.node_indices() {
+            let costs = paths.iter().filter(|p| p.0 == target).map(|p| p.1);
+            assert_eq!(shortest.distances[target.index()], costs.clone().min());
+            assert_eq!(longest.distances[target.index()], costs.max());
+
+            // The paths are valid and have the returned cost
+            for &(paths, longest) in &[(&shortest, false), (&longest, true)] {
+                let path = match paths.path_to(&graph, target) {
+                    Some(path) => path,
+                    None => continue,
+                };
+                assert_eq!(path[0], source);
+                let cost: i32 = path
+                    .windows(2)
+                    .map(|w| {
+                        let costs = graph.edges_connecting(w[0], w[1]).map(|e| *e.weight());
+                        if longest {
+                            costs.max().unwrap()
+                        } else {
+                            costs.min().unwrap()
+                        }
+                    })
+                    .sum();
+                assert_eq!(Some(cost), paths.distances[target.index()]);
+            }
+        }
+    }
+
+    let cyclic = DiGraph::<(), i32>::from_edges(&[(0, 1, 1), (1, 0, 1)]);
+    assert!(dag_shortest_paths(&cyclic, NodeIndex::new(0), |e| *e.weight()).is_err());
+    assert!(dag_longest_paths(&cyclic, NodeIndex::new(0), |e| *e.weight()).is_err());
+}
@@ -0,0 +1,78 @@
+use petgraph::algo::lowest_common_ancestors;
+use petgraph::prelude::*;
+
+/// Compute the lowest common ancestor of `a` and `b` by walking up the parents.
+fn naive_lca(parent: &[Option<usize>], mut a: usize, mut b: usize) -> usize {
+    let depth = |mut n: usize| {
+        let mut d = 0;
+        while let Some(p) = parent[n] {
+            n = p;
+            d += 1;
+        }
+        d
+    };
+    while depth(a) > depth(b) {
+        a = parent[a].unwrap();
+    }
+    while depth(b) > depth(a) {
+        b = parent[b].unwrap();
+    }
+    while a != b {
+        a = parent[a].unwrap();
+        b = parent[b].unwrap();
+    }
+    a
+}
+
+#[test]
+fn lca_matches_naive() {
+    // A tree where the parent of node i is a pseudo-random earlier node.
+    let n = 50;
+    let parent: Vec<_> = (0..n)
+        .map(|i| if i == 0 { None } else { Some((i * 7 + 3) % i) })
+        .collect();
+    let edges: Vec<_> = (1..n)
+        .map(|i| (parent[i].unwrap() as u32, i as u32))
+        .collect();
+    let tree = DiGraph::<(), ()>::from_edges(&edges);
+
+    let lca = lowest_common_ancestors(&tree, NodeIndex::new(0));
+    assert_eq!(lca.root(), NodeIndex::new(0));
+    for a in 0..n {
+        assert_eq!(lca.parent(NodeIndex::new(a)), parent[a].map(NodeIndex::new));
+        for b in 0..n {
+            assert_eq!(
+                lca.lca(NodeIndex::new(a), NodeIndex::new(b)),
+                Some(NodeIndex::new(naive_lca(&parent, a, b))),
+                "lca({}, {})",
+                a,
+                b
+            );
+        }
+    }
+}
+
+#[test]
+fn lca_unreachable_and_spanning_tree() {
+    // A DAG: the diamond 0 -> {1, 2} -> 3, and the isolated node 4.
+    let mut dag = DiGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (1, 3), (2, 3)]);
+    dag.add_node(());
+    let n = NodeIndex::new;
+
+    let lca = lowest_common_ancestors(&dag, n(0));
+    assert_eq!(lca.depth(n(3)), Some(2));
+    let parent = lca.parent(n(3)).unwrap();
+    assert!(parent == n(1) || parent == n(2));
+    assert_eq!(lca.lca(n(3), parent), Some(parent));
+    assert_eq!(lca.lca(n(1), n(2)), Some(n(0)));
+
+    assert_eq!(lca.depth(n(4)), None);
+    assert_eq!(lca.lca(n(4), n(1)), None);
+    assert_eq!(lca.distance(n(1), n(4)), None);
+
+    // A single node.
+    let lca = lowest_common_ancestors(&dag, n(4));
+    assert_eq!(lca.lca(n(4), n(4)), Some(n(4)));
+    assert_eq!(lca.distance(n(4), n(4)), Some(0));
+    assert_eq!(lca.lca(n(0), n(4)), None);
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "ndarray")]
+
+use ndarray::array;
+use petgraph::matrix::{to_adjacency_matrix, to_laplacian, Normalization};
+use petgraph::prelude::*;
+
+#[test]
+fn adjacency_matrix_undirected() {
+    // Loops are counted once, parallel edges are summed
+    let graph = UnGraph::<(), f64>::from_edges(&[(0, 1, 1.), (1, 0, 2.), (1, 1, 4.)]);
+    assert_eq!(
+        to_adjacency_matrix(&graph, |e| *e.weight()),
+        array![[0., 3.], [3., 4.]]
+    );
+}
+
+#[test]
+fn adjacency_matrix_stable_graph() {
+    let mut graph = StableDiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0)]);
+    graph.remove_node(NodeIndex::new(1));
+    assert_eq!(
+        to_adjacency_matrix(&graph, |_| 1.),
+        array![[0., 0.], [1., 0.]]
+    );
+}
+
+#[test]
+fn laplacian_normalizations() {
+    // A star with an isolated node
+    let mut graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (0, 2), (0, 3), (0, 4)]);
+    graph.add_node(());
+    let laplacian = to_laplacian(&graph, Normalization::None, |_| 1.);
+    assert_eq!(laplacian.row(0).to_vec(), vec![4., -1., -1., -1., -1., 0.]);
+    assert_eq!(laplacian.sum(), 0.);
+
+    let symmetric = to_laplacian(&graph, Normalization::Symmetric, |_| 1.);
+    assert_eq!(symmetric, symmetric.t());
+    assert_eq!(symmetric[[0, 1]], -0.5);
+    assert_eq!(symmetric.diag().to_vec(), vec![1., 1., 1., 1., 1., 0.]);
+
+    let random_walk = to_laplacian(&graph, Normalization::RandomWalk, |_| 1.);
+    assert_eq!(
+        random_walk.row(0).to_vec(),
+        vec![1., -0.25, -0.25, -0.25, -0.25, 0.]
+    );
+    assert_eq!(random_walk.row(1).to_vec(), vec![-1., 1., 0., 0., 0., 0.]);
+    assert_eq!(random_walk.row(5).sum(), 0.);
+}
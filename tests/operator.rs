@@ -1,4 +1,7 @@
-use petgraph::operator::complement;
+use petgraph::operator::{
+    bipartite_projection, cartesian_product, complement, difference, intersection, quotient,
+    strong_product, tensor_product, union,
+};
 use petgraph::prelude::*;
 use petgraph::Graph;
 
@@ -38,3 +41,257 @@ fn test_complement() {
         }
     }
 }
+
+/// Return the edges of a graph with named nodes as sorted `(source, target, weight)` triples.
+fn named_edges<Ty: petgraph::EdgeType>(
+    g: &Graph<&'static str, u32, Ty>,
+) -> Vec<(&'static str, &'static str, u32)> {
+    let mut edges: Vec<_> = g
+        .edge_references()
+        .map(|e| (g[e.source()], g[e.target()], *e.weight()))
+        .collect();
+    edges.sort();
+    edges
+}
+
+fn multigraph() -> (Graph<&'static str, u32>, Graph<&'static str, u32>) {
+    let mut a = Graph::new();
+    let x = a.add_node("x");
+    let y = a.add_node("y");
+    a.extend_with_edges(&[(x, y, 1), (x, y, 2), (x, y, 3), (y, x, 4)]);
+
+    let mut b = Graph::new();
+    let z = b.add_node("z");
+    let y = b.add_node("y");
+    let x = b.add_node("x");
+    b.extend_with_edges(&[(x, y, 10), (y, z, 20), (x, y, 30)]);
+    (a, b)
+}
+
+#[test]
+fn test_union() {
+    let (a, b) = multigraph();
+    let g = union(&a, &b, |&n| n, |&n, _| n, |x, y| x + y);
+
+    assert_eq!(
+        g.node_weights().copied().collect::<Vec<_>>(),
+        vec!["x", "y", "z"]
+    );
+    assert_eq!(
+        named_edges(&g),
+        vec![
+            ("x", "y", 3),
+            ("x", "y", 11),
+            ("x", "y", 32),
+            ("y", "x", 4),
+            ("y", "z", 20)
+        ]
+    );
+}
+
+#[test]
+fn test_intersection() {
+    let (a, b) = multigraph();
+    let g = intersection(&a, &b, |&n| n, |&n, _| n, |x, y| x + y);
+
+    assert_eq!(
+        g.node_weights().copied().collect::<Vec<_>>(),
+        vec!["x", "y"]
+    );
+    assert_eq!(named_edges(&g), vec![("x", "y", 11), ("x", "y", 32)]);
+}
+
+#[test]
+fn test_difference() {
+    let (a, b) = multigraph();
+    let g = difference(&a, &b, |&n| n);
+
+    assert_eq!(
+        g.node_weights().copied().collect::<Vec<_>>(),
+        vec!["x", "y"]
+    );
+    assert_eq!(named_edges(&g), vec![("x", "y", 3), ("y", "x", 4)]);
+    assert_eq!(difference(&a, &a, |&n| n).edge_count(), 0);
+}
+
+#[test]
+fn test_set_operators_undirected() {
+    let mut a = Graph::new_undirected();
+    let x = a.add_node("x");
+    let y = a.add_node("y");
+    let x_again = a.add_node("x");
+    a.extend_with_edges(&[(x, y, 1), (y, x_again, 2)]);
+
+    let mut b = Graph::new_undirected();
+    let y = b.add_node("y");
+    let x = b.add_node("x");
+    b.add_edge(y, x, 10);
+
+    // Nodes sharing a key are identified with the first of them.
+    let g = union(&a, &b, |&n| n, |&n, _| n, |x, y| x + y);
+    assert_eq!(g.node_count(), 2);
+    assert_eq!(named_edges(&g), vec![("x", "y", 11), ("y", "x", 2)]);
+
+    let g = intersection(&a, &b, |&n| n, |&n, _| n, |x, y| x + y);
+    assert_eq!(named_edges(&g), vec![("x", "y", 11)]);
+
+    let g = difference(&a, &b, |&n| n);
+    assert_eq!(g.node_count(), 3);
+    assert_eq!(named_edges(&g), vec![("y", "x", 2)]);
+}
+
+#[test]
+fn test_cartesian_product() {
+    let a = DiGraph::<char, u32>::from_edges(&[(0, 1, 1)]);
+    let mut b = DiGraph::<char, u32>::new();
+    let x = b.add_node('x');
+    let y = b.add_node('y');
+    let z = b.add_node('z');
+    b.extend_with_edges(&[(x, y, 10), (y, z, 20)]);
+
+    let g = cartesian_product(&a, &b);
+    assert_eq!(g.node_count(), 6);
+    assert_eq!(g[NodeIndex::new(4)], ('\0', 'y'));
+    let mut edges: Vec<_> = g
+        .edge_references()
+        .map(|e| (e.source().index(), e.target().index(), *e.weight()))
+        .collect();
+    edges.sort();
+    assert_eq!(
+        edges,
+        vec![
+            (0, 1, (None, Some(10))),
+            (0, 3, (Some(1), None)),
+            (1, 2, (None, Some(20))),
+            (1, 4, (Some(1), None)),
+            (2, 5, (Some(1), None)),
+            (3, 4, (None, Some(10))),
+            (4, 5, (None, Some(20))),
+        ]
+    );
+}
+
+#[test]
+fn test_tensor_product_undirected() {
+    // The tensor product of an edge with itself is a perfect matching of
+    // the four pairs.
+    let edge = UnGraph::<(), u32>::from_edges(&[(0, 1, 7)]);
+    let g = tensor_product(&edge, &edge);
+    assert_eq!(g.node_count(), 4);
+    assert_eq!(g.edge_count(), 2);
+    assert!(g.contains_edge(NodeIndex::new(0), NodeIndex::new(3)));
+    assert!(g.contains_edge(NodeIndex::new(1), NodeIndex::new(2)));
+
+    // Self-loops do not produce duplicate edges.
+    let looped = UnGraph::<(), u32>::from_edges(&[(0, 0, 1)]);
+    let g = tensor_product(&looped, &edge);
+    assert_eq!(g.edge_count(), 1);
+    assert_eq!(g.edge_weights().collect::<Vec<_>>(), vec![&(1, 7)]);
+}
+
+#[test]
+fn test_strong_product() {
+    let a = DiGraph::<(), u32>::from_edges(&[(0, 1, 1)]);
+    let b = DiGraph::<(), u32>::from_edges(&[(0, 1, 2)]);
+    let g = strong_product(&a, &b);
+    assert_eq!(g.node_count(), 4);
+    let mut from_start: Vec<_> = g
+        .edges(NodeIndex::new(0))
+        .map(|e| (e.target().index(), *e.weight()))
+        .collect();
+    from_start.sort();
+    assert_eq!(
+        from_start,
+        vec![
+            (1, (None, Some(2))),
+            (2, (Some(1), None)),
+            (3, (Some(1), Some(2))),
+        ]
+    );
+    assert_eq!(g.edge_count(), 5);
+}
+
+#[test]
+fn test_quotient_directed() {
+    let g = DiGraph::<u32, u32>::from_edges(&[
+        (0, 1, 1),
+        (1, 2, 2),
+        (2, 3, 4),
+        (3, 0, 8),
+        (0, 2, 16),
+        (3, 1, 32),
+    ]);
+
+    // Blocks {0, 2} and {1, 3}, keyed by parity.
+    let (q, node_map) = quotient(&g, |n, _| n.index() % 2, |x, &y| x.max(y));
+    let (even, odd) = (node_map[0], node_map[1]);
+    assert_eq!(node_map, vec![even, odd, even, odd]);
+    assert_eq!(q[even], vec![NodeIndex::new(0), NodeIndex::new(2)]);
+    assert_eq!(q[odd], vec![NodeIndex::new(1), NodeIndex::new(3)]);
+
+    assert_eq!(q.edge_count(), 4);
+    assert_eq!(q[q.find_edge(even, odd).unwrap()], 4);
+    assert_eq!(q[q.find_edge(odd, even).unwrap()], 8);
+    assert_eq!(q[q.find_edge(even, even).unwrap()], 16);
+    assert_eq!(q[q.find_edge(odd, odd).unwrap()], 32);
+
+    // Singleton blocks give back the same structure.
+    let (q, node_map) = quotient(&g, |n, _| n, |x, _| x);
+    assert_eq!(q.node_count(), g.node_count());
+    assert_eq!(q.edge_count(), g.edge_count());
+    assert!(node_map.iter().enumerate().all(|(i, n)| n.index() == i));
+}
+
+#[test]
+fn test_bipartite_projection() {
+    // Users 0..3 and items 3..6, with a parallel edge, an edge between users,
+    // and directed edges both ways
+    let mut g = DiGraph::<u32, ()>::from_edges(&[
+        (0, 3),
+        (0, 3),
+        (0, 4),
+        (1, 3),
+        (4, 1),
+        (1, 5),
+        (2, 5),
+        (0, 1),
+    ]);
+    for (i, weight) in g.node_weights_mut().enumerate() {
+        *weight = i as u32;
+    }
+    let users = [NodeIndex::new(2), NodeIndex::new(1), NodeIndex::new(0)];
+    let projection = bipartite_projection(&g, &users, |e| {
+        (
+            e.source.index(),
+            e.target.index(),
+            e.common.to_vec(),
+            e.jaccard(),
+        )
+    });
+    assert_eq!(
+        projection.node_weights().copied().collect::<Vec<_>>(),
+        vec![2, 1, 0]
+    );
+    let edges: Vec<_> = projection
+        .edge_references()
+        .map(|e| (e.source().index(), e.target().index(), e.weight().clone()))
+        .collect();
+    let n = NodeIndex::new;
+    assert_eq!(
+        edges,
+        vec![
+            (0, 1, (2, 1, vec![n(5)], 1. / 3.)),
+            (1, 2, (1, 0, vec![n(3), n(4)], 2. / 3.)),
+        ]
+    );
+
+    // Nodes without shared neighbors are not connected
+    let items = [NodeIndex::new(3), NodeIndex::new(5)];
+    let projection = bipartite_projection(&g, &items, |e| e.common.len());
+    assert_eq!(projection.node_count(), 2);
+    assert_eq!(projection.edge_count(), 1);
+    assert_eq!(
+        projection.edge_weights().copied().collect::<Vec<_>>(),
+        vec![1]
+    );
+}
@@ -0,0 +1,90 @@
+use petgraph::algo::{degree_ordering, reindex, reverse_cuthill_mckee};
+use petgraph::prelude::*;
+
+/// The largest index difference between the endpoints of an edge.
+fn bandwidth<N, E>(graph: &UnGraph<N, E>) -> usize {
+    graph
+        .edge_references()
+        .map(|e| {
+            let (a, b) = (e.source().index(), e.target().index());
+            a.max(b) - a.min(b)
+        })
+        .max()
+        .unwrap_or(0)
+}
+
+/// A `width` x `height` grid with scattered node indices.
+fn scrambled_grid(width: usize, height: usize) -> UnGraph<usize, ()> {
+    let n = width * height;
+    // 7 is coprime with the number of nodes
+    let index = |x: usize, y: usize| ((y * width + x) * 7 % n) as u32;
+    let mut edges = Vec::new();
+    for y in 0..height {
+        for x in 0..width {
+            if x + 1 < width {
+                edges.push((index(x, y), index(x + 1, y)));
+            }
+            if y + 1 < height {
+                edges.push((index(x, y), index(x, y + 1)));
+            }
+        }
+    }
+    let mut graph = UnGraph::from_edges(&edges);
+    for (i, weight) in graph.node_weights_mut().enumerate() {
+        *weight = i;
+    }
+    graph
+}
+
+#[test]
+fn rcm_reduces_bandwidth() {
+    let graph = scrambled_grid(10, 4);
+    let order = reverse_cuthill_mckee(&graph);
+    let mut sorted = order.clone();
+    sorted.sort();
+    assert_eq!(sorted, graph.node_indices().collect::<Vec<_>>());
+
+    let reordered = reindex(&graph, &order);
+    assert!(bandwidth(&graph) > 30);
+    assert!(bandwidth(&reordered) <= 5);
+
+    // The copy has the same edges, renumbered
+    for (i, &node) in order.iter().enumerate() {
+        assert_eq!(reordered[NodeIndex::new(i)], graph[node]);
+    }
+    for (edge, copy) in graph.raw_edges().iter().zip(reordered.raw_edges()) {
+        assert_eq!(order[copy.source().index()], edge.source());
+        assert_eq!(order[copy.target().index()], edge.target());
+    }
+}
+
+#[test]
+fn rcm_disconnected_directed() {
+    let mut graph = DiGraph::<(), ()>::from_edges(&[(0, 2), (3, 2), (4, 5)]);
+    graph.add_node(());
+    let order = reverse_cuthill_mckee(&graph);
+    assert_eq!(order.len(), graph.node_count());
+    // Each component is contiguous, and edges are followed in both directions
+    let position = |n: u32| order.iter().position(|&m| m == NodeIndex::new(n as usize));
+    let mut first = [position(0), position(2), position(3)];
+    first.sort();
+    assert_eq!(first[2].unwrap() - first[0].unwrap(), 2);
+    assert_eq!(
+        (position(4).unwrap() as isize - position(5).unwrap() as isize).abs(),
+        1
+    );
+}
+
+#[test]
+fn degree_ordering_is_stable() {
+    let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (1, 3), (3, 2)]);
+    let n = NodeIndex::new;
+    assert_eq!(degree_ordering(&graph), vec![n(0), n(2), n(3), n(1)]);
+}
+
+#[test]
+#[should_panic]
+fn reindex_not_a_permutation() {
+    let graph = UnGraph::<(), ()>::from_edges(&[(0, 1)]);
+    reindex(&graph, &[NodeIndex::new(0), NodeIndex::new(0)]);
+}
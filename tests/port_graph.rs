@@ -0,0 +1,78 @@
+use petgraph::port_graph::{Port, PortGraph, PortGraphError};
+use petgraph::prelude::*;
+
+#[test]
+fn port_graph_connect() {
+    let mut graph = PortGraph::<u32, char, &str>::new();
+    let a = graph.add_node(0, vec![], vec!["int", "float"]);
+    let b = graph.add_node(1, vec!["float", "int"], vec![]);
+    assert_eq!(graph.port_count(a, Outgoing), 2);
+    assert_eq!(graph.port_count(a, Incoming), 0);
+    assert_eq!(
+        graph.ports(b, Incoming).collect::<Vec<_>>(),
+        vec![Port::input(b, 0), Port::input(b, 1)]
+    );
+    assert_eq!(graph.port_type(Port::input(b, 1)), Some(&"int"));
+    assert_eq!(graph.port_type(Port::input(b, 2)), None);
+
+    let (out0, out1) = (Port::output(a, 0), Port::output(a, 1));
+    let (in0, in1) = (Port::input(b, 0), Port::input(b, 1));
+    assert_eq!(
+        graph.connect(out0, in0, 'x'),
+        Err(PortGraphError::TypeMismatch(out0, in0))
+    );
+    assert_eq!(
+        graph.connect(in1, out0, 'x'),
+        Err(PortGraphError::WrongDirection(in1))
+    );
+    assert_eq!(
+        graph.connect(Port::output(a, 2), in0, 'x'),
+        Err(PortGraphError::MissingPort(Port::output(a, 2)))
+    );
+    assert_eq!(graph.edge_count(), 0);
+
+    let e0 = graph.connect(out0, in1, 'x').unwrap();
+    let e1 = graph.connect(out1, in0, 'y').unwrap();
+    assert_eq!(
+        graph.connect(out1, in0, 'z'),
+        Err(PortGraphError::PortInUse(out1, e1))
+    );
+    assert_eq!(graph.link(in1), Some(e0));
+    assert_eq!(graph.linked_port(out1), Some(in0));
+    assert_eq!(graph.edge_endpoints(e1), Some((out1, in0)));
+
+    // Removing an edge frees its ports and moves the last edge
+    assert_eq!(graph.remove_edge(e0), Some('x'));
+    assert_eq!(graph.remove_edge(EdgeIndex::new(1)), None);
+    assert_eq!(graph.edge_weight(e0), Some(&'y'));
+    assert_eq!(graph.link(in0), Some(e0));
+    assert_eq!(graph.link(out0), None);
+    assert_eq!(graph.linked_port(in1), None);
+    assert!(graph.connect(out0, in1, 'z').is_ok());
+}
+
+#[test]
+fn port_graph_conversion() {
+    let digraph =
+        DiGraph::<char, u32>::from_edges(&[(0, 1, 10), (0, 1, 20), (1, 2, 30), (2, 0, 40)]);
+    let graph = PortGraph::from_graph(digraph.clone(), |w| w % 20);
+    let n = NodeIndex::new;
+    assert_eq!(graph.node_count(), 3);
+    assert_eq!(graph.port_count(n(0), Outgoing), 2);
+    assert_eq!(graph.port_count(n(0), Incoming), 1);
+    assert_eq!(
+        graph.edge_endpoints(EdgeIndex::new(1)),
+        Some((Port::output(n(0), 1), Port::input(n(1), 1)))
+    );
+    assert_eq!(graph.port_type(Port::input(n(1), 1)), Some(&0));
+    assert_eq!(graph.port_type(Port::output(n(2), 0)), Some(&0));
+    assert_eq!(graph.edge_weight(EdgeIndex::new(2)), Some(&30));
+
+    let roundtrip = graph.into_graph();
+    assert_eq!(roundtrip.node_count(), digraph.node_count());
+    for (e, f) in digraph.raw_edges().iter().zip(roundtrip.raw_edges()) {
+        assert_eq!((e.source(), e.target()), (f.source(), f.target()));
+        assert_eq!(e.weight, f.weight.1);
+    }
+    assert_eq!(roundtrip.raw_edges()[1].weight.0, (1, 1));
+}
@@ -35,9 +35,11 @@ use petgraph::graph::{edge_index, node_index, IndexType};
 use petgraph::graphmap::NodeTrait;
 use petgraph::operator::complement;
 use petgraph::prelude::*;
+use petgraph::quickcheck::{WeightedGraph, WeightedGraphConfig};
 use petgraph::visit::{
     EdgeFiltered, EdgeIndexable, IntoEdgeReferences, IntoEdges, IntoNeighbors, IntoNodeIdentifiers,
-    IntoNodeReferences, NodeCount, NodeIndexable, Reversed, Topo, VisitMap, Visitable,
+    IntoNodeReferences, NodeCount, NodeFiltered, NodeIndexable, Reversed, Topo, VisitMap,
+    Visitable,
 };
 use petgraph::EdgeType;
 
@@ -628,6 +630,36 @@ impl<N: Default + Clone + Send + 'static> Arbitrary for DAG<N> {
     }
 }
 
+/// A graph with negative edges but no negative cycle, with the distances from
+/// node 0
+#[derive(Clone, Debug)]
+struct Reweighted<E>(WeightedGraph<E>);
+
+impl<E: From<i32> + Clone + Send + 'static> Arbitrary for Reweighted<E> {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        let config = WeightedGraphConfig::new().planted(true).potentials(50);
+        Reweighted(config.generate(g))
+    }
+}
+
+#[test]
+fn weighted_graph_config_acyclic() {
+    let mut g = quickcheck::StdThreadGen::new(40);
+    let config = WeightedGraphConfig::new()
+        .weights(-50, 50)
+        .acyclic(true)
+        .connected(true);
+    for _ in 0..100 {
+        let w: WeightedGraph<f64> = config.generate(&mut g);
+        if w.graph.node_count() == 0 {
+            continue;
+        }
+        assert!(toposort(&w.graph, None).is_ok());
+        let paths = bellman_ford(&w.graph, node_index(0)).unwrap();
+        assert!(paths.distances.iter().all(|d| d.is_finite()));
+    }
+}
+
 fn is_topo_order<N>(gr: &Graph<N, (), Directed>, order: &[NodeIndex]) -> bool {
     if gr.node_count() != order.len() {
         println!(
@@ -787,6 +819,135 @@ quickcheck! {
     }
 }
 
+quickcheck! {
+    fn dary_queue_pops_in_order(items: Vec<(u8, u16)>, arity: u8) -> bool {
+        use petgraph::algo::priority_queue::{DaryQueue, PriorityQueue};
+        let mut queue = DaryQueue::new(2 + arity as usize % 6);
+        for &(priority, item) in &items {
+            queue.push(priority, item);
+        }
+        let mut popped = Vec::new();
+        while let Some(entry) = queue.pop() {
+            popped.push(entry);
+        }
+        let mut sorted = items;
+        sorted.sort();
+        popped.windows(2).all(|w| w[0].0 <= w[1].0) && {
+            popped.sort();
+            popped == sorted
+        }
+    }
+
+    fn radix_queue_pops_in_order(items: Vec<(u8, u16)>, later: Vec<(u8, u16)>) -> bool {
+        use petgraph::algo::priority_queue::{PriorityQueue, RadixQueue};
+        let mut queue = RadixQueue::new();
+        for &(priority, item) in &items {
+            queue.push(priority, item);
+        }
+        let mut popped = Vec::new();
+        let mut all = items.clone();
+        // Pop half of the items, then push more above the last one popped
+        for _ in 0..items.len() / 2 {
+            popped.extend(queue.pop());
+        }
+        let last = popped.last().map_or(0, |&(priority, _)| priority);
+        for &(priority, item) in &later {
+            let priority = last.saturating_add(priority % 16);
+            queue.push(priority, item);
+            all.push((priority, item));
+        }
+        while let Some(entry) = queue.pop() {
+            popped.push(entry);
+        }
+        all.sort();
+        popped.windows(2).all(|w| w[0].0 <= w[1].0) && {
+            popped.sort();
+            popped == all
+        }
+    }
+
+    fn dijkstra_with_radix_queue(g: Graph<u32, u32>, node: usize) -> bool {
+        use petgraph::algo::dijkstra::dijkstra_with_queue;
+        use petgraph::algo::priority_queue::RadixQueue;
+        if g.node_count() == 0 {
+            return true;
+        }
+        let v = node_index(node % g.node_count());
+        // Costs small enough for the distances not to overflow
+        let cost = |e: petgraph::graph::EdgeReference<u32>| u64::from(*e.weight());
+        dijkstra_with_queue(&g, v, None, cost, RadixQueue::new()) == dijkstra(&g, v, None, cost)
+    }
+
+    fn dijkstra_with_dary_queue(g: Graph<u32, u32>, node: usize, arity: u8) -> bool {
+        use petgraph::algo::dijkstra::dijkstra_with_queue;
+        use petgraph::algo::priority_queue::DaryQueue;
+        if g.node_count() == 0 {
+            return true;
+        }
+        let v = node_index(node % g.node_count());
+        let queue = DaryQueue::new(2 + arity as usize % 6);
+        dijkstra_with_queue(&g, v, None, |e| *e.weight(), queue)
+            == dijkstra(&g, v, None, |e| *e.weight())
+    }
+
+    // checks that the resumable search settles the nodes in order of distance,
+    // with the distances of dijkstra
+    fn dijkstra_settles_in_order(g: Graph<u32, u32>, node: usize) -> bool {
+        use petgraph::algo::dijkstra::Dijkstra;
+        if g.node_count() == 0 {
+            return true;
+        }
+        let v = node_index(node % g.node_count());
+        let distances = dijkstra(&g, v, None, |e| *e.weight());
+        let settled: Vec<_> = Dijkstra::new(&g, v, |e| *e.weight()).collect();
+        settled.len() == distances.len()
+            && settled.windows(2).all(|w| w[0].1 <= w[1].1)
+            && settled.iter().all(|(n, d)| distances[n] == *d)
+    }
+
+    fn dijkstra_bounded_matches_hop_limited_distances(
+        g: Graph<u32, u32>,
+        node: usize,
+        max_hops: usize,
+        max_cost: u32
+    ) -> bool {
+        use petgraph::algo::dijkstra::Dijkstra;
+        if g.node_count() == 0 {
+            return true;
+        }
+        let v = node_index(node % g.node_count());
+        let (max_hops, max_cost) = (max_hops % 6, max_cost % 200);
+        // The distances over the paths of at most max_hops edges
+        let mut distances = vec![None; g.node_count()];
+        distances[v.index()] = Some(0);
+        for _ in 0..max_hops {
+            let mut next = distances.clone();
+            for e in g.edge_references() {
+                if let Some(d) = distances[e.source().index()] {
+                    let d = d + *e.weight();
+                    let old = &mut next[e.target().index()];
+                    if old.map_or(true, |old| d < old) {
+                        *old = Some(d);
+                    }
+                }
+            }
+            distances = next;
+        }
+        let mut settled: Vec<_> = Dijkstra::new(&g, v, |e| *e.weight())
+            .with_max_hops(max_hops)
+            .with_max_cost(max_cost)
+            .map(|(n, d)| (n.index(), d))
+            .collect();
+        settled.sort();
+        let expected: Vec<_> = distances
+            .iter()
+            .enumerate()
+            .filter_map(|(i, d)| d.filter(|&d| d <= max_cost).map(|d| (i, d)))
+            .collect();
+        settled == expected
+    }
+}
+
 quickcheck! {
     // checks that the distances computed by k'th shortest path is always greater or equal compared to their dijkstra computation
     fn k_shortest_path_(g: Graph<u32, u32>, node: usize) -> bool {
@@ -1189,6 +1350,185 @@ quickcheck! {
     }
 }
 
+quickcheck! {
+    fn test_transitive_reduction(g: DAG<()>) -> bool {
+        let g = g.0;
+        let (kept, removed) = petgraph::algo::tred::transitive_reduction(&g).unwrap();
+        if kept.len() + removed.len() != g.edge_count() {
+            return false;
+        }
+        let kept: HashSet<_> = kept.into_iter().collect();
+        let reduced = EdgeFiltered::from_fn(&g, |edge| kept.contains(&edge.id()));
+        let mut closure = naive_closure(&g);
+        closure.sort();
+        let mut reduced_closure = naive_closure(&reduced);
+        reduced_closure.sort();
+        if closure != reduced_closure {
+            return false;
+        }
+        // no kept edge can be removed without shrinking the closure
+        kept.iter().all(|&e| {
+            let filtered = EdgeFiltered::from_fn(&g, |edge| edge.id() != e && kept.contains(&edge.id()));
+            naive_closure_edgecount(&filtered) < closure.len()
+        })
+    }
+}
+
+quickcheck! {
+    fn test_transitive_closure(g: Graph<(), ()>) -> bool {
+        let closure = petgraph::algo::tred::transitive_closure(&g);
+        let mut dfs = Dfs::empty(&g);
+        g.node_indices().all(|a| {
+            // the nodes reachable through at least one edge
+            dfs.reset(&g);
+            dfs.stack.extend(g.neighbors(a));
+            let mut expected = Vec::new();
+            while let Some(b) = dfs.next(&g) {
+                expected.push(b);
+            }
+            expected.sort();
+            closure.reachable_count(a) == expected.len()
+                && closure.reachable_from(a).eq(expected.iter().copied())
+                && g.node_indices().all(|b| closure.is_reachable(a, b) == expected.contains(&b))
+        })
+    }
+}
+
+/// The size of a maximum matching of the bipartite graph with a copy of each
+/// node on either side and an edge `(a, b)` for every pair in `pairs`.
+fn split_matching_size(node_count: usize, pairs: &[(NodeIndex, NodeIndex)]) -> usize {
+    let split = UnGraph::<(), ()>::from_edges(
+        pairs
+            .iter()
+            .map(|&(a, b)| (a.index() as u32, (node_count + b.index()) as u32)),
+    );
+    maximum_matching(&split).len()
+}
+
+/// Check that `chains` partition the nodes of `g`, with consecutive nodes
+/// related by `related`.
+fn is_chain_cover<F>(g: &Graph<(), ()>, chains: &[Vec<NodeIndex>], mut related: F) -> bool
+where
+    F: FnMut(NodeIndex, NodeIndex) -> bool,
+{
+    let mut covered: Vec<_> = chains.iter().flatten().copied().collect();
+    covered.sort();
+    covered == g.node_indices().collect::<Vec<_>>()
+        && chains
+            .iter()
+            .all(|chain| chain.windows(2).all(|w| related(w[0], w[1])))
+}
+
+quickcheck! {
+    fn test_chain_decomposition(g: DAG<()>) -> bool {
+        let g = g.0;
+        let chains = petgraph::algo::chain_decomposition(&g).unwrap();
+        let closure = naive_closure(&g);
+        is_chain_cover(&g, &chains, |a, b| closure.contains(&(a, b)))
+            && chains.len() == g.node_count() - split_matching_size(g.node_count(), &closure)
+    }
+
+    fn test_maximum_antichain(g: DAG<()>) -> bool {
+        let g = g.0;
+        let antichain = petgraph::algo::maximum_antichain(&g).unwrap();
+        let chains = petgraph::algo::chain_decomposition(&g).unwrap();
+        let closure = naive_closure(&g);
+        antichain.len() == chains.len()
+            && antichain.iter().all(|&a| antichain.iter().all(|&b| !closure.contains(&(a, b))))
+    }
+
+    fn test_minimum_path_cover(g: DAG<()>) -> bool {
+        let g = g.0;
+        let paths = petgraph::algo::minimum_path_cover(&g).unwrap();
+        let edges: Vec<_> = g.edge_references().map(|e| (e.source(), e.target())).collect();
+        is_chain_cover(&g, &paths, |a, b| g.contains_edge(a, b))
+            && paths.len() == g.node_count() - split_matching_size(g.node_count(), &edges)
+    }
+}
+
+quickcheck! {
+    fn test_find_cycle_directed(g: Graph<(), ()>) -> bool {
+        match petgraph::algo::find_cycle_directed(&g) {
+            None => !is_cyclic_directed(&g),
+            Some(cycle) => {
+                let mut nodes = cycle.clone();
+                nodes.sort();
+                nodes.dedup();
+                nodes.len() == cycle.len()
+                    && (0..cycle.len()).all(|i| g.contains_edge(cycle[i], cycle[(i + 1) % cycle.len()]))
+            }
+        }
+    }
+
+    fn test_find_cycle_undirected(g: UnGraph<(), ()>) -> bool {
+        match petgraph::algo::find_cycle_undirected(&g) {
+            None => !is_cyclic_undirected(&g),
+            Some(cycle) => {
+                let mut nodes = cycle.clone();
+                nodes.sort();
+                nodes.dedup();
+                let edges_between = |a, b| g.edges_connecting(a, b).count();
+                nodes.len() == cycle.len()
+                    && match cycle.len() {
+                        1 => edges_between(cycle[0], cycle[0]) >= 1,
+                        2 => edges_between(cycle[0], cycle[1]) >= 2,
+                        n => (0..n).all(|i| edges_between(cycle[i], cycle[(i + 1) % n]) >= 1),
+                    }
+            }
+        }
+    }
+}
+
+/// The simple cycles of at most `max_length` nodes, each starting at its node
+/// of smallest index, by exhaustive search.
+fn naive_simple_cycles(g: &Graph<(), ()>, max_length: usize) -> Vec<Vec<NodeIndex>> {
+    fn extend(
+        g: &Graph<(), ()>,
+        path: &mut Vec<NodeIndex>,
+        max_length: usize,
+        cycles: &mut Vec<Vec<NodeIndex>>,
+    ) {
+        let mut successors: Vec<_> = g.neighbors(*path.last().unwrap()).collect();
+        successors.sort();
+        successors.dedup();
+        for next in successors {
+            if next == path[0] {
+                if path.len() <= max_length {
+                    cycles.push(path.clone());
+                }
+            } else if next > path[0] && !path.contains(&next) && path.len() < max_length {
+                path.push(next);
+                extend(g, path, max_length, cycles);
+                path.pop();
+            }
+        }
+    }
+    let mut cycles = Vec::new();
+    for start in g.node_indices() {
+        extend(g, &mut vec![start], max_length, &mut cycles);
+    }
+    cycles.sort();
+    cycles
+}
+
+quickcheck! {
+    fn test_simple_cycles(g: Graph<(), ()>) -> bool {
+        // keep the number of cycles small
+        let g = g.filter_map(|n, _| if n.index() < 7 { Some(()) } else { None }, |_, _| Some(()));
+        let mut cycles: Vec<_> = petgraph::algo::simple_cycles(&g, None).collect();
+        cycles.sort();
+        cycles == naive_simple_cycles(&g, g.node_count())
+    }
+
+    fn test_simple_cycles_max_length(g: Graph<(), ()>, max_length: u8) -> bool {
+        let g = g.filter_map(|n, _| if n.index() < 10 { Some(()) } else { None }, |_, _| Some(()));
+        let max_length = max_length as usize % 5;
+        let mut cycles: Vec<_> = petgraph::algo::simple_cycles(&g, Some(max_length)).collect();
+        cycles.sort();
+        cycles == naive_simple_cycles(&g, max_length)
+    }
+}
+
 quickcheck! {
     fn greedy_fas_remaining_graph_is_acyclic(g: StableDiGraph<(), ()>) -> bool {
         let mut g = g;
@@ -1201,6 +1541,507 @@ quickcheck! {
         !is_cyclic_directed(&g)
     }
 
+    fn greedy_fvs_remaining_graph_is_acyclic(g: StableDiGraph<(), ()>) -> bool {
+        let fvs = petgraph::algo::greedy_feedback_vertex_set(&g);
+        let without = |removed: &[NodeIndex]| {
+            let removed: HashSet<_> = removed.iter().copied().collect();
+            is_cyclic_directed(&NodeFiltered::from_fn(&g, |n| !removed.contains(&n)))
+        };
+        // every node of the set is needed
+        !without(&fvs)
+            && (0..fvs.len()).all(|i| {
+                let mut others = fvs.clone();
+                others.remove(i);
+                without(&others)
+            })
+    }
+
+    fn kernighan_lin_bisection_is_balanced_and_improves(g: StableUnGraph<(), ()>) -> bool {
+        let cut = |a: &[NodeIndex]| {
+            g.edge_references()
+                .filter(|e| a.contains(&e.source()) != a.contains(&e.target()))
+                .filter(|e| e.source() != e.target())
+                .count()
+        };
+        let nodes: Vec<_> = g.node_indices().collect();
+        let initial = &nodes[..nodes.len() / 2];
+        let (a, b) = petgraph::algo::kernighan_lin_bisection(&g, |_| 1., 10);
+        let mut all: Vec<_> = a.iter().chain(&b).copied().collect();
+        all.sort();
+        all == nodes && a.len() == nodes.len() / 2 && cut(&a) <= cut(initial)
+    }
+
+    fn multilevel_partition_is_balanced(g: Small<UnGraph<(), ()>>, k: u8) -> bool {
+        let k = k as usize % 5 + 1;
+        let parts = petgraph::algo::multilevel_partition(&*g, k, 0.2, |_| 1.);
+        let mut all: Vec<_> = parts.iter().flatten().copied().collect();
+        all.sort();
+        let max = (1.2 * g.node_count() as f64 / k as f64).ceil() as usize;
+        parts.len() == k
+            && all == g.node_indices().collect::<Vec<_>>()
+            && parts.iter().all(|p| p.len() <= max)
+    }
+
+    fn treewidth_tree_decomposition_is_valid(g: Small<UnGraph<(), ()>>) -> bool {
+        let valid = |(width, decomposition): (usize, UnGraph<Vec<NodeIndex>, ()>)| {
+            let bags = |v: NodeIndex| -> Vec<NodeIndex> {
+                decomposition
+                    .node_indices()
+                    .filter(|&b| decomposition[b].contains(&v))
+                    .collect()
+            };
+            let max_bag = decomposition.node_weights().map(|b| b.len()).max();
+            // The bags of each node are a subtree, and every edge is in a bag
+            decomposition.node_count() == g.node_count()
+                && decomposition.edge_count() == g.node_count().saturating_sub(1)
+                && petgraph::algo::connected_components(&decomposition) <= 1
+                && width == max_bag.map_or(0, |s| s - 1)
+                && g.node_indices().all(|v| {
+                    let bags = bags(v);
+                    let subtree = NodeFiltered::from_fn(&decomposition, |b| bags.contains(&b));
+                    !bags.is_empty() && {
+                        let mut dfs = Dfs::new(&subtree, bags[0]);
+                        let mut count = 0;
+                        while dfs.next(&subtree).is_some() {
+                            count += 1;
+                        }
+                        count == bags.len()
+                    }
+                })
+                && g.edge_references().all(|e| {
+                    decomposition
+                        .node_weights()
+                        .any(|b| b.contains(&e.source()) && b.contains(&e.target()))
+                })
+        };
+        valid(petgraph::algo::treewidth_min_degree(&*g))
+            && valid(petgraph::algo::treewidth_min_fill_in(&*g))
+    }
+
+    fn percolation_matches_naive(g: Small<StableDiGraph<(), ()>>, seed: u64) -> bool {
+        use petgraph::algo::{edge_percolation, node_percolation, RemovalStrategy};
+        use petgraph::unionfind::UnionFind;
+
+        // The sizes of the largest component and the number of components,
+        // checked at a few steps
+        let naive = |nodes: &[NodeIndex], edges: &[EdgeIndex]| {
+            let mut sets = UnionFind::new(g.node_bound());
+            let mut present = vec![false; g.node_bound()];
+            nodes.iter().for_each(|n| present[n.index()] = true);
+            for &e in edges {
+                let (a, b) = g.edge_endpoints(e).unwrap();
+                if present[a.index()] && present[b.index()] {
+                    sets.union(a.index(), b.index());
+                }
+            }
+            let mut sizes = std::collections::HashMap::new();
+            for n in nodes {
+                *sizes.entry(sets.find(n.index())).or_insert(0) += 1;
+            }
+            (sizes.values().copied().max().unwrap_or(0), sizes.len())
+        };
+        let nodes: Vec<_> = g.node_indices().collect();
+        let edges: Vec<_> = g.edge_indices().collect();
+        let node_scores: Vec<f64> = (0..g.node_bound()).map(|i| (i % 3) as f64).collect();
+        let edge_scores: Vec<f64> = (0..g.edge_bound()).map(|i| (i % 3) as f64).collect();
+
+        [RemovalStrategy::Random(seed), RemovalStrategy::Ranked(&node_scores)]
+            .iter()
+            .all(|&strategy| {
+                let p = node_percolation(&*g, strategy);
+                let mut sorted = p.removed().to_vec();
+                sorted.sort();
+                sorted == nodes
+                    && (0..=nodes.len()).step_by(nodes.len() / 10 + 1).all(|i| {
+                        let rest: Vec<_> = p.removed()[i..].to_vec();
+                        naive(&rest, &edges) == (p.largest_component()[i], p.component_count()[i])
+                    })
+            })
+            && [RemovalStrategy::Random(seed), RemovalStrategy::Ranked(&edge_scores)]
+                .iter()
+                .all(|&strategy| {
+                    let p = edge_percolation(&*g, strategy);
+                    let mut sorted = p.removed().to_vec();
+                    sorted.sort();
+                    sorted == edges
+                        && (0..=edges.len()).step_by(edges.len() / 10 + 1).all(|i| {
+                            let rest: Vec<_> = p.removed()[i..].to_vec();
+                            naive(&nodes, &rest)
+                                == (p.largest_component()[i], p.component_count()[i])
+                        })
+                })
+    }
+
+    fn temporal_paths_match_naive(g: Small<Graph<(), u8>>, start_time: u8) -> bool {
+        use petgraph::algo::temporal::{earliest_arrival, fastest, shortest};
+        use std::collections::HashMap;
+
+        if g.node_count() == 0 {
+            return true;
+        }
+        // Keep the number of journeys small
+        let g = g.filter_map(
+            |_, _| Some(()),
+            |e, &w| if e.index() < 12 { Some(w) } else { None },
+        );
+        let start_time = u32::from(start_time % 8);
+        let edge_time = |w: u8| {
+            let departure = u32::from(w % 8);
+            (departure, departure + u32::from(w / 64) + 1)
+        };
+        let source = node_index(0);
+
+        // Enumerate all journeys, as (node, departure from source, arrival,
+        // travel time)
+        let mut arrival = HashMap::new();
+        let mut duration = HashMap::new();
+        let mut travel = HashMap::new();
+        let mut stack = vec![(source, None, start_time, 0)];
+        while let Some((node, start, time, spent)) = stack.pop() {
+            let update = |map: &mut HashMap<_, _>, value| {
+                let best = map.entry(node).or_insert(value);
+                *best = std::cmp::min(*best, value);
+            };
+            update(&mut arrival, time);
+            update(&mut duration, start.map_or(0, |s| time - s));
+            update(&mut travel, spent);
+            for edge in g.edges(node) {
+                let (departure, arrive) = edge_time(*edge.weight());
+                if departure >= time {
+                    let start = start.or(Some(departure));
+                    stack.push((edge.target(), start, arrive, spent + arrive - departure));
+                }
+            }
+        }
+
+        earliest_arrival(&g, source, start_time, |e| edge_time(*e.weight())) == arrival
+            && fastest(&g, source, start_time, |e| edge_time(*e.weight())) == duration
+            && shortest(&g, source, start_time, |e| edge_time(*e.weight())) == travel
+    }
+
+    fn cow_graph_matches_graph(g: Small<Graph<u8, u8>>, ops: Vec<(u8, u8, u8)>) -> bool {
+        use petgraph::cow_graph::CowGraph;
+
+        fn same<Ty: EdgeType>(g: &Graph<u8, u8, Ty>, cow: &CowGraph<u8, u8, Ty>) -> bool {
+            let edges = |n| g.edges(n).map(|e| (e.id(), e.source(), e.target(), *e.weight()));
+            let cow_edges =
+                |n| cow.edges(n).map(|e| (e.id(), e.source(), e.target(), *e.weight()));
+            g.node_count() == cow.node_count()
+                && g.edge_count() == cow.edge_count()
+                && g.node_indices().all(|n| {
+                    g[n] == cow[n]
+                        && edges(n).eq(cow_edges(n))
+                        && g.neighbors_directed(n, Incoming)
+                            .eq(cow.neighbors_directed(n, Incoming))
+                })
+                && g.edge_references()
+                    .map(|e| (e.source(), e.target(), *e.weight()))
+                    .eq(cow.edge_references().map(|e| (e.source(), e.target(), *e.weight())))
+        }
+
+        fn prop<Ty: EdgeType>(mut g: Graph<u8, u8, Ty>, ops: &[(u8, u8, u8)]) -> bool {
+            let mut cow = CowGraph::from(g.clone());
+            let mut snapshots = Vec::new();
+            for &(op, a, b) in ops {
+                let n = g.node_count();
+                let m = g.edge_count();
+                match op % 5 {
+                    0 => {
+                        g.add_node(a);
+                        cow.add_node(a);
+                    }
+                    1 if n > 0 => {
+                        let (a, b) = (node_index(a as usize % n), node_index(b as usize % n));
+                        g.add_edge(a, b, op);
+                        cow.add_edge(a, b, op);
+                    }
+                    2 if n > 0 => {
+                        let a = node_index(a as usize % n);
+                        g.remove_node(a);
+                        cow.remove_node(a);
+                    }
+                    3 if m > 0 => {
+                        let e = edge_index(a as usize % m);
+                        g.remove_edge(e);
+                        cow.remove_edge(e);
+                    }
+                    _ => snapshots.push((g.clone(), cow.clone())),
+                }
+            }
+            same(&g, &cow)
+                && snapshots.iter().all(|(g, cow)| same(g, cow))
+                && cow.into_graph().edge_references().eq(g.edge_references())
+        }
+
+        let ug = g.map(|_, &w| w, |_, &w| w).into_edge_type();
+        prop(g.0, &ops) && prop::<Undirected>(ug, &ops)
+    }
+
+    fn dense_floyd_warshall_matches_floyd_warshall(g: Graph<(), i8>) -> bool {
+        use petgraph::algo::dense::{dense_floyd_warshall, Cpu};
+
+        // `floyd_warshall` overrides the cost of self-loops
+        let mut g = g;
+        g.retain_edges(|g, e| {
+            let (a, b) = g.edge_endpoints(e).unwrap();
+            a != b
+        });
+        let expected = floyd_warshall(&g, |e| i32::from(*e.weight()));
+        let dist = dense_floyd_warshall(&g, Cpu, |e| f32::from(*e.weight()));
+        match (expected, dist) {
+            (Ok(expected), Ok(dist)) => g.node_indices().all(|a| {
+                g.node_indices().all(|b| {
+                    let d = expected[&(a, b)];
+                    dist.get(a.index(), b.index()) == Some(d as f32).filter(|_| d != std::i32::MAX)
+                })
+            }),
+            (Err(_), Err(_)) => true,
+            _ => false,
+        }
+    }
+
+    fn level_bfs_matches_dijkstra(g: Graph<(), ()>, source: usize) -> bool {
+        use petgraph::algo::dense::{level_bfs, Cpu};
+
+        if g.node_count() == 0 {
+            return true;
+        }
+        let source = node_index(source % g.node_count());
+        let costs = dijkstra(&g, source, None, |_| 1);
+        level_bfs(&g, Cpu, source)
+            .into_iter()
+            .enumerate()
+            .all(|(i, level)| level == costs.get(&node_index(i)).cloned())
+    }
+
+    fn diff_patch_roundtrip(old: Small<Graph<u8, u8>>, ops: Vec<(u8, u8, u8)>) -> bool {
+        use petgraph::diff::{apply_patch, diff};
+
+        fn prop<Ty: EdgeType>(old: Graph<u8, u8, Ty>, ops: &[(u8, u8, u8)]) -> bool {
+            let mut new = old.clone();
+            for &(op, a, b) in ops {
+                let (n, m) = (new.node_count(), new.edge_count());
+                match op % 5 {
+                    0 => {
+                        new.add_node(a);
+                    }
+                    1 if n > 0 => {
+                        new.add_edge(node_index(a as usize % n), node_index(b as usize % n), op);
+                    }
+                    2 if n > 0 => {
+                        new.remove_node(node_index(a as usize % n));
+                    }
+                    3 if m > 0 => {
+                        new.remove_edge(edge_index(a as usize % m));
+                    }
+                    4 if n > 0 => new[node_index(a as usize % n)] = b,
+                    _ => {}
+                }
+            }
+            let patch = diff(&old, &new);
+            let mut copy = old.clone();
+            apply_patch(&mut copy, &patch).is_ok()
+                && diff(&copy, &new).is_empty()
+                && diff(&new, &copy).is_empty()
+                && copy.node_weights().eq(new.node_weights())
+        }
+
+        let ug = old.map(|_, &w| w, |_, &w| w).into_edge_type();
+        prop(old.0, &ops) && prop::<Undirected>(ug, &ops)
+    }
+
+    // checks the levels and neighborhoods against the dijkstra distances with
+    // unit costs
+    fn levels_and_neighborhood_match_dijkstra(g: Graph<(), ()>, node: usize, radius: u8) -> bool {
+        use petgraph::algo::neighborhood::{levels_from, neighborhood};
+        use petgraph::visit::IntoNodeIdentifiers;
+        if g.node_count() == 0 {
+            return true;
+        }
+        let v = node_index(node % g.node_count());
+        let radius = radius as usize % 4;
+        let distances = dijkstra(&g, v, None, |_| 1);
+        let levels = levels_from(&g, v);
+        let ego: Vec<_> = neighborhood(&g, v, radius).node_identifiers().collect();
+        levels.iter().map(|l| l.len()).sum::<usize>() == distances.len()
+            && levels
+                .iter()
+                .enumerate()
+                .all(|(d, l)| l.iter().all(|n| distances[n] == d))
+            && ego.len() == distances.values().filter(|&&d| d <= radius).count()
+            && ego.iter().all(|n| distances.get(n).map_or(false, |&d| d <= radius))
+    }
+
+    // checks the shortest path algorithms against planted distances
+    fn dijkstra_planted(w: WeightedGraph<i64, Undirected>) -> bool {
+        if w.graph.node_count() == 0 {
+            return true;
+        }
+        let res = dijkstra(&w.graph, node_index(0), None, |e| *e.weight());
+        let distances = w.distances.unwrap();
+        res.len() == distances.len() && res.iter().all(|(n, d)| distances[n.index()] == *d)
+    }
+
+    fn floyd_warshall_planted(w: Reweighted<i32>) -> bool {
+        let Reweighted(w) = w;
+        let res = floyd_warshall(&w.graph, |e| *e.weight()).unwrap();
+        let distances = w.distances.unwrap();
+        w.graph
+            .node_indices()
+            .all(|n| res[&(node_index(0), n)] == distances[n.index()])
+    }
+
+    fn bellman_ford_planted(w: Reweighted<f64>) -> bool {
+        let Reweighted(w) = w;
+        if w.graph.node_count() == 0 {
+            return true;
+        }
+        find_negative_cycle(&w.graph, node_index(0)).is_none()
+            && bellman_ford(&w.graph, node_index(0)).unwrap().distances == w.distances.unwrap()
+    }
+
+    // checks that the exact distances to the goal are a consistent heuristic
+    fn astar_checked_exact_heuristic(g: Graph<(), u32>, a: usize, b: usize) -> bool {
+        use petgraph::algo::astar::{astar, astar_checked};
+        if g.node_count() == 0 {
+            return true;
+        }
+        let (start, goal) = (node_index(a % g.node_count()), node_index(b % g.node_count()));
+        let to_goal = dijkstra(Reversed(&g), goal, None, |e| *e.weight());
+        // The nodes that cannot reach the goal are never on a path to it
+        let exact = |n| to_goal.get(&n).copied().unwrap_or(1 << 20);
+        let checked = astar_checked(&g, start, |n| n == goal, |e| *e.weight(), exact);
+        let cost = checked.unwrap().map(|(cost, _)| cost);
+        cost == astar(&g, start, |n| n == goal, |e| *e.weight(), |_| 0).map(|(cost, _)| cost)
+    }
+
+    // checks that the negative cycles reported by bellman_ford_with_cycle are
+    // cycles of the graph with a negative cost
+    fn bellman_ford_with_cycle_reports_cycle(gr: Graph<(), f32>, source: usize) -> bool {
+        use petgraph::algo::bellman_ford_with_cycle;
+        if gr.node_count() == 0 {
+            return true;
+        }
+        let source = node_index(source % gr.node_count());
+        match bellman_ford_with_cycle(&gr, source) {
+            Ok(paths) => {
+                let expected = bellman_ford(&gr, source).unwrap();
+                paths.distances == expected.distances && find_negative_cycle(&gr, source).is_none()
+            }
+            Err(error) => {
+                let cycle = error.cycle();
+                let cost: f32 = (0..cycle.len())
+                    .map(|i| {
+                        gr.edges_connecting(cycle[i], cycle[(i + 1) % cycle.len()])
+                            .map(|e| *e.weight())
+                            .fold(std::f32::INFINITY, f32::min)
+                    })
+                    .sum();
+                cost < 0. && find_negative_cycle(&gr, source).as_deref() == Some(cycle)
+            }
+        }
+    }
+
+    fn spfa_matches_bellman_ford(gr: Graph<(), f32>, source: usize) -> bool {
+        use petgraph::algo::spfa;
+        if gr.node_count() == 0 {
+            return true;
+        }
+        let source = node_index(source % gr.node_count());
+        match (spfa(&gr, source), bellman_ford(&gr, source)) {
+            (Ok(paths), Ok(expected)) => paths
+                .distances
+                .iter()
+                .zip(&expected.distances)
+                .all(|(&d, &e)| d == e || (d - e).abs() <= 1e-3 * e.abs().max(1.)),
+            (Err(_), Err(_)) => true,
+            _ => false,
+        }
+    }
+
+    // checks the k shortest simple paths against all the simple paths
+    fn shortest_simple_paths_match_all_simple_paths(g: Graph<(), u32>, a: usize, b: usize) -> bool {
+        use petgraph::algo::{all_simple_paths, shortest_simple_paths};
+        // Keep the number of simple paths small
+        let g = g.filter_map(|n, _| if n.index() < 6 { Some(()) } else { None }, |_, w| Some(*w));
+        if g.node_count() == 0 {
+            return true;
+        }
+        let (start, goal) = (node_index(a % g.node_count()), node_index(b % g.node_count()));
+        if start == goal {
+            return true;
+        }
+        let cost = |path: &[NodeIndex]| -> u32 {
+            path.windows(2)
+                .map(|s| *g.edge_weight(g.find_edge(s[0], s[1]).unwrap()).unwrap())
+                .sum()
+        };
+        let mut expected: Vec<u32> = all_simple_paths::<Vec<_>, _>(&g, start, goal, 0, None)
+            .map(|path| cost(&path))
+            .collect();
+        expected.sort();
+        let mut paths = HashSet::new();
+        let found: Vec<u32> = shortest_simple_paths(&g, start, goal, |e| *e.weight())
+            .map(|(c, path)| {
+                assert_eq!(c, cost(&path));
+                assert!(paths.insert(path));
+                c
+            })
+            .collect();
+        found == expected
+    }
+
+    fn shortest_walks_match_walk_counts(g: Graph<(), u32>, a: usize, b: usize) -> bool {
+        use petgraph::algo::shortest_walks;
+        let g = g.filter_map(
+            |n, _| if n.index() < 6 { Some(()) } else { None },
+            |_, w| Some(w % 10 + 1),
+        );
+        if g.node_count() == 0 {
+            return true;
+        }
+        let (start, goal) = (node_index(a % g.node_count()), node_index(b % g.node_count()));
+        let k = 30;
+        let mut walks = HashSet::new();
+        let found: Vec<u32> = shortest_walks(&g, start, goal, |e| *e.weight())
+            .take(k)
+            .map(|(c, walk)| {
+                assert_eq!(walk[0], start);
+                assert_eq!(*walk.last().unwrap(), goal);
+                let cost: u32 = walk
+                    .windows(2)
+                    .map(|s| *g.edge_weight(g.find_edge(s[0], s[1]).unwrap()).unwrap())
+                    .sum();
+                assert_eq!(c, cost);
+                assert!(walks.insert(walk));
+                c
+            })
+            .collect();
+
+        // Count the walks of each cost to the goal, by increasing cost
+        let max_cost = 10 * g.node_count() * (k + 1);
+        let mut count = vec![vec![0u64; g.node_count()]; max_cost + 1];
+        count[0][start.index()] = 1;
+        let mut expected = Vec::new();
+        for c in 0..=max_cost {
+            for e in g.edge_references() {
+                let w = *e.weight() as usize;
+                if w <= c {
+                    let n = count[c - w][e.source().index()];
+                    let m = &mut count[c][e.target().index()];
+                    *m = m.saturating_add(n);
+                }
+            }
+            let n = count[c][goal.index()];
+            expected.extend((0..std::cmp::min(n, k as u64)).map(|_| c as u32));
+            if expected.len() >= k {
+                break;
+            }
+        }
+        expected.truncate(k);
+        found == expected
+    }
+
     /// Assert that the size of the feedback arc set of a tournament does not exceed
     /// **|E| / 2 - |V| / 6**
     fn greedy_fas_performance_within_bound(t: Tournament<(), ()>) -> bool {
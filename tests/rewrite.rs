@@ -0,0 +1,240 @@
+use petgraph::acyclic::Acyclic;
+use petgraph::prelude::*;
+use petgraph::rewrite::{Pattern, Rewrite, RewriteError, RewriteNode, TransactionError};
+
+#[test]
+fn pattern_matches() {
+    // 0 -> 1 -> 2 -> 3, with weights
+    let graph = DiGraph::<u32, u32>::from_edges(&[(0, 1, 10), (1, 2, 20), (2, 3, 30)]);
+    let mut pattern = Pattern::new();
+    let a = pattern.add_node(|_: &u32| true);
+    let b = pattern.add_node(|_: &u32| true);
+    let ab = pattern.add_edge(a, b, |w: &u32| *w >= 20);
+
+    let matches = pattern.matches(&graph);
+    let mut found: Vec<_> = matches
+        .iter()
+        .map(|m| (m.node(a).index(), m.node(b).index(), graph[m.edge(ab)]))
+        .collect();
+    found.sort();
+    assert_eq!(found, vec![(1, 2, 20), (2, 3, 30)]);
+
+    let mut pattern = Pattern::<u32, u32>::new();
+    pattern.add_node(|_| true);
+    pattern.add_node(|_| true);
+    // Matches are node-induced
+    let mut nonadjacent: Vec<_> = pattern
+        .matches(&graph)
+        .iter()
+        .map(|m| (m.nodes()[0].index(), m.nodes()[1].index()))
+        .collect();
+    nonadjacent.sort();
+    assert_eq!(
+        nonadjacent,
+        vec![(0, 2), (0, 3), (1, 3), (2, 0), (3, 0), (3, 1)]
+    );
+}
+
+#[test]
+fn rewrite_tracks_indices() {
+    let mut graph = DiGraph::<char, u32>::new();
+    let n: Vec<_> = "abcde".chars().map(|c| graph.add_node(c)).collect();
+    graph.extend_with_edges(&[
+        (n[0], n[1], 1),
+        (n[1], n[2], 2),
+        (n[3], n[1], 3),
+        (n[4], n[3], 4),
+    ]);
+
+    let mut rewrite = Rewrite::new();
+    rewrite
+        .remove_node(n[1])
+        .remove_node(n[0])
+        .remove_node(n[1]);
+    let x = rewrite.add_node('x');
+    rewrite.add_edge(x, n[4], 5).reattach(n[1], x);
+    let rewritten = rewrite.apply(&mut graph).unwrap();
+
+    assert_eq!(rewritten.node(n[0]), None);
+    assert_eq!(rewritten.node(n[1]), None);
+    assert_eq!(rewritten.node(RewriteNode::New(1)), None);
+    let node = |i: usize| rewritten.node(n[i]).unwrap();
+    let x = rewritten.node(x).unwrap();
+    for &i in &[2, 3, 4] {
+        assert_eq!(graph[node(i)], "abcde".chars().nth(i).unwrap());
+    }
+    assert_eq!(graph[x], 'x');
+
+    let mut edges: Vec<_> = graph
+        .edge_references()
+        .map(|e| (graph[e.source()], graph[e.target()], *e.weight()))
+        .collect();
+    edges.sort();
+    // The edge from `a` is dropped with it, the others move to `x`
+    assert_eq!(
+        edges,
+        vec![('d', 'x', 3), ('e', 'd', 4), ('x', 'c', 2), ('x', 'e', 5)]
+    );
+}
+
+#[test]
+fn rewrite_undirected_boundary() {
+    let mut graph = UnGraph::<u32, u32>::from_edges(&[(0, 1, 1), (1, 2, 2), (1, 1, 3), (2, 3, 4)]);
+    let mut rewrite = Rewrite::new();
+    rewrite
+        .remove_node(NodeIndex::new(1))
+        .remove_edge(EdgeIndex::new(0))
+        .reattach(NodeIndex::new(1), NodeIndex::new(3));
+    let rewritten = rewrite.apply(&mut graph).unwrap();
+
+    let n = |i| rewritten.node(NodeIndex::new(i)).unwrap();
+    assert_eq!(graph.node_count(), 3);
+    // The removed edge and the self-loop are not reattached
+    assert_eq!(graph.edge_count(), 2);
+    let e = graph.find_edge(n(3), n(2)).unwrap();
+    assert!(graph[e] == 2 || graph[e] == 4);
+    assert_eq!(graph.edges_connecting(n(2), n(3)).count(), 2);
+}
+
+#[test]
+fn rewrite_errors_leave_graph_unchanged() {
+    let graph = DiGraph::<u32, ()>::from_edges(&[(0, 1), (1, 2)]);
+    let n = NodeIndex::new;
+    let check = |rewrite: Rewrite<u32, ()>, error| {
+        let mut copy = graph.clone();
+        assert_eq!(rewrite.apply(&mut copy), Err(error));
+        assert_eq!(copy.node_count(), graph.node_count());
+        assert_eq!(copy.edge_count(), graph.edge_count());
+    };
+
+    let mut rewrite = Rewrite::new();
+    rewrite.remove_node(n(0)).remove_node(n(3));
+    check(
+        rewrite,
+        RewriteError::MissingNode(RewriteNode::Existing(n(3))),
+    );
+
+    let mut rewrite = Rewrite::new();
+    rewrite.remove_node(n(0)).remove_edge(EdgeIndex::new(2));
+    check(rewrite, RewriteError::MissingEdge(EdgeIndex::new(2)));
+
+    let mut rewrite = Rewrite::new();
+    rewrite.add_node(5);
+    rewrite.add_edge(RewriteNode::New(1), n(0), ());
+    check(rewrite, RewriteError::MissingNode(RewriteNode::New(1)));
+
+    let mut rewrite = Rewrite::new();
+    rewrite.remove_node(n(1)).add_edge(n(0), n(1), ());
+    check(rewrite, RewriteError::RemovedEndpoint(n(1)));
+
+    let mut rewrite = Rewrite::new();
+    rewrite
+        .remove_node(n(1))
+        .remove_node(n(2))
+        .reattach(n(1), n(2));
+    check(rewrite, RewriteError::RemovedEndpoint(n(2)));
+
+    let mut rewrite = Rewrite::new();
+    rewrite.reattach(n(1), n(2));
+    check(rewrite, RewriteError::KeptNode(n(1)));
+}
+
+#[test]
+fn graph_transaction() {
+    let mut graph = UnGraph::<u32, u32>::from_edges(&[(0, 1, 1), (1, 2, 2)]);
+    let n = NodeIndex::new;
+
+    let result = graph.transaction(|_, tx| {
+        tx.remove_node(n(0));
+        tx.add_node(7);
+        Err::<(), _>("abort")
+    });
+    assert_eq!(result.unwrap_err(), TransactionError::Aborted("abort"));
+    let result = graph.transaction(|_, tx| {
+        tx.remove_node(n(0)).remove_node(n(5));
+        Ok::<_, ()>(())
+    });
+    assert_eq!(
+        result.unwrap_err(),
+        TransactionError::Invalid(RewriteError::MissingNode(RewriteNode::Existing(n(5))))
+    );
+    assert_eq!(graph.node_count(), 3);
+    assert_eq!(graph.edge_count(), 2);
+
+    // Split every edge with a new node, reading the graph before the changes
+    let (count, rewritten) = graph
+        .transaction(|graph, tx| {
+            for edge in graph.edge_references() {
+                let middle = tx.add_node(*edge.weight());
+                tx.remove_edge(edge.id())
+                    .add_edge(edge.source(), middle, 0)
+                    .add_edge(middle, edge.target(), 0);
+            }
+            Ok::<_, ()>(graph.edge_count())
+        })
+        .unwrap();
+    assert_eq!(count, 2);
+    assert_eq!(graph.node_count(), 5);
+    assert_eq!(graph.edge_count(), 4);
+    let middle = rewritten.node(RewriteNode::New(1)).unwrap();
+    assert_eq!(graph[middle], 2);
+    assert_eq!(graph.neighbors(middle).count(), 2);
+}
+
+#[test]
+fn acyclic_transaction() {
+    let mut graph =
+        Acyclic::try_from_graph(DiGraph::<u32, ()>::from_edges(&[(0, 1), (1, 2), (2, 3)])).unwrap();
+    let n = NodeIndex::new;
+
+    // Linking the last node back would close a cycle
+    let result = graph.transaction(|_, tx| {
+        tx.remove_node(n(0)).add_edge(n(3), n(1), ());
+        Ok::<_, ()>(())
+    });
+    assert!(matches!(result, Err(TransactionError::Cycle(_))));
+    assert_eq!(graph.node_count(), 4);
+    assert_eq!(graph.edge_count(), 3);
+
+    // Remove node 1, then link its neighbours and add a new source
+    let (_, rewritten) = graph
+        .transaction(|_, tx| {
+            let source = tx.add_node(9);
+            tx.remove_node(n(1))
+                .add_edge(n(0), n(2), ())
+                .add_edge(source, n(3), ());
+            Ok::<_, ()>(())
+        })
+        .unwrap();
+    assert_eq!(graph.node_count(), 4);
+    assert_eq!(graph.edge_count(), 3);
+    let node = |i| rewritten.node(n(i)).unwrap();
+    let source = rewritten.node(RewriteNode::New(0)).unwrap();
+    assert!(graph.get_position(node(0)) < graph.get_position(node(2)));
+    assert!(graph.get_position(node(2)) < graph.get_position(node(3)));
+    assert!(graph.get_position(source) < graph.get_position(node(3)));
+
+    // The order stays valid for later edges
+    assert!(graph.try_add_edge(node(3), node(0), ()).is_err());
+    assert!(graph.try_add_edge(node(0), source, ()).is_ok());
+}
+
+#[test]
+fn acyclic_transaction_keeps_reachability_index() {
+    let mut graph =
+        Acyclic::try_from_graph(DiGraph::<u32, ()>::from_edges(&[(0, 1), (1, 2), (2, 3)])).unwrap();
+    let n = NodeIndex::new;
+    graph.enable_reachability_index();
+
+    graph
+        .transaction(|graph, tx| {
+            tx.remove_edge(graph.find_edge(n(1), n(2)).unwrap())
+                .add_edge(n(3), n(0), ());
+            Ok::<_, ()>(())
+        })
+        .unwrap();
+    assert!(graph.has_reachability_index());
+    assert!(graph.is_reachable(n(3), n(1)));
+    assert!(graph.is_reachable(n(2), n(0)));
+    assert!(!graph.is_reachable(n(0), n(2)));
+}
@@ -0,0 +1,78 @@
+use std::sync::Arc;
+use std::thread;
+
+use petgraph::algo::{connected_components, is_cyclic_directed};
+use petgraph::cow_graph::CowGraph;
+use petgraph::graph::node_index;
+use petgraph::shared::SharedGraph;
+
+#[test]
+fn shared_graph_snapshots_are_isolated() {
+    let shared = SharedGraph::new(CowGraph::<u32, ()>::new());
+    shared.update(|graph| {
+        graph.add_node(0);
+    });
+    let before = shared.snapshot();
+    let added = shared.update(|graph| graph.add_node(1));
+    assert_eq!(added, node_index(1));
+
+    assert_eq!(before.epoch(), 1);
+    assert_eq!(before.node_count(), 1);
+    let after = shared.snapshot();
+    assert_eq!(after.epoch(), 2);
+    assert_eq!(after.node_count(), 2);
+
+    let previous = shared.replace(CowGraph::new());
+    assert_eq!(previous.epoch(), 2);
+    assert_eq!(shared.snapshot().node_count(), 0);
+    assert_eq!(shared.into_inner().epoch(), 3);
+}
+
+#[test]
+fn shared_graph_try_update_is_atomic() {
+    let shared = SharedGraph::new(CowGraph::<(), ()>::new());
+    let result: Result<(), &str> = shared.try_update(|graph| {
+        let a = graph.add_node(());
+        graph.add_edge(a, a, ());
+        Err("cyclic")
+    });
+    assert_eq!(result, Err("cyclic"));
+    assert_eq!(shared.epoch(), 0);
+    assert_eq!(shared.snapshot().node_count(), 0);
+}
+
+#[test]
+fn shared_graph_concurrent_readers() {
+    // A writer extends a path one node at a time, while readers check that
+    // each snapshot is a whole path
+    let shared = Arc::new(SharedGraph::new(CowGraph::<(), ()>::new()));
+    shared.update(|graph| {
+        graph.add_node(());
+    });
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let shared = shared.clone();
+            thread::spawn(move || {
+                let mut last = 0;
+                while last < 200 {
+                    let snapshot = shared.snapshot();
+                    assert!(snapshot.epoch() >= last);
+                    last = snapshot.epoch();
+                    assert_eq!(snapshot.node_count() as u64, last);
+                    assert_eq!(snapshot.edge_count() as u64, last - 1);
+                    assert_eq!(connected_components(&*snapshot), 1);
+                    assert!(!is_cyclic_directed(&*snapshot));
+                }
+            })
+        })
+        .collect();
+    for i in 1..200 {
+        shared.update(|graph| {
+            let next = graph.add_node(());
+            graph.add_edge(node_index(i - 1), next, ());
+        });
+    }
+    for reader in readers {
+        reader.join().unwrap();
+    }
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "nalgebra")]
+
+use petgraph::algo::spectral_clustering;
+use petgraph::prelude::*;
+
+/// Three 4-cliques, joined in a ring by light edges.
+fn three_cliques() -> UnGraph<(), f64> {
+    let mut edges = Vec::new();
+    for c in 0..3 {
+        for i in 0..4 {
+            for j in i + 1..4 {
+                edges.push((4 * c + i, 4 * c + j, 1.));
+            }
+        }
+        edges.push((4 * c, (4 * c + 5) % 12, 0.1));
+    }
+    UnGraph::from_edges(&edges)
+}
+
+fn sorted_clusters(clusters: &[Vec<NodeIndex>]) -> Vec<Vec<usize>> {
+    let mut clusters: Vec<Vec<usize>> = clusters
+        .iter()
+        .map(|c| {
+            let mut c: Vec<_> = c.iter().map(|n| n.index()).collect();
+            c.sort();
+            c
+        })
+        .collect();
+    clusters.sort();
+    clusters
+}
+
+#[test]
+fn spectral_clustering_cliques() {
+    let graph = three_cliques();
+    let clustering = spectral_clustering(&graph, 3, |e| *e.weight());
+    assert_eq!(
+        sorted_clusters(clustering.clusters()),
+        vec![vec![0, 1, 2, 3], vec![4, 5, 6, 7], vec![8, 9, 10, 11]]
+    );
+    assert_eq!(clustering.suggested_k(), 3);
+    assert!(clustering.eigen_gap() > 0.5);
+    assert_eq!(clustering.eigenvalues().len(), 12);
+}
+
+#[test]
+fn spectral_clustering_components() {
+    // A path, a triangle and an isolated node
+    let mut graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (3, 4), (4, 5), (5, 3)]);
+    graph.add_node(());
+    let clustering = spectral_clustering(&graph, 3, |_| 1.);
+    assert_eq!(
+        sorted_clusters(clustering.clusters()),
+        vec![vec![0, 1, 2], vec![3, 4, 5], vec![6]]
+    );
+    let zeros = clustering
+        .eigenvalues()
+        .iter()
+        .filter(|&&x| x.abs() < 1e-9)
+        .count();
+    assert_eq!(zeros, 3);
+}
+
+#[test]
+fn spectral_clustering_isolated_nodes() {
+    // Two triangles and two isolated nodes: one eigenvalue 0 per component
+    let mut graph =
+        UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (3, 4), (4, 5), (5, 3)]);
+    graph.add_node(());
+    graph.add_node(());
+    let clustering = spectral_clustering(&graph, 4, |_| 1.);
+    assert_eq!(
+        sorted_clusters(clustering.clusters()),
+        vec![vec![0, 1, 2], vec![3, 4, 5], vec![6], vec![7]]
+    );
+    assert!(clustering.eigenvalues()[..4].iter().all(|x| x.abs() < 1e-9));
+    assert!(clustering.eigenvalues()[4] > 0.5);
+    assert_eq!(clustering.suggested_k(), 4);
+}
+
+#[test]
+fn spectral_clustering_small() {
+    let graph = UnGraph::<(), ()>::from_edges(&[(0, 1)]);
+    let clustering = spectral_clustering(&graph, 3, |_| 1.);
+    assert_eq!(clustering.clusters().len(), 3);
+    assert_eq!(clustering.eigen_gap(), 0.);
+    assert_eq!(
+        clustering.clusters().iter().map(|c| c.len()).sum::<usize>(),
+        2
+    );
+
+    let empty = UnGraph::<(), ()>::default();
+    let clustering = spectral_clustering(&empty, 1, |_| 1.);
+    assert_eq!(clustering.clusters(), &[vec![]]);
+    assert_eq!(clustering.suggested_k(), 1);
+}
@@ -493,3 +493,90 @@ fn weights_mut_iterator() {
     assert_eq!(gr.node_weights_mut().count(), gr.node_count());
     assert_eq!(gr.edge_weights_mut().count(), gr.edge_count());
 }
+
+#[test]
+fn pruning() {
+    let mut graph = StableGraph::<_, _>::from_edges(&[
+        (0, 1, 'a'),
+        (1, 2, 'b'),
+        (2, 0, 'c'),
+        (2, 3, 'd'),
+        (3, 3, 'l'),
+    ]);
+    graph.add_node(());
+    graph.remove_node(n(1));
+
+    assert_eq!(graph.remove_isolated_nodes(), vec![(n(4), ())]);
+    assert_eq!(graph.remove_self_loops(), vec![(e(4), 'l')]);
+    assert_eq!(graph.edge_count(), 2);
+
+    // The path 0 - 2 - 3 is peeled from its ends
+    assert_eq!(graph.peel_degree_below(1), vec![]);
+    assert_eq!(
+        graph.peel_degree_below(2),
+        vec![(n(0), ()), (n(2), ()), (n(3), ())]
+    );
+    assert_eq!(graph.node_count(), 0);
+}
+
+#[test]
+fn shrink_to_fit() {
+    let mut gr = StableGraph::<u32, ()>::with_capacity(100, 100);
+    let a = gr.add_node(0);
+    let b = gr.add_node(1);
+    let c = gr.add_node(2);
+    gr.extend_with_edges(&[(a, b), (b, c), (c, a)]);
+    gr.remove_node(b);
+    assert!(gr.capacity().0 >= 100);
+    assert!(gr.capacity().1 >= 100);
+
+    gr.shrink_to_fit();
+    assert!(gr.capacity().0 < 100);
+    assert!(gr.capacity().1 < 100);
+
+    // The vacant slots are kept, so that indices are stable
+    assert_eq!(gr.node_bound(), 3);
+    assert_eq!(gr.edge_bound(), 3);
+    assert_eq!(gr[c], 2);
+    assert_eq!(gr.edge_endpoints(e(2)), Some((c, a)));
+    assert_eq!(gr.add_node(3), b);
+}
+
+#[test]
+fn shrink_to_fit_nodes_and_edges() {
+    let mut gr = StableGraph::<u32, ()>::with_capacity(100, 100);
+    let a = gr.add_node(0);
+    let b = gr.add_node(1);
+    gr.add_edge(a, b, ());
+
+    gr.shrink_to_fit_nodes();
+    assert!(gr.capacity().0 < 100);
+    assert!(gr.capacity().1 >= 100);
+    gr.shrink_to_fit_edges();
+    assert!(gr.capacity().1 < 100);
+    assert_eq!(gr.find_edge(a, b), Some(e(0)));
+}
+
+#[test]
+fn reserve() {
+    let mut gr = StableGraph::<u32, ()>::new();
+    let a = gr.add_node(0);
+    let b = gr.add_node(1);
+    gr.add_edge(a, b, ());
+    gr.reserve_nodes(50);
+    gr.reserve_edges(60);
+    assert!(gr.capacity().0 >= 52);
+    assert!(gr.capacity().1 >= 61);
+
+    gr.reserve_exact_nodes(100);
+    gr.reserve_exact_edges(100);
+    assert!(gr.capacity().0 >= 102);
+    assert!(gr.capacity().1 >= 101);
+
+    // Reserving within the capacity does nothing
+    let capacity = gr.capacity();
+    gr.reserve_nodes(10);
+    gr.reserve_exact_edges(10);
+    assert_eq!(gr.capacity(), capacity);
+    assert_eq!(gr.find_edge(a, b), Some(e(0)));
+}
@@ -0,0 +1,50 @@
+use petgraph::algo::summary;
+use petgraph::prelude::*;
+
+#[test]
+fn summary_undirected() {
+    // A triangle with a doubled edge, a loop, and a separate edge
+    let graph = UnGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (2, 0), (1, 0), (2, 2), (3, 4)]);
+    let stats = summary(&graph);
+    assert_eq!(stats.node_count, 5);
+    assert_eq!(stats.edge_count, 6);
+    assert!(!stats.directed);
+    assert_eq!(stats.density, 12. / 20.);
+    assert_eq!((stats.min_degree, stats.max_degree), (1, 4));
+    assert_eq!(stats.mean_degree, 12. / 5.);
+    assert_eq!(stats.component_count, 2);
+    assert!(!stats.is_dag);
+    assert_eq!(stats.self_loop_count, 1);
+    assert_eq!(stats.parallel_edge_count, 1);
+}
+
+#[cfg(feature = "stable_graph")]
+#[test]
+fn summary_dag() {
+    let mut graph = StableDiGraph::<(), ()>::from_edges(&[(0, 1), (1, 2), (0, 2), (2, 3), (3, 0)]);
+    assert!(!summary(&graph).is_dag);
+    graph.remove_node(NodeIndex::new(3));
+    let stats = summary(&graph);
+    assert!(stats.is_dag);
+    assert_eq!(stats.node_count, 3);
+    assert_eq!(stats.edge_count, 3);
+    assert_eq!(stats.density, 0.5);
+    assert_eq!(stats.component_count, 1);
+    assert_eq!(stats.parallel_edge_count, 0);
+
+    // Opposite edges are not parallel in directed graphs
+    let graph = DiGraph::<(), ()>::from_edges(&[(0, 1), (1, 0)]);
+    assert_eq!(summary(&graph).parallel_edge_count, 0);
+}
+
+#[test]
+fn summary_empty() {
+    let graph = DiGraph::<(), ()>::new();
+    let stats = summary(&graph);
+    assert_eq!(stats.node_count, 0);
+    assert_eq!(stats.density, 0.);
+    assert_eq!(stats.mean_degree, 0.);
+    assert_eq!((stats.min_degree, stats.max_degree), (0, 0));
+    assert_eq!(stats.component_count, 0);
+    assert!(stats.is_dag);
+}
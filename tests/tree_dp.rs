@@ -0,0 +1,122 @@
+use petgraph::algo::{tree_centroid, tree_fold, tree_max_weight_independent_set};
+use petgraph::prelude::*;
+use petgraph::visit::Dfs;
+
+/// A pseudo-random tree of `n` nodes, with weights in `-3..10`.
+fn random_tree(n: usize, seed: u64) -> UnGraph<i32, ()> {
+    let mut state = seed;
+    let mut next = |m: usize| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) as usize % m
+    };
+    let mut tree = UnGraph::default();
+    for i in 0..n {
+        let node = tree.add_node(next(13) as i32 - 3);
+        if i > 0 {
+            let parent = NodeIndex::new(next(i));
+            tree.add_edge(parent, node, ());
+        }
+    }
+    tree
+}
+
+#[test]
+fn tree_fold_subtree_sizes() {
+    for seed in 0..10 {
+        let tree = random_tree(30, seed);
+        let root = NodeIndex::new(seed as usize % 30);
+        let sizes = tree_fold(&tree, root, |_, children: &[(NodeIndex, &usize)]| {
+            1 + children.iter().map(|&(_, &s)| s).sum::<usize>()
+        });
+        assert_eq!(*sizes.root_value(), 30);
+        assert_eq!(sizes.preorder().len(), 30);
+        for node in tree.node_indices() {
+            // The subtree of a node: the nodes reachable without going through
+            // its parent
+            let mut without_parent = tree.clone();
+            if let Some(parent) = sizes.parent(node) {
+                let edge = without_parent.find_edge(node, parent).unwrap();
+                without_parent.remove_edge(edge);
+            }
+            let mut dfs = Dfs::new(&without_parent, node);
+            let mut count = 0;
+            while dfs.next(&without_parent).is_some() {
+                count += 1;
+            }
+            assert_eq!(sizes.value(node), Some(&count));
+        }
+    }
+}
+
+#[test]
+fn tree_max_weight_independent_set_brute_force() {
+    for seed in 0..20 {
+        let n = 1 + seed as usize % 12;
+        let tree = random_tree(n, seed);
+        let (weight, set) = tree_max_weight_independent_set(&tree, NodeIndex::new(0), |v| tree[v]);
+
+        assert_eq!(weight, set.iter().map(|&v| tree[v]).sum::<i32>());
+        for edge in tree.edge_indices() {
+            let (a, b) = tree.edge_endpoints(edge).unwrap();
+            assert!(!(set.contains(&a) && set.contains(&b)));
+        }
+
+        let best = (0..1u32 << n)
+            .filter(|mask| {
+                tree.edge_indices().all(|e| {
+                    let (a, b) = tree.edge_endpoints(e).unwrap();
+                    mask & (1 << a.index()) == 0 || mask & (1 << b.index()) == 0
+                })
+            })
+            .map(|mask| {
+                tree.node_indices()
+                    .filter(|v| mask & (1 << v.index()) != 0)
+                    .map(|v| tree[v])
+                    .sum::<i32>()
+            })
+            .max()
+            .unwrap();
+        assert_eq!(weight, best);
+    }
+}
+
+#[test]
+fn tree_centroid_brute_force() {
+    for seed in 0..20 {
+        let n = 1 + seed as usize * 3;
+        let tree = random_tree(n, seed);
+        let (first, second) = tree_centroid(&tree, NodeIndex::new(n / 2));
+
+        // The largest component left by removing each node
+        let largest = |node: NodeIndex| {
+            let mut without = tree.clone();
+            without.retain_edges(|g, e| {
+                let (a, b) = g.edge_endpoints(e).unwrap();
+                a != node && b != node
+            });
+            without
+                .neighbors(node)
+                .chain(tree.neighbors(node))
+                .map(|start| {
+                    let mut dfs = Dfs::new(&without, start);
+                    let mut count = 0;
+                    while dfs.next(&without).is_some() {
+                        count += 1;
+                    }
+                    count
+                })
+                .max()
+                .unwrap_or(0)
+        };
+        let mut expected: Vec<_> = tree
+            .node_indices()
+            .filter(|&v| 2 * largest(v) <= n)
+            .collect();
+        let mut found: Vec<_> = Some(first).into_iter().chain(second).collect();
+        expected.sort();
+        found.sort();
+        assert_eq!(found, expected);
+    }
+}